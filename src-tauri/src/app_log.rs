@@ -0,0 +1,68 @@
+// ── App Log Module ─────────────────────────────────────────────
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use tauri::Manager;
+
+const LOG_FILE: &str = "app.log";
+/// Past this size the log is moved to `app.log.old`, replacing the previous one.
+const MAX_LOG_BYTES: u64 = 1024 * 1024;
+
+static LOG_PATH: OnceLock<PathBuf> = OnceLock::new();
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Resolves the log file; until this runs, messages only reach stderr.
+pub fn init(app: &tauri::AppHandle) {
+    let dir = app
+        .path()
+        .app_log_dir()
+        .unwrap_or_else(|_| std::env::temp_dir());
+    let _ = LOG_PATH.set(dir.join(LOG_FILE));
+}
+
+/// Records a failure that has no caller to report to (setup, background threads)
+/// in a file next to the crash reports, whose tail goes into the system report.
+pub fn error(message: impl std::fmt::Display) {
+    let line = format!(
+        "{} {}\n",
+        chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
+        message
+    );
+    #[cfg(debug_assertions)]
+    eprint!("{}", line);
+
+    let Some(path) = LOG_PATH.get() else {
+        return;
+    };
+    let _guard = WRITE_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if std::fs::metadata(path).is_ok_and(|meta| meta.len() > MAX_LOG_BYTES) {
+        let _ = std::fs::rename(path, path.with_extension("log.old"));
+    }
+    if let Some(dir) = path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    if let Ok(mut file) = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+    {
+        let _ = file.write_all(line.as_bytes());
+    }
+}
+
+/// Last `limit` lines of the log, oldest first.
+pub(crate) fn recent_lines(limit: usize) -> Vec<String> {
+    let Some(content) = LOG_PATH
+        .get()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+    else {
+        return Vec::new();
+    };
+    let lines: Vec<&str> = content.lines().collect();
+    lines[lines.len().saturating_sub(limit)..]
+        .iter()
+        .map(|line| line.to_string())
+        .collect()
+}
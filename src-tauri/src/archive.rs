@@ -0,0 +1,292 @@
+// ── Archive Module ─────────────────────────────────────────────
+use crate::client_folders::{extract_7z, extract_zip, ExtractSummary};
+use crate::error::AppError;
+use crate::jobs::{JobHandle, Jobs};
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+#[derive(serde::Deserialize, Clone, Copy)]
+pub enum ArchiveFormat {
+    #[serde(rename = "zip")]
+    Zip,
+    #[serde(rename = "7z")]
+    SevenZ,
+}
+
+#[derive(serde::Serialize)]
+pub struct ArchiveSummary {
+    pub output_path: String,
+    pub file_count: u64,
+    pub original_size: u64,
+    pub compressed_size: u64,
+}
+
+/// File or folder to store, with its name inside the archive.
+struct ArchiveItem {
+    path: PathBuf,
+    name: String,
+    is_dir: bool,
+    size: u64,
+}
+
+fn collect_items(path: &Path, name: String, items: &mut Vec<ArchiveItem>) -> Result<(), AppError> {
+    if !path.is_dir() {
+        let size = std::fs::metadata(path)
+            .map_err(|e| AppError::io(format!("Falha ao ler '{}'", name), e))?
+            .len();
+        items.push(ArchiveItem {
+            path: path.to_path_buf(),
+            name,
+            is_dir: false,
+            size,
+        });
+        return Ok(());
+    }
+
+    let mut entries: Vec<_> = std::fs::read_dir(path)
+        .map_err(|e| AppError::io(format!("Falha ao listar '{}'", name), e))?
+        .flatten()
+        .collect();
+    entries.sort_by_key(|entry| entry.file_name().to_string_lossy().to_lowercase());
+    items.push(ArchiveItem {
+        path: path.to_path_buf(),
+        name: name.clone(),
+        is_dir: true,
+        size: 0,
+    });
+    for entry in entries {
+        let child_name = format!("{}/{}", name, entry.file_name().to_string_lossy());
+        collect_items(&entry.path(), child_name, items)?;
+    }
+    Ok(())
+}
+
+/// Every selected path lands at the archive root under its own name.
+fn plan_items(paths: &[String], output: &Path) -> Result<Vec<ArchiveItem>, AppError> {
+    let mut items = Vec::new();
+    let mut names = HashSet::new();
+    for path in paths {
+        let path = Path::new(path);
+        if !path.exists() {
+            return Err(AppError::NotFound(format!(
+                "Arquivo não encontrado: {}",
+                path.display()
+            )));
+        }
+        if path.is_dir() && output.starts_with(path) {
+            return Err(AppError::InvalidInput(
+                "O arquivo compactado não pode ser salvo dentro de uma pasta compactada".into(),
+            ));
+        }
+        let name = path
+            .file_name()
+            .ok_or_else(|| {
+                AppError::InvalidInput("Não foi possível determinar o nome do arquivo".into())
+            })?
+            .to_string_lossy()
+            .to_string();
+        if !names.insert(name.to_lowercase()) {
+            return Err(AppError::AlreadyExists(format!(
+                "Há mais de um item chamado '{}' na seleção",
+                name
+            )));
+        }
+        collect_items(path, name, &mut items)?;
+    }
+    Ok(items)
+}
+
+fn write_zip(
+    job: &JobHandle,
+    items: &[ArchiveItem],
+    output: &Path,
+    password: Option<&str>,
+    on_file: &mut impl FnMut(&ArchiveItem),
+) -> Result<(), AppError> {
+    let file = std::fs::File::create(output).map_err(|e| AppError::io("Falha ao criar ZIP", e))?;
+    let mut writer = zip::ZipWriter::new(file);
+    let mut options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    if let Some(password) = password {
+        options = options.with_aes_encryption(zip::AesMode::Aes256, password);
+    }
+
+    for item in items {
+        job.check_cancelled()?;
+        if item.is_dir {
+            writer
+                .add_directory(format!("{}/", item.name), options)
+                .map_err(|e| {
+                    AppError::internal(format!("Falha ao adicionar pasta '{}'", item.name), e)
+                })?;
+            continue;
+        }
+        let mut reader = std::fs::File::open(&item.path)
+            .map_err(|e| AppError::io(format!("Falha ao abrir '{}'", item.name), e))?;
+        writer
+            .start_file(
+                item.name.as_str(),
+                options.large_file(item.size >= u32::MAX as u64),
+            )
+            .map_err(|e| AppError::internal(format!("Falha ao adicionar '{}'", item.name), e))?;
+        std::io::copy(&mut reader, &mut writer)
+            .map_err(|e| AppError::io(format!("Falha ao compactar '{}'", item.name), e))?;
+        on_file(item);
+    }
+
+    writer
+        .finish()
+        .map_err(|e| AppError::internal("Falha ao finalizar ZIP", e))?
+        .flush()
+        .map_err(|e| AppError::io("Falha ao finalizar ZIP", e))
+}
+
+fn write_7z(
+    job: &JobHandle,
+    items: &[ArchiveItem],
+    output: &Path,
+    password: Option<&str>,
+    on_file: &mut impl FnMut(&ArchiveItem),
+) -> Result<(), AppError> {
+    let mut writer = sevenz_rust::SevenZWriter::create(output)
+        .map_err(|e| AppError::internal("Falha ao criar 7z", e))?;
+    if let Some(password) = password {
+        writer.set_content_methods(vec![
+            sevenz_rust::AesEncoderOptions::new(password.into()).into(),
+            sevenz_rust::SevenZMethod::LZMA2.into(),
+        ]);
+    }
+
+    for item in items {
+        job.check_cancelled()?;
+        let entry = sevenz_rust::SevenZArchiveEntry::from_path(&item.path, item.name.clone());
+        if item.is_dir {
+            writer
+                .push_archive_entry::<std::fs::File>(entry, None)
+                .map_err(|e| {
+                    AppError::internal(format!("Falha ao adicionar pasta '{}'", item.name), e)
+                })?;
+            continue;
+        }
+        let reader = std::fs::File::open(&item.path)
+            .map_err(|e| AppError::io(format!("Falha ao abrir '{}'", item.name), e))?;
+        writer
+            .push_archive_entry(entry, Some(reader))
+            .map_err(|e| AppError::internal(format!("Falha ao compactar '{}'", item.name), e))?;
+        on_file(item);
+    }
+
+    writer
+        .finish()
+        .map(|_| ())
+        .map_err(|e| AppError::io("Falha ao finalizar 7z", e))
+}
+
+fn create_archive_impl(
+    app: &tauri::AppHandle,
+    paths: Vec<String>,
+    output: PathBuf,
+    format: ArchiveFormat,
+    password: Option<String>,
+) -> Result<ArchiveSummary, AppError> {
+    if paths.is_empty() {
+        return Err(AppError::InvalidInput(
+            "Selecione ao menos um arquivo para compactar".into(),
+        ));
+    }
+    let items = plan_items(&paths, &output)?;
+    let password = password.as_deref().filter(|password| !password.is_empty());
+    let original_size: u64 = items.iter().map(|item| item.size).sum();
+    let total_files = items.iter().filter(|item| !item.is_dir).count() as u64;
+
+    let output_name = output
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let job = Jobs::start(app, "archive", format!("Compactar {}", output_name));
+    let mut file_count = 0;
+    let mut on_file = |item: &ArchiveItem| {
+        file_count += 1;
+        job.progress(file_count, total_files, item.name.as_str());
+    };
+    let result = match format {
+        ArchiveFormat::Zip => write_zip(&job, &items, &output, password, &mut on_file),
+        ArchiveFormat::SevenZ => write_7z(&job, &items, &output, password, &mut on_file),
+    };
+
+    // Finish only after the cleanup, so shutdown does not exit halfway through it
+    if result.is_err() {
+        let _ = std::fs::remove_file(&output);
+    }
+    job.finish(&result);
+    result?;
+
+    Ok(ArchiveSummary {
+        output_path: output.to_string_lossy().to_string(),
+        file_count,
+        original_size,
+        compressed_size: std::fs::metadata(&output).map(|m| m.len()).unwrap_or(0),
+    })
+}
+
+/// Compresses any local files and folders into a ZIP or 7z, optionally protected
+/// with an AES-256 password. Unlike `zip_entry`, paths are not limited to the
+/// client folders share.
+#[tauri::command]
+pub async fn create_archive(
+    app: tauri::AppHandle,
+    paths: Vec<String>,
+    output: String,
+    format: ArchiveFormat,
+    password: Option<String>,
+) -> Result<ArchiveSummary, AppError> {
+    crate::metrics::timed("create_archive", async move {
+        tauri::async_runtime::spawn_blocking(move || {
+            create_archive_impl(&app, paths, PathBuf::from(output), format, password)
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao compactar arquivos", e))?
+    })
+    .await
+}
+
+/// Extracts a local ZIP or 7z into `dest`, creating it if needed. Existing files
+/// are kept and reported as skipped.
+#[tauri::command]
+pub async fn extract_local_archive(
+    archive: String,
+    dest: String,
+    password: Option<String>,
+) -> Result<ExtractSummary, AppError> {
+    crate::metrics::timed("extract_local_archive", async move {
+        tauri::async_runtime::spawn_blocking(move || {
+            let archive = Path::new(&archive);
+            let dest = Path::new(&dest);
+            if !archive.is_file() {
+                return Err(AppError::NotFound(format!(
+                    "Arquivo não encontrado: {}",
+                    archive.display()
+                )));
+            }
+            std::fs::create_dir_all(dest)
+                .map_err(|e| AppError::io("Falha ao criar pasta de destino", e))?;
+
+            let password = password.as_deref().filter(|password| !password.is_empty());
+            let extension = archive
+                .extension()
+                .map(|e| e.to_string_lossy().to_lowercase())
+                .unwrap_or_default();
+            match extension.as_str() {
+                "zip" => extract_zip(archive, dest, password),
+                "7z" => extract_7z(archive, dest, password),
+                _ => Err(AppError::Unsupported(
+                    "Formato não suportado: use arquivos .zip ou .7z".into(),
+                )),
+            }
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao extrair arquivo", e))?
+    })
+    .await
+}
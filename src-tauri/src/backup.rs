@@ -0,0 +1,622 @@
+// ── Backup Module ──────────────────────────────────────────────
+use crate::error::AppError;
+use crate::hashing::{hash_path, HashAlgorithm};
+use crate::settings::Settings;
+use rusqlite::DatabaseName;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+
+const MANIFEST_ENTRY: &str = "backup.json";
+const DATABASE_ENTRY: &str = "todo.db";
+const SETTINGS_ENTRY: &str = "settings.json";
+const FAVICONS_DIR: &str = "favicons";
+/// Emptied in every copy: the clipboard history may hold passwords and PINs, and
+/// exports and backups often end up on a network share.
+const PRIVATE_TABLES: &[&str] = &["clipboard_history"];
+/// Bumped when the archive layout changes in a way older versions can't read.
+const FORMAT_VERSION: u32 = 1;
+
+pub(crate) const AUTO_BACKUP_ENABLED_KEY: &str = "auto_backup_enabled";
+pub(crate) const AUTO_BACKUP_FOLDER_KEY: &str = "auto_backup_folder";
+pub(crate) const AUTO_BACKUP_KEEP_KEY: &str = "auto_backup_keep";
+pub(crate) const AUTO_BACKUP_LAST_KEY: &str = "auto_backup_last_at";
+const DEFAULT_BACKUP_KEEP: u32 = 7;
+/// Settings describing this machine rather than the user's data; carried over, they
+/// would e.g. make two workstations share one sync device id and skip each other.
+const DEVICE_SETTINGS: &[&str] = &[
+    crate::sync::SYNC_DEVICE_KEY,
+    crate::sync::SYNC_LAST_KEY,
+    crate::WINDOW_GEOMETRY_KEY,
+    crate::AUTOSTART_ENABLED_KEY,
+    AUTO_BACKUP_LAST_KEY,
+];
+const BACKUP_FILE_PREFIX: &str = "adcontec-backup-";
+const BACKUP_INTERVAL_HOURS: i64 = 24;
+/// Leaves startup alone before the first check; after that, checks hourly.
+const SCHEDULER_FIRST_CHECK: Duration = Duration::from_secs(2 * 60);
+const SCHEDULER_TICK: Duration = Duration::from_secs(60 * 60);
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Manifest {
+    format_version: u32,
+    app_version: String,
+    created_at: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct ExportSummary {
+    pub output_path: String,
+    pub file_count: u64,
+    pub size: u64,
+}
+
+#[derive(serde::Serialize)]
+pub struct ImportSummary {
+    pub created_at: String,
+    pub app_version: String,
+    pub settings_restored: usize,
+    /// Settings validated by their own screen (paths, schemes, sync), left as they
+    /// were; they have to be set again there.
+    pub settings_skipped: Vec<String>,
+    pub assets_restored: u64,
+}
+
+/// Scratch file next to the system temp files, removed by the caller.
+fn temp_file(label: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "adcontec-{}-{}-{}.db",
+        label,
+        std::process::id(),
+        chrono::Utc::now().timestamp_millis()
+    ))
+}
+
+/// Consistent copy of the live database through SQLite's online backup, so pages
+/// still in the WAL or being written by the webview end up in the copy as well.
+pub(crate) fn snapshot_database(app: &tauri::AppHandle, dest: &Path) -> Result<(), AppError> {
+    let conn = crate::db::open(app)?;
+    // Folds the WAL back into the main file first; a busy checkpoint is fine, the
+    // backup below still reads through it
+    let _ = conn.execute_batch("PRAGMA wal_checkpoint(TRUNCATE);");
+    if dest.exists() {
+        std::fs::remove_file(dest)
+            .map_err(|e| AppError::io("Falha ao substituir cópia do banco de dados", e))?;
+    }
+    conn.backup(DatabaseName::Main, dest, None)
+        .map_err(|e| AppError::internal("Falha ao copiar banco de dados", e))?;
+    strip_private_tables(dest)
+}
+
+/// Deletes the rows of `PRIVATE_TABLES` from the copy at `path`, then vacuums it so
+/// the freed pages don't keep the content around.
+fn strip_private_tables(path: &Path) -> Result<(), AppError> {
+    let conn = rusqlite::Connection::open(path)
+        .map_err(|e| AppError::internal("Falha ao abrir cópia do banco de dados", e))?;
+    for table in PRIVATE_TABLES {
+        let exists: bool = conn
+            .query_row(
+                "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1)",
+                [table],
+                |row| row.get(0),
+            )
+            .map_err(|e| AppError::internal("Falha ao limpar cópia do banco de dados", e))?;
+        if exists {
+            conn.execute(&format!("DELETE FROM {}", table), [])
+                .map_err(|e| AppError::internal("Falha ao limpar cópia do banco de dados", e))?;
+        }
+    }
+    conn.execute_batch("VACUUM;")
+        .map_err(|e| AppError::internal("Falha ao compactar cópia do banco de dados", e))
+}
+
+/// Replaces the live database contents with `source`, after checking it is a sane SQLite file.
+/// Goes through the backup API too, so connections already open see the restored data.
+pub(crate) fn restore_database(app: &tauri::AppHandle, source: &Path) -> Result<(), AppError> {
+    let check: String =
+        rusqlite::Connection::open_with_flags(source, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .and_then(|conn| conn.query_row("PRAGMA quick_check", [], |row| row.get(0)))
+            .map_err(|e| {
+                AppError::InvalidInput("Banco de dados do backup inválido".into()).with_details(e)
+            })?;
+    if check != "ok" {
+        return Err(
+            AppError::InvalidInput("Banco de dados do backup corrompido".into())
+                .with_details(check),
+        );
+    }
+
+    let mut conn = crate::db::open(app)?;
+    conn.restore(
+        DatabaseName::Main,
+        source,
+        None::<fn(rusqlite::backup::Progress)>,
+    )
+    .map_err(|e| AppError::internal("Falha ao restaurar banco de dados", e))
+}
+
+fn favicons_dir(app: &tauri::AppHandle) -> Result<PathBuf, AppError> {
+    Ok(app
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::internal("Falha ao localizar pasta de dados", e))?
+        .join(FAVICONS_DIR))
+}
+
+fn write_entry(
+    writer: &mut zip::ZipWriter<std::fs::File>,
+    name: &str,
+    content: &[u8],
+) -> Result<(), AppError> {
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    writer
+        .start_file(name, options)
+        .map_err(|e| AppError::internal(format!("Falha ao adicionar '{}'", name), e))?;
+    writer
+        .write_all(content)
+        .map_err(|e| AppError::io(format!("Falha ao gravar '{}'", name), e))
+}
+
+fn export_impl(app: &tauri::AppHandle, dest: &Path) -> Result<ExportSummary, AppError> {
+    let manifest = Manifest {
+        format_version: FORMAT_VERSION,
+        app_version: app.package_info().version.to_string(),
+        created_at: chrono::Local::now().to_rfc3339(),
+    };
+    let mut settings = app.state::<Settings>().all();
+    settings.retain(|key, _| !DEVICE_SETTINGS.contains(&key.as_str()));
+    let settings = serde_json::to_vec_pretty(&settings)
+        .map_err(|e| AppError::internal("Falha ao serializar configurações", e))?;
+
+    let snapshot = temp_file("export");
+    let database = snapshot_database(app, &snapshot).and_then(|_| {
+        std::fs::read(&snapshot).map_err(|e| AppError::io("Falha ao ler cópia do banco", e))
+    });
+    let _ = std::fs::remove_file(&snapshot);
+    let database = database?;
+
+    let file = std::fs::File::create(dest).map_err(|e| AppError::io("Falha ao criar backup", e))?;
+    let mut writer = zip::ZipWriter::new(file);
+    let manifest = serde_json::to_vec_pretty(&manifest)
+        .map_err(|e| AppError::internal("Falha ao serializar backup", e))?;
+    write_entry(&mut writer, MANIFEST_ENTRY, &manifest)?;
+    write_entry(&mut writer, DATABASE_ENTRY, &database)?;
+    write_entry(&mut writer, SETTINGS_ENTRY, &settings)?;
+    let mut file_count = 3;
+
+    if let Ok(entries) = std::fs::read_dir(favicons_dir(app)?) {
+        for entry in entries.flatten().filter(|entry| entry.path().is_file()) {
+            let name = format!("{}/{}", FAVICONS_DIR, entry.file_name().to_string_lossy());
+            let content =
+                std::fs::read(entry.path()).map_err(|e| AppError::io("Falha ao ler ícone", e))?;
+            write_entry(&mut writer, &name, &content)?;
+            file_count += 1;
+        }
+    }
+
+    writer
+        .finish()
+        .map_err(|e| AppError::internal("Falha ao finalizar backup", e))?;
+
+    Ok(ExportSummary {
+        output_path: dest.to_string_lossy().to_string(),
+        file_count,
+        size: std::fs::metadata(dest).map(|m| m.len()).unwrap_or(0),
+    })
+}
+
+fn read_entry(
+    archive: &mut zip::ZipArchive<std::fs::File>,
+    name: &str,
+) -> Result<Option<Vec<u8>>, AppError> {
+    let mut entry = match archive.by_name(name) {
+        Ok(entry) => entry,
+        Err(zip::result::ZipError::FileNotFound) => return Ok(None),
+        Err(e) => return Err(AppError::internal(format!("Falha ao ler '{}'", name), e)),
+    };
+    let mut content = Vec::new();
+    entry
+        .read_to_end(&mut content)
+        .map_err(|e| AppError::io(format!("Falha ao ler '{}'", name), e))?;
+    Ok(Some(content))
+}
+
+fn import_impl(app: &tauri::AppHandle, source: &Path) -> Result<ImportSummary, AppError> {
+    let file = std::fs::File::open(source).map_err(|e| AppError::io("Falha ao abrir backup", e))?;
+    let mut archive = zip::ZipArchive::new(file)
+        .map_err(|e| AppError::InvalidInput("Arquivo de backup inválido".into()).with_details(e))?;
+
+    let manifest: Manifest = read_entry(&mut archive, MANIFEST_ENTRY)?
+        .and_then(|content| serde_json::from_slice(&content).ok())
+        .ok_or_else(|| {
+            AppError::InvalidInput("O arquivo não é um backup do Adcontec Útil".into())
+        })?;
+    if manifest.format_version > FORMAT_VERSION {
+        return Err(AppError::Unsupported(
+            "Backup criado por uma versão mais nova do aplicativo; atualize antes de importar"
+                .into(),
+        ));
+    }
+
+    // Everything is read and validated before anything local is touched
+    let database = read_entry(&mut archive, DATABASE_ENTRY)?
+        .ok_or_else(|| AppError::InvalidInput("Backup sem banco de dados".into()))?;
+    let settings: serde_json::Map<String, serde_json::Value> =
+        match read_entry(&mut archive, SETTINGS_ENTRY)? {
+            Some(content) => serde_json::from_slice(&content).map_err(|e| {
+                AppError::InvalidInput("Configurações do backup inválidas".into()).with_details(e)
+            })?,
+            None => serde_json::Map::new(),
+        };
+
+    let staged = temp_file("import");
+    let restored = std::fs::write(&staged, &database)
+        .map_err(|e| AppError::io("Falha ao preparar restauração", e))
+        .and_then(|_| restore_database(app, &staged));
+    let _ = std::fs::remove_file(&staged);
+    restored?;
+
+    // Backups from before device settings were left out still carry them
+    let store = app.state::<Settings>();
+    let mut settings_restored = 0;
+    let mut settings_skipped = Vec::new();
+    for (key, value) in &settings {
+        if DEVICE_SETTINGS.contains(&key.as_str()) {
+            continue;
+        }
+        if crate::settings::is_reserved(key) {
+            settings_skipped.push(key.clone());
+            continue;
+        }
+        store.set(key, value)?;
+        settings_restored += 1;
+    }
+
+    let icons_dir = favicons_dir(app)?;
+    let mut assets_restored = 0;
+    for i in 0..archive.len() {
+        let mut entry = archive
+            .by_index(i)
+            .map_err(|e| AppError::internal("Falha ao ler item do backup", e))?;
+        let Some(file_name) = entry
+            .enclosed_name()
+            .filter(|_| entry.is_file())
+            .and_then(|path| path.strip_prefix(FAVICONS_DIR).ok().map(Path::to_path_buf))
+        else {
+            continue;
+        };
+
+        std::fs::create_dir_all(&icons_dir)
+            .map_err(|e| AppError::io("Falha ao criar pasta de ícones", e))?;
+        let mut out = std::fs::File::create(icons_dir.join(file_name))
+            .map_err(|e| AppError::io("Falha ao restaurar ícone", e))?;
+        std::io::copy(&mut entry, &mut out)
+            .map_err(|e| AppError::io("Falha ao restaurar ícone", e))?;
+        assets_restored += 1;
+    }
+
+    Ok(ImportSummary {
+        created_at: manifest.created_at,
+        app_version: manifest.app_version,
+        settings_restored,
+        settings_skipped,
+        assets_restored,
+    })
+}
+
+/// Bundles the database, settings and cached icons into one ZIP for moving to another machine.
+#[tauri::command]
+pub async fn export_app_data(
+    app: tauri::AppHandle,
+    dest_zip: String,
+) -> Result<ExportSummary, AppError> {
+    crate::metrics::timed("export_app_data", async move {
+        tauri::async_runtime::spawn_blocking(move || export_impl(&app, Path::new(&dest_zip)))
+            .await
+            .map_err(|e| AppError::internal("Falha ao exportar dados", e))?
+    })
+    .await
+}
+
+/// Restores an `export_app_data` archive over the current data. Settings missing from
+/// the backup, and those only their own screen may change, keep their current value;
+/// backend tables newer than the backup come back on the next launch.
+#[tauri::command]
+pub async fn import_app_data(
+    app: tauri::AppHandle,
+    src_zip: String,
+) -> Result<ImportSummary, AppError> {
+    crate::metrics::timed("import_app_data", async move {
+        let handle = app.clone();
+        let summary =
+            tauri::async_runtime::spawn_blocking(move || import_impl(&handle, Path::new(&src_zip)))
+                .await
+                .map_err(|e| AppError::internal("Falha ao importar dados", e))??;
+
+        // Views holding data in memory reload, and the tray picks up restored links
+        crate::refresh_tray_menu(&app);
+        let _ = app.emit("app-data-imported", ());
+        Ok(summary)
+    })
+    .await
+}
+
+// ── Scheduled Backups ──────────────────────────────────────────
+
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct BackupSettings {
+    pub enabled: bool,
+    /// Local folder or network share; `None` means the app data dir.
+    pub folder: Option<String>,
+    /// Number of backups kept; older ones are deleted.
+    pub keep: u32,
+    #[serde(default)]
+    pub last_backup_at: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+pub struct BackupFile {
+    pub path: String,
+    pub file_name: String,
+    pub size: u64,
+    pub created_at: String,
+}
+
+fn backup_folder(app: &tauri::AppHandle, settings: &Settings) -> Result<PathBuf, AppError> {
+    match settings
+        .get::<String>(AUTO_BACKUP_FOLDER_KEY)
+        .filter(|folder| !folder.trim().is_empty())
+    {
+        Some(folder) => Ok(PathBuf::from(folder.trim())),
+        None => Ok(app
+            .path()
+            .app_data_dir()
+            .map_err(|e| AppError::internal("Falha ao localizar pasta de dados", e))?
+            .join("backups")),
+    }
+}
+
+/// Backups in `folder`, newest first. The timestamp in the name keeps them sortable.
+fn list_backup_files(folder: &Path) -> Vec<BackupFile> {
+    let Ok(entries) = std::fs::read_dir(folder) else {
+        return Vec::new();
+    };
+
+    let mut files: Vec<BackupFile> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            if !file_name.starts_with(BACKUP_FILE_PREFIX) || !file_name.ends_with(".db") {
+                return None;
+            }
+            let metadata = entry.metadata().ok()?;
+            let created_at = metadata
+                .modified()
+                .map(|time| chrono::DateTime::<chrono::Local>::from(time).to_rfc3339())
+                .unwrap_or_default();
+            Some(BackupFile {
+                path: entry.path().to_string_lossy().to_string(),
+                file_name,
+                size: metadata.len(),
+                created_at,
+            })
+        })
+        .collect();
+    files.sort_by(|a, b| b.file_name.cmp(&a.file_name));
+    files
+}
+
+fn sha256(path: &Path) -> Result<String, AppError> {
+    hash_path(path, HashAlgorithm::Sha256, |_, _| Ok(()))
+}
+
+/// `<backup>.sha256` next to the backup, written once the copy was verified.
+fn checksum_file(backup: &Path) -> PathBuf {
+    let mut name = backup.as_os_str().to_owned();
+    name.push(".sha256");
+    PathBuf::from(name)
+}
+
+/// Fails when the backup no longer matches the hash recorded when it was made.
+/// Backups from before hashes were recorded have no file and pass.
+fn verify_checksum(backup: &Path, copy: &Path) -> Result<(), AppError> {
+    let Ok(recorded) = std::fs::read_to_string(checksum_file(backup)) else {
+        return Ok(());
+    };
+    let expected = recorded.split_whitespace().next().unwrap_or_default();
+    if !sha256(copy)?.eq_ignore_ascii_case(expected) {
+        return Err(AppError::InvalidInput(
+            "Backup corrompido: o arquivo não confere com o hash gravado".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Copies the database into the backup folder and prunes the oldest copies.
+pub(crate) fn run_backup(app: &tauri::AppHandle) -> Result<BackupFile, AppError> {
+    let settings = app.state::<Settings>();
+    let folder = backup_folder(app, &settings)?;
+    std::fs::create_dir_all(&folder)
+        .map_err(|e| AppError::io("Falha ao acessar pasta de backup", e))?;
+
+    let file_name = format!(
+        "{}{}.db",
+        BACKUP_FILE_PREFIX,
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    );
+    // Snapshotted locally and hashed, then copied under a temporary name and hashed
+    // again, so a half-copied or mangled file on the share never counts as a backup
+    let snapshot = temp_file("backup");
+    let partial = folder.join(format!("{}.partial", file_name));
+    let target = folder.join(&file_name);
+    let copied = snapshot_database(app, &snapshot)
+        .and_then(|_| sha256(&snapshot))
+        .and_then(|hash| {
+            std::fs::copy(&snapshot, &partial)
+                .map_err(|e| AppError::io("Falha ao copiar backup", e))?;
+            if sha256(&partial)? != hash {
+                return Err(AppError::internal(
+                    "Falha ao copiar backup",
+                    "a cópia gravada não confere com o banco de dados",
+                ));
+            }
+            std::fs::rename(&partial, &target)
+                .map_err(|e| AppError::io("Falha ao salvar backup", e))?;
+            Ok(hash)
+        })
+        .inspect_err(|_| {
+            let _ = std::fs::remove_file(&partial);
+        });
+    let _ = std::fs::remove_file(&snapshot);
+    let hash = copied?;
+    // Same layout as `sha256sum`, so the share can be checked without the app
+    if let Err(e) = std::fs::write(checksum_file(&target), format!("{}  {}\n", hash, file_name)) {
+        crate::app_log::error(format!("Falha ao gravar hash do backup: {}", e));
+    }
+
+    let keep = settings
+        .get::<u32>(AUTO_BACKUP_KEEP_KEY)
+        .unwrap_or(DEFAULT_BACKUP_KEEP)
+        .max(1) as usize;
+    for old in list_backup_files(&folder).iter().skip(keep) {
+        let _ = std::fs::remove_file(&old.path);
+        let _ = std::fs::remove_file(checksum_file(Path::new(&old.path)));
+    }
+
+    settings.set(AUTO_BACKUP_LAST_KEY, chrono::Utc::now().to_rfc3339())?;
+    let size = std::fs::metadata(&target).map(|m| m.len()).unwrap_or(0);
+    Ok(BackupFile {
+        path: target.to_string_lossy().to_string(),
+        file_name,
+        size,
+        created_at: chrono::Local::now().to_rfc3339(),
+    })
+}
+
+fn backup_due(settings: &Settings) -> bool {
+    if !settings
+        .get::<bool>(AUTO_BACKUP_ENABLED_KEY)
+        .unwrap_or(true)
+    {
+        return false;
+    }
+    settings
+        .get::<String>(AUTO_BACKUP_LAST_KEY)
+        .and_then(|last| chrono::DateTime::parse_from_rfc3339(&last).ok())
+        .map(|last| {
+            chrono::Utc::now().signed_duration_since(last)
+                >= chrono::Duration::hours(BACKUP_INTERVAL_HOURS)
+        })
+        .unwrap_or(true)
+}
+
+/// Daily backups; a day missed while the app was closed is made up on the next check.
+pub fn start_backup_scheduler(app: tauri::AppHandle) {
+    std::thread::spawn(move || {
+        std::thread::sleep(SCHEDULER_FIRST_CHECK);
+        loop {
+            if backup_due(&app.state::<Settings>()) {
+                if let Err(e) = run_backup(&app) {
+                    // Usually an offline network share; the next tick tries again
+                    crate::app_log::error(format!("Backup automático falhou: {}", e));
+                    let _ = app.emit("auto-backup-failed", e);
+                }
+            }
+            std::thread::sleep(SCHEDULER_TICK);
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_backup_settings(
+    app: tauri::AppHandle,
+    settings: tauri::State<'_, Settings>,
+) -> Result<BackupSettings, AppError> {
+    Ok(BackupSettings {
+        enabled: settings.get(AUTO_BACKUP_ENABLED_KEY).unwrap_or(true),
+        folder: Some(
+            backup_folder(&app, &settings)?
+                .to_string_lossy()
+                .to_string(),
+        ),
+        keep: settings
+            .get(AUTO_BACKUP_KEEP_KEY)
+            .unwrap_or(DEFAULT_BACKUP_KEEP),
+        last_backup_at: settings.get(AUTO_BACKUP_LAST_KEY),
+    })
+}
+
+#[tauri::command]
+pub fn set_backup_settings(
+    settings: tauri::State<'_, Settings>,
+    backup: BackupSettings,
+) -> Result<(), AppError> {
+    if backup.keep == 0 {
+        return Err(AppError::InvalidInput(
+            "Mantenha pelo menos um backup".into(),
+        ));
+    }
+    let folder = backup
+        .folder
+        .map(|folder| folder.trim().to_string())
+        .filter(|folder| !folder.is_empty());
+
+    settings.set(AUTO_BACKUP_ENABLED_KEY, backup.enabled)?;
+    settings.set(AUTO_BACKUP_FOLDER_KEY, folder)?;
+    settings.set(AUTO_BACKUP_KEEP_KEY, backup.keep)?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn backup_now(app: tauri::AppHandle) -> Result<BackupFile, AppError> {
+    crate::metrics::timed("backup_now", async move {
+        tauri::async_runtime::spawn_blocking(move || run_backup(&app))
+            .await
+            .map_err(|e| AppError::internal("Falha ao criar backup", e))?
+    })
+    .await
+}
+
+#[tauri::command]
+pub fn list_backups(
+    app: tauri::AppHandle,
+    settings: tauri::State<'_, Settings>,
+) -> Result<Vec<BackupFile>, AppError> {
+    Ok(list_backup_files(&backup_folder(&app, &settings)?))
+}
+
+/// Restores a backup made by `backup_now` or the scheduler. The current data is backed
+/// up first, so a restore can itself be undone.
+#[tauri::command]
+pub async fn restore_backup(app: tauri::AppHandle, path: String) -> Result<(), AppError> {
+    crate::metrics::timed("restore_backup", async move {
+        let handle = app.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            let source = PathBuf::from(&path);
+            if !source.is_file() {
+                return Err(AppError::NotFound(format!(
+                    "Backup não encontrado: {}",
+                    path
+                )));
+            }
+            // Staged first: the safety backup's rotation may delete the very file being restored
+            let staged = temp_file("restore");
+            let restored = std::fs::copy(&source, &staged)
+                .map_err(|e| AppError::io("Falha ao preparar restauração", e))
+                .and_then(|_| verify_checksum(&source, &staged))
+                .and_then(|_| run_backup(&handle))
+                .and_then(|_| restore_database(&handle, &staged));
+            let _ = std::fs::remove_file(&staged);
+            restored
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao restaurar backup", e))??;
+
+        crate::refresh_tray_menu(&app);
+        let _ = app.emit("app-data-imported", ());
+        Ok(())
+    })
+    .await
+}
@@ -0,0 +1,495 @@
+// ── Barcode Module ─────────────────────────────────────────────
+use crate::boleto;
+use crate::deep_link::{self, Navigation};
+use crate::error::AppError;
+use base64::Engine;
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+/// Bar and space widths of the Code 128 symbols 0-105, then the stop pattern.
+const CODE128_PATTERNS: [&str; 107] = [
+    "212222", "222122", "222221", "121223", "121322", "131222", "122213", "122312", "132212",
+    "221213", "221312", "231212", "112232", "122132", "122231", "113222", "123122", "123221",
+    "223211", "221132", "221231", "213212", "223112", "312131", "311222", "321122", "321221",
+    "312212", "322112", "322211", "212123", "212321", "232121", "111323", "131123", "131321",
+    "112313", "132113", "132311", "211313", "231113", "231311", "112133", "112331", "132131",
+    "113123", "113321", "133121", "313121", "211331", "231131", "213113", "213311", "213131",
+    "311123", "311321", "331121", "312113", "312311", "332111", "314111", "221411", "431111",
+    "111224", "111422", "121124", "121421", "141122", "141221", "112214", "112412", "122114",
+    "122411", "142112", "142211", "241211", "221114", "413111", "241112", "134111", "111242",
+    "121142", "121241", "114212", "124112", "124211", "411212", "421112", "421211", "212141",
+    "214121", "412121", "111143", "111341", "131141", "114113", "114311", "411113", "411311",
+    "113141", "114131", "311141", "411131", "211412", "211214", "211232", "2331112",
+];
+const CODE128_START_B: usize = 104;
+const CODE128_START_C: usize = 105;
+const CODE128_STOP: usize = 106;
+
+/// EAN-13 left-hand digits with odd parity; the even-parity (G) and right-hand
+/// (R) codes are derived from these.
+const EAN_L_CODES: [&str; 10] = [
+    "0001101", "0011001", "0010011", "0111101", "0100011", "0110001", "0101111", "0111011",
+    "0110111", "0001011",
+];
+/// Parity of the six left-hand digits, chosen by the first digit (not encoded).
+const EAN_PARITY: [&str; 10] = [
+    "LLLLLL", "LLGLGG", "LLGGLG", "LLGGGL", "LGLLGG", "LGGLLG", "LGGGLL", "LGLGLG", "LGLGGL",
+    "LGGLGL",
+];
+
+/// Quiet zones, in modules, that readers need around each kind of symbol.
+const LINEAR_QUIET_ZONE: u32 = 10;
+const QR_QUIET_ZONE: u32 = 4;
+/// Bar height in modules; about 15% of a 44-digit chave's width.
+const LINEAR_HEIGHT: u32 = 50;
+const LINEAR_MODULE_PIXELS: u32 = 2;
+const QR_MODULE_PIXELS: u32 = 8;
+
+#[derive(serde::Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum Symbology {
+    Code128,
+    Ean13,
+    Qr,
+}
+
+#[derive(serde::Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum BarcodeFormat {
+    Svg,
+    Png,
+}
+
+#[derive(serde::Serialize)]
+pub struct GeneratedBarcode {
+    /// What was encoded; for EAN-13 includes the check digit.
+    pub content: String,
+    /// Ready to use as an `<img src>`.
+    pub data_url: String,
+}
+
+#[derive(serde::Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ScannedKind {
+    /// 44-digit NF-e/CT-e/NFC-e/MDF-e access key.
+    Chave,
+    Boleto,
+    Other,
+}
+
+#[derive(serde::Serialize)]
+pub struct ScannedBarcode {
+    pub text: String,
+    /// "code128", "qr" or "itf".
+    pub symbology: String,
+    pub kind: ScannedKind,
+}
+
+/// Dark/light modules of a symbol. Linear symbols have a single row that is
+/// stretched to `LINEAR_HEIGHT`.
+struct Symbol {
+    columns: u32,
+    rows: u32,
+    dark: Vec<bool>,
+    linear: bool,
+}
+
+impl Symbol {
+    fn linear(dark: Vec<bool>) -> Self {
+        Symbol {
+            columns: dark.len() as u32,
+            rows: 1,
+            dark,
+            linear: true,
+        }
+    }
+
+    fn quiet_zone(&self) -> (u32, u32) {
+        if self.linear {
+            (LINEAR_QUIET_ZONE, 0)
+        } else {
+            (QR_QUIET_ZONE, QR_QUIET_ZONE)
+        }
+    }
+
+    fn is_dark(&self, column: u32, row: u32) -> bool {
+        self.dark[(row * self.columns + column) as usize]
+    }
+}
+
+/// Appends the modules of a pattern of alternating bar and space widths.
+fn push_widths(widths: &str, dark: &mut Vec<bool>) {
+    for (i, width) in widths.bytes().enumerate() {
+        dark.extend(std::iter::repeat_n(i % 2 == 0, (width - b'0') as usize));
+    }
+}
+
+/// Code set C packs digit pairs, so all-digit content of even length (access
+/// keys, boleto lines) gets half as wide; anything else goes in code set B.
+fn code128(content: &str) -> Result<Symbol, AppError> {
+    if content.is_empty() {
+        return Err(AppError::InvalidInput(
+            "Informe o conteúdo do código".into(),
+        ));
+    }
+    let mut values = Vec::new();
+    if content.len() % 2 == 0 && content.bytes().all(|b| b.is_ascii_digit()) {
+        values.push(CODE128_START_C);
+        for pair in content.as_bytes().chunks(2) {
+            values.push(((pair[0] - b'0') * 10 + (pair[1] - b'0')) as usize);
+        }
+    } else {
+        values.push(CODE128_START_B);
+        for c in content.chars() {
+            if !(' '..='~').contains(&c) {
+                return Err(AppError::InvalidInput(format!(
+                    "Caractere não suportado no Code 128: {}",
+                    c
+                )));
+            }
+            values.push(c as usize - 32);
+        }
+    }
+    // Check symbol: start value plus each value weighted by its position
+    let checksum = values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| i.max(1) * value)
+        .sum::<usize>()
+        % 103;
+    values.push(checksum);
+    values.push(CODE128_STOP);
+
+    let mut dark = Vec::new();
+    for value in values {
+        push_widths(CODE128_PATTERNS[value], &mut dark);
+    }
+    Ok(Symbol::linear(dark))
+}
+
+fn ean13_check_digit(digits: &[u8]) -> u8 {
+    let sum: u32 = digits
+        .iter()
+        .enumerate()
+        .map(|(i, digit)| *digit as u32 * if i % 2 == 0 { 1 } else { 3 })
+        .sum();
+    ((10 - sum % 10) % 10) as u8
+}
+
+/// Accepts the 12 data digits (the check digit is appended) or all 13, in which
+/// case the check digit must match. Returns the symbol and the full 13 digits.
+fn ean13(content: &str) -> Result<(Symbol, String), AppError> {
+    let digits: Vec<u8> = content
+        .trim()
+        .bytes()
+        .map(|b| b.wrapping_sub(b'0'))
+        .collect();
+    if !(digits.len() == 12 || digits.len() == 13) || digits.iter().any(|digit| *digit > 9) {
+        return Err(AppError::InvalidInput(
+            "O EAN-13 deve ter 12 ou 13 dígitos".into(),
+        ));
+    }
+    let check = ean13_check_digit(&digits[..12]);
+    if digits.len() == 13 && digits[12] != check {
+        return Err(AppError::InvalidInput(format!(
+            "Dígito verificador do EAN-13 inválido (esperado {})",
+            check
+        )));
+    }
+    let mut digits = digits[..12].to_vec();
+    digits.push(check);
+
+    let mut modules = String::from("101");
+    let parity = EAN_PARITY[digits[0] as usize].as_bytes();
+    for (i, digit) in digits[1..7].iter().enumerate() {
+        let code = EAN_L_CODES[*digit as usize];
+        if parity[i] == b'G' {
+            // G code: the R code reversed
+            modules.extend(code.chars().rev().map(|c| if c == '0' { '1' } else { '0' }));
+        } else {
+            modules.push_str(code);
+        }
+    }
+    modules.push_str("01010");
+    for digit in &digits[7..] {
+        let code = EAN_L_CODES[*digit as usize];
+        modules.extend(code.chars().map(|c| if c == '0' { '1' } else { '0' }));
+    }
+    modules.push_str("101");
+
+    let text = digits.iter().map(|digit| (b'0' + digit) as char).collect();
+    Ok((
+        Symbol::linear(modules.chars().map(|c| c == '1').collect()),
+        text,
+    ))
+}
+
+fn qr(content: &str) -> Result<Symbol, AppError> {
+    if content.is_empty() {
+        return Err(AppError::InvalidInput(
+            "Informe o conteúdo do código".into(),
+        ));
+    }
+    let code = qrcode::QrCode::with_error_correction_level(content, qrcode::EcLevel::M)
+        .map_err(|e| AppError::internal("Falha ao gerar QR code", e))?;
+    let size = code.width() as u32;
+    Ok(Symbol {
+        columns: size,
+        rows: size,
+        dark: code
+            .to_colors()
+            .into_iter()
+            .map(|color| color == qrcode::Color::Dark)
+            .collect(),
+        linear: false,
+    })
+}
+
+/// Encodes `content`, returning the symbol and the content as encoded.
+fn encode(symbology: Symbology, content: &str) -> Result<(Symbol, String), AppError> {
+    match symbology {
+        Symbology::Code128 => Ok((code128(content)?, content.to_string())),
+        Symbology::Ean13 => ean13(content),
+        Symbology::Qr => Ok((qr(content)?, content.to_string())),
+    }
+}
+
+fn render_svg(symbol: &Symbol) -> String {
+    let (quiet_x, quiet_y) = symbol.quiet_zone();
+    let bar_height = if symbol.linear { LINEAR_HEIGHT } else { 1 };
+    let width = symbol.columns + 2 * quiet_x;
+    let height = symbol.rows * bar_height + 2 * quiet_y;
+
+    // One path with a rectangle per run of dark modules keeps the file small
+    let mut path = String::new();
+    for row in 0..symbol.rows {
+        let mut column = 0;
+        while column < symbol.columns {
+            if !symbol.is_dark(column, row) {
+                column += 1;
+                continue;
+            }
+            let start = column;
+            while column < symbol.columns && symbol.is_dark(column, row) {
+                column += 1;
+            }
+            path.push_str(&format!(
+                "M{},{}h{}v{}h-{}z",
+                start + quiet_x,
+                row * bar_height + quiet_y,
+                column - start,
+                bar_height,
+                column - start
+            ));
+        }
+    }
+
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {w} {h}" width="{w}" height="{h}" preserveAspectRatio="none" shape-rendering="crispEdges"><rect width="{w}" height="{h}" fill="#fff"/><path d="{path}" fill="#000"/></svg>"##,
+        w = width,
+        h = height,
+        path = path
+    )
+}
+
+fn render_png(symbol: &Symbol) -> Result<Vec<u8>, AppError> {
+    let (quiet_x, quiet_y) = symbol.quiet_zone();
+    let (module_pixels, row_pixels) = if symbol.linear {
+        (LINEAR_MODULE_PIXELS, LINEAR_MODULE_PIXELS * LINEAR_HEIGHT)
+    } else {
+        (QR_MODULE_PIXELS, QR_MODULE_PIXELS)
+    };
+    let width = (symbol.columns + 2 * quiet_x) * module_pixels;
+    let height = symbol.rows * row_pixels + 2 * quiet_y * module_pixels;
+
+    let image = image::GrayImage::from_fn(width, height, |x, y| {
+        let column = (x / module_pixels).checked_sub(quiet_x);
+        let row = y
+            .checked_sub(quiet_y * module_pixels)
+            .map(|y| y / row_pixels);
+        let dark = match (column, row) {
+            (Some(column), Some(row)) if column < symbol.columns && row < symbol.rows => {
+                symbol.is_dark(column, row)
+            }
+            _ => false,
+        };
+        image::Luma([if dark { 0 } else { 255 }])
+    });
+
+    let mut png = std::io::Cursor::new(Vec::new());
+    image::DynamicImage::ImageLuma8(image)
+        .write_to(&mut png, image::ImageFormat::Png)
+        .map_err(|e| AppError::internal("Falha ao gerar imagem do código", e))?;
+    Ok(png.into_inner())
+}
+
+/// SVG markup, for embedding in generated HTML such as the DANFE.
+pub fn svg(symbology: Symbology, content: &str) -> Result<String, AppError> {
+    let (symbol, _) = encode(symbology, content)?;
+    Ok(render_svg(&symbol))
+}
+
+pub fn png(symbology: Symbology, content: &str) -> Result<Vec<u8>, AppError> {
+    let (symbol, _) = encode(symbology, content)?;
+    render_png(&symbol)
+}
+
+/// Code 128, EAN-13 or QR code for `content`, as an SVG or PNG data URL.
+#[tauri::command]
+pub fn generate_barcode(
+    content: String,
+    symbology: Symbology,
+    format: BarcodeFormat,
+) -> Result<GeneratedBarcode, AppError> {
+    let (symbol, content) = encode(symbology, &content)?;
+    let data_url = match format {
+        BarcodeFormat::Svg => format!(
+            "data:image/svg+xml;base64,{}",
+            base64::engine::general_purpose::STANDARD.encode(render_svg(&symbol))
+        ),
+        BarcodeFormat::Png => format!(
+            "data:image/png;base64,{}",
+            base64::engine::general_purpose::STANDARD.encode(render_png(&symbol)?)
+        ),
+    };
+    Ok(GeneratedBarcode { content, data_url })
+}
+
+// ── Scanning ────────────────────────────────────────────────────
+
+/// Models that carry a 44-digit access key: NF-e, CT-e, MDF-e, NFC-e, CT-e OS.
+const ACCESS_KEY_MODELS: [&str; 5] = ["55", "57", "58", "65", "67"];
+
+/// Checks the layout (UF, model) and the mod 11 check digit of an access key,
+/// which tells it apart from a 44-digit boleto barcode.
+fn is_access_key(digits: &str) -> bool {
+    if digits.len() != 44 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return false;
+    }
+    let uf: u32 = digits[..2].parse().unwrap_or(0);
+    if !(11..=53).contains(&uf) || !ACCESS_KEY_MODELS.contains(&&digits[20..22]) {
+        return false;
+    }
+    let sum: u32 = digits[..43]
+        .bytes()
+        .rev()
+        .enumerate()
+        .map(|(i, b)| (b - b'0') as u32 * (2 + i as u32 % 8))
+        .sum();
+    let check = match 11 - sum % 11 {
+        10 | 11 => 0,
+        digit => digit,
+    };
+    (digits.as_bytes()[43] - b'0') as u32 == check
+}
+
+fn classify(text: &str) -> ScannedKind {
+    let trimmed = text.trim();
+    let digits: String = trimmed.chars().filter(char::is_ascii_digit).collect();
+    // Only codes that are digits (and the usual punctuation) throughout
+    if trimmed
+        .chars()
+        .all(|c| c.is_ascii_digit() || " .-".contains(c))
+    {
+        if is_access_key(&digits) {
+            return ScannedKind::Chave;
+        }
+        if boleto::decode_boleto(digits).is_ok() {
+            return ScannedKind::Boleto;
+        }
+    }
+    ScannedKind::Other
+}
+
+fn symbology_name(format: &rxing::BarcodeFormat) -> Option<&'static str> {
+    match format {
+        rxing::BarcodeFormat::CODE_128 => Some("code128"),
+        rxing::BarcodeFormat::QR_CODE => Some("qr"),
+        rxing::BarcodeFormat::ITF => Some("itf"),
+        _ => None,
+    }
+}
+
+/// Every Code 128, QR and ITF code found in the image, keys and boletos first.
+fn scan(image: image::GrayImage) -> Vec<ScannedBarcode> {
+    let (width, height) = image.dimensions();
+    // rxing reports "not found" as an error; an image without codes is simply empty
+    let results = rxing::helpers::detect_multiple_in_luma(image.into_raw(), width, height)
+        .unwrap_or_default();
+
+    let mut scanned: Vec<ScannedBarcode> = Vec::new();
+    for result in &results {
+        let Some(symbology) = symbology_name(result.getBarcodeFormat()) else {
+            continue;
+        };
+        let text = result.getText().trim().to_string();
+        if text.is_empty() || scanned.iter().any(|barcode| barcode.text == text) {
+            continue;
+        }
+        scanned.push(ScannedBarcode {
+            kind: classify(&text),
+            text,
+            symbology: symbology.to_string(),
+        });
+    }
+    scanned.sort_by_key(|barcode| barcode.kind == ScannedKind::Other);
+    scanned
+}
+
+fn clipboard_image(app: &tauri::AppHandle) -> Result<image::GrayImage, AppError> {
+    let image = app.clipboard().read_image().map_err(|_| {
+        AppError::InvalidInput("A área de transferência não contém uma imagem".into())
+    })?;
+    let rgba = image::RgbaImage::from_raw(image.width(), image.height(), image.rgba().to_vec())
+        .ok_or_else(|| AppError::InvalidInput("Imagem inválida na área de transferência".into()))?;
+    Ok(image::DynamicImage::ImageRgba8(rgba).to_luma8())
+}
+
+/// Reads barcodes from the image at `path`, or from the clipboard without one
+/// (e.g. a screenshot of a DANFE or boleto). When the first code is an access
+/// key or a boleto, the matching tool is opened with it.
+#[tauri::command]
+pub async fn scan_barcode_from_image(
+    app: tauri::AppHandle,
+    path: Option<String>,
+) -> Result<Vec<ScannedBarcode>, AppError> {
+    crate::metrics::timed("scan_barcode_from_image", async move {
+        let handle = app.clone();
+        let scanned = tauri::async_runtime::spawn_blocking(move || {
+            let image = match path.filter(|path| !path.trim().is_empty()) {
+                Some(path) => image::open(&path)
+                    .map_err(|e| AppError::InvalidInput(format!("Falha ao abrir a imagem: {}", e)))?
+                    .to_luma8(),
+                None => clipboard_image(&handle)?,
+            };
+            Ok::<_, AppError>(scan(image))
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao ler os códigos da imagem", e))??;
+        if scanned.is_empty() {
+            return Err(AppError::InvalidInput(
+                "Nenhum código de barras ou QR code encontrado na imagem".into(),
+            ));
+        }
+
+        let first = &scanned[0];
+        let route = match first.kind {
+            ScannedKind::Chave => Some(("nfe", "chave")),
+            ScannedKind::Boleto => Some(("boleto", "codigo")),
+            ScannedKind::Other => None,
+        };
+        if let Some((tool, param)) = route {
+            let digits: String = first.text.chars().filter(char::is_ascii_digit).collect();
+            let mut params = serde_json::Map::new();
+            params.insert(param.to_string(), digits.into());
+            deep_link::navigate(
+                &app,
+                Navigation {
+                    tool: tool.to_string(),
+                    params,
+                },
+            );
+        }
+        Ok(scanned)
+    })
+    .await
+}
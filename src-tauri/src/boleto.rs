@@ -0,0 +1,304 @@
+// ── Boleto Module ──────────────────────────────────────────────
+use crate::error::AppError;
+use chrono::{Duration, NaiveDate};
+
+#[derive(serde::Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BoletoKind {
+    /// Bank slip (boleto de cobrança), 44-digit barcode / 47-digit line.
+    Bancario,
+    /// Utility bills, taxes and other convênio documents, starting with 8.
+    Arrecadacao,
+}
+
+#[derive(serde::Serialize)]
+pub struct BoletoInfo {
+    pub kind: BoletoKind,
+    pub barcode: String,
+    /// Formatted the way it is printed on the document.
+    pub linha_digitavel: String,
+    pub bank_code: Option<String>,
+    pub bank_name: Option<String>,
+    /// Arrecadação segment, e.g. "Energia elétrica e gás".
+    pub segment: Option<String>,
+    /// Convênio / órgão code, or the CNPJ prefix for segment 6.
+    pub company_id: Option<String>,
+    /// `YYYY-MM-DD`; for arrecadação only when the issuer put it in the free field.
+    pub due_date: Option<String>,
+    /// `None` when the document leaves the amount open.
+    pub amount_cents: Option<u64>,
+}
+
+fn digits_of(code: &str) -> Vec<u32> {
+    code.bytes().map(|b| (b - b'0') as u32).collect()
+}
+
+/// Módulo 10: weights 2,1,2,1... from the right, summing the digits of each product.
+fn mod10(digits: &str) -> u32 {
+    let sum: u32 = digits_of(digits)
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, d)| {
+            let product = d * if i % 2 == 0 { 2 } else { 1 };
+            product / 10 + product % 10
+        })
+        .sum();
+    (10 - sum % 10) % 10
+}
+
+/// Módulo 11 sum with weights 2..9 from the right.
+fn mod11_sum(digits: &str) -> u32 {
+    digits_of(digits)
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, d)| d * (2 + (i as u32 % 8)))
+        .sum()
+}
+
+/// General check digit of a bank slip barcode: 0, 10 and 11 become 1.
+fn mod11_bancario(digits: &str) -> u32 {
+    match 11 - mod11_sum(digits) % 11 {
+        0 | 10 | 11 => 1,
+        dv => dv,
+    }
+}
+
+/// Módulo 11 as used by arrecadação: remainders 0 and 1 give 0.
+fn mod11_arrecadacao(digits: &str) -> u32 {
+    match mod11_sum(digits) % 11 {
+        0 | 1 => 0,
+        rest => 11 - rest,
+    }
+}
+
+fn digit_at(code: &str, index: usize) -> u32 {
+    (code.as_bytes()[index] - b'0') as u32
+}
+
+fn check(ok: bool, what: &str) -> Result<(), AppError> {
+    if ok {
+        Ok(())
+    } else {
+        Err(AppError::InvalidInput(format!(
+            "Dígito verificador inválido ({}). Confira se o código foi digitado corretamente.",
+            what
+        )))
+    }
+}
+
+fn bank_name(code: &str) -> Option<&'static str> {
+    Some(match code {
+        "001" => "Banco do Brasil",
+        "004" => "Banco do Nordeste",
+        "033" => "Santander",
+        "041" => "Banrisul",
+        "070" => "BRB",
+        "077" => "Banco Inter",
+        "085" => "Ailos",
+        "104" => "Caixa Econômica Federal",
+        "136" => "Unicred",
+        "208" => "BTG Pactual",
+        "212" => "Banco Original",
+        "237" => "Bradesco",
+        "260" => "Nubank",
+        "290" => "PagSeguro",
+        "323" => "Mercado Pago",
+        "336" => "C6 Bank",
+        "341" => "Itaú",
+        "389" => "Mercantil do Brasil",
+        "422" => "Safra",
+        "633" => "Rendimento",
+        "655" => "Banco Votorantim",
+        "745" => "Citibank",
+        "748" => "Sicredi",
+        "756" => "Sicoob",
+        _ => return None,
+    })
+}
+
+fn segment_name(segment: u8) -> Option<&'static str> {
+    Some(match segment {
+        b'1' => "Prefeituras",
+        b'2' => "Saneamento",
+        b'3' => "Energia elétrica e gás",
+        b'4' => "Telecomunicações",
+        b'5' => "Órgãos governamentais",
+        b'6' => "Carnês e assemelhados",
+        b'7' => "Multas de trânsito",
+        b'9' => "Uso exclusivo do banco",
+        _ => return None,
+    })
+}
+
+/// The due date factor counts days from 1997-10-07 and restarted at 1000 on
+/// 2025-02-22 after reaching 9999, so the same factor maps to two dates; the one
+/// closer to today is the right one.
+fn due_date_from_factor(factor: i64) -> Option<NaiveDate> {
+    if factor == 0 {
+        return None;
+    }
+    let first_cycle = NaiveDate::from_ymd_opt(1997, 10, 7)? + Duration::days(factor);
+    let second_cycle = NaiveDate::from_ymd_opt(2025, 2, 22)? + Duration::days(factor - 1000);
+    let today = chrono::Local::now().date_naive();
+    Some(
+        if (first_cycle - today).num_days().abs() <= (second_cycle - today).num_days().abs() {
+            first_cycle
+        } else {
+            second_cycle
+        },
+    )
+}
+
+fn decode_bancario(barcode: String) -> Result<BoletoInfo, AppError> {
+    let without_dv = format!("{}{}", &barcode[..4], &barcode[5..]);
+    check(
+        mod11_bancario(&without_dv) == digit_at(&barcode, 4),
+        "código de barras",
+    )?;
+
+    let free = &barcode[19..44];
+    let field1 = format!("{}{}", &barcode[..4], &free[..5]);
+    let field2 = &free[5..15];
+    let field3 = &free[15..25];
+    let linha_digitavel = format!(
+        "{}.{}{} {}.{}{} {}.{}{} {} {}",
+        &field1[..5],
+        &field1[5..],
+        mod10(&field1),
+        &field2[..5],
+        &field2[5..],
+        mod10(field2),
+        &field3[..5],
+        &field3[5..],
+        mod10(field3),
+        &barcode[4..5],
+        &barcode[5..19],
+    );
+
+    let factor: i64 = barcode[5..9].parse().unwrap_or(0);
+    let amount: u64 = barcode[9..19].parse().unwrap_or(0);
+    let bank_code = barcode[..3].to_string();
+
+    Ok(BoletoInfo {
+        kind: BoletoKind::Bancario,
+        linha_digitavel,
+        bank_name: bank_name(&bank_code).map(str::to_string),
+        bank_code: Some(bank_code),
+        segment: None,
+        company_id: None,
+        due_date: due_date_from_factor(factor).map(|d| d.format("%Y-%m-%d").to_string()),
+        amount_cents: (amount > 0).then_some(amount),
+        barcode,
+    })
+}
+
+/// Rebuilds the barcode from the 47-digit line, checking each field on the way.
+fn bancario_line_to_barcode(line: &str) -> Result<String, AppError> {
+    for (field, (start, end)) in [(0, 9), (10, 20), (21, 31)].into_iter().enumerate() {
+        check(
+            mod10(&line[start..end]) == digit_at(line, end),
+            &format!("campo {}", field + 1),
+        )?;
+    }
+    Ok(format!(
+        "{}{}{}{}{}{}",
+        &line[..4],
+        &line[32..33],
+        &line[33..47],
+        &line[4..9],
+        &line[10..20],
+        &line[21..31]
+    ))
+}
+
+fn arrecadacao_dv(barcode: &str, digits: &str) -> u32 {
+    match barcode.as_bytes()[2] {
+        b'6' | b'7' => mod10(digits),
+        _ => mod11_arrecadacao(digits),
+    }
+}
+
+fn decode_arrecadacao(barcode: String) -> Result<BoletoInfo, AppError> {
+    if !matches!(barcode.as_bytes()[2], b'6'..=b'9') {
+        return Err(AppError::InvalidInput(
+            "Identificador de valor inválido no código de arrecadação".into(),
+        ));
+    }
+    let without_dv = format!("{}{}", &barcode[..3], &barcode[4..]);
+    check(
+        arrecadacao_dv(&barcode, &without_dv) == digit_at(&barcode, 3),
+        "código de barras",
+    )?;
+
+    let linha_digitavel = (0..4)
+        .map(|block| {
+            let part = &barcode[block * 11..block * 11 + 11];
+            format!("{}-{}", part, arrecadacao_dv(&barcode, part))
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let segment = barcode.as_bytes()[1];
+    // Segment 6 identifies the issuer by CNPJ prefix instead of a 4-digit convênio
+    let (company_id, free) = if segment == b'6' {
+        (&barcode[15..23], &barcode[23..44])
+    } else {
+        (&barcode[15..19], &barcode[19..44])
+    };
+    // Value IDs 7 and 9 carry a reference quantity, not an amount in reais
+    let amount: u64 = barcode[4..15].parse().unwrap_or(0);
+    let amount_cents =
+        (matches!(barcode.as_bytes()[2], b'6' | b'8') && amount > 0).then_some(amount);
+    // Many issuers start the free field with the due date as YYYYMMDD
+    let due_date = NaiveDate::parse_from_str(&free[..8], "%Y%m%d")
+        .ok()
+        .filter(|date| (2000..=2099).contains(&chrono::Datelike::year(date)))
+        .map(|date| date.format("%Y-%m-%d").to_string());
+
+    Ok(BoletoInfo {
+        kind: BoletoKind::Arrecadacao,
+        linha_digitavel,
+        bank_code: None,
+        bank_name: None,
+        segment: segment_name(segment).map(str::to_string),
+        company_id: Some(company_id.to_string()),
+        due_date,
+        amount_cents,
+        barcode,
+    })
+}
+
+fn arrecadacao_line_to_barcode(line: &str) -> Result<String, AppError> {
+    let barcode: String = (0..4)
+        .map(|block| &line[block * 12..block * 12 + 11])
+        .collect();
+    for block in 0..4 {
+        let part = &line[block * 12..block * 12 + 11];
+        check(
+            arrecadacao_dv(&barcode, part) == digit_at(line, block * 12 + 11),
+            &format!("bloco {}", block + 1),
+        )?;
+    }
+    Ok(barcode)
+}
+
+/// Accepts the barcode (44 digits) or the linha digitável (47 for bank slips, 48 for
+/// arrecadação), with or without punctuation, and checks every check digit.
+#[tauri::command]
+pub fn decode_boleto(code: String) -> Result<BoletoInfo, AppError> {
+    let digits: String = code.chars().filter(char::is_ascii_digit).collect();
+    let arrecadacao = digits.starts_with('8');
+
+    match (digits.len(), arrecadacao) {
+        (44, false) => decode_bancario(digits),
+        (44, true) => decode_arrecadacao(digits),
+        (47, false) => decode_bancario(bancario_line_to_barcode(&digits)?),
+        (48, true) => decode_arrecadacao(arrecadacao_line_to_barcode(&digits)?),
+        _ => Err(AppError::InvalidInput(format!(
+            "Código com {} dígitos. Informe o código de barras (44) ou a linha digitável (47 ou 48).",
+            digits.len()
+        ))),
+    }
+}
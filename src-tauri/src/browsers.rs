@@ -0,0 +1,259 @@
+// ── Browsers Module ────────────────────────────────────────────
+use std::path::PathBuf;
+
+/// Browsers a link can be sent to explicitly, instead of the system default.
+#[derive(Clone, Copy, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Browser {
+    Edge,
+    Chrome,
+    Firefox,
+}
+
+#[derive(serde::Serialize)]
+pub struct BrowserProfile {
+    /// Value passed back as `profile` to `open_external_link`.
+    pub id: String,
+    pub name: String,
+}
+
+impl Browser {
+    /// Same value the frontend sends, used when logging opened links.
+    pub fn id(self) -> &'static str {
+        match self {
+            Browser::Edge => "edge",
+            Browser::Chrome => "chrome",
+            Browser::Firefox => "firefox",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Browser::Edge => "Microsoft Edge",
+            Browser::Chrome => "Google Chrome",
+            Browser::Firefox => "Mozilla Firefox",
+        }
+    }
+
+    #[cfg(windows)]
+    fn executable_name(self) -> &'static str {
+        match self {
+            Browser::Edge => "msedge.exe",
+            Browser::Chrome => "chrome.exe",
+            Browser::Firefox => "firefox.exe",
+        }
+    }
+
+    /// Install locations relative to `PROGRAMFILES`, `PROGRAMFILES(X86)` or `LOCALAPPDATA`.
+    #[cfg(windows)]
+    fn install_path(self) -> &'static str {
+        match self {
+            Browser::Edge => r"Microsoft\Edge\Application\msedge.exe",
+            Browser::Chrome => r"Google\Chrome\Application\chrome.exe",
+            Browser::Firefox => r"Mozilla Firefox\firefox.exe",
+        }
+    }
+
+    #[cfg(not(windows))]
+    fn command_names(self) -> &'static [&'static str] {
+        match self {
+            Browser::Edge => &["microsoft-edge", "microsoft-edge-stable"],
+            Browser::Chrome => &["google-chrome", "google-chrome-stable", "chromium"],
+            Browser::Firefox => &["firefox"],
+        }
+    }
+
+    fn private_flag(self) -> &'static str {
+        match self {
+            Browser::Edge => "--inprivate",
+            Browser::Chrome => "--incognito",
+            Browser::Firefox => "--private-window",
+        }
+    }
+
+    fn profile_args(self, profile: &str) -> Vec<String> {
+        match self {
+            Browser::Edge | Browser::Chrome => vec![format!("--profile-directory={}", profile)],
+            Browser::Firefox => vec!["-P".into(), profile.into()],
+        }
+    }
+
+    /// Folder holding `Local State` (Chromium) or `profiles.ini` (Firefox).
+    fn user_data_dir(self) -> Option<PathBuf> {
+        #[cfg(windows)]
+        let path = {
+            let local = || std::env::var("LOCALAPPDATA").ok().map(PathBuf::from);
+            let roaming = || std::env::var("APPDATA").ok().map(PathBuf::from);
+            match self {
+                Browser::Edge => local()?.join(r"Microsoft\Edge\User Data"),
+                Browser::Chrome => local()?.join(r"Google\Chrome\User Data"),
+                Browser::Firefox => roaming()?.join(r"Mozilla\Firefox"),
+            }
+        };
+
+        #[cfg(not(windows))]
+        let path = {
+            let home = PathBuf::from(std::env::var("HOME").ok()?);
+            match self {
+                Browser::Edge => home.join(".config/microsoft-edge"),
+                Browser::Chrome => home.join(".config/google-chrome"),
+                Browser::Firefox => home.join(".mozilla/firefox"),
+            }
+        };
+
+        Some(path)
+    }
+}
+
+/// Reads a `REG_SZ` value, `None` when the key or value is missing.
+#[cfg(windows)]
+fn read_registry_string(
+    root: windows_sys::Win32::System::Registry::HKEY,
+    sub_key: &str,
+) -> Option<String> {
+    use windows_sys::Win32::System::Registry::{RegGetValueW, RRF_RT_REG_SZ};
+
+    let sub_key_wide: Vec<u16> = sub_key.encode_utf16().chain(std::iter::once(0)).collect();
+    let mut buffer = vec![0u16; 1024];
+    let mut size = (buffer.len() * 2) as u32;
+
+    let result = unsafe {
+        RegGetValueW(
+            root,
+            sub_key_wide.as_ptr(),
+            std::ptr::null(),
+            RRF_RT_REG_SZ,
+            std::ptr::null_mut(),
+            buffer.as_mut_ptr() as *mut _,
+            &mut size,
+        )
+    };
+    if result != 0 {
+        return None;
+    }
+
+    let len = (size as usize / 2).saturating_sub(1);
+    Some(String::from_utf16_lossy(&buffer[..len]))
+}
+
+/// App Paths registration first (covers custom install folders), then the usual locations.
+#[cfg(windows)]
+fn resolve_executable(browser: Browser) -> Option<PathBuf> {
+    use windows_sys::Win32::System::Registry::{HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE};
+
+    let app_paths_key = format!(
+        r"SOFTWARE\Microsoft\Windows\CurrentVersion\App Paths\{}",
+        browser.executable_name()
+    );
+    for root in [HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE] {
+        if let Some(path) = read_registry_string(root, &app_paths_key) {
+            let path = PathBuf::from(path.trim_matches('"'));
+            if path.exists() {
+                return Some(path);
+            }
+        }
+    }
+
+    ["PROGRAMFILES", "PROGRAMFILES(X86)", "LOCALAPPDATA"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .map(|base| PathBuf::from(base).join(browser.install_path()))
+        .find(|path| path.exists())
+}
+
+#[cfg(not(windows))]
+fn resolve_executable(browser: Browser) -> Option<PathBuf> {
+    let search_path = std::env::var_os("PATH")?;
+    browser.command_names().iter().find_map(|name| {
+        std::env::split_paths(&search_path)
+            .map(|dir| dir.join(name))
+            .find(|path| path.is_file())
+    })
+}
+
+pub fn open_in_browser(
+    browser: Browser,
+    profile: Option<&str>,
+    private: bool,
+    url: &str,
+) -> Result<(), String> {
+    let executable = resolve_executable(browser)
+        .ok_or_else(|| format!("{} não está instalado", browser.label()))?;
+
+    let mut command = std::process::Command::new(executable);
+    if let Some(profile) = profile.map(str::trim).filter(|p| !p.is_empty()) {
+        command.args(browser.profile_args(profile));
+    }
+    if private {
+        command.arg(browser.private_flag());
+    }
+    command
+        .arg(url)
+        .spawn()
+        .map_err(|e| format!("Falha ao abrir {}: {}", browser.label(), e))?;
+    Ok(())
+}
+
+/// Chromium keeps display names for each profile folder in `Local State`.
+fn chromium_profiles(user_data: &std::path::Path) -> Vec<BrowserProfile> {
+    let Some(local_state) = std::fs::read_to_string(user_data.join("Local State"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+    else {
+        return Vec::new();
+    };
+
+    let Some(cache) = local_state
+        .pointer("/profile/info_cache")
+        .and_then(|cache| cache.as_object())
+    else {
+        return Vec::new();
+    };
+
+    cache
+        .iter()
+        .map(|(directory, info)| BrowserProfile {
+            id: directory.clone(),
+            name: info
+                .get("name")
+                .and_then(|name| name.as_str())
+                .unwrap_or(directory)
+                .to_string(),
+        })
+        .collect()
+}
+
+/// Firefox selects profiles by the `Name=` entries of `profiles.ini`.
+fn firefox_profiles(user_data: &std::path::Path) -> Vec<BrowserProfile> {
+    let Ok(content) = std::fs::read_to_string(user_data.join("profiles.ini")) else {
+        return Vec::new();
+    };
+
+    let mut in_profile = false;
+    let mut profiles = Vec::new();
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_profile = line.starts_with("[Profile");
+        } else if let Some(name) = line.strip_prefix("Name=").filter(|_| in_profile) {
+            profiles.push(BrowserProfile {
+                id: name.to_string(),
+                name: name.to_string(),
+            });
+        }
+    }
+    profiles
+}
+
+#[tauri::command]
+pub fn list_browser_profiles(browser: Browser) -> Vec<BrowserProfile> {
+    let Some(user_data) = browser.user_data_dir() else {
+        return Vec::new();
+    };
+
+    let mut profiles = match browser {
+        Browser::Edge | Browser::Chrome => chromium_profiles(&user_data),
+        Browser::Firefox => firefox_profiles(&user_data),
+    };
+    profiles.sort_by_key(|profile| profile.name.to_lowercase());
+    profiles
+}
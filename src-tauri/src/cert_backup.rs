@@ -0,0 +1,208 @@
+// ── Certificate Backup Module ──────────────────────────────────
+use crate::error::AppError;
+use crate::CertInfo;
+use std::io::{Read, Write};
+
+const MANIFEST_NAME: &str = "certificados.json";
+
+#[derive(serde::Serialize)]
+pub struct SkippedCertificate {
+    pub subject: String,
+    pub thumbprint: String,
+    pub reason: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct CertBackupReport {
+    pub output_path: String,
+    pub exported: Vec<CertInfo>,
+    /// Certificates with a private key that could not be exported, e.g. A3 tokens.
+    pub skipped: Vec<SkippedCertificate>,
+}
+
+#[derive(serde::Serialize)]
+pub struct RestoreFailure {
+    pub file: String,
+    pub reason: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct CertRestoreReport {
+    pub installed: Vec<CertInfo>,
+    pub failed: Vec<RestoreFailure>,
+}
+
+#[cfg(any(windows, target_os = "linux"))]
+fn export_identity(thumbprint: &str, password: &str) -> Result<Vec<u8>, AppError> {
+    crate::nfe::export_cert_pfx_with_password(thumbprint, password).map(|(pfx, _)| pfx)
+}
+
+#[cfg(not(any(windows, target_os = "linux")))]
+fn export_identity(_thumbprint: &str, _password: &str) -> Result<Vec<u8>, AppError> {
+    Err(AppError::Unsupported(
+        "Exportação de certificados disponível apenas no Windows e Linux".into(),
+    ))
+}
+
+fn require_password(password: &str) -> Result<(), AppError> {
+    if password.is_empty() {
+        return Err(AppError::InvalidInput(
+            "Informe uma senha para proteger o backup".into(),
+        ));
+    }
+    Ok(())
+}
+
+fn write_backup(
+    output_path: &str,
+    password: &str,
+    entries: &[(CertInfo, Vec<u8>)],
+) -> Result<(), AppError> {
+    let file = std::fs::File::create(output_path)
+        .map_err(|e| AppError::io("Falha ao criar arquivo de backup", e))?;
+    let mut writer = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Stored)
+        .with_aes_encryption(zip::AesMode::Aes256, password);
+
+    for (info, pfx) in entries {
+        writer
+            .start_file(format!("{}.pfx", info.thumbprint.replace(':', "")), options)
+            .map_err(|e| AppError::internal("Falha ao gravar backup", e))?;
+        writer
+            .write_all(pfx)
+            .map_err(|e| AppError::io("Falha ao gravar backup", e))?;
+    }
+    // Subjects and expiry dates, to know what the archive holds before restoring it
+    let manifest =
+        serde_json::to_vec_pretty(&entries.iter().map(|(info, _)| info).collect::<Vec<_>>())
+            .map_err(|e| AppError::internal("Falha ao gravar backup", e))?;
+    writer
+        .start_file(MANIFEST_NAME, options)
+        .map_err(|e| AppError::internal("Falha ao gravar backup", e))?;
+    writer
+        .write_all(&manifest)
+        .map_err(|e| AppError::io("Falha ao gravar backup", e))?;
+
+    writer
+        .finish()
+        .map_err(|e| AppError::internal("Falha ao finalizar backup", e))?
+        .flush()
+        .map_err(|e| AppError::io("Falha ao finalizar backup", e))
+}
+
+/// Exports every certificate with an exportable private key into one ZIP encrypted
+/// with AES-256. Each .pfx inside is protected by the same `password`, so the
+/// archive can be restored on the reimaged machine with `restore_certificates`.
+#[tauri::command]
+pub async fn backup_certificates(
+    output_path: String,
+    password: String,
+) -> Result<CertBackupReport, AppError> {
+    crate::metrics::timed("backup_certificates", async move {
+        require_password(&password)?;
+        tauri::async_runtime::spawn_blocking(move || {
+            let mut entries = Vec::new();
+            let mut skipped = Vec::new();
+            for info in crate::certs_impl()? {
+                if !info.has_private_key {
+                    continue;
+                }
+                let exported = if info.is_exportable {
+                    export_identity(&info.thumbprint, &password)
+                } else {
+                    Err(AppError::InvalidInput(
+                        "Chave privada não exportável (token A3 ou importada sem exportação)"
+                            .into(),
+                    ))
+                };
+                match exported {
+                    Ok(pfx) => entries.push((info, pfx)),
+                    Err(e) => skipped.push(SkippedCertificate {
+                        subject: info.subject,
+                        thumbprint: info.thumbprint,
+                        reason: e.to_string(),
+                    }),
+                }
+            }
+            if entries.is_empty() {
+                return Err(AppError::NotFound(
+                    "Nenhum certificado exportável encontrado".into(),
+                ));
+            }
+
+            let written = write_backup(&output_path, &password, &entries);
+            // The key material should not linger in memory
+            for (_, pfx) in entries.iter_mut() {
+                pfx.fill(0);
+            }
+            written?;
+            Ok(CertBackupReport {
+                output_path,
+                exported: entries.into_iter().map(|(info, _)| info).collect(),
+                skipped,
+            })
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao gerar backup de certificados", e))?
+    })
+    .await
+}
+
+/// Installs every certificate in a backup made by `backup_certificates`. One that
+/// fails does not stop the others.
+#[tauri::command]
+pub async fn restore_certificates(
+    path: String,
+    password: String,
+) -> Result<CertRestoreReport, AppError> {
+    crate::metrics::timed("restore_certificates", async move {
+        require_password(&password)?;
+        tauri::async_runtime::spawn_blocking(move || {
+            let file = std::fs::File::open(&path)
+                .map_err(|e| AppError::io("Falha ao abrir arquivo de backup", e))?;
+            let mut zip = zip::ZipArchive::new(file).map_err(|e| {
+                AppError::InvalidInput("Arquivo de backup inválido".into()).with_details(e)
+            })?;
+
+            let mut report = CertRestoreReport {
+                installed: Vec::new(),
+                failed: Vec::new(),
+            };
+            for i in 0..zip.len() {
+                let mut entry = zip
+                    .by_index_decrypt(i, password.as_bytes())
+                    .map_err(crate::client_folders::zip_entry_error)?;
+                let name = entry.name().to_string();
+                if !name.to_ascii_lowercase().ends_with(".pfx") {
+                    continue;
+                }
+                let mut pfx = Vec::new();
+                entry
+                    .read_to_end(&mut pfx)
+                    .map_err(|e| AppError::io("Falha ao ler arquivo de backup", e))?;
+                let installed = crate::install_pfx_impl(&mut pfx, &password);
+                pfx.fill(0);
+                match installed {
+                    Ok(info) => report.installed.push(info),
+                    Err(AppError::Unsupported(message)) => {
+                        return Err(AppError::Unsupported(message))
+                    }
+                    Err(e) => report.failed.push(RestoreFailure {
+                        file: name,
+                        reason: e.to_string(),
+                    }),
+                }
+            }
+            if report.installed.is_empty() && report.failed.is_empty() {
+                return Err(AppError::InvalidInput(
+                    "O arquivo não contém certificados".into(),
+                ));
+            }
+            Ok(report)
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao restaurar certificados", e))?
+    })
+    .await
+}
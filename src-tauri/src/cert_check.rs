@@ -0,0 +1,241 @@
+// ── Certificate Check Module ───────────────────────────────────
+use crate::error::AppError;
+
+/// UF answering the status query when none is given.
+const DEFAULT_UF: &str = "RS";
+
+const SVRS_STATUS: &str = "https://nfe.svrs.rs.gov.br/ws/NfeStatusServico/NfeStatusServico4.asmx";
+const SVAN_STATUS: &str =
+    "https://www.sefazvirtual.fazenda.gov.br/NFeStatusServico4/NFeStatusServico4.asmx";
+
+/// NFeStatusServico4 endpoints of the UFs with their own authorizer; the others
+/// are served by SVRS, except MA which uses SVAN.
+const STATUS_SERVICO: &[(&str, &str)] = &[
+    (
+        "AM",
+        "https://nfe.sefaz.am.gov.br/services2/services/NfeStatusServico4",
+    ),
+    (
+        "BA",
+        "https://nfe.sefaz.ba.gov.br/webservices/NFeStatusServico4/NFeStatusServico4.asmx",
+    ),
+    (
+        "GO",
+        "https://nfe.sefaz.go.gov.br/nfe/services/NFeStatusServico4",
+    ),
+    (
+        "MG",
+        "https://nfe.fazenda.mg.gov.br/nfe2/services/NFeStatusServico4",
+    ),
+    ("MS", "https://nfe.sefaz.ms.gov.br/ws/NFeStatusServico4"),
+    (
+        "MT",
+        "https://nfe.sefaz.mt.gov.br/nfews/v2/services/NfeStatusServico4",
+    ),
+    (
+        "PE",
+        "https://nfe.sefaz.pe.gov.br/nfe-service/services/NFeStatusServico4",
+    ),
+    ("PR", "https://nfe.sefa.pr.gov.br/nfe/NFeStatusServico4"),
+    (
+        "RS",
+        "https://nfe.sefazrs.rs.gov.br/ws/NfeStatusServico/NfeStatusServico4.asmx",
+    ),
+    (
+        "SP",
+        "https://nfe.fazenda.sp.gov.br/ws/nfestatusservico4.asmx",
+    ),
+    ("MA", SVAN_STATUS),
+];
+
+/// IBGE codes, sent as `cUF` in the request.
+const UF_CODES: &[(&str, u32)] = &[
+    ("RO", 11),
+    ("AC", 12),
+    ("AM", 13),
+    ("RR", 14),
+    ("PA", 15),
+    ("AP", 16),
+    ("TO", 17),
+    ("MA", 21),
+    ("PI", 22),
+    ("CE", 23),
+    ("RN", 24),
+    ("PB", 25),
+    ("PE", 26),
+    ("AL", 27),
+    ("SE", 28),
+    ("BA", 29),
+    ("MG", 31),
+    ("ES", 32),
+    ("RJ", 33),
+    ("SP", 35),
+    ("PR", 41),
+    ("SC", 42),
+    ("RS", 43),
+    ("MS", 50),
+    ("MT", 51),
+    ("GO", 52),
+    ("DF", 53),
+];
+
+/// 107: "Serviço em Operação".
+const STATUS_IN_OPERATION: &str = "107";
+
+#[derive(serde::Serialize)]
+pub struct CertificateTest {
+    pub thumbprint: String,
+    pub uf: String,
+    pub endpoint: String,
+    /// The SEFAZ accepted the certificate in the TLS handshake and answered.
+    pub handshake_ok: bool,
+    /// Handshake succeeded and the service reported itself in operation.
+    pub success: bool,
+    pub c_stat: Option<String>,
+    pub x_motivo: Option<String>,
+    /// Why the handshake or the request failed, when it did.
+    pub error: Option<String>,
+    pub elapsed_ms: u64,
+}
+
+fn endpoint(uf: &str) -> &'static str {
+    STATUS_SERVICO
+        .iter()
+        .find(|(code, _)| *code == uf)
+        .map(|(_, url)| *url)
+        .unwrap_or(SVRS_STATUS)
+}
+
+fn uf_code(uf: &str) -> Result<u32, AppError> {
+    UF_CODES
+        .iter()
+        .find(|(code, _)| *code == uf)
+        .map(|(_, ibge)| *ibge)
+        .ok_or_else(|| AppError::InvalidInput(format!("UF inválida: {}", uf)))
+}
+
+fn build_request(uf_code: u32) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?><soap12:Envelope xmlns:soap12="http://www.w3.org/2003/05/soap-envelope" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xmlns:xsd="http://www.w3.org/2001/XMLSchema"><soap12:Body><nfeDadosMsg xmlns="http://www.portalfiscal.inf.br/nfe/wsdl/NFeStatusServico4"><consStatServ xmlns="http://www.portalfiscal.inf.br/nfe" versao="4.00"><tpAmb>1</tpAmb><cUF>{uf}</cUF><xServ>STATUS</xServ></consStatServ></nfeDadosMsg></soap12:Body></soap12:Envelope>"#,
+        uf = uf_code,
+    )
+}
+
+/// Outcome of the exchange: `Ok(body)` once the server answered with success,
+/// `Err((handshake_ok, message))` otherwise.
+#[cfg(any(windows, target_os = "linux"))]
+async fn send_status_request(
+    thumbprint: &str,
+    url: &str,
+    body: String,
+) -> Result<Result<String, (bool, String)>, AppError> {
+    let (mut pfx_bytes, password, _) = crate::nfe::export_cert_pfx(thumbprint)?;
+    let identity = reqwest::Identity::from_pkcs12_der(&pfx_bytes, &password)
+        .map_err(|e| AppError::internal("Falha ao criar identidade TLS", e));
+    pfx_bytes.fill(0);
+
+    let client = reqwest::Client::builder()
+        .identity(identity?)
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .map_err(|e| AppError::internal("Falha ao criar cliente HTTP", e))?;
+    let response = match client
+        .post(url)
+        .header("Content-Type", "application/soap+xml; charset=utf-8")
+        .body(body)
+        .send()
+        .await
+    {
+        Ok(response) => response,
+        Err(e) => {
+            return Ok(Err((
+                false,
+                AppError::ServiceUnavailable("Falha na comunicação com SEFAZ".into())
+                    .with_details(e)
+                    .to_string(),
+            )))
+        }
+    };
+    let status = response.status();
+    let text = match response.text().await {
+        Ok(text) => text,
+        Err(e) => {
+            return Ok(Err((
+                true,
+                AppError::ServiceUnavailable("Falha ao ler resposta da SEFAZ".into())
+                    .with_details(e)
+                    .to_string(),
+            )))
+        }
+    };
+    if !status.is_success() {
+        return Ok(Err((true, format!("SEFAZ retornou status {}", status))));
+    }
+    Ok(Ok(text))
+}
+
+#[cfg(not(any(windows, target_os = "linux")))]
+async fn send_status_request(
+    _thumbprint: &str,
+    _url: &str,
+    _body: String,
+) -> Result<Result<String, (bool, String)>, AppError> {
+    Err(AppError::Unsupported(
+        "Teste de certificado disponível apenas no Windows e Linux".into(),
+    ))
+}
+
+/// Performs the mutual-TLS handshake against the NFeStatusServico of `uf`
+/// (default RS) with the certificate and reports whether the SEFAZ accepted it.
+/// Handshake and service failures come back in the result, not as errors.
+#[tauri::command]
+pub async fn test_certificate(
+    thumbprint: String,
+    uf: Option<String>,
+) -> Result<CertificateTest, AppError> {
+    crate::metrics::timed("test_certificate", async move {
+        let uf = uf
+            .map(|uf| uf.trim().to_uppercase())
+            .filter(|uf| !uf.is_empty())
+            .unwrap_or_else(|| DEFAULT_UF.into());
+        let code = uf_code(&uf)?;
+        let url = endpoint(&uf);
+        // The store lookup may list every certificate to match a SHA-256 thumbprint
+        let thumbprint =
+            tauri::async_runtime::spawn_blocking(move || crate::sha1_thumbprint(&thumbprint))
+                .await
+                .map_err(|e| AppError::internal("Falha ao localizar certificado", e))??;
+
+        let started = std::time::Instant::now();
+        let outcome = send_status_request(&thumbprint, url, build_request(code)).await?;
+        let mut test = CertificateTest {
+            thumbprint,
+            uf,
+            endpoint: url.into(),
+            handshake_ok: true,
+            success: false,
+            c_stat: None,
+            x_motivo: None,
+            error: None,
+            elapsed_ms: started.elapsed().as_millis() as u64,
+        };
+        match outcome {
+            Ok(body) => {
+                let tag = |name: &str| {
+                    crate::nfe::extract_tag_content(&body, name)
+                        .map(|value| value.trim().to_string())
+                        .filter(|value| !value.is_empty())
+                };
+                test.c_stat = tag("cStat");
+                test.x_motivo = tag("xMotivo");
+                test.success = test.c_stat.as_deref() == Some(STATUS_IN_OPERATION);
+            }
+            Err((handshake_ok, message)) => {
+                test.handshake_ok = handshake_ok;
+                test.error = Some(message);
+            }
+        }
+        Ok(test)
+    })
+    .await
+}
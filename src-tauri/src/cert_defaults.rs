@@ -0,0 +1,210 @@
+// ── Certificate Defaults Module ────────────────────────────────
+use crate::error::AppError;
+use crate::CertInfo;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use tauri::Manager;
+
+/// Preferred certificate per CNPJ, so queries for a company don't depend on
+/// picking the right one from the store list.
+pub struct CertDefaults {
+    conn: Mutex<Connection>,
+}
+
+#[derive(serde::Serialize)]
+pub struct CertificateGroup {
+    /// Digits only.
+    pub cnpj: String,
+    /// SHA-1 thumbprint of the chosen default, if it is still installed.
+    pub default_thumbprint: Option<String>,
+    /// Latest expiry first.
+    pub certificates: Vec<CertInfo>,
+}
+
+impl CertDefaults {
+    pub fn open(app: &tauri::AppHandle) -> Result<Self, String> {
+        let conn = crate::db::open(app)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS cert_defaults (
+                cnpj TEXT PRIMARY KEY,
+                thumbprint TEXT NOT NULL,
+                updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+            );",
+        )
+        .map_err(|e| format!("Falha ao criar tabela de certificados padrão: {}", e))?;
+        Ok(CertDefaults {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    fn get(&self, cnpj: &str) -> Result<Option<String>, AppError> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            "SELECT thumbprint FROM cert_defaults WHERE cnpj = ?1",
+            params![cnpj],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| AppError::internal("Falha ao consultar certificado padrão", e))
+    }
+
+    fn all(&self) -> Result<BTreeMap<String, String>, AppError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare("SELECT cnpj, thumbprint FROM cert_defaults")
+            .map_err(|e| AppError::internal("Falha ao consultar certificados padrão", e))?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(|e| AppError::internal("Falha ao consultar certificados padrão", e))?;
+        rows.collect::<Result<_, _>>()
+            .map_err(|e| AppError::internal("Falha ao ler certificados padrão", e))
+    }
+
+    fn set(&self, cnpj: &str, thumbprint: Option<&str>) -> Result<(), AppError> {
+        let conn = self.conn.lock().unwrap();
+        match thumbprint {
+            Some(thumbprint) => conn.execute(
+                "INSERT OR REPLACE INTO cert_defaults (cnpj, thumbprint, updated_at)
+                 VALUES (?1, ?2, datetime('now'))",
+                params![cnpj, thumbprint],
+            ),
+            None => conn.execute("DELETE FROM cert_defaults WHERE cnpj = ?1", params![cnpj]),
+        }
+        .map_err(|e| AppError::internal("Falha ao salvar certificado padrão", e))?;
+        Ok(())
+    }
+}
+
+/// Certificates that can sign for a company, latest expiry first.
+fn signing_certs(cnpj: &str) -> Result<Vec<CertInfo>, AppError> {
+    let mut certs: Vec<CertInfo> = crate::certs_impl()?
+        .into_iter()
+        .filter(|cert| cert.has_private_key && cert.cnpj == cnpj)
+        .collect();
+    certs.sort_by(|a, b| b.not_after.cmp(&a.not_after));
+    Ok(certs)
+}
+
+/// SHA-1 thumbprint of the certificate to use for `cnpj`: the default set for it
+/// when still installed, otherwise the valid exportable one expiring last.
+pub(crate) fn resolve_thumbprint(app: &tauri::AppHandle, cnpj: &str) -> Result<String, AppError> {
+    let cnpj = crate::clients::normalize_cnpj(cnpj)?;
+    let certs = signing_certs(&cnpj)?;
+    let preferred = match app.try_state::<CertDefaults>() {
+        Some(defaults) => defaults.get(&cnpj)?,
+        None => None,
+    };
+    if let Some(cert) =
+        preferred.and_then(|thumbprint| certs.iter().find(|cert| cert.thumbprint == thumbprint))
+    {
+        return Ok(cert.thumbprint.clone());
+    }
+
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    certs
+        .into_iter()
+        .find(|cert| cert.is_exportable && (cert.not_after == "N/A" || cert.not_after >= today))
+        .map(|cert| cert.thumbprint)
+        .ok_or_else(|| {
+            AppError::NotFound(format!(
+                "Nenhum certificado válido e exportável para o CNPJ {}",
+                cnpj
+            ))
+        })
+}
+
+/// Certificates with a private key grouped by the CNPJ they carry, with the
+/// default of each company. Certificates without a CNPJ (e-CPF) are left out.
+#[tauri::command]
+pub async fn get_certificate_groups(
+    defaults: tauri::State<'_, CertDefaults>,
+) -> Result<Vec<CertificateGroup>, AppError> {
+    crate::metrics::timed("get_certificate_groups", async move {
+        let certs = tauri::async_runtime::spawn_blocking(crate::certs_impl)
+            .await
+            .map_err(|e| AppError::internal("Falha ao listar certificados", e))??;
+        let preferred = defaults.all()?;
+
+        let mut groups: BTreeMap<String, Vec<CertInfo>> = BTreeMap::new();
+        for cert in certs {
+            if cert.has_private_key && !cert.cnpj.is_empty() {
+                groups.entry(cert.cnpj.clone()).or_default().push(cert);
+            }
+        }
+        Ok(groups
+            .into_iter()
+            .map(|(cnpj, mut certificates)| {
+                certificates.sort_by(|a, b| b.not_after.cmp(&a.not_after));
+                let default_thumbprint = preferred
+                    .get(&cnpj)
+                    .filter(|thumbprint| certificates.iter().any(|c| &c.thumbprint == *thumbprint))
+                    .cloned();
+                CertificateGroup {
+                    cnpj,
+                    default_thumbprint,
+                    certificates,
+                }
+            })
+            .collect())
+    })
+    .await
+}
+
+/// The default certificate set for `cnpj`, or `None` when there is none or it
+/// was removed from the store.
+#[tauri::command]
+pub async fn get_default_certificate(
+    defaults: tauri::State<'_, CertDefaults>,
+    cnpj: String,
+) -> Result<Option<CertInfo>, AppError> {
+    crate::metrics::timed("get_default_certificate", async move {
+        let cnpj = crate::clients::normalize_cnpj(&cnpj)?;
+        let Some(thumbprint) = defaults.get(&cnpj)? else {
+            return Ok(None);
+        };
+        tauri::async_runtime::spawn_blocking(move || {
+            Ok(signing_certs(&cnpj)?
+                .into_iter()
+                .find(|cert| cert.thumbprint == thumbprint))
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao consultar certificado padrão", e))?
+    })
+    .await
+}
+
+/// Sets the certificate used for `cnpj` when a query names the company instead of
+/// a certificate. `None` clears it. The certificate must carry that CNPJ.
+#[tauri::command]
+pub async fn set_default_certificate(
+    defaults: tauri::State<'_, CertDefaults>,
+    cnpj: String,
+    thumbprint: Option<String>,
+) -> Result<(), AppError> {
+    crate::metrics::timed("set_default_certificate", async move {
+        let cnpj = crate::clients::normalize_cnpj(&cnpj)?;
+        let Some(thumbprint) = thumbprint.filter(|t| !t.trim().is_empty()) else {
+            return defaults.set(&cnpj, None);
+        };
+        let owner = cnpj.clone();
+        let thumbprint = tauri::async_runtime::spawn_blocking(move || {
+            let thumbprint = crate::sha1_thumbprint(&thumbprint)?;
+            if signing_certs(&owner)?
+                .iter()
+                .any(|cert| cert.thumbprint == thumbprint)
+            {
+                Ok(thumbprint)
+            } else {
+                Err(AppError::InvalidInput(format!(
+                    "O certificado não pertence ao CNPJ {} ou não tem chave privada",
+                    owner
+                )))
+            }
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao salvar certificado padrão", e))??;
+        defaults.set(&cnpj, Some(&thumbprint))
+    })
+    .await
+}
@@ -0,0 +1,507 @@
+// ── Certificate Details Module ─────────────────────────────────
+use crate::error::AppError;
+use x509_parser::certificate::X509Certificate;
+use x509_parser::der_parser::asn1_rs::{Any, FromDer};
+use x509_parser::extensions::{GeneralName, ParsedExtension};
+use x509_parser::time::ASN1Time;
+use x509_parser::x509::X509Name;
+
+/// ICP-Brasil chains have 3 or 4 levels; this only guards against loops.
+const MAX_CHAIN_LEN: usize = 8;
+
+#[derive(serde::Serialize)]
+pub struct ChainCertificate {
+    pub subject: String,
+    pub issuer: String,
+    pub not_after: String,
+    pub thumbprint: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct AltName {
+    pub kind: String,
+    pub value: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct CertificatePolicy {
+    pub oid: String,
+    /// Certificate type (e.g. "ICP-Brasil A1") for ICP-Brasil policies.
+    pub name: Option<String>,
+}
+
+/// ICP-Brasil certificate types the SEFAZ services care about.
+#[derive(serde::Serialize, Clone, Copy, PartialEq)]
+pub enum CertType {
+    #[serde(rename = "e-CNPJ A1")]
+    ECnpjA1,
+    #[serde(rename = "e-CNPJ A3")]
+    ECnpjA3,
+    #[serde(rename = "e-CPF A1")]
+    ECpfA1,
+    #[serde(rename = "e-CPF A3")]
+    ECpfA3,
+    /// Application certificate issued to a company for NF-e servers.
+    #[serde(rename = "NF-e")]
+    NFe,
+}
+
+#[derive(serde::Serialize)]
+pub struct CertificateDetails {
+    pub thumbprint: String,
+    pub thumbprint_sha256: String,
+    pub serial_number: String,
+    /// Full distinguished names, unlike the simple names in `CertInfo`.
+    pub subject: String,
+    pub issuer: String,
+    pub not_before: String,
+    pub not_after: String,
+    pub subject_alt_names: Vec<AltName>,
+    pub key_usage: Vec<String>,
+    pub extended_key_usage: Vec<String>,
+    pub policies: Vec<CertificatePolicy>,
+    /// From the certificate up to its root, as far as the store could build it.
+    pub chain: Vec<ChainCertificate>,
+}
+
+fn format_hash(hash: &[u8]) -> String {
+    hash.iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// SHA-1 of the DER, formatted like the Windows store thumbprints ("AA:BB:...").
+pub(crate) fn der_thumbprint(der: &[u8]) -> String {
+    use sha1::{Digest, Sha1};
+
+    format_hash(&Sha1::digest(der))
+}
+
+pub(crate) fn der_thumbprint_sha256(der: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+
+    format_hash(&Sha256::digest(der))
+}
+
+/// The CN, as Windows shows names in certmgr; the full name when there is none.
+pub(crate) fn simple_name(name: &X509Name) -> String {
+    name.iter_common_name()
+        .next()
+        .and_then(|cn| cn.as_str().ok())
+        .map(str::to_string)
+        .unwrap_or_else(|| name.to_string())
+}
+
+fn format_time(time: ASN1Time) -> String {
+    chrono::DateTime::from_timestamp(time.timestamp(), 0)
+        .map(|date| date.to_rfc3339())
+        .unwrap_or_else(|| "N/A".to_string())
+}
+
+/// Level named by an ICP-Brasil policy OID (2.16.76.1.2.<type>.<n>), e.g. "A1".
+fn icp_brasil_level(oid: &str) -> Option<&'static str> {
+    let kind = oid.strip_prefix("2.16.76.1.2.")?.split('.').next()?;
+    Some(match kind {
+        "1" => "A1",
+        "2" => "A2",
+        "3" => "A3",
+        "4" => "A4",
+        "101" => "S1",
+        "102" => "S2",
+        "103" => "S3",
+        "104" => "S4",
+        _ => return None,
+    })
+}
+
+fn icp_brasil_policy(oid: &str) -> Option<String> {
+    icp_brasil_level(oid).map(|level| format!("ICP-Brasil {}", level))
+}
+
+/// Birth date and CPF from the ICP-Brasil holder/responsible field, which packs
+/// birth date (8), CPF (11), NIS (11), RG (15) and issuing body digits together.
+fn person_data(text: &str) -> String {
+    let (Some(birth), Some(cpf)) = (text.get(..8), text.get(8..19)) else {
+        return text.to_string();
+    };
+    if !birth.chars().chain(cpf.chars()).all(|c| c.is_ascii_digit()) {
+        return text.to_string();
+    }
+    let mut parts = Vec::new();
+    if birth != "00000000" {
+        parts.push(format!(
+            "Nascimento: {}/{}/{}",
+            &birth[..2],
+            &birth[2..4],
+            &birth[4..]
+        ));
+    }
+    parts.push(format!(
+        "CPF: {}.{}.{}-{}",
+        &cpf[..3],
+        &cpf[3..6],
+        &cpf[6..9],
+        &cpf[9..]
+    ));
+    parts.join(", ")
+}
+
+/// ICP-Brasil puts the CNPJ, CPF and responsible person in otherName entries.
+fn other_name(oid: &str, value: &[u8]) -> AltName {
+    // [0] EXPLICIT around an OCTET STRING or a string type
+    let text = Any::from_der(value)
+        .ok()
+        .and_then(|(_, outer)| Any::from_der(outer.data).ok())
+        .map(|(_, inner)| String::from_utf8_lossy(inner.data).trim().to_string())
+        .unwrap_or_default();
+    let (kind, value) = match oid {
+        "2.16.76.1.3.1" => ("Dados do titular", person_data(&text)),
+        "2.16.76.1.3.2" => ("Responsável", text),
+        "2.16.76.1.3.3" => ("CNPJ", text),
+        "2.16.76.1.3.4" => ("Dados do responsável", person_data(&text)),
+        "2.16.76.1.3.5" => ("Título de eleitor", text),
+        "2.16.76.1.3.6" | "2.16.76.1.3.7" => ("CEI", text),
+        "2.16.76.1.3.8" => ("Nome empresarial", text),
+        _ => (oid, text),
+    };
+    AltName {
+        kind: kind.to_string(),
+        value,
+    }
+}
+
+fn alt_name(name: &GeneralName) -> AltName {
+    let (kind, value) = match name {
+        GeneralName::OtherName(oid, value) => return other_name(&oid.to_id_string(), value),
+        GeneralName::RFC822Name(email) => ("E-mail", email.to_string()),
+        GeneralName::DNSName(dns) => ("DNS", dns.to_string()),
+        GeneralName::URI(uri) => ("URI", uri.to_string()),
+        GeneralName::DirectoryName(name) => ("Nome", name.to_string()),
+        GeneralName::IPAddress(bytes) => {
+            let ip = match bytes.len() {
+                4 => <[u8; 4]>::try_from(*bytes)
+                    .map(|octets| std::net::Ipv4Addr::from(octets).to_string())
+                    .unwrap_or_default(),
+                16 => <[u8; 16]>::try_from(*bytes)
+                    .map(|octets| std::net::Ipv6Addr::from(octets).to_string())
+                    .unwrap_or_default(),
+                _ => hex::encode_upper(bytes),
+            };
+            ("IP", ip)
+        }
+        GeneralName::RegisteredID(oid) => ("OID", oid.to_id_string()),
+        GeneralName::X400Address(_) | GeneralName::EDIPartyName(_) => ("Outro", String::new()),
+    };
+    AltName {
+        kind: kind.to_string(),
+        value,
+    }
+}
+
+fn key_usage(cert: &X509Certificate) -> Vec<String> {
+    let Ok(Some(extension)) = cert.key_usage() else {
+        return Vec::new();
+    };
+    let usage = extension.value;
+    [
+        (usage.digital_signature(), "Assinatura digital"),
+        (usage.non_repudiation(), "Não repúdio"),
+        (usage.key_encipherment(), "Cifragem de chave"),
+        (usage.data_encipherment(), "Cifragem de dados"),
+        (usage.key_agreement(), "Acordo de chave"),
+        (usage.key_cert_sign(), "Assinatura de certificado"),
+        (usage.crl_sign(), "Assinatura de LCR"),
+        (usage.encipher_only(), "Somente cifragem"),
+        (usage.decipher_only(), "Somente decifragem"),
+    ]
+    .into_iter()
+    .filter(|(set, _)| *set)
+    .map(|(_, name)| name.to_string())
+    .collect()
+}
+
+fn extended_key_usage(cert: &X509Certificate) -> Vec<String> {
+    let Ok(Some(extension)) = cert.extended_key_usage() else {
+        return Vec::new();
+    };
+    let usage = extension.value;
+    let mut names: Vec<String> = [
+        (usage.any, "Qualquer finalidade"),
+        (usage.server_auth, "Autenticação de servidor"),
+        (usage.client_auth, "Autenticação de cliente"),
+        (usage.code_signing, "Assinatura de código"),
+        (usage.email_protection, "Proteção de e-mail"),
+        (usage.time_stamping, "Carimbo de tempo"),
+        (usage.ocsp_signing, "Assinatura de OCSP"),
+    ]
+    .into_iter()
+    .filter(|(set, _)| *set)
+    .map(|(_, name)| name.to_string())
+    .collect();
+    names.extend(usage.other.iter().map(|oid| oid.to_id_string()));
+    names
+}
+
+fn policies(cert: &X509Certificate) -> Vec<CertificatePolicy> {
+    let mut policies = Vec::new();
+    for extension in cert.extensions() {
+        if let ParsedExtension::CertificatePolicies(infos) = extension.parsed_extension() {
+            for info in infos {
+                let oid = info.policy_id.to_id_string();
+                policies.push(CertificatePolicy {
+                    name: icp_brasil_policy(&oid),
+                    oid,
+                });
+            }
+        }
+    }
+    policies
+}
+
+/// The A1/A3 level comes from the policies extension; the otherName fields tell
+/// e-CNPJ (CNPJ) from e-CPF (holder data), and application certificates carry the
+/// company name as well. `None` outside ICP-Brasil or for other levels.
+pub(crate) fn certificate_type(der: &[u8]) -> Option<CertType> {
+    let (_, cert) = x509_parser::parse_x509_certificate(der).ok()?;
+    let a3 = match policies(&cert)
+        .iter()
+        .find_map(|policy| icp_brasil_level(&policy.oid))?
+    {
+        "A1" => false,
+        "A3" => true,
+        _ => return None,
+    };
+    let other_names: Vec<String> = match cert.subject_alternative_name() {
+        Ok(Some(extension)) => extension
+            .value
+            .general_names
+            .iter()
+            .filter_map(|name| match name {
+                GeneralName::OtherName(oid, _) => Some(oid.to_id_string()),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    let has = |oid: &str| other_names.iter().any(|name| name == oid);
+
+    if has("2.16.76.1.3.3") {
+        Some(if has("2.16.76.1.3.8") {
+            CertType::NFe
+        } else if a3 {
+            CertType::ECnpjA3
+        } else {
+            CertType::ECnpjA1
+        })
+    } else if has("2.16.76.1.3.1") {
+        Some(if a3 {
+            CertType::ECpfA3
+        } else {
+            CertType::ECpfA1
+        })
+    } else {
+        None
+    }
+}
+
+/// Follows issuer to subject from the first certificate, so the stores may hand
+/// over the other certificates in any order.
+fn build_chain(certs: &[(&[u8], X509Certificate)]) -> Vec<ChainCertificate> {
+    let mut order = vec![0];
+    while order.len() < MAX_CHAIN_LEN {
+        let (_, current) = &certs[order[order.len() - 1]];
+        // Self-signed: the root
+        if current.subject().as_raw() == current.issuer().as_raw() {
+            break;
+        }
+        let issuer = certs.iter().enumerate().position(|(index, (_, cert))| {
+            !order.contains(&index) && cert.subject().as_raw() == current.issuer().as_raw()
+        });
+        match issuer {
+            Some(index) => order.push(index),
+            None => break,
+        }
+    }
+
+    order
+        .into_iter()
+        .map(|index| {
+            let (der, cert) = &certs[index];
+            ChainCertificate {
+                subject: simple_name(cert.subject()),
+                issuer: simple_name(cert.issuer()),
+                not_after: format_time(cert.validity().not_after),
+                thumbprint: der_thumbprint(der),
+            }
+        })
+        .collect()
+}
+
+fn details(ders: &[Vec<u8>]) -> Result<CertificateDetails, AppError> {
+    let certs: Vec<(&[u8], X509Certificate)> = ders
+        .iter()
+        .filter_map(|der| {
+            x509_parser::parse_x509_certificate(der)
+                .ok()
+                .map(|(_, cert)| (der.as_slice(), cert))
+        })
+        .collect();
+    // The stores always put the requested certificate first
+    let Some((der, cert)) = certs.first().filter(|(der, _)| *der == ders[0].as_slice()) else {
+        return Err(AppError::InvalidInput(
+            "Não foi possível ler o certificado".into(),
+        ));
+    };
+
+    let subject_alt_names = match cert.subject_alternative_name() {
+        Ok(Some(extension)) => extension.value.general_names.iter().map(alt_name).collect(),
+        _ => Vec::new(),
+    };
+    Ok(CertificateDetails {
+        thumbprint: der_thumbprint(der),
+        thumbprint_sha256: der_thumbprint_sha256(der),
+        serial_number: hex::encode_upper(cert.raw_serial()),
+        subject: cert.subject().to_string(),
+        issuer: cert.issuer().to_string(),
+        not_before: format_time(cert.validity().not_before),
+        not_after: format_time(cert.validity().not_after),
+        subject_alt_names,
+        key_usage: key_usage(cert),
+        extended_key_usage: extended_key_usage(cert),
+        policies: policies(cert),
+        chain: build_chain(&certs),
+    })
+}
+
+// ── Store Access ───────────────────────────────────────────────
+
+/// The certificate's DER first, then its chain as the Windows chain engine builds it
+/// from the user and system stores.
+#[cfg(windows)]
+fn certificate_ders(thumbprint: &str) -> Result<Vec<Vec<u8>>, AppError> {
+    use windows_sys::Win32::Security::Cryptography::*;
+
+    unsafe fn der_of(cert: *const CERT_CONTEXT) -> Vec<u8> {
+        std::slice::from_raw_parts((*cert).pbCertEncoded, (*cert).cbCertEncoded as usize).to_vec()
+    }
+
+    let mut hash = hex::decode(thumbprint.replace(':', ""))
+        .map_err(|_| AppError::InvalidInput("Impressão digital inválida".into()))?;
+    let blob = CRYPT_INTEGER_BLOB {
+        cbData: hash.len() as u32,
+        pbData: hash.as_mut_ptr(),
+    };
+    let store_wide: Vec<u16> = "MY\0".encode_utf16().collect();
+
+    unsafe {
+        let store = CertOpenSystemStoreW(0, store_wide.as_ptr());
+        if store.is_null() {
+            return Err(AppError::Internal(
+                "Falha ao abrir repositório de certificados".into(),
+            ));
+        }
+        let cert = CertFindCertificateInStore(
+            store,
+            X509_ASN_ENCODING | PKCS_7_ASN_ENCODING,
+            0,
+            CERT_FIND_SHA1_HASH,
+            &blob as *const _ as *const _,
+            std::ptr::null(),
+        );
+        if cert.is_null() {
+            CertCloseStore(store, 0);
+            return Err(AppError::NotFound("Certificado não encontrado".into()));
+        }
+
+        let mut ders = vec![der_of(cert)];
+        let mut para: CERT_CHAIN_PARA = std::mem::zeroed();
+        para.cbSize = std::mem::size_of::<CERT_CHAIN_PARA>() as u32;
+        let mut chain: *mut CERT_CHAIN_CONTEXT = std::ptr::null_mut();
+        // No revocation flags: offline machines would wait on the CRL downloads
+        if CertGetCertificateChain(
+            0,
+            cert,
+            std::ptr::null(),
+            (*cert).hCertStore,
+            &para,
+            0,
+            std::ptr::null(),
+            &mut chain,
+        ) != 0
+        {
+            if (*chain).cChain > 0 {
+                let simple = &**(*chain).rgpChain;
+                for index in 1..simple.cElement as usize {
+                    let element = &**simple.rgpElement.add(index);
+                    ders.push(der_of(element.pCertContext));
+                }
+            }
+            CertFreeCertificateChain(chain);
+        }
+
+        CertFreeCertificateContext(cert);
+        CertCloseStore(store, 0);
+        Ok(ders)
+    }
+}
+
+/// The identity's certificate, then the chain the trust evaluation builds from the
+/// keychains.
+#[cfg(target_os = "macos")]
+#[allow(deprecated)] // certificate_at_index is the only way to read the built chain
+fn certificate_ders(thumbprint: &str) -> Result<Vec<Vec<u8>>, AppError> {
+    use security_framework::item::{ItemClass, ItemSearchOptions, Limit, Reference, SearchResult};
+    use security_framework::policy::SecPolicy;
+    use security_framework::trust::SecTrust;
+
+    let cert = ItemSearchOptions::new()
+        .class(ItemClass::identity())
+        .load_refs(true)
+        .limit(Limit::All)
+        .search()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|item| match item {
+            SearchResult::Ref(Reference::Identity(identity)) => identity.certificate().ok(),
+            _ => None,
+        })
+        .find(|cert| der_thumbprint(&cert.to_der()).eq_ignore_ascii_case(thumbprint))
+        .ok_or_else(|| AppError::NotFound("Certificado não encontrado".into()))?;
+
+    let mut ders = vec![cert.to_der()];
+    if let Ok(trust) = SecTrust::create_with_certificates(&[cert], &[SecPolicy::create_x509()]) {
+        // An untrusted chain is still built as far as the keychains allow
+        let _ = trust.evaluate_with_error();
+        for index in 1..trust.certificate_count() {
+            if let Some(cert) = trust.certificate_at_index(index) {
+                ders.push(cert.to_der());
+            }
+        }
+    }
+    Ok(ders)
+}
+
+#[cfg(target_os = "linux")]
+fn certificate_ders(thumbprint: &str) -> Result<Vec<Vec<u8>>, AppError> {
+    crate::cert_store::certificate_ders(thumbprint)
+}
+
+#[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
+fn certificate_ders(_thumbprint: &str) -> Result<Vec<Vec<u8>>, AppError> {
+    Err(AppError::Unsupported(
+        "Detalhes de certificados indisponíveis neste sistema".into(),
+    ))
+}
+
+/// Serial number, validity, alternative names (with the ICP-Brasil CNPJ/CPF fields),
+/// key usage, policies and issuer chain, the details certmgr shows.
+#[tauri::command]
+pub async fn get_certificate_details(thumbprint: String) -> Result<CertificateDetails, AppError> {
+    crate::metrics::timed("get_certificate_details", async move {
+        tauri::async_runtime::spawn_blocking(move || details(&certificate_ders(&thumbprint)?))
+            .await
+            .map_err(|e| AppError::internal("Falha ao ler certificado", e))?
+    })
+    .await
+}
@@ -0,0 +1,112 @@
+// ── Certificate Duplicates Module ──────────────────────────────
+use crate::error::AppError;
+use crate::CertInfo;
+use std::collections::BTreeMap;
+
+#[derive(serde::Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateReason {
+    Expired,
+    /// Still valid, but a renewal expiring later is installed.
+    Superseded,
+}
+
+#[derive(serde::Serialize)]
+pub struct DuplicateCertificate {
+    pub certificate: CertInfo,
+    pub reason: DuplicateReason,
+}
+
+#[derive(serde::Serialize)]
+pub struct DuplicateGroup {
+    /// CNPJ (digits only) or, for certificates without one, the subject.
+    pub key: String,
+    /// The renewal expiring last, which is never flagged.
+    pub current: CertInfo,
+    pub duplicates: Vec<DuplicateCertificate>,
+}
+
+#[derive(serde::Serialize)]
+pub struct DuplicateReport {
+    pub groups: Vec<DuplicateGroup>,
+    /// Thumbprints removed from the store; empty unless `delete` was set.
+    pub deleted: Vec<String>,
+}
+
+/// Renewals of the same company or person, grouped by CNPJ or else by subject.
+/// Only certificates with a private key count, so the chain is left alone.
+fn group_duplicates(certs: Vec<CertInfo>) -> Vec<DuplicateGroup> {
+    let mut groups: BTreeMap<String, Vec<CertInfo>> = BTreeMap::new();
+    for cert in certs.into_iter().filter(|cert| cert.has_private_key) {
+        let key = if cert.cnpj.is_empty() {
+            cert.subject.clone()
+        } else {
+            cert.cnpj.clone()
+        };
+        groups.entry(key).or_default().push(cert);
+    }
+
+    // ISO dates compare correctly as strings; "N/A" is never treated as expired
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    groups
+        .into_iter()
+        .filter(|(_, certs)| certs.len() > 1)
+        .map(|(key, mut certs)| {
+            certs.sort_by(|a, b| b.not_after.cmp(&a.not_after));
+            let current = certs.remove(0);
+            let duplicates = certs
+                .into_iter()
+                .map(|certificate| {
+                    let expired = certificate.not_after != "N/A" && certificate.not_after < today;
+                    let reason = if expired {
+                        DuplicateReason::Expired
+                    } else {
+                        DuplicateReason::Superseded
+                    };
+                    DuplicateCertificate {
+                        certificate,
+                        reason,
+                    }
+                })
+                .collect();
+            DuplicateGroup {
+                key,
+                current,
+                duplicates,
+            }
+        })
+        .collect()
+}
+
+/// Lists older renewals of the same certificate. With `delete`, the flagged ones
+/// are removed in the same call; `only_expired` limits that to expired ones.
+#[tauri::command]
+pub async fn find_duplicate_certificates(
+    delete: Option<bool>,
+    only_expired: Option<bool>,
+) -> Result<DuplicateReport, AppError> {
+    crate::metrics::timed("find_duplicate_certificates", async move {
+        let delete = delete.unwrap_or(false);
+        let only_expired = only_expired.unwrap_or(false);
+        tauri::async_runtime::spawn_blocking(move || {
+            let groups = group_duplicates(crate::certs_impl()?);
+            let deleted: Vec<String> = if delete {
+                groups
+                    .iter()
+                    .flat_map(|group| &group.duplicates)
+                    .filter(|dup| !only_expired || dup.reason == DuplicateReason::Expired)
+                    .map(|dup| dup.certificate.thumbprint.clone())
+                    .collect()
+            } else {
+                Vec::new()
+            };
+            if !deleted.is_empty() {
+                crate::delete_certs_impl(deleted.clone())?;
+            }
+            Ok(DuplicateReport { groups, deleted })
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao procurar certificados duplicados", e))?
+    })
+    .await
+}
@@ -0,0 +1,167 @@
+// ── Certificate Passwords Module ───────────────────────────────
+use crate::error::AppError;
+
+/// Credential Manager entries are named `<prefix><vault key>`.
+#[cfg(windows)]
+const TARGET_PREFIX: &str = "AdcontecUtil:pfx:";
+
+/// Hex digits of a SHA-1 or SHA-256 thumbprint in upper case, so "aa:bb:…" and
+/// "AABB…" name the same entry. Nothing is looked up in the store, which lets a
+/// password be kept for a .pfx that isn't installed yet.
+fn vault_key(thumbprint: &str) -> Result<String, AppError> {
+    let key = thumbprint
+        .chars()
+        .filter(|c| c.is_ascii_hexdigit())
+        .collect::<String>()
+        .to_uppercase();
+    match key.len() {
+        40 | 64 => Ok(key),
+        _ => Err(AppError::InvalidInput(format!(
+            "Impressão digital inválida: {}",
+            thumbprint
+        ))),
+    }
+}
+
+#[cfg(windows)]
+fn target_name(key: &str) -> Vec<u16> {
+    format!("{}{}", TARGET_PREFIX, key)
+        .encode_utf16()
+        .chain(std::iter::once(0))
+        .collect()
+}
+
+/// Saves the PFX password as a generic credential of the current user, encrypted
+/// by Windows and kept across sessions (but not roamed to other machines).
+#[cfg(windows)]
+pub(crate) fn store_password(thumbprint: &str, password: &str) -> Result<(), AppError> {
+    use windows_sys::Win32::Security::Credentials::*;
+
+    let key = vault_key(thumbprint)?;
+    let mut target = target_name(&key);
+    let mut user: Vec<u16> = key.encode_utf16().chain(std::iter::once(0)).collect();
+    // UTF-16, as Credential Manager shows generic passwords
+    let mut blob: Vec<u8> = password
+        .encode_utf16()
+        .flat_map(|unit| unit.to_le_bytes())
+        .collect();
+
+    let written = unsafe {
+        let mut credential: CREDENTIALW = std::mem::zeroed();
+        credential.Type = CRED_TYPE_GENERIC;
+        credential.TargetName = target.as_mut_ptr();
+        credential.UserName = user.as_mut_ptr();
+        credential.CredentialBlobSize = blob.len() as u32;
+        credential.CredentialBlob = blob.as_mut_ptr();
+        credential.Persist = CRED_PERSIST_LOCAL_MACHINE;
+        CredWriteW(&credential, 0)
+    };
+    let error = std::io::Error::last_os_error();
+    blob.fill(0);
+    if written == 0 {
+        return Err(AppError::internal(
+            "Falha ao salvar senha do certificado",
+            error,
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(windows)]
+pub(crate) fn read_password(thumbprint: &str) -> Result<Option<String>, AppError> {
+    use windows_sys::Win32::Foundation::ERROR_NOT_FOUND;
+    use windows_sys::Win32::Security::Credentials::*;
+
+    let target = target_name(&vault_key(thumbprint)?);
+    unsafe {
+        let mut credential: *mut CREDENTIALW = std::ptr::null_mut();
+        if CredReadW(target.as_ptr(), CRED_TYPE_GENERIC, 0, &mut credential) == 0 {
+            let error = std::io::Error::last_os_error();
+            if error.raw_os_error() == Some(ERROR_NOT_FOUND as i32) {
+                return Ok(None);
+            }
+            return Err(AppError::internal(
+                "Falha ao ler senha do certificado",
+                error,
+            ));
+        }
+        let blob = std::slice::from_raw_parts(
+            (*credential).CredentialBlob,
+            (*credential).CredentialBlobSize as usize,
+        );
+        let units: Vec<u16> = blob
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .collect();
+        CredFree(credential as *const _);
+        Ok(Some(String::from_utf16_lossy(&units)))
+    }
+}
+
+/// Returns whether there was a password to remove.
+#[cfg(windows)]
+pub(crate) fn delete_password(thumbprint: &str) -> Result<bool, AppError> {
+    use windows_sys::Win32::Foundation::ERROR_NOT_FOUND;
+    use windows_sys::Win32::Security::Credentials::*;
+
+    let target = target_name(&vault_key(thumbprint)?);
+    if unsafe { CredDeleteW(target.as_ptr(), CRED_TYPE_GENERIC, 0) } == 0 {
+        let error = std::io::Error::last_os_error();
+        if error.raw_os_error() == Some(ERROR_NOT_FOUND as i32) {
+            return Ok(false);
+        }
+        return Err(AppError::internal(
+            "Falha ao remover senha do certificado",
+            error,
+        ));
+    }
+    Ok(true)
+}
+
+#[cfg(not(windows))]
+pub(crate) fn store_password(thumbprint: &str, _password: &str) -> Result<(), AppError> {
+    vault_key(thumbprint)?;
+    Err(unsupported())
+}
+
+#[cfg(not(windows))]
+pub(crate) fn read_password(thumbprint: &str) -> Result<Option<String>, AppError> {
+    vault_key(thumbprint)?;
+    Err(unsupported())
+}
+
+#[cfg(not(windows))]
+pub(crate) fn delete_password(thumbprint: &str) -> Result<bool, AppError> {
+    vault_key(thumbprint)?;
+    Err(unsupported())
+}
+
+#[cfg(not(windows))]
+fn unsupported() -> AppError {
+    AppError::Unsupported(
+        "Cofre de senhas disponível apenas no Windows (Gerenciador de Credenciais)".into(),
+    )
+}
+
+/// Remembers the password of a .pfx under its SHA-1 or SHA-256 thumbprint, so
+/// importing the same file again does not ask for it. Reading it back takes the
+/// same thumbprint; passwords saved by `install_certificate` answer to either.
+#[tauri::command]
+pub fn save_pfx_password(thumbprint: String, password: String) -> Result<(), AppError> {
+    if password.is_empty() {
+        return Err(AppError::InvalidInput(
+            "A senha do certificado não pode ser vazia".into(),
+        ));
+    }
+    store_password(&thumbprint, &password)
+}
+
+#[tauri::command]
+pub fn get_pfx_password(thumbprint: String) -> Result<Option<String>, AppError> {
+    read_password(&thumbprint)
+}
+
+#[tauri::command]
+pub fn delete_pfx_password(thumbprint: String) -> Result<bool, AppError> {
+    delete_password(&thumbprint)
+}
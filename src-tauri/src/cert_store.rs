@@ -0,0 +1,345 @@
+// ── Certificate Store Module ───────────────────────────────────
+use crate::error::AppError;
+use crate::settings::Settings;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+pub(crate) const SETTING_KEY: &str = "certificate_store";
+
+/// Where certificates are read from on Linux, which has no per-user store like
+/// Windows' "MY" or the macOS keychain.
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CertStoreConfig {
+    /// Folder of .pfx/.p12 files. Their contents are encrypted, so each file needs
+    /// its password, keyed by file name; files without one are tried with an
+    /// empty password.
+    PfxFolder {
+        path: String,
+        #[serde(default)]
+        passwords: HashMap<String, String>,
+    },
+    /// NSS database, as used by Chrome (`~/.pki/nssdb`) and Firefox profiles.
+    /// Read through `certutil`/`pk12util` from the NSS tools package.
+    Nss { path: String },
+}
+
+impl Default for CertStoreConfig {
+    fn default() -> Self {
+        let home = std::env::var("HOME").unwrap_or_default();
+        CertStoreConfig::Nss {
+            path: format!("{}/.pki/nssdb", home),
+        }
+    }
+}
+
+/// Read by `certs_impl` and the PFX export, which run without access to `Settings`.
+static STORE: Mutex<Option<CertStoreConfig>> = Mutex::new(None);
+
+pub fn load(settings: &Settings) {
+    *STORE.lock().unwrap() = settings.get(SETTING_KEY);
+}
+
+#[cfg(target_os = "linux")]
+fn current() -> CertStoreConfig {
+    STORE.lock().unwrap().clone().unwrap_or_default()
+}
+
+#[tauri::command]
+pub fn get_certificate_store() -> CertStoreConfig {
+    STORE.lock().unwrap().clone().unwrap_or_default()
+}
+
+/// Only used on Linux; on Windows and macOS the system store is always used.
+#[tauri::command]
+pub fn set_certificate_store(
+    settings: tauri::State<'_, Settings>,
+    store: CertStoreConfig,
+) -> Result<(), AppError> {
+    settings.set(SETTING_KEY, &store)?;
+    *STORE.lock().unwrap() = Some(store);
+    Ok(())
+}
+
+// ── PFX Folder ─────────────────────────────────────────────────
+
+#[cfg(target_os = "linux")]
+struct PfxEntry {
+    info: crate::CertInfo,
+    parsed: openssl::pkcs12::ParsedPkcs12_2,
+}
+
+#[cfg(target_os = "linux")]
+fn pfx_folder_entries(
+    path: &str,
+    passwords: &HashMap<String, String>,
+) -> Result<Vec<PfxEntry>, AppError> {
+    use openssl::pkcs12::Pkcs12;
+
+    let dir = std::fs::read_dir(path)
+        .map_err(|e| AppError::io("Falha ao abrir pasta de certificados", e))?;
+    let mut entries = Vec::new();
+    for entry in dir.flatten() {
+        let file_path = entry.path();
+        let is_pfx = file_path.extension().is_some_and(|extension| {
+            extension.eq_ignore_ascii_case("pfx") || extension.eq_ignore_ascii_case("p12")
+        });
+        if !is_pfx {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().to_string();
+        let password = passwords.get(&name).map(String::as_str).unwrap_or("");
+
+        // A wrong password only hides that file; the others are still listed
+        let parsed = match std::fs::read(&file_path)
+            .map_err(|e| e.to_string())
+            .and_then(|der| Pkcs12::from_der(&der).map_err(|e| e.to_string()))
+            .and_then(|pkcs12| pkcs12.parse2(password).map_err(|e| e.to_string()))
+        {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                crate::app_log::error(format!("Certificado ignorado ({}): {}", name, e));
+                continue;
+            }
+        };
+        let Some(der) = parsed.cert.as_ref().and_then(|cert| cert.to_der().ok()) else {
+            continue;
+        };
+        let has_private_key = parsed.pkey.is_some();
+        if let Some(info) = crate::cert_info_from_der(&der, has_private_key, has_private_key) {
+            entries.push(PfxEntry { info, parsed });
+        }
+    }
+    Ok(entries)
+}
+
+#[cfg(target_os = "linux")]
+fn export_from_pfx_folder(
+    path: &str,
+    passwords: &HashMap<String, String>,
+    thumbprint: &str,
+    password: &str,
+) -> Result<(Vec<u8>, String), AppError> {
+    use openssl::pkcs12::Pkcs12;
+
+    let entry = pfx_folder_entries(path, passwords)?
+        .into_iter()
+        .find(|entry| entry.info.thumbprint.eq_ignore_ascii_case(thumbprint))
+        .ok_or_else(|| AppError::NotFound("Certificado não encontrado".into()))?;
+    let (Some(pkey), Some(cert)) = (&entry.parsed.pkey, &entry.parsed.cert) else {
+        return Err(AppError::InvalidInput(
+            "O certificado não possui chave privada".into(),
+        ));
+    };
+
+    // Re-encrypted under the caller's password, chain included
+    let mut builder = Pkcs12::builder();
+    builder.pkey(pkey).cert(cert);
+    if let Some(ca) = entry.parsed.ca {
+        builder.ca(ca);
+    }
+    let pfx = builder
+        .build2(password)
+        .and_then(|pkcs12| pkcs12.to_der())
+        .map_err(|e| AppError::internal("Falha ao exportar PFX", e))?;
+    Ok((pfx, entry.info.cnpj))
+}
+
+// ── NSS Database ───────────────────────────────────────────────
+
+/// Runs one of the NSS tools and returns its stdout.
+#[cfg(target_os = "linux")]
+fn nss_tool(program: &str, args: &[&str]) -> Result<Vec<u8>, AppError> {
+    let output = std::process::Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => AppError::ServiceUnavailable(format!(
+                "{} não encontrado. Instale o pacote libnss3-tools",
+                program
+            )),
+            _ => AppError::io("Falha ao ler banco de certificados NSS", e),
+        })?;
+    if !output.status.success() {
+        return Err(
+            AppError::Internal("Falha ao ler banco de certificados NSS".into())
+                .with_details(String::from_utf8_lossy(&output.stderr).trim()),
+        );
+    }
+    Ok(output.stdout)
+}
+
+#[cfg(target_os = "linux")]
+struct NssEntry {
+    info: crate::CertInfo,
+    /// How the NSS tools address the certificate.
+    nickname: String,
+    der: Vec<u8>,
+}
+
+#[cfg(target_os = "linux")]
+fn nss_entries(path: &str) -> Result<Vec<NssEntry>, AppError> {
+    let db = format!("sql:{}", path);
+    let listing = nss_tool("certutil", &["-L", "-d", &db])?;
+    let listing = String::from_utf8_lossy(&listing);
+
+    // A header, then "<nickname>  <trust flags>" per line, e.g. "EMPRESA LTDA  u,u,u"
+    let mut entries = Vec::new();
+    for line in listing
+        .lines()
+        .skip_while(|line| !line.contains("SSL,S/MIME"))
+        .skip(1)
+    {
+        let Some((nickname, trust)) = line.trim_end().rsplit_once(char::is_whitespace) else {
+            continue;
+        };
+        let nickname = nickname.trim();
+        if nickname.is_empty() || !trust.contains(',') {
+            continue;
+        }
+        // "u" marks a user certificate, i.e. one whose private key is in the database
+        let has_private_key = trust.contains('u');
+        let der = nss_tool("certutil", &["-L", "-d", &db, "-n", nickname, "-r"])?;
+        // Keys in the software database can always be exported with pk12util
+        if let Some(info) = crate::cert_info_from_der(&der, has_private_key, has_private_key) {
+            entries.push(NssEntry {
+                info,
+                nickname: nickname.to_string(),
+                der,
+            });
+        }
+    }
+    Ok(entries)
+}
+
+#[cfg(target_os = "linux")]
+fn pk12util_export(
+    work_dir: &std::path::Path,
+    path: &str,
+    nickname: &str,
+    password: &str,
+) -> Result<Vec<u8>, AppError> {
+    let pfx_path = work_dir.join("export.p12");
+    let password_path = work_dir.join("senha");
+    // The database itself is expected to have no password, as Chrome creates it
+    let db_password_path = work_dir.join("senha_nss");
+    std::fs::write(&password_path, password)
+        .and_then(|_| std::fs::write(&db_password_path, ""))
+        .map_err(|e| AppError::io("Falha ao criar arquivo temporário", e))?;
+    nss_tool(
+        "pk12util",
+        &[
+            "-o",
+            &pfx_path.to_string_lossy(),
+            "-n",
+            nickname,
+            "-d",
+            &format!("sql:{}", path),
+            "-w",
+            &password_path.to_string_lossy(),
+            "-k",
+            &db_password_path.to_string_lossy(),
+        ],
+    )?;
+    std::fs::read(&pfx_path).map_err(|e| AppError::io("Falha ao ler PFX exportado", e))
+}
+
+#[cfg(target_os = "linux")]
+fn export_from_nss(
+    path: &str,
+    thumbprint: &str,
+    password: &str,
+) -> Result<(Vec<u8>, String), AppError> {
+    use rand::Rng;
+    use std::os::unix::fs::DirBuilderExt;
+
+    let NssEntry { info, nickname, .. } = nss_entries(path)?
+        .into_iter()
+        .find(|entry| entry.info.thumbprint.eq_ignore_ascii_case(thumbprint))
+        .ok_or_else(|| AppError::NotFound("Certificado não encontrado".into()))?;
+    if !info.has_private_key {
+        return Err(AppError::InvalidInput(
+            "O certificado não possui chave privada".into(),
+        ));
+    }
+
+    // pk12util only writes to a file and reads passwords from files, so both go in a
+    // folder only this user can read, removed right after
+    let random: u64 = rand::thread_rng().gen();
+    let work_dir = std::env::temp_dir().join(format!("adcontec_pk12_{}", random));
+    std::fs::DirBuilder::new()
+        .mode(0o700)
+        .create(&work_dir)
+        .map_err(|e| AppError::io("Falha ao criar pasta temporária", e))?;
+    let exported = pk12util_export(&work_dir, path, &nickname, password);
+    let _ = std::fs::remove_dir_all(&work_dir);
+    Ok((exported?, info.cnpj))
+}
+
+// ── Store Access ───────────────────────────────────────────────
+
+#[cfg(target_os = "linux")]
+pub(crate) fn list() -> Result<Vec<crate::CertInfo>, AppError> {
+    match current() {
+        CertStoreConfig::PfxFolder { path, passwords } => {
+            Ok(pfx_folder_entries(&path, &passwords)?
+                .into_iter()
+                .map(|entry| entry.info)
+                .collect())
+        }
+        CertStoreConfig::Nss { path } => Ok(nss_entries(&path)?
+            .into_iter()
+            .map(|entry| entry.info)
+            .collect()),
+    }
+}
+
+/// Certificate and private key as PFX bytes protected by `password`, plus the
+/// CNPJ found in the certificate, like the Windows export.
+#[cfg(target_os = "linux")]
+pub(crate) fn export_pfx(thumbprint: &str, password: &str) -> Result<(Vec<u8>, String), AppError> {
+    match current() {
+        CertStoreConfig::PfxFolder { path, passwords } => {
+            export_from_pfx_folder(&path, &passwords, thumbprint, password)
+        }
+        CertStoreConfig::Nss { path } => export_from_nss(&path, thumbprint, password),
+    }
+}
+
+/// The certificate's DER first, then the other certificates the store holds that
+/// its chain may be built from, in no particular order.
+#[cfg(target_os = "linux")]
+pub(crate) fn certificate_ders(thumbprint: &str) -> Result<Vec<Vec<u8>>, AppError> {
+    let not_found = || AppError::NotFound("Certificado não encontrado".into());
+    match current() {
+        CertStoreConfig::PfxFolder { path, passwords } => {
+            let entry = pfx_folder_entries(&path, &passwords)?
+                .into_iter()
+                .find(|entry| entry.info.thumbprint.eq_ignore_ascii_case(thumbprint))
+                .ok_or_else(not_found)?;
+            let mut ders: Vec<Vec<u8>> = entry
+                .parsed
+                .cert
+                .iter()
+                .filter_map(|cert| cert.to_der().ok())
+                .collect();
+            if let Some(ca) = &entry.parsed.ca {
+                ders.extend(ca.iter().filter_map(|cert| cert.to_der().ok()));
+            }
+            Ok(ders)
+        }
+        CertStoreConfig::Nss { path } => {
+            let (matching, others): (Vec<_>, Vec<_>) = nss_entries(&path)?
+                .into_iter()
+                .partition(|entry| entry.info.thumbprint.eq_ignore_ascii_case(thumbprint));
+            if matching.is_empty() {
+                return Err(not_found());
+            }
+            Ok(matching
+                .into_iter()
+                .chain(others)
+                .map(|entry| entry.der)
+                .collect())
+        }
+    }
+}
@@ -1,9 +1,17 @@
 // ── Client Folders Module ───────────────────────────────────────
+use crate::error::AppError;
+use crate::jobs::{JobHandle, Jobs};
+use crate::settings::Settings;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use tauri::{Emitter, Manager};
 
-const NETWORK_BASE_PATH: &str = r"\\SRV-ADDS\Clientes$";
+const DEFAULT_NETWORK_BASE_PATH: &str = r"\\SRV-ADDS\Clientes$";
+pub(crate) const NETWORK_BASE_PATH_KEY: &str = "network_base_path";
+pub(crate) const NETWORK_MODULE_ENABLED_KEY: &str = "network_module_enabled";
 
-#[derive(serde::Serialize)]
+#[derive(serde::Serialize, Clone)]
 pub struct DirEntry {
     pub name: String,
     pub is_dir: bool,
@@ -12,33 +20,87 @@ pub struct DirEntry {
     pub extension: String,
 }
 
-/// Validates that the requested path is within NETWORK_BASE_PATH.
-fn validate_path(requested: &str) -> Result<PathBuf, String> {
-    let path = PathBuf::from(requested);
+/// Returns the configured network base path, falling back to the office default.
+pub(crate) fn network_base_path(settings: &Settings) -> String {
+    settings
+        .get::<String>(NETWORK_BASE_PATH_KEY)
+        .filter(|path| !path.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_NETWORK_BASE_PATH.to_string())
+}
 
-    let canonical = std::fs::canonicalize(&path)
-        .map_err(|e| format!("Caminho inválido ou inacessível: {}", e))?;
+/// Machines without access to the share can switch the whole module off.
+pub(crate) fn network_module_enabled(settings: &Settings) -> bool {
+    settings
+        .get::<bool>(NETWORK_MODULE_ENABLED_KEY)
+        .unwrap_or(true)
+}
+
+fn ensure_module_enabled(settings: &Settings) -> Result<(), AppError> {
+    if network_module_enabled(settings) {
+        Ok(())
+    } else {
+        Err(AppError::AccessDenied(
+            "Módulo de pastas de rede desativado nas configurações".into(),
+        ))
+    }
+}
 
-    // Normalize UNC prefix: \\?\UNC\server\share → \\server\share
+/// Normalize UNC prefix: \\?\UNC\server\share → \\server\share
+fn normalize_canonical(canonical: &std::path::Path) -> String {
     let canonical_str = canonical.to_string_lossy().to_string();
-    let normalized = if canonical_str.starts_with(r"\\?\UNC\") {
-        format!(r"\\{}", &canonical_str[8..])
-    } else if canonical_str.starts_with(r"\\?\") {
-        canonical_str[4..].to_string()
+    if let Some(rest) = canonical_str.strip_prefix(r"\\?\UNC\") {
+        format!(r"\\{}", rest)
+    } else if let Some(rest) = canonical_str.strip_prefix(r"\\?\") {
+        rest.to_string()
     } else {
         canonical_str
-    };
+    }
+}
+
+/// Case-insensitive, component-aware prefix check (`\\srv\a` must not match `\\srv\ab`).
+fn is_within_base(path: &str, base: &str) -> bool {
+    let path_lower = path.to_lowercase();
+    let base_lower = base.trim_end_matches(['\\', '/']).to_lowercase();
+
+    match path_lower.strip_prefix(&base_lower) {
+        Some(rest) => rest.is_empty() || rest.starts_with(['\\', '/']),
+        None => false,
+    }
+}
+
+/// Validates that the requested path is within the configured network base path.
+pub(crate) fn validate_path(settings: &Settings, requested: &str) -> Result<PathBuf, AppError> {
+    ensure_module_enabled(settings)?;
+    let path = PathBuf::from(requested);
 
-    let base_lower = NETWORK_BASE_PATH.to_lowercase();
-    let norm_lower = normalized.to_lowercase();
+    let canonical = std::fs::canonicalize(&path)
+        .map_err(|e| AppError::io("Caminho inválido ou inacessível", e))?;
+    let normalized = normalize_canonical(&canonical);
 
-    if !norm_lower.starts_with(&base_lower) {
-        return Err("Acesso negado: caminho fora do diretório permitido".into());
+    if !is_within_base(&normalized, &network_base_path(settings)) {
+        return Err(AppError::AccessDenied(
+            "Acesso negado: caminho fora do diretório permitido".into(),
+        ));
     }
 
     Ok(PathBuf::from(normalized))
 }
 
+/// Rejects names that are empty or would escape the parent folder.
+fn validate_entry_name(name: &str) -> Result<(), AppError> {
+    if name.contains('\\') || name.contains('/') || name.contains('\0') {
+        return Err(AppError::InvalidInput(
+            "Nome inválido: não pode conter barras ou caracteres nulos".into(),
+        ));
+    }
+    if name.trim().is_empty() || name == "." || name == ".." {
+        return Err(AppError::InvalidInput(
+            "Nome inválido: informe um nome".into(),
+        ));
+    }
+    Ok(())
+}
+
 fn format_system_time(time: std::time::SystemTime) -> String {
     let duration = time
         .duration_since(std::time::UNIX_EPOCH)
@@ -105,11 +167,58 @@ fn format_system_time(time: std::time::SystemTime) -> String {
 }
 
 #[tauri::command]
-pub fn list_network_folders() -> Result<Vec<String>, String> {
-    let base = PathBuf::from(NETWORK_BASE_PATH);
+pub fn get_network_module_enabled(settings: tauri::State<'_, Settings>) -> bool {
+    network_module_enabled(&settings)
+}
+
+#[tauri::command]
+pub fn set_network_module_enabled(
+    settings: tauri::State<'_, Settings>,
+    enabled: bool,
+) -> Result<(), AppError> {
+    Ok(settings.set(NETWORK_MODULE_ENABLED_KEY, enabled)?)
+}
+
+#[tauri::command]
+pub fn get_network_base_path(settings: tauri::State<'_, Settings>) -> String {
+    network_base_path(&settings)
+}
+
+/// Stores a new base path after checking that it exists and is a folder.
+/// An empty path restores the default.
+#[tauri::command]
+pub fn set_network_base_path(
+    settings: tauri::State<'_, Settings>,
+    path: String,
+) -> Result<String, AppError> {
+    let trimmed = path.trim();
+    if trimmed.is_empty() {
+        settings.set(NETWORK_BASE_PATH_KEY, "")?;
+        return Ok(DEFAULT_NETWORK_BASE_PATH.to_string());
+    }
+
+    let canonical = std::fs::canonicalize(trimmed)
+        .map_err(|e| AppError::io("Caminho inválido ou inacessível", e))?;
+    if !canonical.is_dir() {
+        return Err(AppError::InvalidInput(
+            "O caminho base deve ser uma pasta".into(),
+        ));
+    }
+
+    let normalized = normalize_canonical(&canonical);
+    settings.set(NETWORK_BASE_PATH_KEY, &normalized)?;
+
+    Ok(normalized)
+}
+
+#[tauri::command]
+pub fn list_network_folders(settings: tauri::State<'_, Settings>) -> Result<Vec<String>, AppError> {
+    ensure_module_enabled(&settings)?;
+    let base_path = network_base_path(&settings);
+    let base = PathBuf::from(&base_path);
 
     let entries = std::fs::read_dir(&base)
-        .map_err(|e| format!("Falha ao acessar {}: {}", NETWORK_BASE_PATH, e))?;
+        .map_err(|e| AppError::io(format!("Falha ao acessar {}", base_path), e))?;
 
     let mut folders: Vec<String> = entries
         .filter_map(|entry| {
@@ -126,41 +235,323 @@ pub fn list_network_folders() -> Result<Vec<String>, String> {
     Ok(folders)
 }
 
-#[tauri::command]
-pub fn list_directory(path: String) -> Result<Vec<DirEntry>, String> {
-    let validated = validate_path(&path)?;
+// ── Share Connection ───────────────────────────────────────────
+
+#[derive(serde::Serialize)]
+pub struct ShareStatus {
+    pub path: String,
+    /// The server answered, even if only to refuse access.
+    pub reachable: bool,
+    pub needs_credentials: bool,
+    pub message: Option<String>,
+}
 
-    let entries = std::fs::read_dir(&validated)
-        .map_err(|e| format!("Falha ao listar diretório: {}", e))?;
+/// `\\server\share\sub` → `\\server\share`, the resource WNet actually connects to.
+#[cfg(windows)]
+fn share_root(base: &str) -> String {
+    let trimmed = base.trim_start_matches(['\\', '/']);
+    let mut parts = trimmed.split(['\\', '/']).filter(|p| !p.is_empty());
+    match (parts.next(), parts.next()) {
+        (Some(server), Some(share)) => format!(r"\\{}\{}", server, share),
+        _ => base.to_string(),
+    }
+}
 
-    let mut items: Vec<DirEntry> = entries
-        .filter_map(|entry| {
-            let entry = entry.ok()?;
-            let metadata = entry.metadata().ok()?;
-            let name = entry.file_name().to_string_lossy().to_string();
-            let is_dir = metadata.is_dir();
-            let size = if is_dir { 0 } else { metadata.len() };
-            let modified = metadata
-                .modified()
-                .map(format_system_time)
-                .unwrap_or_default();
-            let extension = if is_dir {
-                String::new()
+/// Win32 codes that mean "the server answered but wants other credentials".
+const CREDENTIAL_ERRORS: [i32; 3] = [5, 1219, 1326];
+
+/// Win32 codes that mean nothing answered: server not found or not listening,
+/// connection dropped, timeout, no network, network or host unreachable.
+const UNREACHABLE_ERRORS: [i32; 7] = [51, 53, 64, 121, 1222, 1231, 1232];
+
+#[tauri::command]
+pub fn check_share_connection(settings: tauri::State<'_, Settings>) -> ShareStatus {
+    let base_path = network_base_path(&settings);
+    if let Err(message) = ensure_module_enabled(&settings) {
+        return ShareStatus {
+            path: base_path,
+            reachable: false,
+            needs_credentials: false,
+            message: Some(message.to_string()),
+        };
+    }
+
+    match std::fs::read_dir(&base_path) {
+        Ok(_) => ShareStatus {
+            path: base_path,
+            reachable: true,
+            needs_credentials: false,
+            message: None,
+        },
+        Err(e) => {
+            let code = e.raw_os_error();
+            let needs_credentials = code.is_some_and(|code| CREDENTIAL_ERRORS.contains(&code));
+            let reachable = code.is_some_and(|code| !UNREACHABLE_ERRORS.contains(&code));
+            let message = if needs_credentials {
+                format!("Acesso negado a {}. Informe usuário e senha.", base_path)
+            } else if reachable {
+                format!("Pasta de rede indisponível ({}): {}", base_path, e)
             } else {
-                std::path::Path::new(&name)
-                    .extension()
-                    .map(|e| e.to_string_lossy().to_lowercase())
-                    .unwrap_or_default()
+                format!("Servidor inacessível ({}): {}", base_path, e)
             };
 
-            Some(DirEntry {
-                name,
-                is_dir,
-                size,
-                modified,
-                extension,
-            })
+            ShareStatus {
+                path: base_path,
+                reachable,
+                needs_credentials,
+                message: Some(message),
+            }
+        }
+    }
+}
+
+#[cfg(windows)]
+#[tauri::command]
+pub fn connect_share(
+    settings: tauri::State<'_, Settings>,
+    username: String,
+    password: String,
+) -> Result<(), AppError> {
+    use windows_sys::Win32::Foundation::{
+        ERROR_ACCESS_DENIED, ERROR_BAD_NETPATH, ERROR_BAD_NET_NAME, ERROR_LOGON_FAILURE,
+        ERROR_SESSION_CREDENTIAL_CONFLICT, NO_ERROR,
+    };
+    use windows_sys::Win32::NetworkManagement::WNet::{
+        WNetAddConnection2W, CONNECT_TEMPORARY, NETRESOURCEW, RESOURCETYPE_DISK,
+    };
+
+    ensure_module_enabled(&settings)?;
+    let remote = share_root(&network_base_path(&settings));
+    let mut remote_wide: Vec<u16> = remote.encode_utf16().chain(std::iter::once(0)).collect();
+    let username_wide: Vec<u16> = username.encode_utf16().chain(std::iter::once(0)).collect();
+    let password_wide: Vec<u16> = password.encode_utf16().chain(std::iter::once(0)).collect();
+
+    let resource = NETRESOURCEW {
+        dwScope: 0,
+        dwType: RESOURCETYPE_DISK,
+        dwDisplayType: 0,
+        dwUsage: 0,
+        lpLocalName: std::ptr::null_mut(),
+        lpRemoteName: remote_wide.as_mut_ptr(),
+        lpComment: std::ptr::null_mut(),
+        lpProvider: std::ptr::null_mut(),
+    };
+
+    let result = unsafe {
+        WNetAddConnection2W(
+            &resource,
+            password_wide.as_ptr(),
+            username_wide.as_ptr(),
+            CONNECT_TEMPORARY,
+        )
+    };
+
+    match result {
+        NO_ERROR => Ok(()),
+        ERROR_LOGON_FAILURE | ERROR_ACCESS_DENIED => {
+            Err(AppError::AccessDenied("Usuário ou senha inválidos".into()))
+        }
+        ERROR_SESSION_CREDENTIAL_CONFLICT => Err(AppError::AlreadyExists(format!(
+            "Já existe uma conexão com {} usando outro usuário",
+            remote
+        ))),
+        ERROR_BAD_NETPATH | ERROR_BAD_NET_NAME => Err(AppError::NotFound(format!(
+            "Servidor ou compartilhamento não encontrado: {}",
+            remote
+        ))),
+        code => Err(AppError::ServiceUnavailable(format!(
+            "Falha ao conectar em {} (código {})",
+            remote, code
+        ))),
+    }
+}
+
+#[cfg(not(windows))]
+#[tauri::command]
+pub fn connect_share(
+    _settings: tauri::State<'_, Settings>,
+    _username: String,
+    _password: String,
+) -> Result<(), AppError> {
+    Err(AppError::Unsupported(
+        "Conexão com compartilhamentos disponível apenas no Windows".into(),
+    ))
+}
+
+/// Runs off the main thread: on a slow share `read_dir` can take seconds.
+#[tauri::command]
+pub async fn list_directory(
+    settings: tauri::State<'_, Settings>,
+    path: String,
+) -> Result<Vec<DirEntry>, AppError> {
+    crate::metrics::timed("list_directory", async move {
+        let validated = validate_path(&settings, &path)?;
+        tauri::async_runtime::spawn_blocking(move || read_sorted_entries(&validated))
+            .await
+            .map_err(|e| AppError::internal("Falha ao listar diretório", e))?
+    })
+    .await
+}
+
+#[derive(serde::Serialize)]
+pub struct DirectoryPage {
+    pub entries: Vec<DirEntry>,
+    pub total: usize,
+    pub offset: usize,
+}
+
+/// Returns one slice of the sorted listing so the frontend can virtualize huge folders.
+#[tauri::command]
+pub async fn list_directory_page(
+    settings: tauri::State<'_, Settings>,
+    path: String,
+    offset: usize,
+    limit: usize,
+) -> Result<DirectoryPage, AppError> {
+    crate::metrics::timed("list_directory_page", async move {
+        let validated = validate_path(&settings, &path)?;
+        let items = tauri::async_runtime::spawn_blocking(move || read_sorted_entries(&validated))
+            .await
+            .map_err(|e| AppError::internal("Falha ao listar diretório", e))??;
+        let total = items.len();
+
+        let entries = items.into_iter().skip(offset).take(limit).collect();
+
+        Ok(DirectoryPage {
+            entries,
+            total,
+            offset,
+        })
+    })
+    .await
+}
+
+/// Entries per `directory-chunk` event, unless the share is slow enough that
+/// `STREAM_FLUSH_INTERVAL` passes first.
+const STREAM_CHUNK_SIZE: usize = 200;
+const STREAM_FLUSH_INTERVAL: std::time::Duration = std::time::Duration::from_millis(150);
+
+static NEXT_LISTING_ID: AtomicU64 = AtomicU64::new(1);
+
+#[derive(Clone, serde::Serialize)]
+pub struct DirectoryChunk {
+    pub listing_id: u64,
+    pub path: String,
+    /// In `read_dir` order; the frontend sorts once the listing completes.
+    pub entries: Vec<DirEntry>,
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct DirectoryComplete {
+    pub listing_id: u64,
+    pub path: String,
+    pub total: usize,
+    pub error: Option<AppError>,
+}
+
+fn stream_entries(app: &tauri::AppHandle, listing_id: u64, dir: &std::path::Path) {
+    let path = dir.to_string_lossy().to_string();
+    let emit_chunk = |entries: &mut Vec<DirEntry>| {
+        let _ = app.emit(
+            "directory-chunk",
+            DirectoryChunk {
+                listing_id,
+                path: path.clone(),
+                entries: std::mem::take(entries),
+            },
+        );
+    };
+
+    let mut total = 0;
+    let result = std::fs::read_dir(dir).map(|entries| {
+        let mut chunk = Vec::with_capacity(STREAM_CHUNK_SIZE);
+        let mut last_flush = std::time::Instant::now();
+        for entry in entries.flatten() {
+            if let Some(item) = to_dir_entry(entry) {
+                chunk.push(item);
+                total += 1;
+            }
+            if chunk.len() >= STREAM_CHUNK_SIZE
+                || (!chunk.is_empty() && last_flush.elapsed() >= STREAM_FLUSH_INTERVAL)
+            {
+                emit_chunk(&mut chunk);
+                last_flush = std::time::Instant::now();
+            }
+        }
+        if !chunk.is_empty() {
+            emit_chunk(&mut chunk);
+        }
+    });
+
+    let _ = app.emit(
+        "directory-complete",
+        DirectoryComplete {
+            listing_id,
+            path: path.clone(),
+            total,
+            error: result
+                .err()
+                .map(|e| AppError::io("Falha ao listar diretório", e)),
+        },
+    );
+}
+
+/// Starts listing `path` in the background and returns its listing id at once.
+/// Entries arrive on `directory-chunk` events as the share yields them, then
+/// `directory-complete` reports the total or the error; events carry the id so
+/// the frontend can drop those of a folder it already left.
+/// Validation runs off the main thread too: canonicalizing a path on an
+/// unreachable share can block for as long as the listing itself.
+#[tauri::command]
+pub async fn list_directory_stream(app: tauri::AppHandle, path: String) -> Result<u64, AppError> {
+    crate::metrics::timed("list_directory_stream", async move {
+        let validate_app = app.clone();
+        let validated = tauri::async_runtime::spawn_blocking(move || {
+            let settings = validate_app.state::<Settings>();
+            validate_path(&settings, &path)
         })
+        .await
+        .map_err(|e| AppError::internal("Falha ao listar diretório", e))??;
+        let listing_id = NEXT_LISTING_ID.fetch_add(1, Ordering::Relaxed);
+        tauri::async_runtime::spawn_blocking(move || stream_entries(&app, listing_id, &validated));
+        Ok(listing_id)
+    })
+    .await
+}
+
+fn to_dir_entry(entry: std::fs::DirEntry) -> Option<DirEntry> {
+    let metadata = entry.metadata().ok()?;
+    let name = entry.file_name().to_string_lossy().to_string();
+    let is_dir = metadata.is_dir();
+    let size = if is_dir { 0 } else { metadata.len() };
+    let modified = metadata
+        .modified()
+        .map(format_system_time)
+        .unwrap_or_default();
+    let extension = if is_dir {
+        String::new()
+    } else {
+        std::path::Path::new(&name)
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default()
+    };
+
+    Some(DirEntry {
+        name,
+        is_dir,
+        size,
+        modified,
+        extension,
+    })
+}
+
+fn read_sorted_entries(dir: &std::path::Path) -> Result<Vec<DirEntry>, AppError> {
+    let entries =
+        std::fs::read_dir(dir).map_err(|e| AppError::io("Falha ao listar diretório", e))?;
+
+    let mut items: Vec<DirEntry> = entries
+        .filter_map(|entry| to_dir_entry(entry.ok()?))
         .collect();
 
     // Sort: directories first, then alphabetically
@@ -173,73 +564,462 @@ pub fn list_directory(path: String) -> Result<Vec<DirEntry>, String> {
     Ok(items)
 }
 
+#[derive(Clone, serde::Serialize)]
+pub struct FolderSize {
+    pub path: String,
+    pub total_bytes: u64,
+    pub file_count: u64,
+    pub dir_count: u64,
+    pub inaccessible: u64,
+    pub done: bool,
+}
+
+fn compute_folder_size(app: &tauri::AppHandle, root: &std::path::Path) -> FolderSize {
+    let mut result = FolderSize {
+        path: root.to_string_lossy().to_string(),
+        total_bytes: 0,
+        file_count: 0,
+        dir_count: 0,
+        inaccessible: 0,
+        done: false,
+    };
+
+    let mut last_emit = std::time::Instant::now();
+    let mut pending = vec![root.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(_) => {
+                result.inaccessible += 1;
+                continue;
+            }
+        };
+
+        for entry in entries.flatten() {
+            // symlink_metadata: junctions/links are counted but never followed
+            match entry.path().symlink_metadata() {
+                Ok(meta) if meta.is_dir() => {
+                    result.dir_count += 1;
+                    pending.push(entry.path());
+                }
+                Ok(meta) => {
+                    result.file_count += 1;
+                    result.total_bytes += meta.len();
+                }
+                Err(_) => result.inaccessible += 1,
+            }
+        }
+
+        if last_emit.elapsed() >= std::time::Duration::from_millis(250) {
+            last_emit = std::time::Instant::now();
+            let _ = app.emit("folder-size-progress", result.clone());
+        }
+    }
+
+    result.done = true;
+    let _ = app.emit("folder-size-progress", result.clone());
+    result
+}
+
+#[tauri::command]
+pub async fn get_folder_size(
+    app: tauri::AppHandle,
+    settings: tauri::State<'_, Settings>,
+    path: String,
+) -> Result<FolderSize, AppError> {
+    crate::metrics::timed("get_folder_size", async move {
+        let validated = validate_path(&settings, &path)?;
+
+        if !validated.is_dir() {
+            return Err(AppError::InvalidInput(
+                "Caminho não é um diretório válido".into(),
+            ));
+        }
+
+        tauri::async_runtime::spawn_blocking(move || compute_folder_size(&app, &validated))
+            .await
+            .map_err(|e| AppError::internal("Falha ao calcular tamanho da pasta", e))
+    })
+    .await
+}
+
+#[derive(serde::Serialize)]
+pub struct EntryPermissions {
+    pub can_read: bool,
+    pub can_write: bool,
+    pub can_delete: bool,
+    pub full_control: bool,
+}
+
+#[derive(serde::Serialize)]
+pub struct EntryProperties {
+    pub path: String,
+    pub is_dir: bool,
+    pub size: u64,
+    pub owner: String,
+    pub readonly: bool,
+    pub hidden: bool,
+    pub system: bool,
+    pub created: String,
+    pub modified: String,
+    pub accessed: String,
+    /// Effective rights of the current user; `None` when the ACL can't be read.
+    pub permissions: Option<EntryPermissions>,
+}
+
+/// Returns (hidden, system) file attributes.
+#[cfg(windows)]
+fn entry_attributes(path: &std::path::Path) -> (bool, bool) {
+    use windows_sys::Win32::Storage::FileSystem::*;
+
+    let wide: Vec<u16> = path
+        .to_string_lossy()
+        .encode_utf16()
+        .chain(std::iter::once(0))
+        .collect();
+    let attributes = unsafe { GetFileAttributesW(wide.as_ptr()) };
+    if attributes == INVALID_FILE_ATTRIBUTES {
+        return (false, false);
+    }
+
+    (
+        attributes & FILE_ATTRIBUTE_HIDDEN != 0,
+        attributes & FILE_ATTRIBUTE_SYSTEM != 0,
+    )
+}
+
+#[cfg(not(windows))]
+fn entry_attributes(path: &std::path::Path) -> (bool, bool) {
+    let hidden = path
+        .file_name()
+        .map(|name| name.to_string_lossy().starts_with('.'))
+        .unwrap_or(false);
+    (hidden, false)
+}
+
+#[cfg(windows)]
+unsafe fn account_name(sid: windows_sys::Win32::Security::PSID) -> String {
+    use windows_sys::Win32::Security::*;
+
+    let mut name = vec![0u16; 256];
+    let mut name_len = name.len() as u32;
+    let mut domain = vec![0u16; 256];
+    let mut domain_len = domain.len() as u32;
+    let mut sid_type: SID_NAME_USE = 0;
+
+    let ok = LookupAccountSidW(
+        std::ptr::null(),
+        sid,
+        name.as_mut_ptr(),
+        &mut name_len,
+        domain.as_mut_ptr(),
+        &mut domain_len,
+        &mut sid_type,
+    );
+    if ok == 0 {
+        return String::new();
+    }
+
+    let name = String::from_utf16_lossy(&name[..name_len as usize]);
+    let domain = String::from_utf16_lossy(&domain[..domain_len as usize]);
+    if domain.is_empty() {
+        name
+    } else {
+        format!("{}\\{}", domain, name)
+    }
+}
+
+/// Returns the owner account and the current user's effective rights.
+#[cfg(windows)]
+fn entry_security(path: &std::path::Path) -> (String, Option<EntryPermissions>) {
+    use windows_sys::Win32::Foundation::{LocalFree, ERROR_SUCCESS};
+    use windows_sys::Win32::Security::Authorization::*;
+    use windows_sys::Win32::Security::*;
+    use windows_sys::Win32::Storage::FileSystem::*;
+
+    let wide: Vec<u16> = path
+        .to_string_lossy()
+        .encode_utf16()
+        .chain(std::iter::once(0))
+        .collect();
+
+    let mut owner: PSID = std::ptr::null_mut();
+    let mut dacl: *mut ACL = std::ptr::null_mut();
+    let mut descriptor: PSECURITY_DESCRIPTOR = std::ptr::null_mut();
+
+    unsafe {
+        let status = GetNamedSecurityInfoW(
+            wide.as_ptr(),
+            SE_FILE_OBJECT,
+            OWNER_SECURITY_INFORMATION | DACL_SECURITY_INFORMATION,
+            &mut owner,
+            std::ptr::null_mut(),
+            &mut dacl,
+            std::ptr::null_mut(),
+            &mut descriptor,
+        );
+        if status != ERROR_SUCCESS {
+            return (String::new(), None);
+        }
+
+        let owner_name = account_name(owner);
+
+        let permissions = if dacl.is_null() {
+            // A null DACL grants everyone full access
+            Some(EntryPermissions {
+                can_read: true,
+                can_write: true,
+                can_delete: true,
+                full_control: true,
+            })
+        } else {
+            let current_user: Vec<u16> = "CURRENT_USER\0".encode_utf16().collect();
+            let mut trustee: TRUSTEE_W = std::mem::zeroed();
+            BuildTrusteeWithNameW(&mut trustee, current_user.as_ptr());
+
+            let mut rights: u32 = 0;
+            if GetEffectiveRightsFromAclW(dacl, &trustee, &mut rights) == ERROR_SUCCESS {
+                Some(EntryPermissions {
+                    can_read: rights & FILE_GENERIC_READ == FILE_GENERIC_READ,
+                    can_write: rights & FILE_WRITE_DATA != 0,
+                    can_delete: rights & DELETE != 0,
+                    full_control: rights & FILE_ALL_ACCESS == FILE_ALL_ACCESS,
+                })
+            } else {
+                None
+            }
+        };
+
+        LocalFree(descriptor);
+        (owner_name, permissions)
+    }
+}
+
+#[cfg(not(windows))]
+fn entry_security(_path: &std::path::Path) -> (String, Option<EntryPermissions>) {
+    (String::new(), None)
+}
+
 #[tauri::command]
-pub fn rename_entry(old_path: String, new_name: String) -> Result<(), String> {
-    let validated_old = validate_path(&old_path)?;
+pub fn get_entry_properties(
+    settings: tauri::State<'_, Settings>,
+    path: String,
+) -> Result<EntryProperties, AppError> {
+    let validated = validate_path(&settings, &path)?;
+
+    let metadata =
+        std::fs::metadata(&validated).map_err(|e| AppError::io("Falha ao ler propriedades", e))?;
+    let (hidden, system) = entry_attributes(&validated);
+    let (owner, permissions) = entry_security(&validated);
+    let time_or_empty = |time: std::io::Result<std::time::SystemTime>| {
+        time.map(format_system_time).unwrap_or_default()
+    };
+
+    Ok(EntryProperties {
+        path: validated.to_string_lossy().to_string(),
+        is_dir: metadata.is_dir(),
+        size: if metadata.is_dir() { 0 } else { metadata.len() },
+        owner,
+        readonly: metadata.permissions().readonly(),
+        hidden,
+        system,
+        created: time_or_empty(metadata.created()),
+        modified: time_or_empty(metadata.modified()),
+        accessed: time_or_empty(metadata.accessed()),
+        permissions,
+    })
+}
 
-    if new_name.contains('\\') || new_name.contains('/') || new_name.contains('\0') {
-        return Err("Nome inválido: não pode conter barras ou caracteres nulos".into());
+/// Probes by actually opening for write: ACLs alone miss share-level permissions
+/// and the read-only attribute.
+fn probe_writable(path: &std::path::Path) -> bool {
+    if path.is_dir() {
+        let probe = path.join(format!(".adcontec-write-probe-{}", std::process::id()));
+        let created = std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&probe)
+            .is_ok();
+        if created {
+            let _ = std::fs::remove_file(&probe);
+        }
+        created
+    } else {
+        // Opening in append mode without writing leaves contents and mtime untouched
+        std::fs::OpenOptions::new().append(true).open(path).is_ok()
     }
+}
+
+#[tauri::command]
+pub fn can_write(settings: tauri::State<'_, Settings>, path: String) -> Result<bool, AppError> {
+    let validated = validate_path(&settings, &path)?;
+    Ok(probe_writable(&validated))
+}
+
+#[tauri::command]
+pub fn rename_entry(
+    settings: tauri::State<'_, Settings>,
+    old_path: String,
+    new_name: String,
+) -> Result<(), AppError> {
+    rename_entry_impl(&settings, &old_path, &new_name)
+}
+
+fn rename_entry_impl(settings: &Settings, old_path: &str, new_name: &str) -> Result<(), AppError> {
+    let validated_old = validate_path(settings, old_path)?;
 
-    let parent = validated_old
-        .parent()
-        .ok_or("Não foi possível determinar o diretório pai")?;
-    let new_path = parent.join(&new_name);
+    validate_entry_name(new_name)?;
+
+    let parent = validated_old.parent().ok_or_else(|| {
+        AppError::InvalidInput("Não foi possível determinar o diretório pai".into())
+    })?;
+    let new_path = parent.join(new_name);
 
     // Validate new path is still within base
     let new_path_str = new_path.to_string_lossy().to_string();
-    let base_lower = NETWORK_BASE_PATH.to_lowercase();
-    if !new_path_str.to_lowercase().starts_with(&base_lower) {
-        return Err("Acesso negado: caminho de destino fora do diretório permitido".into());
+    if !is_within_base(&new_path_str, &network_base_path(settings)) {
+        return Err(AppError::AccessDenied(
+            "Acesso negado: caminho de destino fora do diretório permitido".into(),
+        ));
     }
 
     if new_path.exists() {
-        return Err(format!("Já existe um item com o nome '{}'", new_name));
+        return Err(AppError::AlreadyExists(format!(
+            "Já existe um item com o nome '{}'",
+            new_name
+        )));
     }
 
-    std::fs::rename(&validated_old, &new_path)
-        .map_err(|e| format!("Falha ao renomear: {}", e))?;
+    std::fs::rename(&validated_old, &new_path).map_err(|e| AppError::io("Falha ao renomear", e))?;
 
     Ok(())
 }
 
 #[tauri::command]
-pub fn move_entry(source_path: String, dest_folder: String) -> Result<(), String> {
-    let validated_source = validate_path(&source_path)?;
-    let validated_dest = validate_path(&dest_folder)?;
+pub fn move_entry(
+    settings: tauri::State<'_, Settings>,
+    source_path: String,
+    dest_folder: String,
+) -> Result<(), AppError> {
+    move_entry_impl(&settings, &source_path, &dest_folder)
+}
+
+fn move_entry_impl(
+    settings: &Settings,
+    source_path: &str,
+    dest_folder: &str,
+) -> Result<(), AppError> {
+    let validated_source = validate_path(settings, source_path)?;
+    let validated_dest = validate_path(settings, dest_folder)?;
 
     if !validated_dest.is_dir() {
-        return Err("Destino não é um diretório válido".into());
+        return Err(AppError::InvalidInput(
+            "Destino não é um diretório válido".into(),
+        ));
     }
 
-    let file_name = validated_source
-        .file_name()
-        .ok_or("Não foi possível determinar o nome do arquivo")?;
+    let file_name = validated_source.file_name().ok_or_else(|| {
+        AppError::InvalidInput("Não foi possível determinar o nome do arquivo".into())
+    })?;
     let dest_path = validated_dest.join(file_name);
 
     if dest_path.exists() {
-        return Err(format!(
+        return Err(AppError::AlreadyExists(format!(
             "Já existe um item com o nome '{}' no destino",
             file_name.to_string_lossy()
-        ));
+        )));
     }
 
     std::fs::rename(&validated_source, &dest_path)
-        .map_err(|e| format!("Falha ao mover: {}", e))?;
+        .map_err(|e| AppError::io("Falha ao mover", e))?;
+
+    Ok(())
+}
+
+/// Sends a file or folder to the Recycle Bin. Shares without a Recycle Bin make
+/// the shell ask before destroying the item instead of silently deleting it.
+#[cfg(windows)]
+pub(crate) fn move_to_recycle_bin(path: &std::path::Path) -> Result<(), AppError> {
+    use windows_sys::Win32::UI::Shell::*;
+
+    // pFrom is a list of paths terminated by an extra null
+    let from: Vec<u16> = path
+        .to_string_lossy()
+        .encode_utf16()
+        .chain([0, 0])
+        .collect();
+
+    let mut op = SHFILEOPSTRUCTW {
+        hwnd: std::ptr::null_mut(),
+        wFunc: FO_DELETE,
+        pFrom: from.as_ptr(),
+        pTo: std::ptr::null(),
+        fFlags: (FOF_ALLOWUNDO
+            | FOF_NOCONFIRMATION
+            | FOF_SILENT
+            | FOF_NOERRORUI
+            | FOF_WANTNUKEWARNING) as u16,
+        fAnyOperationsAborted: 0,
+        hNameMappings: std::ptr::null_mut(),
+        lpszProgressTitle: std::ptr::null(),
+    };
+
+    let result = unsafe { SHFileOperationW(&mut op) };
+    if result != 0 {
+        return Err(AppError::Internal(format!(
+            "Falha ao enviar para a Lixeira (código {})",
+            result
+        )));
+    }
+    if op.fAnyOperationsAborted != 0 {
+        return Err(AppError::Cancelled("Exclusão cancelada".into()));
+    }
 
     Ok(())
 }
 
+/// Without the Windows shell there is no Recycle Bin to send to, so the item is
+/// deleted permanently.
+#[cfg(not(windows))]
+pub(crate) fn move_to_recycle_bin(path: &std::path::Path) -> Result<(), AppError> {
+    let metadata =
+        std::fs::symlink_metadata(path).map_err(|e| AppError::io("Falha ao excluir", e))?;
+    if metadata.is_dir() {
+        std::fs::remove_dir_all(path).map_err(|e| AppError::io("Falha ao excluir pasta", e))
+    } else {
+        std::fs::remove_file(path).map_err(|e| AppError::io("Falha ao excluir arquivo", e))
+    }
+}
+
 #[tauri::command]
-pub fn delete_entry(path: String, is_dir: bool) -> Result<(), String> {
-    let validated = validate_path(&path)?;
+pub fn delete_entry(
+    settings: tauri::State<'_, Settings>,
+    path: String,
+    is_dir: bool,
+    permanent: Option<bool>,
+) -> Result<(), AppError> {
+    delete_entry_impl(&settings, &path, is_dir, permanent.unwrap_or(false))
+}
+
+fn delete_entry_impl(
+    settings: &Settings,
+    path: &str,
+    is_dir: bool,
+    permanent: bool,
+) -> Result<(), AppError> {
+    let validated = validate_path(settings, path)?;
+
+    if !permanent {
+        return move_to_recycle_bin(&validated);
+    }
 
     if is_dir {
         std::fs::remove_dir_all(&validated)
-            .map_err(|e| format!("Falha ao excluir pasta: {}", e))?;
+            .map_err(|e| AppError::io("Falha ao excluir pasta", e))?;
     } else {
         std::fs::remove_file(&validated)
-            .map_err(|e| format!("Falha ao excluir arquivo: {}", e))?;
+            .map_err(|e| AppError::io("Falha ao excluir arquivo", e))?;
     }
 
     Ok(())
@@ -260,11 +1040,17 @@ fn copy_dir_recursive(src: &std::path::Path, dst: &std::path::Path) -> std::io::
 }
 
 #[tauri::command]
-pub fn copy_paths_to_directory(source_paths: Vec<String>, dest_dir: String) -> Result<(), String> {
-    let validated_dest = validate_path(&dest_dir)?;
+pub fn copy_paths_to_directory(
+    settings: tauri::State<'_, Settings>,
+    source_paths: Vec<String>,
+    dest_dir: String,
+) -> Result<(), AppError> {
+    let validated_dest = validate_path(&settings, &dest_dir)?;
 
     if !validated_dest.is_dir() {
-        return Err("Destino não é um diretório válido".into());
+        return Err(AppError::InvalidInput(
+            "Destino não é um diretório válido".into(),
+        ));
     }
 
     let mut errors = Vec::new();
@@ -317,44 +1103,900 @@ pub fn copy_paths_to_directory(source_paths: Vec<String>, dest_dir: String) -> R
     if errors.is_empty() {
         Ok(())
     } else {
-        Err(errors.join("\n"))
+        Err(AppError::Internal(errors.join("\n")))
     }
 }
 
-#[tauri::command]
-pub fn create_directory(parent_path: String, folder_name: String) -> Result<(), String> {
-    let validated_parent = validate_path(&parent_path)?;
+#[derive(Clone, serde::Serialize)]
+pub struct CopyProgress {
+    pub source: String,
+    pub current_file: String,
+    pub copied_bytes: u64,
+    pub total_bytes: u64,
+}
 
-    if !validated_parent.is_dir() {
-        return Err("Caminho pai não é um diretório válido".into());
+/// Emit at most one progress event per this many bytes of a single file.
+const PROGRESS_STEP_BYTES: u64 = 8 * 1024 * 1024;
+
+/// Links and junctions count as nothing: copying and zipping skip them.
+fn entry_total_size(path: &std::path::Path) -> u64 {
+    match std::fs::symlink_metadata(path) {
+        Ok(meta) if meta.is_symlink() => 0,
+        Ok(meta) if meta.is_dir() => std::fs::read_dir(path)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|entry| entry_total_size(&entry.path()))
+                    .sum()
+            })
+            .unwrap_or(0),
+        Ok(meta) => meta.len(),
+        Err(_) => 0,
     }
+}
+
+/// Copies `reader` into `writer` in 1 MiB chunks, calling `on_progress` with the
+/// running byte count roughly every PROGRESS_STEP_BYTES and once at the end.
+fn stream_with_progress<R: std::io::Read, W: std::io::Write>(
+    reader: &mut R,
+    writer: &mut W,
+    mut on_progress: impl FnMut(u64),
+) -> std::io::Result<u64> {
+    let mut buffer = vec![0u8; 1024 * 1024];
+    let mut total = 0u64;
+    let mut since_last_emit = 0u64;
 
-    if folder_name.contains('\\') || folder_name.contains('/') || folder_name.contains('\0') {
-        return Err("Nome inválido: não pode conter barras ou caracteres nulos".into());
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        writer.write_all(&buffer[..read])?;
+        total += read as u64;
+        since_last_emit += read as u64;
+        if since_last_emit >= PROGRESS_STEP_BYTES {
+            since_last_emit = 0;
+            on_progress(total);
+        }
     }
 
-    let new_path = validated_parent.join(&folder_name);
+    on_progress(total);
+    Ok(total)
+}
 
-    if new_path.exists() {
-        return Err(format!("Já existe um item com o nome '{}'", folder_name));
-    }
+fn copy_file_with_progress(
+    app: &tauri::AppHandle,
+    src: &std::path::Path,
+    dst: &std::path::Path,
+    progress: &mut CopyProgress,
+) -> std::io::Result<()> {
+    let mut reader = std::fs::File::open(src)?;
+    let mut writer = std::fs::File::create(dst)?;
+
+    progress.current_file = src.to_string_lossy().to_string();
+    let start = progress.copied_bytes;
 
-    std::fs::create_dir(&new_path)
-        .map_err(|e| format!("Falha ao criar pasta: {}", e))?;
+    stream_with_progress(&mut reader, &mut writer, |copied| {
+        progress.copied_bytes = start + copied;
+        let _ = app.emit("copy-progress", progress.clone());
+    })?;
+
+    Ok(())
+}
 
+/// Links and junctions are skipped rather than followed, so a junction pointing
+/// back up the tree cannot make the copy recurse forever.
+fn copy_dir_with_progress(
+    app: &tauri::AppHandle,
+    job: &JobHandle,
+    src: &std::path::Path,
+    dst: &std::path::Path,
+    overwrite: bool,
+    progress: &mut CopyProgress,
+) -> Result<(), AppError> {
+    let copy_error = |e| AppError::io("Falha ao copiar pasta", e);
+    std::fs::create_dir_all(dst).map_err(copy_error)?;
+    for entry in std::fs::read_dir(src).map_err(copy_error)? {
+        job.check_cancelled()?;
+        let entry = entry.map_err(copy_error)?;
+        let path = entry.path();
+        let meta = path.symlink_metadata().map_err(copy_error)?;
+        let dest_path = dst.join(entry.file_name());
+        if meta.is_symlink() {
+            continue;
+        } else if meta.is_dir() {
+            copy_dir_with_progress(app, job, &path, &dest_path, overwrite, progress)?;
+        } else if overwrite || !dest_path.exists() {
+            copy_file_with_progress(app, &path, &dest_path, progress).map_err(copy_error)?;
+        } else {
+            // Still counted, so progress reaches the total
+            progress.copied_bytes += meta.len();
+            let _ = app.emit("copy-progress", progress.clone());
+        }
+        job.progress(
+            progress.copied_bytes,
+            progress.total_bytes,
+            progress.current_file.as_str(),
+        );
+    }
     Ok(())
 }
 
+/// Runs off the main thread so a large copy keeps the window and its
+/// `copy-progress` events responsive.
 #[tauri::command]
-pub fn open_file(path: String) -> Result<(), String> {
-    let validated = validate_path(&path)?;
+pub async fn copy_entry(
+    app: tauri::AppHandle,
+    source_path: String,
+    dest_folder: String,
+    overwrite: bool,
+) -> Result<(), AppError> {
+    crate::metrics::timed("copy_entry", async move {
+        tauri::async_runtime::spawn_blocking(move || {
+            let settings = app.state::<Settings>();
+            copy_entry_impl(&app, &settings, &source_path, &dest_folder, overwrite)
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao copiar", e))?
+    })
+    .await
+}
 
-    #[cfg(windows)]
-    {
-        std::process::Command::new("cmd")
-            .args(["/C", "start", "", &validated.to_string_lossy()])
-            .spawn()
-            .map_err(|e| format!("Falha ao abrir arquivo: {}", e))?;
+fn copy_entry_impl(
+    app: &tauri::AppHandle,
+    settings: &Settings,
+    source_path: &str,
+    dest_folder: &str,
+    overwrite: bool,
+) -> Result<(), AppError> {
+    let validated_source = validate_path(settings, source_path)?;
+    let validated_dest = validate_path(settings, dest_folder)?;
+
+    if !validated_dest.is_dir() {
+        return Err(AppError::InvalidInput(
+            "Destino não é um diretório válido".into(),
+        ));
+    }
+
+    if validated_dest.starts_with(&validated_source) {
+        return Err(AppError::InvalidInput(
+            "Não é possível copiar uma pasta para dentro dela mesma".into(),
+        ));
+    }
+
+    let file_name = validated_source.file_name().ok_or_else(|| {
+        AppError::InvalidInput("Não foi possível determinar o nome do arquivo".into())
+    })?;
+    let dest_path = validated_dest.join(file_name);
+
+    if dest_path == validated_source {
+        return Err(AppError::InvalidInput(
+            "Origem e destino são o mesmo item".into(),
+        ));
+    }
+
+    if dest_path.exists() {
+        if !overwrite {
+            return Err(AppError::AlreadyExists(format!(
+                "Já existe um item com o nome '{}' no destino",
+                file_name.to_string_lossy()
+            )));
+        }
+        if dest_path.is_dir() != validated_source.is_dir() {
+            return Err(AppError::AlreadyExists(format!(
+                "'{}' já existe no destino com um tipo diferente",
+                file_name.to_string_lossy()
+            )));
+        }
+    }
+
+    let mut progress = CopyProgress {
+        source: validated_source.to_string_lossy().to_string(),
+        current_file: String::new(),
+        copied_bytes: 0,
+        total_bytes: entry_total_size(&validated_source),
+    };
+
+    if !validated_source.is_dir() {
+        return copy_file_with_progress(app, &validated_source, &dest_path, &mut progress)
+            .map_err(|e| AppError::io("Falha ao copiar", e));
+    }
+
+    let job = Jobs::start(
+        app,
+        "copy",
+        format!("Copiar {}", file_name.to_string_lossy()),
+    );
+    let result = copy_dir_with_progress(
+        app,
+        &job,
+        &validated_source,
+        &dest_path,
+        overwrite,
+        &mut progress,
+    );
+    job.finish(&result);
+    result
+}
+
+// ── Drag & Drop Import ─────────────────────────────────────────
+
+#[derive(Clone, Copy, Default, serde::Deserialize)]
+pub enum ConflictPolicy {
+    #[default]
+    Skip,
+    Overwrite,
+    KeepBoth,
+}
+
+/// Folder currently open in the browser; files dropped on the window land here.
+#[derive(Default)]
+pub struct DropTarget(Mutex<Option<(PathBuf, ConflictPolicy)>>);
+
+#[tauri::command]
+pub fn set_drop_target(
+    settings: tauri::State<'_, Settings>,
+    drop_target: tauri::State<'_, DropTarget>,
+    folder: Option<String>,
+    conflict: Option<ConflictPolicy>,
+) -> Result<(), AppError> {
+    let target = match folder {
+        Some(folder) => {
+            let validated = validate_path(&settings, &folder)?;
+            if !validated.is_dir() {
+                return Err(AppError::InvalidInput(
+                    "Destino não é um diretório válido".into(),
+                ));
+            }
+            Some((validated, conflict.unwrap_or_default()))
+        }
+        None => None,
+    };
+
+    *drop_target.0.lock().unwrap() = target;
+    Ok(())
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct ImportSummary {
+    pub dest_folder: String,
+    pub imported: Vec<String>,
+    pub skipped: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+/// `nota.xml` → `nota (2).xml`, `nota (3).xml`, ... until a free name is found.
+pub(crate) fn unique_destination(
+    dest_folder: &std::path::Path,
+    file_name: &std::ffi::OsStr,
+) -> PathBuf {
+    let original = std::path::Path::new(file_name);
+    let stem = original
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let extension = original
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+
+    (2..)
+        .map(|n| dest_folder.join(format!("{} ({}){}", stem, n, extension)))
+        .find(|candidate| !candidate.exists())
+        .unwrap()
+}
+
+fn import_dropped_files_impl(
+    app: &tauri::AppHandle,
+    settings: &Settings,
+    paths: &[PathBuf],
+    dest_folder: &str,
+    conflict: ConflictPolicy,
+) -> Result<ImportSummary, AppError> {
+    let validated_dest = validate_path(settings, dest_folder)?;
+    if !validated_dest.is_dir() {
+        return Err(AppError::InvalidInput(
+            "Destino não é um diretório válido".into(),
+        ));
+    }
+
+    let mut summary = ImportSummary {
+        dest_folder: validated_dest.to_string_lossy().to_string(),
+        imported: Vec::new(),
+        skipped: Vec::new(),
+        errors: Vec::new(),
+    };
+
+    for source_path in paths {
+        let source = source_path.to_string_lossy().to_string();
+        let Some(file_name) = source_path.file_name() else {
+            summary.errors.push(format!(
+                "Não foi possível determinar o nome de '{}'",
+                source
+            ));
+            continue;
+        };
+
+        if validated_dest.starts_with(source_path) {
+            summary.errors.push(format!(
+                "Não é possível copiar '{}' para dentro dele mesmo",
+                file_name.to_string_lossy()
+            ));
+            continue;
+        }
+
+        let mut dest_path = validated_dest.join(file_name);
+        if dest_path.exists() {
+            match conflict {
+                ConflictPolicy::Skip => {
+                    summary.skipped.push(source);
+                    continue;
+                }
+                ConflictPolicy::Overwrite if dest_path.is_dir() != source_path.is_dir() => {
+                    summary.errors.push(format!(
+                        "'{}' já existe no destino com um tipo diferente",
+                        file_name.to_string_lossy()
+                    ));
+                    continue;
+                }
+                ConflictPolicy::Overwrite => {}
+                ConflictPolicy::KeepBoth => {
+                    dest_path = unique_destination(&validated_dest, file_name);
+                }
+            }
+        }
+
+        let mut progress = CopyProgress {
+            source: source.clone(),
+            current_file: String::new(),
+            copied_bytes: 0,
+            total_bytes: entry_total_size(source_path),
+        };
+
+        let result = if source_path.is_dir() {
+            let overwrite = matches!(conflict, ConflictPolicy::Overwrite);
+            copy_dir_with_progress(app, source_path, &dest_path, overwrite, &mut progress)
+        } else {
+            copy_file_with_progress(app, source_path, &dest_path, &mut progress)
+        };
+
+        match result {
+            Ok(()) => summary
+                .imported
+                .push(dest_path.to_string_lossy().to_string()),
+            Err(e) => summary.errors.push(format!(
+                "Falha ao copiar '{}': {}",
+                file_name.to_string_lossy(),
+                e
+            )),
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Copies files from outside the allowed roots (e.g. dragged from Explorer) into a client folder.
+#[tauri::command]
+pub async fn import_dropped_files(
+    app: tauri::AppHandle,
+    paths: Vec<String>,
+    dest_folder: String,
+    conflict: Option<ConflictPolicy>,
+) -> Result<ImportSummary, AppError> {
+    crate::metrics::timed("import_dropped_files", async move {
+        tauri::async_runtime::spawn_blocking(move || {
+            let settings = app.state::<Settings>();
+            let paths: Vec<PathBuf> = paths.into_iter().map(PathBuf::from).collect();
+            import_dropped_files_impl(
+                &app,
+                &settings,
+                &paths,
+                &dest_folder,
+                conflict.unwrap_or_default(),
+            )
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao importar arquivos", e))?
+    })
+    .await
+}
+
+/// Window drop handler: imports into the current drop target, reporting via events.
+pub fn handle_file_drop(app: &tauri::AppHandle, paths: Vec<PathBuf>) {
+    let Some((dest_folder, conflict)) = app.state::<DropTarget>().0.lock().unwrap().clone() else {
+        return;
+    };
+
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let settings = app.state::<Settings>();
+        let dest_folder = dest_folder.to_string_lossy();
+        match import_dropped_files_impl(&app, &settings, &paths, &dest_folder, conflict) {
+            Ok(summary) => {
+                let _ = app.emit("import-finished", summary);
+            }
+            Err(e) => {
+                let _ = app.emit("import-failed", e);
+            }
+        }
+    });
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct ZipProgress {
+    pub source: String,
+    pub current_file: String,
+    pub processed_bytes: u64,
+    pub total_bytes: u64,
+}
+
+#[derive(serde::Serialize)]
+pub struct ZipSummary {
+    pub output_path: String,
+    pub file_count: u64,
+    pub original_size: u64,
+    pub compressed_size: u64,
+}
+
+struct ZipContext<'a> {
+    app: &'a tauri::AppHandle,
+    job: &'a JobHandle,
+    writer: zip::ZipWriter<std::fs::File>,
+    options: zip::write::SimpleFileOptions,
+    progress: ZipProgress,
+    file_count: u64,
+}
+
+/// Symlinks and junctions (reparse points) are left out instead of followed, so
+/// a link cannot pull in files from outside the folder or loop back into it.
+fn add_to_zip(ctx: &mut ZipContext, path: &std::path::Path, name: &str) -> Result<(), AppError> {
+    let meta = path
+        .symlink_metadata()
+        .map_err(|e| AppError::io(format!("Falha ao ler '{}'", name), e))?;
+    if meta.is_symlink() {
+        return Ok(());
+    }
+    if meta.is_dir() {
+        ctx.writer
+            .add_directory(format!("{}/", name), ctx.options)
+            .map_err(|e| AppError::internal(format!("Falha ao adicionar pasta '{}'", name), e))?;
+
+        let mut entries: Vec<_> = std::fs::read_dir(path)
+            .map_err(|e| AppError::io(format!("Falha ao listar '{}'", name), e))?
+            .flatten()
+            .collect();
+        entries.sort_by_key(|entry| entry.file_name().to_string_lossy().to_lowercase());
+
+        for entry in entries {
+            let child_name = format!("{}/{}", name, entry.file_name().to_string_lossy());
+            add_to_zip(ctx, &entry.path(), &child_name)?;
+        }
+        return Ok(());
+    }
+
+    ctx.job.check_cancelled()?;
+    let mut reader = std::fs::File::open(path)
+        .map_err(|e| AppError::io(format!("Falha ao abrir '{}'", name), e))?;
+    let size = reader.metadata().map(|m| m.len()).unwrap_or(0);
+    let options = ctx.options.large_file(size >= u32::MAX as u64);
+
+    ctx.writer
+        .start_file(name, options)
+        .map_err(|e| AppError::internal(format!("Falha ao adicionar '{}'", name), e))?;
+
+    ctx.progress.current_file = name.to_string();
+    let start = ctx.progress.processed_bytes;
+    let app = ctx.app;
+    let job = ctx.job;
+    let progress = &mut ctx.progress;
+
+    stream_with_progress(&mut reader, &mut ctx.writer, |processed| {
+        progress.processed_bytes = start + processed;
+        job.progress(
+            progress.processed_bytes,
+            progress.total_bytes,
+            progress.current_file.as_str(),
+        );
+        let _ = app.emit("zip-progress", progress.clone());
+    })
+    .map_err(|e| AppError::io(format!("Falha ao compactar '{}'", name), e))?;
+
+    ctx.file_count += 1;
+    Ok(())
+}
+
+/// Runs off the main thread, so the window stays responsive and `cancel_job` can
+/// be handled while the archive is written.
+#[tauri::command]
+pub async fn zip_entry(
+    app: tauri::AppHandle,
+    path: String,
+    output_zip: String,
+    compression_level: Option<i64>,
+) -> Result<ZipSummary, AppError> {
+    crate::metrics::timed("zip_entry", async move {
+        tauri::async_runtime::spawn_blocking(move || {
+            let settings = app.state::<Settings>();
+            zip_entry_impl(&app, &settings, &path, &output_zip, compression_level)
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao compactar", e))?
+    })
+    .await
+}
+
+fn zip_entry_impl(
+    app: &tauri::AppHandle,
+    settings: &Settings,
+    path: &str,
+    output_zip: &str,
+    compression_level: Option<i64>,
+) -> Result<ZipSummary, AppError> {
+    let validated = validate_path(settings, path)?;
+
+    let level = compression_level.unwrap_or(6);
+    if !(0..=9).contains(&level) {
+        return Err(AppError::InvalidInput(
+            "Nível de compressão deve estar entre 0 e 9".into(),
+        ));
+    }
+
+    let output = PathBuf::from(output_zip);
+    if output.starts_with(&validated) {
+        return Err(AppError::InvalidInput(
+            "O arquivo ZIP não pode ser salvo dentro da pasta compactada".into(),
+        ));
+    }
+
+    let root_name = validated
+        .file_name()
+        .ok_or_else(|| {
+            AppError::InvalidInput("Não foi possível determinar o nome do arquivo".into())
+        })?
+        .to_string_lossy()
+        .to_string();
+
+    let options = if level == 0 {
+        zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored)
+    } else {
+        zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated)
+            .compression_level(Some(level))
+    };
+
+    let file = std::fs::File::create(&output).map_err(|e| AppError::io("Falha ao criar ZIP", e))?;
+
+    let total_bytes = entry_total_size(&validated);
+    let job = Jobs::start(app, "zip", format!("Compactar {}", root_name));
+    let mut ctx = ZipContext {
+        app,
+        job: &job,
+        writer: zip::ZipWriter::new(file),
+        options,
+        progress: ZipProgress {
+            source: validated.to_string_lossy().to_string(),
+            current_file: String::new(),
+            processed_bytes: 0,
+            total_bytes,
+        },
+        file_count: 0,
+    };
+
+    let added = add_to_zip(&mut ctx, &validated, &root_name);
+    // Taking the context apart ends its borrow of `job` before `finish` consumes it
+    let ZipContext {
+        writer, file_count, ..
+    } = ctx;
+    let result = added.and_then(|_| {
+        writer
+            .finish()
+            .map(|_| ())
+            .map_err(|e| AppError::internal("Falha ao finalizar ZIP", e))
+    });
+
+    // Finish only after the cleanup, so shutdown does not exit halfway through it
+    if result.is_err() {
+        let _ = std::fs::remove_file(&output);
+    }
+    job.finish(&result);
+    result?;
+
+    let compressed_size = std::fs::metadata(&output).map(|m| m.len()).unwrap_or(0);
+
+    Ok(ZipSummary {
+        output_path: output.to_string_lossy().to_string(),
+        file_count,
+        original_size: total_bytes,
+        compressed_size,
+    })
+}
+
+#[derive(serde::Serialize)]
+pub struct ExtractSummary {
+    pub extracted: u64,
+    pub skipped: Vec<String>,
+}
+
+/// Turns an archive entry name into a relative path, rejecting anything that
+/// could escape the destination (absolute paths, drive letters, `..`).
+fn safe_relative_path(name: &str) -> Option<PathBuf> {
+    let mut result = PathBuf::new();
+    for part in name.split(['/', '\\']) {
+        match part {
+            "" | "." => continue,
+            ".." => return None,
+            p if p.contains(':') => return None,
+            p => result.push(p),
+        }
+    }
+    if result.as_os_str().is_empty() {
+        None
+    } else {
+        Some(result)
+    }
+}
+
+pub(crate) fn zip_entry_error(error: zip::result::ZipError) -> AppError {
+    match error {
+        zip::result::ZipError::InvalidPassword => AppError::InvalidInput("Senha incorreta".into()),
+        zip::result::ZipError::UnsupportedArchive(message)
+            if message == zip::result::ZipError::PASSWORD_REQUIRED =>
+        {
+            AppError::InvalidInput("Arquivo protegido por senha: informe a senha".into())
+        }
+        e => AppError::internal("Falha ao ler item do ZIP", e),
+    }
+}
+
+/// Entries that already exist in `dest` or would land outside it are skipped.
+/// Guards against archive bombs: extraction stops once an archive holds more
+/// entries, or inflates to more bytes, than the share should take in one go.
+const MAX_EXTRACT_ENTRIES: u64 = 100_000;
+const MAX_EXTRACT_BYTES: u64 = 20 * 1024 * 1024 * 1024;
+
+fn extract_limit_error() -> AppError {
+    AppError::InvalidInput(format!(
+        "O arquivo excede o limite de extração ({} itens ou {} GB descompactados)",
+        MAX_EXTRACT_ENTRIES,
+        MAX_EXTRACT_BYTES / (1024 * 1024 * 1024)
+    ))
+}
+
+/// Copies the entry if it fits in what is left of `MAX_EXTRACT_BYTES`. Returns
+/// false, with the output incomplete, when it does not; declared sizes can lie.
+fn copy_within_limit<R: std::io::Read + ?Sized>(
+    reader: &mut R,
+    out: &mut std::fs::File,
+    written: &mut u64,
+) -> std::io::Result<bool> {
+    let remaining = MAX_EXTRACT_BYTES - *written;
+    let copied = std::io::copy(&mut std::io::Read::take(reader, remaining + 1), out)?;
+    if copied > remaining {
+        return Ok(false);
+    }
+    *written += copied;
+    Ok(true)
+}
+
+pub(crate) fn extract_zip(
+    archive: &std::path::Path,
+    dest: &std::path::Path,
+    password: Option<&str>,
+) -> Result<ExtractSummary, AppError> {
+    let file = std::fs::File::open(archive).map_err(|e| AppError::io("Falha ao abrir ZIP", e))?;
+    let mut zip = zip::ZipArchive::new(file)
+        .map_err(|e| AppError::InvalidInput("Arquivo ZIP inválido".into()).with_details(e))?;
+
+    if zip.len() as u64 > MAX_EXTRACT_ENTRIES {
+        return Err(extract_limit_error());
+    }
+
+    let mut summary = ExtractSummary {
+        extracted: 0,
+        skipped: Vec::new(),
+    };
+    let mut written = 0;
+
+    for i in 0..zip.len() {
+        let mut entry = match password {
+            Some(password) => zip.by_index_decrypt(i, password.as_bytes()),
+            None => zip.by_index(i),
+        }
+        .map_err(zip_entry_error)?;
+
+        let relative = match entry.enclosed_name() {
+            Some(path) => path,
+            None => {
+                summary.skipped.push(entry.name().to_string());
+                continue;
+            }
+        };
+        let target = dest.join(&relative);
+
+        if entry.is_dir() {
+            std::fs::create_dir_all(&target)
+                .map_err(|e| AppError::io("Falha ao criar pasta", e))?;
+            continue;
+        }
+
+        if target.exists() {
+            summary.skipped.push(relative.to_string_lossy().to_string());
+            continue;
+        }
+
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| AppError::io("Falha ao criar pasta", e))?;
+        }
+
+        let name = relative.to_string_lossy();
+        let mut out = std::fs::File::create(&target)
+            .map_err(|e| AppError::io(format!("Falha ao criar '{}'", name), e))?;
+        let within_limit = copy_within_limit(&mut entry, &mut out, &mut written)
+            .map_err(|e| AppError::io(format!("Falha ao extrair '{}'", name), e))?;
+        if !within_limit {
+            drop(out);
+            let _ = std::fs::remove_file(&target);
+            return Err(extract_limit_error());
+        }
+        summary.extracted += 1;
+    }
+
+    Ok(summary)
+}
+
+pub(crate) fn extract_7z(
+    archive: &std::path::Path,
+    dest: &std::path::Path,
+    password: Option<&str>,
+) -> Result<ExtractSummary, AppError> {
+    let mut summary = ExtractSummary {
+        extracted: 0,
+        skipped: Vec::new(),
+    };
+    let mut entries = 0;
+    let mut written = 0;
+    let mut exceeded = false;
+
+    let file = std::fs::File::open(archive).map_err(|e| AppError::io("Falha ao abrir 7z", e))?;
+    let password = password
+        .map(sevenz_rust::Password::from)
+        .unwrap_or_else(sevenz_rust::Password::empty);
+    sevenz_rust::decompress_with_extract_fn_and_password(
+        file,
+        dest,
+        password,
+        |entry, reader, _| {
+            entries += 1;
+            if entries > MAX_EXTRACT_ENTRIES {
+                exceeded = true;
+                return Err(sevenz_rust::Error::other("limite de extração"));
+            }
+            let target = safe_relative_path(entry.name()).map(|relative| dest.join(relative));
+
+            let target = match target {
+                Some(path) if entry.is_directory() => {
+                    std::fs::create_dir_all(&path).map_err(sevenz_rust::Error::io)?;
+                    return Ok(true);
+                }
+                Some(path) if !path.exists() => path,
+                _ => {
+                    // Solid archives share one stream: skipped entries must still be consumed
+                    std::io::copy(reader, &mut std::io::sink()).map_err(sevenz_rust::Error::io)?;
+                    summary.skipped.push(entry.name().to_string());
+                    return Ok(true);
+                }
+            };
+
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent).map_err(sevenz_rust::Error::io)?;
+            }
+            let mut out = std::fs::File::create(&target).map_err(sevenz_rust::Error::io)?;
+            if !copy_within_limit(reader, &mut out, &mut written).map_err(sevenz_rust::Error::io)? {
+                drop(out);
+                let _ = std::fs::remove_file(&target);
+                exceeded = true;
+                return Err(sevenz_rust::Error::other("limite de extração"));
+            }
+            summary.extracted += 1;
+            Ok(true)
+        },
+    )
+    .map_err(|e| match e {
+        _ if exceeded => extract_limit_error(),
+        sevenz_rust::Error::PasswordRequired => {
+            AppError::InvalidInput("Arquivo protegido por senha: informe a senha".into())
+        }
+        sevenz_rust::Error::MaybeBadPassword(_) => AppError::InvalidInput("Senha incorreta".into()),
+        e => AppError::internal("Falha ao extrair 7z", e),
+    })?;
+
+    Ok(summary)
+}
+
+/// Runs off the main thread; large archives would otherwise freeze the window.
+#[tauri::command]
+pub async fn extract_archive(
+    settings: tauri::State<'_, Settings>,
+    archive_path: String,
+    dest_folder: String,
+) -> Result<ExtractSummary, AppError> {
+    crate::metrics::timed("extract_archive", async move {
+        let validated_archive = validate_path(&settings, &archive_path)?;
+        let validated_dest = validate_path(&settings, &dest_folder)?;
+
+        if !validated_dest.is_dir() {
+            return Err(AppError::InvalidInput(
+                "Destino não é um diretório válido".into(),
+            ));
+        }
+
+        let extension = validated_archive
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+
+        tauri::async_runtime::spawn_blocking(move || match extension.as_str() {
+            "zip" => extract_zip(&validated_archive, &validated_dest, None),
+            "7z" => extract_7z(&validated_archive, &validated_dest, None),
+            _ => Err(AppError::Unsupported(
+                "Formato não suportado: use arquivos .zip ou .7z".into(),
+            )),
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao extrair arquivo", e))?
+    })
+    .await
+}
+
+#[tauri::command]
+pub fn create_directory(
+    settings: tauri::State<'_, Settings>,
+    parent_path: String,
+    folder_name: String,
+) -> Result<(), AppError> {
+    create_folder(settings, parent_path, folder_name)
+}
+
+#[tauri::command]
+pub fn create_folder(
+    settings: tauri::State<'_, Settings>,
+    parent_path: String,
+    name: String,
+) -> Result<(), AppError> {
+    let validated_parent = validate_path(&settings, &parent_path)?;
+
+    if !validated_parent.is_dir() {
+        return Err(AppError::InvalidInput(
+            "Caminho pai não é um diretório válido".into(),
+        ));
+    }
+
+    validate_entry_name(&name)?;
+
+    let new_path = validated_parent.join(&name);
+
+    if new_path.exists() {
+        return Err(AppError::AlreadyExists(format!(
+            "Já existe um item com o nome '{}'",
+            name
+        )));
+    }
+
+    std::fs::create_dir(&new_path).map_err(|e| AppError::io("Falha ao criar pasta", e))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn open_file(settings: tauri::State<'_, Settings>, path: String) -> Result<(), AppError> {
+    let validated = validate_path(&settings, &path)?;
+
+    #[cfg(windows)]
+    {
+        std::process::Command::new("cmd")
+            .args(["/C", "start", "", &validated.to_string_lossy()])
+            .spawn()
+            .map_err(|e| AppError::io("Falha ao abrir arquivo", e))?;
     }
 
     #[cfg(not(windows))]
@@ -362,8 +2004,497 @@ pub fn open_file(path: String) -> Result<(), String> {
         std::process::Command::new("xdg-open")
             .arg(&validated)
             .spawn()
-            .map_err(|e| format!("Falha ao abrir arquivo: {}", e))?;
+            .map_err(|e| AppError::io("Falha ao abrir arquivo", e))?;
     }
 
     Ok(())
 }
+
+/// Opens Explorer on the parent folder with the entry pre-selected.
+#[tauri::command]
+pub fn reveal_in_explorer(
+    settings: tauri::State<'_, Settings>,
+    path: String,
+) -> Result<(), AppError> {
+    let validated = validate_path(&settings, &path)?;
+    if !validated.exists() {
+        return Err(AppError::NotFound("Item não encontrado".into()));
+    }
+
+    #[cfg(windows)]
+    {
+        std::process::Command::new("explorer")
+            .arg(format!("/select,{}", validated.to_string_lossy()))
+            .spawn()
+            .map_err(|e| AppError::io("Falha ao abrir o Explorer", e))?;
+    }
+
+    #[cfg(not(windows))]
+    {
+        // No portable "select" equivalent, so just open the containing folder
+        let folder = validated.parent().unwrap_or(&validated);
+        std::process::Command::new("xdg-open")
+            .arg(folder)
+            .spawn()
+            .map_err(|e| AppError::io("Falha ao abrir pasta", e))?;
+    }
+
+    Ok(())
+}
+
+/// `\\srv\share\a b.pdf` → `file://srv/share/a%20b.pdf`, `C:\x` → `file:///C:/x`.
+fn to_file_uri(path: &str) -> String {
+    let (prefix, rest) = match path.strip_prefix(r"\\") {
+        Some(unc) => ("file://", unc),
+        None => ("file:///", path),
+    };
+
+    let mut uri = String::from(prefix);
+    for byte in rest.replace('\\', "/").bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => {
+                uri.push(byte as char)
+            }
+            _ => uri.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    uri
+}
+
+#[tauri::command]
+pub fn copy_path_to_clipboard(
+    app: tauri::AppHandle,
+    settings: tauri::State<'_, Settings>,
+    path: String,
+    as_file_uri: bool,
+) -> Result<String, AppError> {
+    use tauri_plugin_clipboard_manager::ClipboardExt;
+
+    let validated = validate_path(&settings, &path)?;
+    let display_path = validated.to_string_lossy().to_string();
+    let text = if as_file_uri {
+        to_file_uri(&display_path)
+    } else {
+        display_path
+    };
+
+    app.clipboard()
+        .write_text(text.clone())
+        .map_err(|e| AppError::internal("Falha ao copiar para a área de transferência", e))?;
+
+    Ok(text)
+}
+
+// ── Batch Operations ───────────────────────────────────────────
+
+#[derive(serde::Deserialize)]
+pub enum FsOp {
+    Rename {
+        path: String,
+        new_name: String,
+    },
+    Move {
+        path: String,
+        dest_folder: String,
+    },
+    Copy {
+        path: String,
+        dest_folder: String,
+        #[serde(default)]
+        overwrite: bool,
+    },
+    Delete {
+        path: String,
+        #[serde(default)]
+        permanent: bool,
+    },
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct BatchItemResult {
+    pub index: usize,
+    pub path: String,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct BatchProgress {
+    pub completed: usize,
+    pub total: usize,
+    pub item: BatchItemResult,
+}
+
+fn run_fs_op(app: &tauri::AppHandle, settings: &Settings, op: &FsOp) -> Result<(), AppError> {
+    match op {
+        FsOp::Rename { path, new_name } => rename_entry_impl(settings, path, new_name),
+        FsOp::Move { path, dest_folder } => move_entry_impl(settings, path, dest_folder),
+        FsOp::Copy {
+            path,
+            dest_folder,
+            overwrite,
+        } => copy_entry_impl(app, settings, path, dest_folder, *overwrite),
+        FsOp::Delete { path, permanent } => {
+            let is_dir = std::path::Path::new(path).is_dir();
+            delete_entry_impl(settings, path, is_dir, *permanent)
+        }
+    }
+}
+
+/// Runs every operation even if earlier ones fail and reports each outcome.
+#[tauri::command]
+pub async fn batch_operation(
+    app: tauri::AppHandle,
+    ops: Vec<FsOp>,
+) -> Result<Vec<BatchItemResult>, AppError> {
+    crate::metrics::timed("batch_operation", async move {
+        tauri::async_runtime::spawn_blocking(move || {
+            let settings = app.state::<Settings>();
+            let total = ops.len();
+            let mut results = Vec::with_capacity(total);
+            let job = Jobs::start(&app, "batch_operation", "Operações em lote");
+            let mut outcome = Ok(());
+
+            for (index, op) in ops.iter().enumerate() {
+                // Operations already done stay done; the rest are not started
+                if let Err(e) = job.check_cancelled() {
+                    outcome = Err(e);
+                    break;
+                }
+                let path = match op {
+                    FsOp::Rename { path, .. }
+                    | FsOp::Move { path, .. }
+                    | FsOp::Copy { path, .. }
+                    | FsOp::Delete { path, .. } => path.clone(),
+                };
+
+                let outcome = run_fs_op(&app, &settings, op);
+                let item = BatchItemResult {
+                    index,
+                    path,
+                    success: outcome.is_ok(),
+                    error: outcome.err().map(|e| e.to_string()),
+                };
+
+                job.progress((index + 1) as u64, total as u64, item.path.as_str());
+                let _ = app.emit(
+                    "batch-progress",
+                    BatchProgress {
+                        completed: index + 1,
+                        total,
+                        item: item.clone(),
+                    },
+                );
+                results.push(item);
+            }
+
+            job.finish(&outcome);
+            results
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao executar operações em lote", e))
+    })
+    .await
+}
+
+// ── Storage Report ─────────────────────────────────────────────
+
+#[derive(Clone, serde::Serialize)]
+pub struct ClientStorage {
+    pub name: String,
+    pub path: String,
+    pub total_bytes: u64,
+    pub file_count: u64,
+    pub last_modified: String,
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct StorageReport {
+    pub base_path: String,
+    pub generated_at: String,
+    pub total_bytes: u64,
+    pub clients: Vec<ClientStorage>,
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct StorageReportProgress {
+    pub current: String,
+    pub processed: usize,
+    pub total: usize,
+}
+
+/// Last computed report; walking the whole share takes minutes, so it is reused until refreshed.
+#[derive(Default)]
+pub struct StorageReportCache(Mutex<Option<StorageReport>>);
+
+fn client_storage(name: String, root: &std::path::Path) -> ClientStorage {
+    let mut total_bytes = 0;
+    let mut file_count = 0;
+    let mut last_modified: Option<std::time::SystemTime> = None;
+    let mut pending = vec![root.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Ok(meta) = entry.path().symlink_metadata() else {
+                continue;
+            };
+            if meta.is_dir() {
+                pending.push(entry.path());
+                continue;
+            }
+
+            file_count += 1;
+            total_bytes += meta.len();
+            if let Ok(modified) = meta.modified() {
+                last_modified = last_modified.max(Some(modified));
+            }
+        }
+    }
+
+    ClientStorage {
+        name,
+        path: root.to_string_lossy().to_string(),
+        total_bytes,
+        file_count,
+        last_modified: last_modified.map(format_system_time).unwrap_or_default(),
+    }
+}
+
+fn build_storage_report(
+    app: &tauri::AppHandle,
+    base_path: String,
+) -> Result<StorageReport, AppError> {
+    let mut folders: Vec<(String, PathBuf)> = std::fs::read_dir(&base_path)
+        .map_err(|e| AppError::io(format!("Falha ao acessar {}", base_path), e))?
+        .flatten()
+        .filter(|entry| entry.file_type().map(|t| t.is_dir()).unwrap_or(false))
+        .map(|entry| {
+            (
+                entry.file_name().to_string_lossy().to_string(),
+                entry.path(),
+            )
+        })
+        .collect();
+    folders.sort_by_key(|(name, _)| name.to_lowercase());
+
+    let total = folders.len();
+    let mut clients = Vec::with_capacity(total);
+    for (processed, (name, path)) in folders.into_iter().enumerate() {
+        let _ = app.emit(
+            "storage-report-progress",
+            StorageReportProgress {
+                current: name.clone(),
+                processed,
+                total,
+            },
+        );
+        clients.push(client_storage(name, &path));
+    }
+
+    // Biggest clients first, which is what the report is read for
+    clients.sort_by(|a, b| b.total_bytes.cmp(&a.total_bytes));
+
+    Ok(StorageReport {
+        base_path,
+        generated_at: format_system_time(std::time::SystemTime::now()),
+        total_bytes: clients.iter().map(|c| c.total_bytes).sum(),
+        clients,
+    })
+}
+
+#[tauri::command]
+pub async fn get_storage_report(
+    app: tauri::AppHandle,
+    refresh: Option<bool>,
+) -> Result<StorageReport, AppError> {
+    crate::metrics::timed("get_storage_report", async move {
+        tauri::async_runtime::spawn_blocking(move || {
+            let settings = app.state::<Settings>();
+            ensure_module_enabled(&settings)?;
+            let base_path = network_base_path(&settings);
+            let cache = app.state::<StorageReportCache>();
+
+            if !refresh.unwrap_or(false) {
+                if let Some(report) = cache.0.lock().unwrap().as_ref() {
+                    if report.base_path == base_path {
+                        return Ok(report.clone());
+                    }
+                }
+            }
+
+            let report = build_storage_report(&app, base_path)?;
+            *cache.0.lock().unwrap() = Some(report.clone());
+            Ok(report)
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao gerar relatório de armazenamento", e))?
+    })
+    .await
+}
+
+// ── Duplicate Files ────────────────────────────────────────────
+
+#[derive(serde::Serialize)]
+pub struct DuplicateFileGroup {
+    pub hash: String,
+    pub size: u64,
+    pub paths: Vec<String>,
+}
+
+#[derive(serde::Serialize)]
+pub struct DuplicateFilesReport {
+    pub groups: Vec<DuplicateFileGroup>,
+    pub wasted_bytes: u64,
+}
+
+fn collect_files(dir: &std::path::Path, files: &mut Vec<(PathBuf, u64)>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            collect_files(&entry.path(), files);
+        } else if file_type.is_file() {
+            if let Ok(metadata) = entry.metadata() {
+                files.push((entry.path(), metadata.len()));
+            }
+        }
+    }
+}
+
+fn hash_file(path: &std::path::Path) -> std::io::Result<String> {
+    use sha2::{Digest, Sha256};
+
+    let mut reader = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut reader, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Groups identical files under `roots`, which must not overlap. Only files
+/// sharing a size are hashed.
+pub(crate) fn find_duplicates(roots: &[PathBuf]) -> DuplicateFilesReport {
+    let mut files = Vec::new();
+    for root in roots {
+        collect_files(root, &mut files);
+    }
+
+    let mut by_size: std::collections::HashMap<u64, Vec<PathBuf>> =
+        std::collections::HashMap::new();
+    for (path, size) in files {
+        // Empty files are trivially "identical" and waste nothing
+        if size > 0 {
+            by_size.entry(size).or_default().push(path);
+        }
+    }
+
+    let mut groups = Vec::new();
+    for (size, paths) in by_size {
+        if paths.len() < 2 {
+            continue;
+        }
+
+        let mut by_hash: std::collections::HashMap<String, Vec<String>> =
+            std::collections::HashMap::new();
+        for path in paths {
+            if let Ok(hash) = hash_file(&path) {
+                by_hash
+                    .entry(hash)
+                    .or_default()
+                    .push(path.to_string_lossy().to_string());
+            }
+        }
+
+        for (hash, mut paths) in by_hash {
+            if paths.len() > 1 {
+                paths.sort_by_key(|p| p.to_lowercase());
+                groups.push(DuplicateFileGroup { hash, size, paths });
+            }
+        }
+    }
+
+    let wasted = |group: &DuplicateFileGroup| group.size * (group.paths.len() as u64 - 1);
+    groups.sort_by(|a, b| wasted(b).cmp(&wasted(a)));
+    let wasted_bytes = groups.iter().map(wasted).sum();
+
+    DuplicateFilesReport {
+        groups,
+        wasted_bytes,
+    }
+}
+
+#[tauri::command]
+pub async fn find_duplicate_files(
+    app: tauri::AppHandle,
+    root: String,
+) -> Result<DuplicateFilesReport, AppError> {
+    crate::metrics::timed("find_duplicate_files", async move {
+        tauri::async_runtime::spawn_blocking(move || {
+            let settings = app.state::<Settings>();
+            let validated = validate_path(&settings, &root)?;
+            if !validated.is_dir() {
+                return Err(AppError::InvalidInput(
+                    "O caminho informado não é uma pasta".into(),
+                ));
+            }
+            Ok(find_duplicates(&[validated]))
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao procurar duplicados", e))?
+    })
+    .await
+}
+
+// ── File Previews ──────────────────────────────────────────────
+
+const PREVIEW_MIN_SIZE: u32 = 16;
+const PREVIEW_MAX_SIZE: u32 = 1024;
+
+/// Returns a base64 PNG thumbnail for images and the first page of PDFs.
+#[tauri::command]
+pub async fn get_file_preview(
+    app: tauri::AppHandle,
+    path: String,
+    max_size: u32,
+) -> Result<String, AppError> {
+    crate::metrics::timed("get_file_preview", async move {
+        tauri::async_runtime::spawn_blocking(move || {
+            let settings = app.state::<Settings>();
+            let validated = validate_path(&settings, &path)?;
+            if !validated.is_file() {
+                return Err(AppError::InvalidInput(
+                    "Apenas arquivos possuem pré-visualização".into(),
+                ));
+            }
+
+            let max_size = max_size.clamp(PREVIEW_MIN_SIZE, PREVIEW_MAX_SIZE);
+            let extension = validated
+                .extension()
+                .map(|e| e.to_string_lossy().to_lowercase())
+                .unwrap_or_default();
+
+            match extension.as_str() {
+                "png" | "jpg" | "jpeg" => {
+                    let image = image::open(&validated).map_err(|e| {
+                        AppError::InvalidInput("Falha ao abrir imagem".into()).with_details(e)
+                    })?;
+                    crate::pdf_utils::encode_png_base64(&image.thumbnail(max_size, max_size))
+                }
+                "pdf" => crate::pdf_utils::render_pdf_thumbnail(&app, &validated, max_size),
+                _ => Err(AppError::Unsupported(format!(
+                    "Pré-visualização não suportada para arquivos .{}",
+                    extension
+                ))),
+            }
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao gerar pré-visualização", e))?
+    })
+    .await
+}
@@ -0,0 +1,416 @@
+// ── Clients Module ─────────────────────────────────────────────
+use crate::error::AppError;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::sync::Mutex;
+use tauri::Manager;
+
+/// Client registry, plus the NFe queries made for each client so other modules
+/// can look things up by client instead of by raw CNPJ, folder or access key.
+pub struct Clients {
+    conn: Mutex<Connection>,
+}
+
+#[derive(serde::Serialize)]
+pub struct Client {
+    pub id: i64,
+    pub name: String,
+    /// Digits only.
+    pub cnpj: Option<String>,
+    /// Inscrição estadual.
+    pub ie: Option<String>,
+    /// Tax regime, e.g. "Simples Nacional" or "Lucro Presumido".
+    pub regime: Option<String>,
+    pub contact: Option<String>,
+    /// Folder on the network share, matched against `client_folders.folder_path`.
+    pub folder_path: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(serde::Deserialize)]
+pub struct ClientInput {
+    pub name: String,
+    pub cnpj: Option<String>,
+    pub ie: Option<String>,
+    pub regime: Option<String>,
+    pub contact: Option<String>,
+    pub folder_path: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+pub struct ClientFolderLink {
+    pub id: i64,
+    pub folder_name: String,
+    pub folder_path: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct NfeQueryRecord {
+    pub id: i64,
+    pub access_key: String,
+    /// CNPJ of the certificate used for the query.
+    pub cnpj: String,
+    /// Issuer CNPJ, taken from the access key.
+    pub emitter_cnpj: String,
+    pub queried_at: String,
+}
+
+/// Everything in the app that belongs to a client.
+#[derive(serde::Serialize)]
+pub struct ClientLinks {
+    pub certificates: Vec<crate::CertInfo>,
+    pub folders: Vec<ClientFolderLink>,
+    pub nfe_queries: Vec<NfeQueryRecord>,
+}
+
+const SELECT_COLUMNS: &str = "SELECT id, name, cnpj, ie, regime, contact, folder_path,
+    created_at, updated_at FROM clients";
+
+fn row_to_client(row: &rusqlite::Row) -> rusqlite::Result<Client> {
+    Ok(Client {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        cnpj: row.get(2)?,
+        ie: row.get(3)?,
+        regime: row.get(4)?,
+        contact: row.get(5)?,
+        folder_path: row.get(6)?,
+        created_at: row.get(7)?,
+        updated_at: row.get(8)?,
+    })
+}
+
+/// Strips punctuation and checks the two CNPJ check digits.
+pub(crate) fn normalize_cnpj(cnpj: &str) -> Result<String, AppError> {
+    let digits: String = cnpj.chars().filter(char::is_ascii_digit).collect();
+    let invalid = || AppError::InvalidInput(format!("CNPJ inválido: {}", cnpj.trim()));
+    if digits.len() != 14 || digits.bytes().all(|b| b == digits.as_bytes()[0]) {
+        return Err(invalid());
+    }
+
+    let numbers: Vec<u32> = digits.bytes().map(|b| (b - b'0') as u32).collect();
+    let check_digit = |len: usize| {
+        let weights = [6, 5, 4, 3, 2, 9, 8, 7, 6, 5, 4, 3, 2];
+        let sum: u32 = numbers[..len]
+            .iter()
+            .zip(&weights[13 - len..])
+            .map(|(n, w)| n * w)
+            .sum();
+        match sum % 11 {
+            0 | 1 => 0,
+            rest => 11 - rest,
+        }
+    };
+    if check_digit(12) != numbers[12] || check_digit(13) != numbers[13] {
+        return Err(invalid());
+    }
+    Ok(digits)
+}
+
+fn optional_text(value: &Option<String>) -> Option<String> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+fn db_error(message: &str, e: rusqlite::Error) -> AppError {
+    if let rusqlite::Error::SqliteFailure(failure, _) = &e {
+        if failure.extended_code == rusqlite::ffi::SQLITE_CONSTRAINT_UNIQUE {
+            return AppError::AlreadyExists("Já existe um cliente com este CNPJ".into());
+        }
+    }
+    AppError::internal(message, e)
+}
+
+impl Clients {
+    pub fn open(app: &tauri::AppHandle) -> Result<Self, String> {
+        let conn = crate::db::open(app)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS clients (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL,
+                cnpj TEXT UNIQUE,
+                ie TEXT,
+                regime TEXT,
+                contact TEXT,
+                folder_path TEXT,
+                created_at TEXT NOT NULL DEFAULT (datetime('now')),
+                updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+            );
+            CREATE TABLE IF NOT EXISTS nfe_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                access_key TEXT NOT NULL,
+                cnpj TEXT NOT NULL,
+                emitter_cnpj TEXT NOT NULL,
+                queried_at TEXT NOT NULL DEFAULT (datetime('now'))
+            );
+            CREATE INDEX IF NOT EXISTS idx_nfe_history_cnpj ON nfe_history (cnpj);
+            CREATE INDEX IF NOT EXISTS idx_nfe_history_emitter ON nfe_history (emitter_cnpj);",
+        )
+        .map_err(|e| format!("Falha ao criar tabela de clientes: {}", e))?;
+
+        Ok(Clients {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    pub(crate) fn get(&self, id: i64) -> Result<Client, AppError> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            &format!("{} WHERE id = ?1", SELECT_COLUMNS),
+            params![id],
+            row_to_client,
+        )
+        .optional()
+        .map_err(|e| AppError::internal("Falha ao consultar cliente", e))?
+        .ok_or_else(|| AppError::NotFound("Cliente não encontrado".into()))
+    }
+
+    pub(crate) fn find_by_cnpj(&self, cnpj: &str) -> Result<Option<Client>, AppError> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            &format!("{} WHERE cnpj = ?1", SELECT_COLUMNS),
+            params![cnpj],
+            row_to_client,
+        )
+        .optional()
+        .map_err(|e| AppError::internal("Falha ao consultar cliente", e))
+    }
+
+    fn save(&self, id: Option<i64>, input: &ClientInput) -> Result<i64, AppError> {
+        let name = input.name.trim();
+        if name.is_empty() {
+            return Err(AppError::InvalidInput(
+                "O cliente precisa de um nome".into(),
+            ));
+        }
+        let cnpj = optional_text(&input.cnpj)
+            .map(|cnpj| normalize_cnpj(&cnpj))
+            .transpose()?;
+        let values = params![
+            name,
+            cnpj,
+            optional_text(&input.ie),
+            optional_text(&input.regime),
+            optional_text(&input.contact),
+            optional_text(&input.folder_path),
+            id,
+        ];
+
+        let conn = self.conn.lock().unwrap();
+        match id {
+            None => {
+                conn.execute(
+                    "INSERT INTO clients (name, cnpj, ie, regime, contact, folder_path)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                    &values[..6],
+                )
+                .map_err(|e| db_error("Falha ao salvar cliente", e))?;
+                Ok(conn.last_insert_rowid())
+            }
+            Some(id) => {
+                let changed = conn
+                    .execute(
+                        "UPDATE clients SET name = ?1, cnpj = ?2, ie = ?3, regime = ?4,
+                            contact = ?5, folder_path = ?6, updated_at = datetime('now')
+                         WHERE id = ?7",
+                        values,
+                    )
+                    .map_err(|e| db_error("Falha ao salvar cliente", e))?;
+                if changed == 0 {
+                    return Err(AppError::NotFound("Cliente não encontrado".into()));
+                }
+                Ok(id)
+            }
+        }
+    }
+}
+
+/// Logs a successful NFe query; failures only matter for the client history.
+pub(crate) fn record_nfe_query(app: &tauri::AppHandle, access_key: &str, cnpj: &str) {
+    let Some(clients) = app.try_state::<Clients>() else {
+        return;
+    };
+    let emitter_cnpj = access_key.get(6..20).unwrap_or_default();
+    let conn = clients.conn.lock().unwrap();
+    if let Err(e) = conn.execute(
+        "INSERT INTO nfe_history (access_key, cnpj, emitter_cnpj) VALUES (?1, ?2, ?3)",
+        params![access_key, cnpj, emitter_cnpj],
+    ) {
+        crate::app_log::error(format!("Falha ao registrar consulta NFe: {}", e));
+    }
+}
+
+/// `query` matches name, CNPJ (with or without punctuation) or IE.
+#[tauri::command]
+pub fn list_clients(
+    clients: tauri::State<'_, Clients>,
+    query: Option<String>,
+) -> Result<Vec<Client>, AppError> {
+    let query = query.as_deref().map(str::trim).unwrap_or_default();
+    let digits: String = query.chars().filter(char::is_ascii_digit).collect();
+
+    let conn = clients.conn.lock().unwrap();
+    let mut stmt = conn
+        .prepare(&format!(
+            "{} WHERE ?1 = '' OR name LIKE '%' || ?1 || '%' OR ie LIKE '%' || ?1 || '%'
+                OR (?2 <> '' AND cnpj LIKE '%' || ?2 || '%')
+             ORDER BY name COLLATE NOCASE",
+            SELECT_COLUMNS
+        ))
+        .map_err(|e| AppError::internal("Falha ao consultar clientes", e))?;
+    let rows = stmt
+        .query_map(params![query, digits], row_to_client)
+        .map_err(|e| AppError::internal("Falha ao consultar clientes", e))?;
+    rows.collect::<Result<_, _>>()
+        .map_err(|e| AppError::internal("Falha ao ler clientes", e))
+}
+
+#[tauri::command]
+pub fn get_client(clients: tauri::State<'_, Clients>, id: i64) -> Result<Client, AppError> {
+    clients.get(id)
+}
+
+#[tauri::command]
+pub fn create_client(
+    clients: tauri::State<'_, Clients>,
+    input: ClientInput,
+) -> Result<Client, AppError> {
+    let id = clients.save(None, &input)?;
+    clients.get(id)
+}
+
+#[tauri::command]
+pub fn update_client(
+    clients: tauri::State<'_, Clients>,
+    id: i64,
+    input: ClientInput,
+) -> Result<Client, AppError> {
+    clients.save(Some(id), &input)?;
+    clients.get(id)
+}
+
+#[tauri::command]
+pub fn delete_client(clients: tauri::State<'_, Clients>, id: i64) -> Result<(), AppError> {
+    let conn = clients.conn.lock().unwrap();
+    conn.execute("DELETE FROM clients WHERE id = ?1", params![id])
+        .map_err(|e| AppError::internal("Falha ao excluir cliente", e))?;
+    Ok(())
+}
+
+/// Resolves the client owning a CNPJ or a folder, for modules that only know those.
+/// The folder may be the client's own folder or anything inside it.
+#[tauri::command]
+pub fn find_client(
+    clients: tauri::State<'_, Clients>,
+    cnpj: Option<String>,
+    folder_path: Option<String>,
+) -> Result<Option<Client>, AppError> {
+    let cnpj = optional_text(&cnpj).map(|cnpj| {
+        cnpj.chars()
+            .filter(char::is_ascii_digit)
+            .collect::<String>()
+    });
+    let folder_path = optional_text(&folder_path);
+
+    let conn = clients.conn.lock().unwrap();
+    conn.query_row(
+        &format!(
+            "{} WHERE cnpj = ?1
+                OR (folder_path IS NOT NULL AND (
+                    ?2 = folder_path COLLATE NOCASE
+                    OR substr(?2, 1, length(folder_path) + 1) = folder_path || '\\' COLLATE NOCASE
+                    OR substr(?2, 1, length(folder_path) + 1) = folder_path || '/' COLLATE NOCASE))
+             ORDER BY cnpj = ?1 DESC, length(folder_path) DESC LIMIT 1",
+            SELECT_COLUMNS
+        ),
+        params![cnpj, folder_path],
+        row_to_client,
+    )
+    .optional()
+    .map_err(|e| AppError::internal("Falha ao consultar cliente", e))
+}
+
+/// Certificates with the client's CNPJ, registered folders under the client's folder
+/// and NFe queries made with or about the client's CNPJ.
+#[tauri::command]
+pub async fn get_client_links(app: tauri::AppHandle, id: i64) -> Result<ClientLinks, AppError> {
+    crate::metrics::timed("get_client_links", async move {
+        let client = app.state::<Clients>().get(id)?;
+
+        let certificates = match &client.cnpj {
+            // The certificate store is only read on Windows; elsewhere there is nothing to link
+            Some(cnpj) => tauri::async_runtime::spawn_blocking(crate::certs_impl)
+                .await
+                .map_err(|e| AppError::internal("Falha ao listar certificados", e))?
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|cert| &cert.cnpj == cnpj)
+                .collect(),
+            None => Vec::new(),
+        };
+
+        let clients = app.state::<Clients>();
+        let conn = clients.conn.lock().unwrap();
+
+        let folders = match &client.folder_path {
+            Some(path) => {
+                let mut stmt = conn
+                    .prepare(
+                        "SELECT id, folder_name, folder_path FROM client_folders
+                     WHERE folder_path = ?1 COLLATE NOCASE
+                        OR substr(folder_path, 1, length(?1) + 1) = ?1 || '\\' COLLATE NOCASE
+                        OR substr(folder_path, 1, length(?1) + 1) = ?1 || '/' COLLATE NOCASE
+                     ORDER BY folder_name",
+                    )
+                    .map_err(|e| AppError::internal("Falha ao consultar pastas do cliente", e))?;
+                let rows = stmt
+                    .query_map(params![path], |row| {
+                        Ok(ClientFolderLink {
+                            id: row.get(0)?,
+                            folder_name: row.get(1)?,
+                            folder_path: row.get(2)?,
+                        })
+                    })
+                    .map_err(|e| AppError::internal("Falha ao consultar pastas do cliente", e))?;
+                rows.collect::<Result<_, _>>()
+                    .map_err(|e| AppError::internal("Falha ao ler pastas do cliente", e))?
+            }
+            None => Vec::new(),
+        };
+
+        let nfe_queries = match &client.cnpj {
+            Some(cnpj) => {
+                let mut stmt = conn
+                    .prepare(
+                        "SELECT id, access_key, cnpj, emitter_cnpj, queried_at FROM nfe_history
+                     WHERE cnpj = ?1 OR emitter_cnpj = ?1 ORDER BY id DESC",
+                    )
+                    .map_err(|e| AppError::internal("Falha ao consultar histórico NFe", e))?;
+                let rows = stmt
+                    .query_map(params![cnpj], |row| {
+                        Ok(NfeQueryRecord {
+                            id: row.get(0)?,
+                            access_key: row.get(1)?,
+                            cnpj: row.get(2)?,
+                            emitter_cnpj: row.get(3)?,
+                            queried_at: row.get(4)?,
+                        })
+                    })
+                    .map_err(|e| AppError::internal("Falha ao consultar histórico NFe", e))?;
+                rows.collect::<Result<_, _>>()
+                    .map_err(|e| AppError::internal("Falha ao ler histórico NFe", e))?
+            }
+            None => Vec::new(),
+        };
+
+        Ok(ClientLinks {
+            certificates,
+            folders,
+            nfe_queries,
+        })
+    })
+    .await
+}
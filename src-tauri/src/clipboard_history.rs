@@ -0,0 +1,432 @@
+// ── Clipboard History Module ───────────────────────────────────
+use crate::error::AppError;
+use crate::settings::Settings;
+use rusqlite::{params, Connection, OptionalExtension};
+use sha2::{Digest, Sha256};
+use std::sync::Mutex;
+use tauri::{Emitter, Manager};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+/// Off until the user turns it on: everything copied is stored as-is.
+const ENABLED_KEY: &str = "clipboard_history_enabled";
+const MAX_TEXT_BYTES_KEY: &str = "clipboard_max_text_bytes";
+const MAX_IMAGE_BYTES_KEY: &str = "clipboard_max_image_bytes";
+const HISTORY_LIMIT_KEY: &str = "clipboard_history_limit";
+const IGNORE_PATTERNS_KEY: &str = "clipboard_ignore_patterns";
+
+const DEFAULT_MAX_TEXT_BYTES: usize = 1024 * 1024;
+const DEFAULT_MAX_IMAGE_BYTES: usize = 10 * 1024 * 1024;
+const DEFAULT_HISTORY_LIMIT: u32 = 200;
+
+const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(1000);
+
+/// Number of entries shown in the tray submenu.
+pub const TRAY_RECENT_COUNT: u32 = 10;
+
+pub struct ClipboardHistory {
+    conn: Mutex<Connection>,
+}
+
+#[derive(serde::Serialize)]
+pub struct ClipboardEntry {
+    pub id: i64,
+    pub kind: String,
+    pub text: Option<String>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub size: u64,
+    pub pinned: bool,
+    pub copied_at: String,
+}
+
+enum Captured {
+    Text(String),
+    Image {
+        png: Vec<u8>,
+        width: u32,
+        height: u32,
+    },
+}
+
+impl ClipboardHistory {
+    pub fn open(app: &tauri::AppHandle) -> Result<Self, String> {
+        let conn = crate::db::open(app)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS clipboard_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                kind TEXT NOT NULL,
+                content TEXT,
+                image BLOB,
+                width INTEGER,
+                height INTEGER,
+                size INTEGER NOT NULL,
+                hash TEXT NOT NULL UNIQUE,
+                pinned INTEGER NOT NULL DEFAULT 0,
+                copied_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f', 'now'))
+            );",
+        )
+        .map_err(|e| format!("Falha ao criar tabela do histórico: {}", e))?;
+
+        Ok(ClipboardHistory {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// Stores a new entry, or moves an identical one back to the top.
+    fn record(&self, captured: &Captured, limit: u32) -> Result<(), AppError> {
+        let conn = self.conn.lock().unwrap();
+        let result = match captured {
+            Captured::Text(text) => conn.execute(
+                "INSERT INTO clipboard_history (kind, content, size, hash)
+                 VALUES ('text', ?1, ?2, ?3)
+                 ON CONFLICT(hash) DO UPDATE
+                 SET copied_at = strftime('%Y-%m-%d %H:%M:%f', 'now')",
+                params![text, text.len() as i64, content_hash(text.as_bytes())],
+            ),
+            Captured::Image { png, width, height } => conn.execute(
+                "INSERT INTO clipboard_history (kind, image, width, height, size, hash)
+                 VALUES ('image', ?1, ?2, ?3, ?4, ?5)
+                 ON CONFLICT(hash) DO UPDATE
+                 SET copied_at = strftime('%Y-%m-%d %H:%M:%f', 'now')",
+                params![png, width, height, png.len() as i64, content_hash(png)],
+            ),
+        };
+        result.map_err(|e| AppError::internal("Falha ao gravar histórico", e))?;
+
+        // Pinned entries never count towards the limit
+        conn.execute(
+            "DELETE FROM clipboard_history
+             WHERE pinned = 0 AND id NOT IN (
+                 SELECT id FROM clipboard_history WHERE pinned = 0
+                 ORDER BY copied_at DESC LIMIT ?1
+             )",
+            params![limit],
+        )
+        .map_err(|e| AppError::internal("Falha ao limpar histórico", e))?;
+
+        Ok(())
+    }
+
+    pub fn recent(&self, query: Option<&str>, limit: u32) -> Result<Vec<ClipboardEntry>, AppError> {
+        let conn = self.conn.lock().unwrap();
+        let pattern = query
+            .filter(|q| !q.trim().is_empty())
+            .map(|q| format!("%{}%", q.trim()));
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, kind, content, width, height, size, pinned, copied_at
+                 FROM clipboard_history
+                 WHERE ?1 IS NULL OR content LIKE ?1
+                 ORDER BY pinned DESC, copied_at DESC
+                 LIMIT ?2",
+            )
+            .map_err(|e| AppError::internal("Falha ao consultar histórico", e))?;
+
+        let rows = stmt
+            .query_map(params![pattern, limit], |row| {
+                Ok(ClipboardEntry {
+                    id: row.get(0)?,
+                    kind: row.get(1)?,
+                    text: row.get(2)?,
+                    width: row.get(3)?,
+                    height: row.get(4)?,
+                    size: row.get::<_, i64>(5)? as u64,
+                    pinned: row.get(6)?,
+                    copied_at: row.get(7)?,
+                })
+            })
+            .map_err(|e| AppError::internal("Falha ao consultar histórico", e))?;
+
+        rows.collect::<Result<_, _>>()
+            .map_err(|e| AppError::internal("Falha ao ler histórico", e))
+    }
+}
+
+fn content_hash(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+fn is_ignored(text: &str, patterns: &[String]) -> bool {
+    let lower = text.to_lowercase();
+    patterns
+        .iter()
+        .filter(|p| !p.trim().is_empty())
+        .any(|p| lower.contains(&p.trim().to_lowercase()))
+}
+
+fn encode_png(rgba: &[u8], width: u32, height: u32) -> Option<Vec<u8>> {
+    let buffer = image::RgbaImage::from_raw(width, height, rgba.to_vec())?;
+    let mut png = std::io::Cursor::new(Vec::new());
+    image::DynamicImage::ImageRgba8(buffer)
+        .write_to(&mut png, image::ImageFormat::Png)
+        .ok()?;
+    Some(png.into_inner())
+}
+
+/// Whether the program that filled the clipboard asked monitors to leave the content
+/// alone, as password managers do, through the formats Windows' own history honours.
+#[cfg(windows)]
+fn is_private_content() -> bool {
+    use windows_sys::Win32::System::DataExchange::{
+        CloseClipboard, GetClipboardData, IsClipboardFormatAvailable, OpenClipboard,
+        RegisterClipboardFormatW,
+    };
+    use windows_sys::Win32::System::Memory::{GlobalLock, GlobalUnlock};
+
+    let register = |name: &str| {
+        let wide: Vec<u16> = name.encode_utf16().chain(std::iter::once(0)).collect();
+        unsafe { RegisterClipboardFormatW(wide.as_ptr()) }
+    };
+    let exclude = register("ExcludeClipboardContentFromMonitorProcessing");
+    let can_include = register("CanIncludeInClipboardHistory");
+
+    unsafe {
+        if exclude != 0 && IsClipboardFormatAvailable(exclude) != 0 {
+            return true;
+        }
+        if can_include == 0 || IsClipboardFormatAvailable(can_include) == 0 {
+            return false;
+        }
+        // Unknown either way while another program holds the clipboard; skip this poll
+        if OpenClipboard(std::ptr::null_mut()) == 0 {
+            return true;
+        }
+        // A DWORD; 0 means "keep out of the history"
+        let mut allowed = true;
+        let handle = GetClipboardData(can_include);
+        if !handle.is_null() {
+            let data = GlobalLock(handle) as *const u32;
+            if !data.is_null() {
+                allowed = data.read_unaligned() != 0;
+                GlobalUnlock(handle);
+            }
+        }
+        CloseClipboard();
+        !allowed
+    }
+}
+
+#[cfg(not(windows))]
+fn is_private_content() -> bool {
+    false
+}
+
+/// Reads the clipboard, returning `None` when it is unchanged since the last poll
+/// (tracked through `last_hash`), marked private by its source, or filtered by the
+/// size limits and ignore list.
+fn capture(
+    app: &tauri::AppHandle,
+    settings: &Settings,
+    last_hash: &mut String,
+) -> Option<Captured> {
+    if is_private_content() {
+        return None;
+    }
+
+    if let Ok(text) = app.clipboard().read_text() {
+        let hash = content_hash(text.as_bytes());
+        if hash == *last_hash {
+            return None;
+        }
+        *last_hash = hash;
+
+        let max_bytes = settings
+            .get::<usize>(MAX_TEXT_BYTES_KEY)
+            .unwrap_or(DEFAULT_MAX_TEXT_BYTES);
+        let patterns = settings
+            .get::<Vec<String>>(IGNORE_PATTERNS_KEY)
+            .unwrap_or_default();
+
+        if text.trim().is_empty() || text.len() > max_bytes || is_ignored(&text, &patterns) {
+            return None;
+        }
+        return Some(Captured::Text(text));
+    }
+
+    let image = app.clipboard().read_image().ok()?;
+    // Hash the raw pixels so an unchanged image is not re-encoded every poll
+    let hash = content_hash(image.rgba());
+    if hash == *last_hash {
+        return None;
+    }
+    *last_hash = hash;
+
+    let (width, height) = (image.width(), image.height());
+    let png = encode_png(image.rgba(), width, height)?;
+
+    let max_bytes = settings
+        .get::<usize>(MAX_IMAGE_BYTES_KEY)
+        .unwrap_or(DEFAULT_MAX_IMAGE_BYTES);
+    if png.len() > max_bytes {
+        return None;
+    }
+    Some(Captured::Image { png, width, height })
+}
+
+/// Polls the clipboard in the background; there is no cross-platform change notification.
+pub fn start_watcher(app: tauri::AppHandle) {
+    std::thread::spawn(move || {
+        let mut last_hash = String::new();
+
+        loop {
+            std::thread::sleep(POLL_INTERVAL);
+
+            let settings = app.state::<Settings>();
+            if !settings.get::<bool>(ENABLED_KEY).unwrap_or(false) {
+                continue;
+            }
+
+            let Some(captured) = capture(&app, &settings, &mut last_hash) else {
+                continue;
+            };
+
+            let limit = settings
+                .get::<u32>(HISTORY_LIMIT_KEY)
+                .unwrap_or(DEFAULT_HISTORY_LIMIT);
+            let history = app.state::<ClipboardHistory>();
+            if history.record(&captured, limit).is_ok() {
+                history_changed(&app);
+            }
+        }
+    });
+}
+
+fn history_changed(app: &tauri::AppHandle) {
+    let _ = app.emit("clipboard-history-changed", ());
+    crate::refresh_tray_menu(app);
+}
+
+/// Shortens an entry to a single line that fits in a menu item.
+pub fn menu_label(entry: &ClipboardEntry) -> String {
+    match &entry.text {
+        Some(text) => {
+            let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+            if line.chars().count() > 40 {
+                format!("{}…", line.chars().take(40).collect::<String>())
+            } else {
+                line
+            }
+        }
+        None => format!(
+            "Imagem {}×{}",
+            entry.width.unwrap_or(0),
+            entry.height.unwrap_or(0)
+        ),
+    }
+}
+
+/// Puts a stored entry back on the clipboard.
+pub fn paste_entry(app: &tauri::AppHandle, id: i64) -> Result<(), AppError> {
+    let history = app.state::<ClipboardHistory>();
+    let row = {
+        let conn = history.conn.lock().unwrap();
+        conn.query_row(
+            "SELECT content, image, width, height FROM clipboard_history WHERE id = ?1",
+            params![id],
+            |row| {
+                Ok((
+                    row.get::<_, Option<String>>(0)?,
+                    row.get::<_, Option<Vec<u8>>>(1)?,
+                    row.get::<_, Option<u32>>(2)?,
+                    row.get::<_, Option<u32>>(3)?,
+                ))
+            },
+        )
+        .optional()
+        .map_err(|e| AppError::internal("Falha ao consultar histórico", e))?
+    };
+
+    match row {
+        Some((Some(text), _, _, _)) => app
+            .clipboard()
+            .write_text(text)
+            .map_err(|e| AppError::internal("Falha ao copiar para a área de transferência", e)),
+        Some((None, Some(png), _, _)) => {
+            let decoded = image::load_from_memory(&png)
+                .map_err(|e| AppError::internal("Imagem do histórico inválida", e))?
+                .to_rgba8();
+            let (width, height) = decoded.dimensions();
+            let image = tauri::image::Image::new_owned(decoded.into_raw(), width, height);
+            app.clipboard()
+                .write_image(&image)
+                .map_err(|e| AppError::internal("Falha ao copiar para a área de transferência", e))
+        }
+        _ => Err(AppError::NotFound(
+            "Item do histórico não encontrado".into(),
+        )),
+    }
+}
+
+#[tauri::command]
+pub fn list_clipboard_history(
+    history: tauri::State<'_, ClipboardHistory>,
+    limit: Option<u32>,
+) -> Result<Vec<ClipboardEntry>, AppError> {
+    history.recent(None, limit.unwrap_or(DEFAULT_HISTORY_LIMIT))
+}
+
+#[tauri::command]
+pub fn search_clipboard_history(
+    history: tauri::State<'_, ClipboardHistory>,
+    query: String,
+    limit: Option<u32>,
+) -> Result<Vec<ClipboardEntry>, AppError> {
+    history.recent(Some(&query), limit.unwrap_or(DEFAULT_HISTORY_LIMIT))
+}
+
+/// Returns the stored PNG of an image entry as base64.
+#[tauri::command]
+pub fn get_clipboard_image(
+    history: tauri::State<'_, ClipboardHistory>,
+    id: i64,
+) -> Result<String, AppError> {
+    let conn = history.conn.lock().unwrap();
+    let png: Option<Vec<u8>> = conn
+        .query_row(
+            "SELECT image FROM clipboard_history WHERE id = ?1",
+            params![id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| AppError::internal("Falha ao consultar histórico", e))?
+        .ok_or_else(|| AppError::NotFound("Item do histórico não encontrado".into()))?;
+
+    let png = png.ok_or_else(|| AppError::InvalidInput("O item não é uma imagem".into()))?;
+    Ok(base64::Engine::encode(
+        &base64::engine::general_purpose::STANDARD,
+        png,
+    ))
+}
+
+#[tauri::command]
+pub fn pin_clipboard_entry(app: tauri::AppHandle, id: i64, pinned: bool) -> Result<(), AppError> {
+    {
+        let history = app.state::<ClipboardHistory>();
+        let conn = history.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE clipboard_history SET pinned = ?1 WHERE id = ?2",
+            params![pinned, id],
+        )
+        .map_err(|e| AppError::internal("Falha ao fixar item", e))?;
+    }
+    history_changed(&app);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn delete_clipboard_entry(app: tauri::AppHandle, id: i64) -> Result<(), AppError> {
+    {
+        let history = app.state::<ClipboardHistory>();
+        let conn = history.conn.lock().unwrap();
+        conn.execute("DELETE FROM clipboard_history WHERE id = ?1", params![id])
+            .map_err(|e| AppError::internal("Falha ao excluir item", e))?;
+    }
+    history_changed(&app);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn paste_clipboard_entry(app: tauri::AppHandle, id: i64) -> Result<(), AppError> {
+    paste_entry(&app, id)
+}
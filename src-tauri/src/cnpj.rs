@@ -0,0 +1,326 @@
+// ── CNPJ Lookup Module ─────────────────────────────────────────
+use crate::error::AppError;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde_json::Value;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::Manager;
+
+const BRASILAPI_URL: &str = "https://brasilapi.com.br/api/cnpj/v1/";
+const RECEITAWS_URL: &str = "https://receitaws.com.br/v1/cnpj/";
+/// Registry data rarely changes; `refresh` bypasses the cache when it does.
+const CACHE_TTL_DAYS: i64 = 30;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+/// BrasilAPI throttles bursts; ReceitaWS allows 3 queries a minute on the free plan.
+const BRASILAPI_INTERVAL: Duration = Duration::from_secs(1);
+const RECEITAWS_INTERVAL: Duration = Duration::from_secs(20);
+
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+pub struct Cnae {
+    pub codigo: String,
+    pub descricao: String,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Default)]
+pub struct Endereco {
+    pub logradouro: String,
+    pub numero: String,
+    pub complemento: String,
+    pub bairro: String,
+    pub municipio: String,
+    pub uf: String,
+    pub cep: String,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+pub struct Socio {
+    pub nome: String,
+    pub qualificacao: String,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+pub struct CompanyInfo {
+    pub cnpj: String,
+    pub razao_social: String,
+    pub nome_fantasia: String,
+    pub situacao_cadastral: String,
+    pub data_situacao_cadastral: String,
+    pub cnae_principal: Option<Cnae>,
+    pub cnaes_secundarios: Vec<Cnae>,
+    pub endereco: Endereco,
+    pub email: String,
+    pub telefone: String,
+    pub socios: Vec<Socio>,
+    /// "brasilapi" or "receitaws".
+    pub fonte: String,
+    pub consultado_em: String,
+}
+
+/// Cache of looked up companies plus the per-provider request pacing.
+pub struct CnpjLookup {
+    conn: Mutex<Connection>,
+    brasilapi_next: Mutex<Instant>,
+    receitaws_next: Mutex<Instant>,
+}
+
+fn text(value: &Value, key: &str) -> String {
+    match value.get(key) {
+        Some(Value::String(s)) => s.trim().to_string(),
+        Some(Value::Number(n)) => n.to_string(),
+        _ => String::new(),
+    }
+}
+
+fn items<'a>(value: &'a Value, key: &str) -> impl Iterator<Item = &'a Value> {
+    value
+        .get(key)
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+}
+
+fn from_brasilapi(cnpj: &str, body: &Value) -> CompanyInfo {
+    let logradouro = [
+        text(body, "descricao_tipo_de_logradouro"),
+        text(body, "logradouro"),
+    ]
+    .iter()
+    .filter(|part| !part.is_empty())
+    .cloned()
+    .collect::<Vec<_>>()
+    .join(" ");
+    let cnae_codigo = text(body, "cnae_fiscal");
+
+    CompanyInfo {
+        cnpj: cnpj.to_string(),
+        razao_social: text(body, "razao_social"),
+        nome_fantasia: text(body, "nome_fantasia"),
+        situacao_cadastral: text(body, "descricao_situacao_cadastral"),
+        data_situacao_cadastral: text(body, "data_situacao_cadastral"),
+        cnae_principal: (!cnae_codigo.is_empty()).then(|| Cnae {
+            codigo: cnae_codigo,
+            descricao: text(body, "cnae_fiscal_descricao"),
+        }),
+        cnaes_secundarios: items(body, "cnaes_secundarios")
+            .map(|cnae| Cnae {
+                codigo: text(cnae, "codigo"),
+                descricao: text(cnae, "descricao"),
+            })
+            .filter(|cnae| !cnae.codigo.is_empty() && cnae.codigo != "0")
+            .collect(),
+        endereco: Endereco {
+            logradouro,
+            numero: text(body, "numero"),
+            complemento: text(body, "complemento"),
+            bairro: text(body, "bairro"),
+            municipio: text(body, "municipio"),
+            uf: text(body, "uf"),
+            cep: text(body, "cep"),
+        },
+        email: text(body, "email"),
+        telefone: text(body, "ddd_telefone_1"),
+        socios: items(body, "qsa")
+            .map(|socio| Socio {
+                nome: text(socio, "nome_socio"),
+                qualificacao: text(socio, "qualificacao_socio"),
+            })
+            .collect(),
+        fonte: "brasilapi".into(),
+        consultado_em: chrono::Utc::now().to_rfc3339(),
+    }
+}
+
+fn from_receitaws(cnpj: &str, body: &Value) -> CompanyInfo {
+    let cnae = |item: &Value| Cnae {
+        codigo: text(item, "code"),
+        descricao: text(item, "text"),
+    };
+
+    CompanyInfo {
+        cnpj: cnpj.to_string(),
+        razao_social: text(body, "nome"),
+        nome_fantasia: text(body, "fantasia"),
+        situacao_cadastral: text(body, "situacao"),
+        data_situacao_cadastral: text(body, "data_situacao"),
+        cnae_principal: items(body, "atividade_principal").next().map(cnae),
+        cnaes_secundarios: items(body, "atividades_secundarias")
+            .map(cnae)
+            .filter(|cnae| !cnae.codigo.is_empty() && cnae.codigo != "00.00-0-00")
+            .collect(),
+        endereco: Endereco {
+            logradouro: text(body, "logradouro"),
+            numero: text(body, "numero"),
+            complemento: text(body, "complemento"),
+            bairro: text(body, "bairro"),
+            municipio: text(body, "municipio"),
+            uf: text(body, "uf"),
+            cep: text(body, "cep"),
+        },
+        email: text(body, "email"),
+        telefone: text(body, "telefone"),
+        socios: items(body, "qsa")
+            .map(|socio| Socio {
+                nome: text(socio, "nome"),
+                qualificacao: text(socio, "qual"),
+            })
+            .collect(),
+        fonte: "receitaws".into(),
+        consultado_em: chrono::Utc::now().to_rfc3339(),
+    }
+}
+
+/// Claims the provider's next request slot, or says how long until there is one.
+fn reserve_slot(next: &Mutex<Instant>, interval: Duration) -> Result<(), Duration> {
+    let mut next = next.lock().unwrap();
+    let now = Instant::now();
+    if *next > now {
+        return Err(*next - now);
+    }
+    *next = now + interval;
+    Ok(())
+}
+
+async fn get_json(client: &reqwest::Client, url: &str) -> Result<Value, AppError> {
+    let response = client.get(url).send().await.map_err(|e| {
+        AppError::ServiceUnavailable("Falha ao consultar CNPJ".into()).with_details(e)
+    })?;
+    let status = response.status();
+    let body = response.text().await.map_err(|e| {
+        AppError::ServiceUnavailable("Falha ao ler resposta da consulta de CNPJ".into())
+            .with_details(e)
+    })?;
+
+    if status == reqwest::StatusCode::NOT_FOUND {
+        return Err(AppError::NotFound("CNPJ não encontrado na Receita".into()));
+    }
+    if status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+        return Err(AppError::ServiceUnavailable(format!(
+            "Serviço de consulta indisponível ({})",
+            status
+        )));
+    }
+    if !status.is_success() {
+        return Err(
+            AppError::ServiceRejected(format!("Consulta de CNPJ recusada ({})", status))
+                .with_details(body),
+        );
+    }
+    serde_json::from_str(&body)
+        .map_err(|e| AppError::internal("Resposta inválida da consulta de CNPJ", e))
+}
+
+/// BrasilAPI first; ReceitaWS only when BrasilAPI is down or throttling us.
+async fn fetch(lookup: &CnpjLookup, cnpj: &str) -> Result<CompanyInfo, AppError> {
+    let client = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .user_agent(concat!("AdcontecUtil/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .map_err(|e| AppError::internal("Falha ao criar cliente HTTP", e))?;
+
+    // A short wait is friendlier than failing when several lookups come in a row
+    while let Err(wait) = reserve_slot(&lookup.brasilapi_next, BRASILAPI_INTERVAL) {
+        tokio::time::sleep(wait).await;
+    }
+    let brasilapi_error = match get_json(&client, &format!("{}{}", BRASILAPI_URL, cnpj)).await {
+        Ok(body) => return Ok(from_brasilapi(cnpj, &body)),
+        Err(e @ AppError::NotFound(_)) => return Err(e),
+        Err(e) => e,
+    };
+
+    if let Err(wait) = reserve_slot(&lookup.receitaws_next, RECEITAWS_INTERVAL) {
+        return Err(AppError::ServiceUnavailable(format!(
+            "Limite de consultas atingido, tente novamente em {} s",
+            wait.as_secs() + 1
+        ))
+        .with_details(brasilapi_error));
+    }
+    let body = get_json(&client, &format!("{}{}", RECEITAWS_URL, cnpj)).await?;
+    // ReceitaWS answers 200 with `status: "ERROR"` for invalid or unknown CNPJs
+    if text(&body, "status") == "ERROR" {
+        return Err(AppError::NotFound(format!(
+            "CNPJ não encontrado: {}",
+            text(&body, "message")
+        )));
+    }
+    Ok(from_receitaws(cnpj, &body))
+}
+
+impl CnpjLookup {
+    pub fn open(app: &tauri::AppHandle) -> Result<Self, String> {
+        let conn = crate::db::open(app)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS cnpj_cache (
+                cnpj TEXT PRIMARY KEY,
+                data TEXT NOT NULL,
+                fetched_at TEXT NOT NULL DEFAULT (datetime('now'))
+            );",
+        )
+        .map_err(|e| format!("Falha ao criar cache de CNPJ: {}", e))?;
+
+        let now = Instant::now();
+        Ok(CnpjLookup {
+            conn: Mutex::new(conn),
+            brasilapi_next: Mutex::new(now),
+            receitaws_next: Mutex::new(now),
+        })
+    }
+
+    /// Cached entry, ignoring its age when `allow_expired` is set.
+    fn cached(&self, cnpj: &str, allow_expired: bool) -> Option<CompanyInfo> {
+        let conn = self.conn.lock().unwrap();
+        let data: String = conn
+            .query_row(
+                "SELECT data FROM cnpj_cache
+                 WHERE cnpj = ?1 AND (?2 OR fetched_at >= datetime('now', ?3))",
+                params![cnpj, allow_expired, format!("-{} days", CACHE_TTL_DAYS)],
+                |row| row.get(0),
+            )
+            .optional()
+            .ok()??;
+        serde_json::from_str(&data).ok()
+    }
+
+    fn store(&self, info: &CompanyInfo) -> Result<(), AppError> {
+        let data = serde_json::to_string(info).map_err(|e| e.to_string())?;
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT OR REPLACE INTO cnpj_cache (cnpj, data, fetched_at)
+             VALUES (?1, ?2, datetime('now'))",
+            params![info.cnpj, data],
+        )
+        .map_err(|e| AppError::internal("Falha ao salvar consulta de CNPJ", e))?;
+        Ok(())
+    }
+}
+
+/// Company registry data for onboarding clients. Answers from the local cache for
+/// 30 days unless `refresh` is set, and falls back to an old copy when offline.
+#[tauri::command]
+pub async fn lookup_cnpj(
+    app: tauri::AppHandle,
+    cnpj: String,
+    refresh: Option<bool>,
+) -> Result<CompanyInfo, AppError> {
+    crate::metrics::timed("lookup_cnpj", async move {
+        let cnpj = crate::clients::normalize_cnpj(&cnpj)?;
+        let lookup = app
+            .try_state::<CnpjLookup>()
+            .ok_or_else(|| AppError::Unsupported("Consulta de CNPJ indisponível".into()))?;
+
+        if !refresh.unwrap_or(false) {
+            if let Some(cached) = lookup.cached(&cnpj, false) {
+                return Ok(cached);
+            }
+        }
+
+        match fetch(&lookup, &cnpj).await {
+            Ok(info) => {
+                lookup.store(&info)?;
+                Ok(info)
+            }
+            Err(e @ AppError::NotFound(_)) => Err(e),
+            Err(e) => lookup.cached(&cnpj, true).ok_or(e),
+        }
+    })
+    .await
+}
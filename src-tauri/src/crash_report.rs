@@ -0,0 +1,203 @@
+// ── Crash Report Module ────────────────────────────────────────
+use crate::error::AppError;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use tauri::Manager;
+
+const RECENT_COMMANDS_LIMIT: usize = 20;
+/// Reports kept on disk, pending and seen together; older ones are deleted on startup.
+const REPORTS_LIMIT: usize = 50;
+const SEEN_DIR: &str = "seen";
+
+static CRASH_DIR: OnceLock<PathBuf> = OnceLock::new();
+static RECENT_COMMANDS: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+#[derive(serde::Serialize)]
+pub struct CrashReport {
+    pub file_name: String,
+    pub created: String,
+    pub content: String,
+}
+
+/// Called for every IPC invoke so a report shows what the user was doing.
+pub fn record_command(command: &str) {
+    let mut recent = RECENT_COMMANDS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if recent.len() == RECENT_COMMANDS_LIMIT {
+        recent.pop_front();
+    }
+    recent.push_back(format!(
+        "{} {}",
+        chrono::Local::now().format("%H:%M:%S"),
+        command
+    ));
+}
+
+fn build_report(info: &std::panic::PanicHookInfo, version: &str) -> String {
+    let thread = std::thread::current();
+    // try_lock: the panic may have happened while the list was being updated
+    let commands = RECENT_COMMANDS
+        .try_lock()
+        .map(|recent| recent.iter().cloned().collect::<Vec<_>>().join("\n"))
+        .unwrap_or_else(|_| "(indisponível)".to_string());
+
+    format!(
+        "Adcontec Útil {version}\n\
+         Data: {date}\n\
+         Sistema: {os} ({arch})\n\
+         Thread: {thread}\n\n\
+         {info}\n\n\
+         Últimos comandos:\n{commands}\n\n\
+         Backtrace:\n{backtrace}\n",
+        date = chrono::Local::now().format("%d/%m/%Y %H:%M:%S"),
+        os = std::env::consts::OS,
+        arch = std::env::consts::ARCH,
+        thread = thread.name().unwrap_or("sem nome"),
+        backtrace = std::backtrace::Backtrace::force_capture(),
+    )
+}
+
+/// Writes a report file for every panic; the console is hidden in release builds,
+/// so without this crashes leave no trace.
+pub fn install(app: &tauri::AppHandle) {
+    let dir = app
+        .path()
+        .app_log_dir()
+        .unwrap_or_else(|_| std::env::temp_dir())
+        .join("crashes");
+    prune_reports(&dir);
+    let _ = CRASH_DIR.set(dir);
+
+    let version = app.package_info().version.to_string();
+    let previous = std::panic::take_hook();
+
+    std::panic::set_hook(Box::new(move |info| {
+        if let Some(dir) = CRASH_DIR.get() {
+            let file_name = format!(
+                "crash-{}.txt",
+                chrono::Local::now().format("%Y%m%d-%H%M%S%.3f")
+            );
+            let _ = std::fs::create_dir_all(dir);
+            let _ = std::fs::write(dir.join(file_name), build_report(info, &version));
+        }
+        previous(info);
+    }));
+}
+
+fn crash_dir() -> Result<&'static PathBuf, AppError> {
+    CRASH_DIR
+        .get()
+        .ok_or_else(|| AppError::Internal("Relatórios de erro não inicializados".into()))
+}
+
+/// Resolves a report by bare file name, refusing anything that points elsewhere.
+fn report_path(file_name: &str) -> Result<PathBuf, AppError> {
+    if Path::new(file_name).file_name() != Some(std::ffi::OsStr::new(file_name)) {
+        return Err(AppError::InvalidInput("Nome de relatório inválido".into()));
+    }
+    let path = crash_dir()?.join(file_name);
+    if !path.is_file() {
+        return Err(AppError::NotFound("Relatório não encontrado".into()));
+    }
+    Ok(path)
+}
+
+/// Moves the report aside so it is not offered again on the next launch.
+fn mark_seen(path: &Path) -> Result<(), AppError> {
+    let seen_dir = crash_dir()?.join(SEEN_DIR);
+    std::fs::create_dir_all(&seen_dir)
+        .map_err(|e| AppError::io("Falha ao arquivar relatório", e))?;
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| AppError::InvalidInput("Nome de relatório inválido".into()))?;
+    std::fs::rename(path, seen_dir.join(file_name))
+        .map_err(|e| AppError::io("Falha ao arquivar relatório", e))
+}
+
+/// Every report on disk, including the ones already seen, newest first.
+fn report_paths(dir: &Path) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = [dir.to_path_buf(), dir.join(SEEN_DIR)]
+        .iter()
+        .filter_map(|dir| std::fs::read_dir(dir).ok())
+        .flat_map(|entries| entries.flatten())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .collect();
+    // Names carry the timestamp, so they sort chronologically
+    paths.sort_by(|a, b| b.file_name().cmp(&a.file_name()));
+    paths
+}
+
+/// Keeps the newest REPORTS_LIMIT reports, so a crash loop cannot fill the disk.
+fn prune_reports(dir: &Path) {
+    for path in report_paths(dir).iter().skip(REPORTS_LIMIT) {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+/// File names of every report on disk, including the ones already seen, newest first.
+pub(crate) fn report_file_names() -> Vec<String> {
+    let Ok(dir) = crash_dir() else {
+        return Vec::new();
+    };
+    report_paths(dir)
+        .iter()
+        .filter_map(|path| path.file_name())
+        .map(|name| name.to_string_lossy().to_string())
+        .collect()
+}
+
+/// Returns the newest report not yet copied or dismissed, if the last run crashed.
+#[tauri::command]
+pub fn get_pending_crash_report() -> Result<Option<CrashReport>, AppError> {
+    let Ok(entries) = std::fs::read_dir(crash_dir()?) else {
+        return Ok(None);
+    };
+
+    let newest = entries
+        .flatten()
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "txt"))
+        .max_by_key(|entry| entry.file_name());
+    let Some(entry) = newest else {
+        return Ok(None);
+    };
+
+    let content = std::fs::read_to_string(entry.path())
+        .map_err(|e| AppError::io("Falha ao ler relatório", e))?;
+    let created = entry
+        .metadata()
+        .and_then(|meta| meta.modified())
+        .map(|time| {
+            chrono::DateTime::<chrono::Local>::from(time)
+                .format("%d/%m/%Y %H:%M")
+                .to_string()
+        })
+        .unwrap_or_default();
+
+    Ok(Some(CrashReport {
+        file_name: entry.file_name().to_string_lossy().to_string(),
+        created,
+        content,
+    }))
+}
+
+#[tauri::command]
+pub fn copy_crash_report(app: tauri::AppHandle, file_name: String) -> Result<(), AppError> {
+    use tauri_plugin_clipboard_manager::ClipboardExt;
+
+    let path = report_path(&file_name)?;
+    let content =
+        std::fs::read_to_string(&path).map_err(|e| AppError::io("Falha ao ler relatório", e))?;
+    app.clipboard()
+        .write_text(content)
+        .map_err(|e| AppError::internal("Falha ao copiar para a área de transferência", e))?;
+
+    mark_seen(&path)
+}
+
+#[tauri::command]
+pub fn dismiss_crash_report(file_name: String) -> Result<(), AppError> {
+    mark_seen(&report_path(&file_name)?)
+}
@@ -0,0 +1,24 @@
+// ── Database Module ────────────────────────────────────────────
+use rusqlite::Connection;
+use tauri::Manager;
+
+const DATABASE_FILE: &str = "todo.db";
+
+/// Opens the same SQLite file the frontend reaches through tauri-plugin-sql.
+/// Tables owned by the backend are created by their modules, not by the plugin migrations.
+pub fn open(app: &tauri::AppHandle) -> Result<Connection, String> {
+    let dir = app
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("Falha ao localizar pasta de dados: {}", e))?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Falha ao criar pasta de dados: {}", e))?;
+
+    let conn = Connection::open(dir.join(DATABASE_FILE))
+        .map_err(|e| format!("Falha ao abrir banco de dados: {}", e))?;
+
+    // The webview holds its own connection to the same file
+    conn.busy_timeout(std::time::Duration::from_secs(5))
+        .map_err(|e| format!("Falha ao configurar banco de dados: {}", e))?;
+
+    Ok(conn)
+}
@@ -0,0 +1,106 @@
+// ── Deep Link Module ───────────────────────────────────────────
+use std::sync::Mutex;
+use tauri::{Emitter, Manager, Url};
+use tauri_plugin_deep_link::DeepLinkExt;
+
+const SCHEME: &str = "utilhub";
+
+/// Which tool to open and with what inputs, as sent to the frontend.
+#[derive(Clone, serde::Serialize)]
+pub struct Navigation {
+    pub tool: String,
+    pub params: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Navigation that arrived before the frontend was listening (e.g. the link launched the app).
+#[derive(Default)]
+pub struct PendingNavigation(Mutex<Option<Navigation>>);
+
+/// Name given to the first path segment for tools that take a single value.
+fn main_param(tool: &str) -> &'static str {
+    match tool {
+        "nfe" => "chave",
+        "boleto" => "codigo",
+        "client" => "nome",
+        _ => "value",
+    }
+}
+
+/// `utilhub://nfe/{chave}` → `{ tool: "nfe", params: { chave } }`; query pairs become params too.
+fn parse(url: &Url) -> Option<Navigation> {
+    if url.scheme() != SCHEME {
+        return None;
+    }
+    let tool = url
+        .host_str()
+        .filter(|host| !host.is_empty())?
+        .to_lowercase();
+
+    let mut params = serde_json::Map::new();
+    let value = url
+        .path_segments()
+        .into_iter()
+        .flatten()
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| {
+            percent_encoding::percent_decode_str(segment)
+                .decode_utf8_lossy()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+    if !value.is_empty() {
+        params.insert(main_param(&tool).to_string(), value.into());
+    }
+    for (key, value) in url.query_pairs() {
+        params.insert(key.to_string(), value.to_string().into());
+    }
+
+    Some(Navigation { tool, params })
+}
+
+/// Brings the window forward and tells the frontend which tool to show.
+pub fn navigate(app: &tauri::AppHandle, navigation: Navigation) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+
+    *app.state::<PendingNavigation>().0.lock().unwrap() = Some(navigation.clone());
+    let _ = app.emit("navigate", navigation);
+}
+
+fn handle_urls(app: &tauri::AppHandle, urls: Vec<Url>) {
+    // Only the last link matters when several arrive at once
+    if let Some(navigation) = urls.iter().filter_map(parse).last() {
+        navigate(app, navigation);
+    }
+}
+
+pub fn init(app: &tauri::AppHandle) {
+    app.manage(PendingNavigation::default());
+
+    // Installers register the scheme; this covers portable and dev builds
+    #[cfg(any(windows, target_os = "linux"))]
+    if let Err(e) = app.deep_link().register_all() {
+        crate::app_log::error(format!(
+            "Falha ao registrar o protocolo {}://: {}",
+            SCHEME, e
+        ));
+    }
+
+    let handle = app.clone();
+    app.deep_link()
+        .on_open_url(move |event| handle_urls(&handle, event.urls()));
+
+    if let Ok(Some(urls)) = app.deep_link().get_current() {
+        handle_urls(app, urls);
+    }
+}
+
+/// Called by the frontend once mounted to pick up a link that launched the app.
+#[tauri::command]
+pub fn take_pending_navigation(pending: tauri::State<'_, PendingNavigation>) -> Option<Navigation> {
+    pending.0.lock().unwrap().take()
+}
@@ -0,0 +1,139 @@
+// ── Local Duplicates Module ────────────────────────────────────
+use crate::client_folders::{find_duplicates, move_to_recycle_bin, DuplicateFilesReport};
+use crate::error::AppError;
+use crate::hashing::{hash_path, HashAlgorithm};
+use std::path::{Path, PathBuf};
+
+#[derive(serde::Deserialize)]
+pub struct DuplicateRemoval {
+    /// Copy that stays; every file in `remove` is checked against it first.
+    pub keep: String,
+    pub remove: Vec<String>,
+}
+
+#[derive(serde::Serialize)]
+pub struct RemovedDuplicate {
+    pub path: String,
+    pub removed: bool,
+    pub error: Option<String>,
+}
+
+/// Folders as given, minus any folder inside another one, so no file is scanned
+/// twice and reported as its own duplicate.
+fn scan_roots(folders: &[String]) -> Result<Vec<PathBuf>, AppError> {
+    let mut roots = Vec::new();
+    for folder in folders {
+        let path = PathBuf::from(folder);
+        if !path.is_dir() {
+            return Err(AppError::InvalidInput(format!(
+                "O caminho informado não é uma pasta: {}",
+                folder
+            )));
+        }
+        // Compared in canonical form; on Windows that adds a `\\?\` prefix that
+        // should not reach the report
+        let canonical = path
+            .canonicalize()
+            .map_err(|e| AppError::io(format!("Falha ao acessar {}", folder), e))?;
+        roots.push((canonical, path));
+    }
+    // Parents sort before their children
+    roots.sort();
+    let mut kept: Vec<(PathBuf, PathBuf)> = Vec::new();
+    for (canonical, path) in roots {
+        if !kept.iter().any(|(parent, _)| canonical.starts_with(parent)) {
+            kept.push((canonical, path));
+        }
+    }
+    Ok(kept.into_iter().map(|(_, path)| path).collect())
+}
+
+fn sha256(path: &Path) -> Result<String, AppError> {
+    hash_path(path, HashAlgorithm::Sha256, |_, _| Ok(()))
+}
+
+/// Removes one duplicate after confirming it still matches the kept copy byte for
+/// byte, so a file edited since the scan is never deleted by mistake.
+fn remove_duplicate(
+    keep: &Path,
+    keep_hash: &str,
+    path: &Path,
+    permanent: bool,
+) -> Result<(), AppError> {
+    let same_file = match (keep.canonicalize(), path.canonicalize()) {
+        (Ok(keep), Ok(path)) => keep == path,
+        _ => false,
+    };
+    if same_file {
+        return Err(AppError::InvalidInput(
+            "Este é o arquivo que será mantido".into(),
+        ));
+    }
+    if sha256(path)? != keep_hash {
+        return Err(AppError::InvalidInput(
+            "O arquivo mudou desde a busca e não é mais uma cópia".into(),
+        ));
+    }
+    if permanent {
+        std::fs::remove_file(path).map_err(|e| AppError::io("Falha ao excluir arquivo", e))
+    } else {
+        move_to_recycle_bin(path)
+    }
+}
+
+/// Same as `find_duplicate_files`, over local folders picked by the user instead
+/// of a folder on the client share.
+#[tauri::command]
+pub async fn find_local_duplicates(folders: Vec<String>) -> Result<DuplicateFilesReport, AppError> {
+    crate::metrics::timed("find_local_duplicates", async move {
+        if folders.is_empty() {
+            return Err(AppError::InvalidInput(
+                "Selecione pelo menos 1 pasta.".into(),
+            ));
+        }
+        tauri::async_runtime::spawn_blocking(move || Ok(find_duplicates(&scan_roots(&folders)?)))
+            .await
+            .map_err(|e| AppError::internal("Falha ao procurar duplicados", e))?
+    })
+    .await
+}
+
+/// Deletes the chosen copies from each duplicate group, keeping `keep`. Each file
+/// is hashed again first and skipped if it no longer matches; by default files go
+/// to the Recycle Bin.
+#[tauri::command]
+pub async fn remove_local_duplicates(
+    removals: Vec<DuplicateRemoval>,
+    permanent: Option<bool>,
+) -> Result<Vec<RemovedDuplicate>, AppError> {
+    crate::metrics::timed("remove_local_duplicates", async move {
+        let permanent = permanent.unwrap_or(false);
+        tauri::async_runtime::spawn_blocking(move || {
+            let mut results = Vec::new();
+            for removal in removals {
+                let keep = Path::new(&removal.keep);
+                let keep_hash = sha256(keep).map_err(|e| {
+                    AppError::NotFound(format!(
+                        "Arquivo a manter não está acessível: {}",
+                        removal.keep
+                    ))
+                    .with_details(e)
+                });
+                for path in removal.remove {
+                    let outcome = keep_hash.clone().and_then(|keep_hash| {
+                        remove_duplicate(keep, &keep_hash, Path::new(&path), permanent)
+                    });
+                    results.push(RemovedDuplicate {
+                        path,
+                        removed: outcome.is_ok(),
+                        error: outcome.err().map(|e| e.to_string()),
+                    });
+                }
+            }
+            results
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao remover duplicados", e))
+    })
+    .await
+}
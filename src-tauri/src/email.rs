@@ -0,0 +1,196 @@
+// ── E-mail Module ──────────────────────────────────────────────
+use crate::error::AppError;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use std::path::{Path, PathBuf};
+
+/// Addresses keep their separators readable; mail clients accept `;` and `,` as is.
+const MAILTO_RECIPIENTS: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'@')
+    .remove(b'.')
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b';')
+    .remove(b',');
+
+#[derive(serde::Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum EmailMethod {
+    Outlook,
+    /// Default mail client through a `mailto:` link, which cannot carry attachments.
+    Mailto,
+}
+
+#[derive(serde::Serialize)]
+pub struct EmailDraft {
+    pub method: EmailMethod,
+    /// Whether the files are in the message; with `mailto` they are shown in
+    /// Explorer instead, to be dragged in.
+    pub attachments_included: bool,
+}
+
+/// Values reach the script through environment variables, never through the
+/// script text, so nothing typed by the user is parsed by PowerShell.
+#[cfg(windows)]
+const OUTLOOK_SCRIPT: &str = r#"
+$ErrorActionPreference = 'Stop'
+$outlook = New-Object -ComObject Outlook.Application
+$mail = $outlook.CreateItem(0)
+$mail.To = $env:ADCONTEC_MAIL_TO
+$mail.Subject = $env:ADCONTEC_MAIL_SUBJECT
+$mail.Body = $env:ADCONTEC_MAIL_BODY
+if ($env:ADCONTEC_MAIL_ATTACHMENTS) {
+    foreach ($path in $env:ADCONTEC_MAIL_ATTACHMENTS.Split("`n")) {
+        [void]$mail.Attachments.Add($path)
+    }
+}
+$mail.Display()
+"#;
+
+/// Opens a new Outlook message window with everything filled in; the user reviews
+/// and sends it. Fails when Outlook is not installed or its COM server refuses.
+#[cfg(windows)]
+fn outlook_draft(
+    to: &str,
+    subject: &str,
+    body: &str,
+    attachments: &[PathBuf],
+) -> Result<(), AppError> {
+    use std::os::windows::process::CommandExt;
+    /// Keeps a console window from flashing while PowerShell runs.
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    let attachments = attachments
+        .iter()
+        .map(|path| path.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let output = std::process::Command::new("powershell")
+        .args([
+            "-NoProfile",
+            "-NonInteractive",
+            "-ExecutionPolicy",
+            "Bypass",
+            "-Command",
+            OUTLOOK_SCRIPT,
+        ])
+        .env("ADCONTEC_MAIL_TO", to)
+        .env("ADCONTEC_MAIL_SUBJECT", subject)
+        .env("ADCONTEC_MAIL_BODY", body)
+        .env("ADCONTEC_MAIL_ATTACHMENTS", attachments)
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .map_err(|e| AppError::io("Falha ao iniciar o PowerShell", e))?;
+    if !output.status.success() {
+        return Err(AppError::ServiceUnavailable(
+            "Não foi possível criar o e-mail no Outlook".into(),
+        )
+        .with_details(String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(())
+}
+
+#[cfg(not(windows))]
+fn outlook_draft(
+    _to: &str,
+    _subject: &str,
+    _body: &str,
+    _attachments: &[PathBuf],
+) -> Result<(), AppError> {
+    Err(AppError::Unsupported(
+        "Outlook disponível apenas no Windows".into(),
+    ))
+}
+
+fn mailto_url(to: &str, subject: &str, body: &str) -> String {
+    let mut url = format!("mailto:{}", utf8_percent_encode(to, MAILTO_RECIPIENTS));
+    let mut params = Vec::new();
+    if !subject.is_empty() {
+        params.push(format!(
+            "subject={}",
+            utf8_percent_encode(subject, NON_ALPHANUMERIC)
+        ));
+    }
+    if !body.is_empty() {
+        // Line breaks in mailto bodies must be CRLF
+        let body = body.replace("\r\n", "\n").replace('\n', "\r\n");
+        params.push(format!(
+            "body={}",
+            utf8_percent_encode(&body, NON_ALPHANUMERIC)
+        ));
+    }
+    if !params.is_empty() {
+        url.push('?');
+        url.push_str(&params.join("&"));
+    }
+    url
+}
+
+/// Shows the first attachment selected in Explorer, ready to drag into the message.
+fn reveal_attachment(path: &Path) -> Result<(), AppError> {
+    #[cfg(windows)]
+    {
+        std::process::Command::new("explorer")
+            .arg(format!("/select,{}", path.to_string_lossy()))
+            .spawn()
+            .map_err(|e| AppError::io("Falha ao abrir o Explorer", e))?;
+    }
+
+    #[cfg(not(windows))]
+    {
+        let folder = path.parent().unwrap_or(path);
+        std::process::Command::new("xdg-open")
+            .arg(folder)
+            .spawn()
+            .map_err(|e| AppError::io("Falha ao abrir pasta", e))?;
+    }
+
+    Ok(())
+}
+
+/// Creates an e-mail draft with the generated DANFE/PDF/XML files attached. Outlook
+/// is tried first; without it the default mail client opens through `mailto:` and
+/// the files are shown in Explorer, since `mailto:` cannot attach them.
+#[tauri::command]
+pub async fn compose_email(
+    to: String,
+    subject: String,
+    body: String,
+    attachments: Vec<String>,
+) -> Result<EmailDraft, AppError> {
+    crate::metrics::timed("compose_email", async move {
+        let attachments: Vec<PathBuf> = attachments.iter().map(PathBuf::from).collect();
+        if let Some(missing) = attachments.iter().find(|path| !path.is_file()) {
+            return Err(AppError::NotFound(format!(
+                "Anexo não encontrado: {}",
+                missing.display()
+            )));
+        }
+        let to = to.trim().to_string();
+
+        tauri::async_runtime::spawn_blocking(move || {
+            match outlook_draft(&to, &subject, &body, &attachments) {
+                Ok(()) => {
+                    return Ok(EmailDraft {
+                        method: EmailMethod::Outlook,
+                        attachments_included: true,
+                    })
+                }
+                Err(e) => {
+                    crate::app_log::error(format!("Outlook indisponível, usando mailto: {}", e))
+                }
+            }
+
+            crate::open_link_normal_impl(&mailto_url(&to, &subject, &body))?;
+            if let Some(first) = attachments.first() {
+                reveal_attachment(first)?;
+            }
+            Ok(EmailDraft {
+                method: EmailMethod::Mailto,
+                attachments_included: attachments.is_empty(),
+            })
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao criar e-mail", e))?
+    })
+    .await
+}
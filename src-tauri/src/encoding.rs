@@ -0,0 +1,287 @@
+// ── Encoding Module ────────────────────────────────────────────
+use crate::error::AppError;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+/// Largest payload handed to or taken from the webview as a string; anything
+/// bigger has to go through files, which are streamed.
+const MAX_INLINE_BYTES: u64 = 16 * 1024 * 1024;
+const CHUNK_SIZE: usize = 64 * 1024;
+
+#[derive(serde::Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum TextEncoding {
+    Base64,
+    Hex,
+}
+
+#[derive(serde::Serialize)]
+pub struct EncodedFile {
+    /// Encoded text, when no output file was given.
+    pub content: Option<String>,
+    pub output_path: Option<String>,
+    pub size: u64,
+    pub encoded_size: u64,
+}
+
+#[derive(serde::Serialize)]
+pub struct DecodedFile {
+    pub output_path: String,
+    pub size: u64,
+}
+
+/// Drops line breaks and spaces, which web forms and e-mails add to long base64 strings.
+struct SkipWhitespace<R>(R);
+
+impl<R: Read> Read for SkipWhitespace<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        loop {
+            let read = self.0.read(buf)?;
+            if read == 0 {
+                return Ok(0);
+            }
+            let mut kept = 0;
+            for i in 0..read {
+                if !buf[i].is_ascii_whitespace() {
+                    buf[kept] = buf[i];
+                    kept += 1;
+                }
+            }
+            if kept > 0 {
+                return Ok(kept);
+            }
+        }
+    }
+}
+
+fn encode_stream(
+    mut reader: impl Read,
+    encoding: TextEncoding,
+    writer: impl Write,
+) -> std::io::Result<()> {
+    match encoding {
+        TextEncoding::Base64 => {
+            let mut encoder = base64::write::EncoderWriter::new(
+                writer,
+                &base64::engine::general_purpose::STANDARD,
+            );
+            std::io::copy(&mut reader, &mut encoder)?;
+            encoder.finish()?.flush()
+        }
+        TextEncoding::Hex => {
+            let mut writer = writer;
+            let mut buffer = vec![0u8; CHUNK_SIZE];
+            loop {
+                let read = reader.read(&mut buffer)?;
+                if read == 0 {
+                    break;
+                }
+                writer.write_all(hex::encode(&buffer[..read]).as_bytes())?;
+            }
+            writer.flush()
+        }
+    }
+}
+
+fn hex_value(digit: u8) -> Option<u8> {
+    match digit {
+        b'0'..=b'9' => Some(digit - b'0'),
+        b'a'..=b'f' => Some(digit - b'a' + 10),
+        b'A'..=b'F' => Some(digit - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Malformed input surfaces as `InvalidData`, which callers report as invalid input.
+fn decode_stream(
+    reader: impl Read,
+    encoding: TextEncoding,
+    mut writer: impl Write,
+) -> std::io::Result<()> {
+    let mut reader = SkipWhitespace(reader);
+    match encoding {
+        TextEncoding::Base64 => {
+            let mut decoder = base64::read::DecoderReader::new(
+                reader,
+                &base64::engine::general_purpose::STANDARD,
+            );
+            std::io::copy(&mut decoder, &mut writer)?;
+        }
+        TextEncoding::Hex => {
+            let invalid = || {
+                std::io::Error::new(std::io::ErrorKind::InvalidData, "caractere não hexadecimal")
+            };
+            let mut buffer = vec![0u8; CHUNK_SIZE];
+            let mut decoded = Vec::with_capacity(CHUNK_SIZE / 2);
+            // High nibble carried over when a chunk ends mid-byte
+            let mut pending: Option<u8> = None;
+            loop {
+                let read = reader.read(&mut buffer)?;
+                if read == 0 {
+                    break;
+                }
+                decoded.clear();
+                for &digit in &buffer[..read] {
+                    let value = hex_value(digit).ok_or_else(invalid)?;
+                    match pending.take() {
+                        Some(high) => decoded.push(high << 4 | value),
+                        None => pending = Some(value),
+                    }
+                }
+                writer.write_all(&decoded)?;
+            }
+            if pending.is_some() {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    "número ímpar de dígitos",
+                ));
+            }
+        }
+    }
+    writer.flush()
+}
+
+fn decode_error(e: std::io::Error, encoding: TextEncoding) -> AppError {
+    if e.kind() != std::io::ErrorKind::InvalidData {
+        return AppError::io("Falha ao gravar arquivo decodificado", e);
+    }
+    let label = match encoding {
+        TextEncoding::Base64 => "Base64 inválido",
+        TextEncoding::Hex => "Hexadecimal inválido",
+    };
+    AppError::InvalidInput(label.into()).with_details(e)
+}
+
+/// Pasted payloads may come as a data URL (`data:application/xml;base64,...`).
+fn strip_data_url(content: &str) -> &str {
+    match content.trim_start().strip_prefix("data:") {
+        Some(rest) => rest.split_once(',').map(|(_, data)| data).unwrap_or(rest),
+        None => content,
+    }
+}
+
+fn encode_file_impl(
+    path: &Path,
+    encoding: TextEncoding,
+    output_path: Option<&Path>,
+) -> Result<EncodedFile, AppError> {
+    let file = std::fs::File::open(path)
+        .map_err(|e| AppError::io(format!("Falha ao abrir {}", path.display()), e))?;
+    let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+    let reader = BufReader::new(file);
+
+    let Some(output_path) = output_path else {
+        if size > MAX_INLINE_BYTES {
+            return Err(AppError::InvalidInput(format!(
+                "Arquivo maior que {} MB: salve o resultado em um arquivo",
+                MAX_INLINE_BYTES / (1024 * 1024)
+            )));
+        }
+        let mut content = Vec::new();
+        encode_stream(reader, encoding, &mut content)
+            .map_err(|e| AppError::io("Falha ao ler arquivo", e))?;
+        // Base64 and hex output is always ASCII
+        let content = String::from_utf8(content).unwrap_or_default();
+        return Ok(EncodedFile {
+            encoded_size: content.len() as u64,
+            content: Some(content),
+            output_path: None,
+            size,
+        });
+    };
+
+    let output = std::fs::File::create(output_path)
+        .map_err(|e| AppError::io("Falha ao criar arquivo de saída", e))?;
+    encode_stream(reader, encoding, BufWriter::new(output)).map_err(|e| {
+        let _ = std::fs::remove_file(output_path);
+        AppError::io("Falha ao codificar arquivo", e)
+    })?;
+    Ok(EncodedFile {
+        content: None,
+        output_path: Some(output_path.to_string_lossy().to_string()),
+        size,
+        encoded_size: std::fs::metadata(output_path).map(|m| m.len()).unwrap_or(0),
+    })
+}
+
+fn decode_to_file_impl(
+    content: Option<String>,
+    input_path: Option<String>,
+    encoding: TextEncoding,
+    output_path: &Path,
+) -> Result<DecodedFile, AppError> {
+    let reader: Box<dyn Read> = match (content, input_path) {
+        (Some(content), None) => {
+            if content.len() as u64 > MAX_INLINE_BYTES {
+                return Err(AppError::InvalidInput(
+                    "Conteúdo muito grande para colar: informe um arquivo".into(),
+                ));
+            }
+            Box::new(std::io::Cursor::new(
+                strip_data_url(&content).as_bytes().to_vec(),
+            ))
+        }
+        (None, Some(input_path)) => Box::new(BufReader::new(
+            std::fs::File::open(&input_path)
+                .map_err(|e| AppError::io(format!("Falha ao abrir {}", input_path), e))?,
+        )),
+        _ => {
+            return Err(AppError::InvalidInput(
+                "Informe o conteúdo ou o arquivo a decodificar".into(),
+            ))
+        }
+    };
+
+    let output = std::fs::File::create(output_path)
+        .map_err(|e| AppError::io("Falha ao criar arquivo de saída", e))?;
+    // A half-decoded file would look like a valid one, so it is removed on failure
+    decode_stream(reader, encoding, BufWriter::new(output)).map_err(|e| {
+        let _ = std::fs::remove_file(output_path);
+        decode_error(e, encoding)
+    })?;
+    Ok(DecodedFile {
+        output_path: output_path.to_string_lossy().to_string(),
+        size: std::fs::metadata(output_path).map(|m| m.len()).unwrap_or(0),
+    })
+}
+
+/// Encodes a file as base64 or hex. Without `output_path` the text is returned
+/// directly, which is limited to small files.
+#[tauri::command]
+pub async fn encode_file(
+    path: String,
+    encoding: TextEncoding,
+    output_path: Option<String>,
+) -> Result<EncodedFile, AppError> {
+    crate::metrics::timed("encode_file", async move {
+        tauri::async_runtime::spawn_blocking(move || {
+            encode_file_impl(
+                Path::new(&path),
+                encoding,
+                output_path.as_deref().map(Path::new),
+            )
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao codificar arquivo", e))?
+    })
+    .await
+}
+
+/// Decodes pasted `content` or the text in `input_path` into `output_path`.
+/// Whitespace is ignored, so line-wrapped payloads decode as-is.
+#[tauri::command]
+pub async fn decode_to_file(
+    content: Option<String>,
+    input_path: Option<String>,
+    encoding: TextEncoding,
+    output_path: String,
+) -> Result<DecodedFile, AppError> {
+    crate::metrics::timed("decode_to_file", async move {
+        tauri::async_runtime::spawn_blocking(move || {
+            decode_to_file_impl(content, input_path, encoding, Path::new(&output_path))
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao decodificar arquivo", e))?
+    })
+    .await
+}
@@ -0,0 +1,123 @@
+// ── Error Module ───────────────────────────────────────────────
+use serde::ser::SerializeStruct;
+use std::fmt;
+
+/// Error returned by commands, sent to the frontend as `{ code, message, details }`
+/// so it can react to the kind of failure instead of parsing the text.
+#[derive(Debug, Clone)]
+pub enum AppError {
+    /// Input rejected before doing any work (malformed access key, empty selection...).
+    InvalidInput(String),
+    NotFound(String),
+    AlreadyExists(String),
+    AccessDenied(String),
+    /// A remote service could not be reached or timed out, e.g. SEFAZ offline.
+    ServiceUnavailable(String),
+    /// A remote service answered, but refused the request.
+    ServiceRejected(String),
+    /// Feature not available on this platform.
+    Unsupported(String),
+    /// The user cancelled the operation before it finished.
+    Cancelled(String),
+    Internal(String),
+    /// Any of the above plus the underlying error text.
+    Detailed(Box<AppError>, String),
+}
+
+impl AppError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::InvalidInput(_) => "invalid_input",
+            AppError::NotFound(_) => "not_found",
+            AppError::AlreadyExists(_) => "already_exists",
+            AppError::AccessDenied(_) => "access_denied",
+            AppError::ServiceUnavailable(_) => "service_unavailable",
+            AppError::ServiceRejected(_) => "service_rejected",
+            AppError::Unsupported(_) => "unsupported",
+            AppError::Cancelled(_) => "cancelled",
+            AppError::Internal(_) => "internal",
+            AppError::Detailed(inner, _) => inner.code(),
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            AppError::InvalidInput(message)
+            | AppError::NotFound(message)
+            | AppError::AlreadyExists(message)
+            | AppError::AccessDenied(message)
+            | AppError::ServiceUnavailable(message)
+            | AppError::ServiceRejected(message)
+            | AppError::Unsupported(message)
+            | AppError::Cancelled(message)
+            | AppError::Internal(message) => message,
+            AppError::Detailed(inner, _) => inner.message(),
+        }
+    }
+
+    pub fn details(&self) -> Option<&str> {
+        match self {
+            AppError::Detailed(_, details) => Some(details),
+            _ => None,
+        }
+    }
+
+    pub fn with_details(self, details: impl fmt::Display) -> Self {
+        let inner = match self {
+            AppError::Detailed(inner, _) => *inner,
+            other => other,
+        };
+        AppError::Detailed(Box::new(inner), details.to_string())
+    }
+
+    /// Classifies a filesystem error by its kind, keeping the OS text as details.
+    pub fn io(message: impl Into<String>, error: std::io::Error) -> Self {
+        let message = message.into();
+        let base = match error.kind() {
+            std::io::ErrorKind::NotFound => AppError::NotFound(message),
+            std::io::ErrorKind::AlreadyExists => AppError::AlreadyExists(message),
+            std::io::ErrorKind::PermissionDenied => AppError::AccessDenied(message),
+            _ => AppError::Internal(message),
+        };
+        base.with_details(error)
+    }
+
+    pub fn internal(message: impl Into<String>, error: impl fmt::Display) -> Self {
+        AppError::Internal(message.into()).with_details(error)
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.details() {
+            Some(details) => write!(f, "{}: {}", self.message(), details),
+            None => f.write_str(self.message()),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+/// Helpers still returning `Result<_, String>` surface as internal errors.
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        AppError::Internal(message)
+    }
+}
+
+impl From<&str> for AppError {
+    fn from(message: &str) -> Self {
+        AppError::Internal(message.to_string())
+    }
+}
+
+impl serde::Serialize for AppError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::metrics::note_dispatch_error(|| self.to_string());
+        let mut state = serializer.serialize_struct("AppError", 3)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", self.message())?;
+        state.serialize_field("details", &self.details())?;
+        state.end()
+    }
+}
@@ -0,0 +1,211 @@
+// ── Favicons Module ────────────────────────────────────────────
+use crate::error::AppError;
+use base64::Engine;
+use sha2::{Digest, Sha256};
+use std::path::PathBuf;
+use std::time::Duration;
+use tauri::{Manager, Url};
+
+const CACHE_DIR: &str = "favicons";
+/// Icons are fetched again after a week, in case the site changed them.
+const CACHE_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_ICON_BYTES: usize = 512 * 1024;
+/// Only the start of the page is scanned for `<link rel="icon">`.
+const MAX_HTML_BYTES: usize = 256 * 1024;
+
+#[derive(serde::Serialize)]
+pub struct Favicon {
+    /// Cached file in the app data dir.
+    pub path: String,
+    /// Ready to use as an `<img src>`.
+    pub data_url: String,
+}
+
+/// Recognizes the image formats sites actually serve as icons.
+fn sniff_mime(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(b"\x89PNG") {
+        Some("image/png")
+    } else if bytes.starts_with(&[0, 0, 1, 0]) {
+        Some("image/x-icon")
+    } else if bytes.starts_with(b"GIF8") {
+        Some("image/gif")
+    } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("image/jpeg")
+    } else if bytes.starts_with(b"RIFF") && bytes.get(8..12) == Some(b"WEBP") {
+        Some("image/webp")
+    } else {
+        let head = String::from_utf8_lossy(&bytes[..bytes.len().min(512)]).to_lowercase();
+        head.contains("<svg").then_some("image/svg+xml")
+    }
+}
+
+fn cache_path(app: &tauri::AppHandle, host: &str) -> Result<PathBuf, AppError> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| AppError::internal("Falha ao localizar pasta de dados", e))?
+        .join(CACHE_DIR);
+    std::fs::create_dir_all(&dir).map_err(|e| AppError::io("Falha ao criar pasta de ícones", e))?;
+    let name = hex::encode(Sha256::digest(host.as_bytes()));
+    Ok(dir.join(name))
+}
+
+fn to_favicon(path: &std::path::Path, bytes: &[u8]) -> Option<Favicon> {
+    let mime = sniff_mime(bytes)?;
+    Some(Favicon {
+        path: path.to_string_lossy().into_owned(),
+        data_url: format!(
+            "data:{};base64,{}",
+            mime,
+            base64::engine::general_purpose::STANDARD.encode(bytes)
+        ),
+    })
+}
+
+fn read_cached(path: &std::path::Path) -> Option<Favicon> {
+    let age = std::fs::metadata(path)
+        .ok()?
+        .modified()
+        .ok()?
+        .elapsed()
+        .ok()?;
+    if age > CACHE_TTL {
+        return None;
+    }
+    to_favicon(path, &std::fs::read(path).ok()?)
+}
+
+/// Value of `name="..."` inside a single HTML tag, quoted or not.
+fn tag_attribute(tag: &str, name: &str) -> Option<String> {
+    let lower = tag.to_ascii_lowercase();
+    let mut search_from = 0;
+    while let Some(found) = lower[search_from..].find(name) {
+        let start = search_from + found;
+        search_from = start + name.len();
+        let preceded_by_space = lower[..start].ends_with(|c: char| c.is_ascii_whitespace());
+        let rest = lower[search_from..].trim_start();
+        if !preceded_by_space || !rest.starts_with('=') {
+            continue;
+        }
+
+        let value_start = tag.len() - rest.len() + 1;
+        let value = tag[value_start..].trim_start();
+        return match value.chars().next()? {
+            quote @ ('"' | '\'') => value[1..].split(quote).next().map(str::to_string),
+            _ => value
+                .split(|c: char| c.is_ascii_whitespace() || c == '>')
+                .next()
+                .map(str::to_string),
+        };
+    }
+    None
+}
+
+/// `<link rel="icon">` candidates in page order, larger `apple-touch-icon`s last.
+fn icon_links(html: &str, page: &Url) -> Vec<Url> {
+    let lower = html.to_ascii_lowercase();
+    let mut icons = Vec::new();
+    let mut touch_icons = Vec::new();
+
+    for (start, _) in lower.match_indices("<link") {
+        let Some(end) = lower[start..].find('>') else {
+            break;
+        };
+        let tag = &html[start..start + end];
+        let Some(rel) = tag_attribute(tag, "rel").map(|rel| rel.to_ascii_lowercase()) else {
+            continue;
+        };
+        let Some(href) = tag_attribute(tag, "href").and_then(|href| page.join(&href).ok()) else {
+            continue;
+        };
+
+        if rel.split_whitespace().any(|part| part == "icon") {
+            icons.push(href);
+        } else if rel.contains("apple-touch-icon") {
+            touch_icons.push(href);
+        }
+    }
+
+    icons.extend(touch_icons);
+    icons
+}
+
+async fn download(client: &reqwest::Client, url: &Url, limit: usize) -> Option<Vec<u8>> {
+    let response = client.get(url.as_str()).send().await.ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
+    if response
+        .content_length()
+        .is_some_and(|length| length as usize > limit)
+    {
+        return None;
+    }
+    let bytes = response.bytes().await.ok()?;
+    (bytes.len() <= limit).then(|| bytes.to_vec())
+}
+
+/// The page's declared icons first, then the conventional `/favicon.ico`.
+async fn find_icon(client: &reqwest::Client, page: &Url) -> Option<Vec<u8>> {
+    let mut candidates = Vec::new();
+    if let Some(html) = download(client, page, MAX_HTML_BYTES).await {
+        candidates = icon_links(&String::from_utf8_lossy(&html), page);
+    }
+    if let Ok(fallback) = page.join("/favicon.ico") {
+        candidates.push(fallback);
+    }
+
+    for candidate in candidates {
+        if let Some(bytes) = download(client, &candidate, MAX_ICON_BYTES).await {
+            if sniff_mime(&bytes).is_some() {
+                return Some(bytes);
+            }
+        }
+    }
+    None
+}
+
+/// Downloads the site's icon once and serves it from the cache afterwards, so the
+/// webview never has to make cross-origin requests for it.
+#[tauri::command]
+pub async fn fetch_favicon(app: tauri::AppHandle, url: String) -> Result<Favicon, AppError> {
+    crate::metrics::timed("fetch_favicon", async move {
+        let page = Url::parse(url.trim())
+            .ok()
+            .filter(|page| matches!(page.scheme(), "http" | "https"))
+            .ok_or_else(|| {
+                AppError::InvalidInput("Ícones só podem ser buscados de links http/https".into())
+            })?;
+        let host = page
+            .host_str()
+            .ok_or_else(|| AppError::InvalidInput("URL sem endereço do site".into()))?
+            .to_ascii_lowercase();
+
+        let path = cache_path(&app, &host)?;
+        if let Some(cached) = read_cached(&path) {
+            return Ok(cached);
+        }
+
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .user_agent(concat!("AdcontecUtil/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .map_err(|e| AppError::internal("Falha ao criar cliente HTTP", e))?;
+
+        let Some(bytes) = find_icon(&client, &page).await else {
+            // An expired copy still beats no icon while the site is unreachable
+            return std::fs::read(&path)
+                .ok()
+                .and_then(|bytes| to_favicon(&path, &bytes))
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Nenhum ícone encontrado para {}", host))
+                });
+        };
+
+        std::fs::write(&path, &bytes).map_err(|e| AppError::io("Falha ao salvar ícone", e))?;
+        to_favicon(&path, &bytes)
+            .ok_or_else(|| AppError::internal("Falha ao ler ícone", "formato não reconhecido"))
+    })
+    .await
+}
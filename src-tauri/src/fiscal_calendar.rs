@@ -0,0 +1,685 @@
+// ── Fiscal Calendar Module ─────────────────────────────────────
+use crate::error::AppError;
+use crate::settings::Settings;
+use chrono::{Datelike, Duration, Local, NaiveDate, TimeZone, Utc, Weekday};
+use rusqlite::{params, Connection};
+use std::sync::Mutex;
+use tauri::{Emitter, Manager};
+
+const CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+const DEFAULT_UPCOMING_DAYS: u32 = 30;
+const MAX_UPCOMING_DAYS: u32 = 400;
+
+/// Days before a deadline its reminder goes out; 0 turns the reminders off.
+const LEAD_DAYS_KEY: &str = "fiscal_reminder_lead_days";
+const DEFAULT_LEAD_DAYS: i64 = 3;
+/// Local hour the deadline reminders fire at.
+const REMINDER_HOUR: u32 = 9;
+
+/// How a deadline that falls on a weekend or holiday moves.
+#[derive(Clone, Copy, PartialEq)]
+enum NonBusiness {
+    Anticipate,
+    Postpone,
+}
+
+/// Built-in federal obligations, refreshed on every start so corrected deadlines
+/// reach existing installs. `month` is the offset from the competência month for
+/// monthly obligations and the calendar month for annual ones, whose competência
+/// is the previous year. `rule` is `day:N`, `business_day:N` or `last_business_day`.
+struct Seed {
+    code: &'static str,
+    name: &'static str,
+    description: &'static str,
+    annual: bool,
+    month: u32,
+    rule: &'static str,
+    non_business: NonBusiness,
+    /// Regimes the obligation applies to when the client has no explicit flag;
+    /// empty means every regime.
+    regimes: &'static [&'static str],
+}
+
+const SIMPLES: &str = "simples";
+const MEI: &str = "mei";
+const PRESUMIDO: &str = "presumido";
+const REAL: &str = "real";
+
+const SEEDS: &[Seed] = &[
+    Seed {
+        code: "das",
+        name: "DAS / PGDAS-D",
+        description: "Apuração e guia do Simples Nacional",
+        annual: false,
+        month: 1,
+        rule: "day:20",
+        non_business: NonBusiness::Postpone,
+        regimes: &[SIMPLES],
+    },
+    Seed {
+        code: "das_mei",
+        name: "DAS-MEI",
+        description: "Guia mensal do Microempreendedor Individual",
+        annual: false,
+        month: 1,
+        rule: "day:20",
+        non_business: NonBusiness::Postpone,
+        regimes: &[MEI],
+    },
+    Seed {
+        code: "esocial",
+        name: "eSocial (folha)",
+        description: "Eventos periódicos da folha de pagamento",
+        annual: false,
+        month: 1,
+        rule: "day:15",
+        non_business: NonBusiness::Anticipate,
+        regimes: &[SIMPLES, PRESUMIDO, REAL],
+    },
+    Seed {
+        code: "efd_reinf",
+        name: "EFD-Reinf",
+        description: "Retenções e demais informações fiscais",
+        annual: false,
+        month: 1,
+        rule: "day:15",
+        non_business: NonBusiness::Anticipate,
+        regimes: &[SIMPLES, PRESUMIDO, REAL],
+    },
+    Seed {
+        code: "dctfweb",
+        name: "DCTFWeb",
+        description: "Declaração de débitos previdenciários e retenções",
+        annual: false,
+        month: 1,
+        rule: "day:15",
+        non_business: NonBusiness::Anticipate,
+        regimes: &[SIMPLES, PRESUMIDO, REAL],
+    },
+    Seed {
+        code: "inss",
+        name: "INSS (DARF DCTFWeb)",
+        description: "Contribuições previdenciárias da competência",
+        annual: false,
+        month: 1,
+        rule: "day:20",
+        non_business: NonBusiness::Anticipate,
+        regimes: &[SIMPLES, PRESUMIDO, REAL],
+    },
+    Seed {
+        code: "fgts",
+        name: "FGTS Digital",
+        description: "Recolhimento do FGTS da competência",
+        annual: false,
+        month: 1,
+        rule: "day:20",
+        non_business: NonBusiness::Anticipate,
+        regimes: &[SIMPLES, PRESUMIDO, REAL],
+    },
+    Seed {
+        code: "efd_icms_ipi",
+        name: "EFD ICMS/IPI",
+        description: "SPED Fiscal; o prazo varia por UF, confira o da SEFAZ do cliente",
+        annual: false,
+        month: 1,
+        rule: "day:20",
+        non_business: NonBusiness::Anticipate,
+        regimes: &[PRESUMIDO, REAL],
+    },
+    Seed {
+        code: "efd_contribuicoes",
+        name: "EFD-Contribuições",
+        description: "PIS/COFINS, até o 10º dia útil do segundo mês",
+        annual: false,
+        month: 2,
+        rule: "business_day:10",
+        non_business: NonBusiness::Anticipate,
+        regimes: &[PRESUMIDO, REAL],
+    },
+    Seed {
+        code: "defis",
+        name: "DEFIS",
+        description: "Declaração anual do Simples Nacional",
+        annual: true,
+        month: 3,
+        rule: "day:31",
+        non_business: NonBusiness::Anticipate,
+        regimes: &[SIMPLES],
+    },
+    Seed {
+        code: "dasn_simei",
+        name: "DASN-SIMEI",
+        description: "Declaração anual do MEI",
+        annual: true,
+        month: 5,
+        rule: "day:31",
+        non_business: NonBusiness::Anticipate,
+        regimes: &[MEI],
+    },
+    Seed {
+        code: "ecd",
+        name: "ECD",
+        description: "Escrituração Contábil Digital",
+        annual: true,
+        month: 6,
+        rule: "last_business_day",
+        non_business: NonBusiness::Anticipate,
+        regimes: &[PRESUMIDO, REAL],
+    },
+    Seed {
+        code: "ecf",
+        name: "ECF",
+        description: "Escrituração Contábil Fiscal",
+        annual: true,
+        month: 7,
+        rule: "last_business_day",
+        non_business: NonBusiness::Anticipate,
+        regimes: &[PRESUMIDO, REAL],
+    },
+];
+
+pub struct FiscalCalendar {
+    conn: Mutex<Connection>,
+}
+
+#[derive(serde::Serialize, Clone)]
+pub struct FiscalObligation {
+    pub code: String,
+    pub name: String,
+    pub description: String,
+    /// "monthly" or "annual".
+    pub frequency: String,
+    pub regimes: Vec<String>,
+}
+
+#[derive(serde::Serialize)]
+pub struct ClientObligation {
+    #[serde(flatten)]
+    pub obligation: FiscalObligation,
+    pub applies: bool,
+    /// False when `applies` comes from the client's regime.
+    pub explicit: bool,
+}
+
+#[derive(serde::Serialize, Clone)]
+pub struct ObligationClient {
+    pub id: i64,
+    pub name: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct UpcomingObligation {
+    pub code: String,
+    pub name: String,
+    pub description: String,
+    /// `YYYY-MM-DD`, already moved off weekends and national holidays.
+    pub due_date: String,
+    /// `MM/YYYY`, or `YYYY` for annual obligations.
+    pub competencia: String,
+    pub days_left: i64,
+    pub clients: Vec<ObligationClient>,
+}
+
+struct ClientRow {
+    id: i64,
+    name: String,
+    regime: Option<String>,
+}
+
+/// Easter Sunday (anonymous Gregorian algorithm); Carnival, Good Friday and
+/// Corpus Christi follow from it.
+fn easter(year: i32) -> NaiveDate {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32).unwrap()
+}
+
+/// National holidays plus the movable days banks close on. State and city
+/// holidays are not considered.
+fn is_holiday(date: NaiveDate) -> bool {
+    const FIXED: &[(u32, u32)] = &[
+        (1, 1),
+        (4, 21),
+        (5, 1),
+        (9, 7),
+        (10, 12),
+        (11, 2),
+        (11, 15),
+        (11, 20),
+        (12, 25),
+    ];
+    if FIXED.contains(&(date.month(), date.day())) {
+        return true;
+    }
+    let easter = easter(date.year());
+    [-48, -47, -2, 60]
+        .iter()
+        .any(|offset| easter + Duration::days(*offset) == date)
+}
+
+fn is_business_day(date: NaiveDate) -> bool {
+    !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) && !is_holiday(date)
+}
+
+fn last_day_of_month(year: i32, month: u32) -> NaiveDate {
+    let (next_year, next_month) = if month == 12 {
+        (year + 1, 1)
+    } else {
+        (year, month + 1)
+    };
+    NaiveDate::from_ymd_opt(next_year, next_month, 1).unwrap() - Duration::days(1)
+}
+
+fn shift_to_business_day(mut date: NaiveDate, non_business: NonBusiness) -> NaiveDate {
+    let step = match non_business {
+        NonBusiness::Anticipate => Duration::days(-1),
+        NonBusiness::Postpone => Duration::days(1),
+    };
+    while !is_business_day(date) {
+        date += step;
+    }
+    date
+}
+
+fn due_in_month(year: i32, month: u32, rule: &str, non_business: NonBusiness) -> NaiveDate {
+    let last = last_day_of_month(year, month);
+    match rule.split_once(':') {
+        Some(("business_day", n)) => {
+            let n: usize = n.parse().unwrap_or(1);
+            last.with_day(1)
+                .unwrap()
+                .iter_days()
+                .take_while(|date| *date <= last)
+                .filter(|date| is_business_day(*date))
+                .nth(n.saturating_sub(1))
+                .unwrap_or(last)
+        }
+        Some(("day", n)) => {
+            let day = n.parse::<u32>().unwrap_or(1).clamp(1, last.day());
+            shift_to_business_day(last.with_day(day).unwrap(), non_business)
+        }
+        _ => shift_to_business_day(last, NonBusiness::Anticipate),
+    }
+}
+
+fn add_months(year: i32, month: u32, offset: i32) -> (i32, u32) {
+    let index = year * 12 + month as i32 - 1 + offset;
+    (index.div_euclid(12), index.rem_euclid(12) as u32 + 1)
+}
+
+/// Due dates between `from` and `to` (inclusive), with their competência.
+fn occurrences(
+    seed: &Seed,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> impl Iterator<Item = (NaiveDate, String)> + '_ {
+    let candidates: Vec<(NaiveDate, String)> = if seed.annual {
+        (from.year()..=to.year())
+            .map(|year| {
+                (
+                    due_in_month(year, seed.month, seed.rule, seed.non_business),
+                    (year - 1).to_string(),
+                )
+            })
+            .collect()
+    } else {
+        // Start a month early: anticipation can pull a deadline back into `from`'s month
+        let (mut year, mut month) = add_months(from.year(), from.month(), -(seed.month as i32) - 1);
+        let mut dates = Vec::new();
+        loop {
+            let (due_year, due_month) = add_months(year, month, seed.month as i32);
+            let due = due_in_month(due_year, due_month, seed.rule, seed.non_business);
+            if due > to + Duration::days(31) {
+                break;
+            }
+            dates.push((due, format!("{:02}/{}", month, year)));
+            (year, month) = add_months(year, month, 1);
+        }
+        dates
+    };
+    candidates
+        .into_iter()
+        .filter(move |(due, _)| *due >= from && *due <= to)
+}
+
+/// Maps the free-text regime of the client registry to the seed keys.
+fn regime_key(regime: &str) -> Option<&'static str> {
+    let regime = regime.to_lowercase();
+    if regime.contains("mei") || regime.contains("microempreendedor") {
+        Some(MEI)
+    } else if regime.contains("simples") {
+        Some(SIMPLES)
+    } else if regime.contains("presumido") {
+        Some(PRESUMIDO)
+    } else if regime.contains("real") {
+        Some(REAL)
+    } else {
+        None
+    }
+}
+
+/// Applicability by regime alone; clients without a known regime get nothing
+/// until it is filled in or the obligation is flagged for them.
+fn applies_by_regime(seed: &Seed, regime: Option<&str>) -> bool {
+    if seed.regimes.is_empty() {
+        return true;
+    }
+    regime
+        .and_then(regime_key)
+        .is_some_and(|key| seed.regimes.contains(&key))
+}
+
+fn seed(code: &str) -> Result<&'static Seed, AppError> {
+    SEEDS
+        .iter()
+        .find(|seed| seed.code == code)
+        .ok_or_else(|| AppError::NotFound(format!("Obrigação desconhecida: {}", code)))
+}
+
+fn to_obligation(seed: &Seed) -> FiscalObligation {
+    FiscalObligation {
+        code: seed.code.into(),
+        name: seed.name.into(),
+        description: seed.description.into(),
+        frequency: if seed.annual { "annual" } else { "monthly" }.into(),
+        regimes: seed
+            .regimes
+            .iter()
+            .map(|regime| regime.to_string())
+            .collect(),
+    }
+}
+
+impl FiscalCalendar {
+    pub fn open(app: &tauri::AppHandle) -> Result<Self, String> {
+        let conn = crate::db::open(app)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS fiscal_obligations (
+                code TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                description TEXT NOT NULL DEFAULT '',
+                annual INTEGER NOT NULL DEFAULT 0,
+                month INTEGER NOT NULL,
+                rule TEXT NOT NULL,
+                regimes TEXT NOT NULL DEFAULT ''
+            );
+            CREATE TABLE IF NOT EXISTS client_obligations (
+                client_id INTEGER NOT NULL,
+                obligation_code TEXT NOT NULL,
+                applies INTEGER NOT NULL,
+                PRIMARY KEY (client_id, obligation_code)
+            );
+            CREATE TABLE IF NOT EXISTS fiscal_reminders_sent (
+                obligation_code TEXT NOT NULL,
+                client_id INTEGER NOT NULL,
+                due_date TEXT NOT NULL,
+                PRIMARY KEY (obligation_code, client_id, due_date)
+            );",
+        )
+        .map_err(|e| format!("Falha ao criar tabelas do calendário fiscal: {}", e))?;
+
+        for seed in SEEDS {
+            conn.execute(
+                "INSERT INTO fiscal_obligations
+                     (code, name, description, annual, month, rule, regimes)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                 ON CONFLICT(code) DO UPDATE SET
+                     name = excluded.name, description = excluded.description,
+                     annual = excluded.annual, month = excluded.month,
+                     rule = excluded.rule, regimes = excluded.regimes",
+                params![
+                    seed.code,
+                    seed.name,
+                    seed.description,
+                    seed.annual,
+                    seed.month,
+                    seed.rule,
+                    seed.regimes.join(",")
+                ],
+            )
+            .map_err(|e| format!("Falha ao carregar obrigações fiscais: {}", e))?;
+        }
+
+        Ok(FiscalCalendar {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    fn clients(&self, client_id: Option<i64>) -> Result<Vec<ClientRow>, AppError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, name, regime FROM clients
+                 WHERE ?1 IS NULL OR id = ?1 ORDER BY name COLLATE NOCASE",
+            )
+            .map_err(|e| AppError::internal("Falha ao consultar clientes", e))?;
+        let rows = stmt
+            .query_map(params![client_id], |row| {
+                Ok(ClientRow {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    regime: row.get(2)?,
+                })
+            })
+            .map_err(|e| AppError::internal("Falha ao consultar clientes", e))?;
+        rows.collect::<Result<_, _>>()
+            .map_err(|e| AppError::internal("Falha ao ler clientes", e))
+    }
+
+    /// Explicit per-client flags as `(client_id, obligation_code, applies)`.
+    fn flags(&self) -> Result<Vec<(i64, String, bool)>, AppError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare("SELECT client_id, obligation_code, applies FROM client_obligations")
+            .map_err(|e| AppError::internal("Falha ao consultar obrigações dos clientes", e))?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .map_err(|e| AppError::internal("Falha ao consultar obrigações dos clientes", e))?;
+        rows.collect::<Result<_, _>>()
+            .map_err(|e| AppError::internal("Falha ao ler obrigações dos clientes", e))
+    }
+
+    /// Obligations that fall due between today and `days` from now, each with the
+    /// clients it applies to. Without clients in the registry nothing is listed.
+    fn upcoming(
+        &self,
+        days: u32,
+        client_id: Option<i64>,
+    ) -> Result<Vec<UpcomingObligation>, AppError> {
+        let clients = self.clients(client_id)?;
+        let flags = self.flags()?;
+        let today = Local::now().date_naive();
+        let until = today + Duration::days(days as i64);
+
+        let mut upcoming = Vec::new();
+        for seed in SEEDS {
+            let applicable: Vec<ObligationClient> = clients
+                .iter()
+                .filter(|client| {
+                    flags
+                        .iter()
+                        .find(|(id, code, _)| *id == client.id && code == seed.code)
+                        .map(|(_, _, applies)| *applies)
+                        .unwrap_or_else(|| applies_by_regime(seed, client.regime.as_deref()))
+                })
+                .map(|client| ObligationClient {
+                    id: client.id,
+                    name: client.name.clone(),
+                })
+                .collect();
+            if applicable.is_empty() {
+                continue;
+            }
+
+            for (due, competencia) in occurrences(seed, today, until) {
+                upcoming.push(UpcomingObligation {
+                    code: seed.code.into(),
+                    name: seed.name.into(),
+                    description: seed.description.into(),
+                    due_date: due.format("%Y-%m-%d").to_string(),
+                    competencia,
+                    days_left: (due - today).num_days(),
+                    clients: applicable.clone(),
+                });
+            }
+        }
+        upcoming.sort_by(|a, b| a.due_date.cmp(&b.due_date).then(a.name.cmp(&b.name)));
+        Ok(upcoming)
+    }
+
+    /// Records that a reminder went out; false when it already had.
+    fn claim_reminder(&self, code: &str, client_id: i64, due_date: &str) -> Result<bool, AppError> {
+        let conn = self.conn.lock().unwrap();
+        let inserted = conn
+            .execute(
+                "INSERT OR IGNORE INTO fiscal_reminders_sent (obligation_code, client_id, due_date)
+                 VALUES (?1, ?2, ?3)",
+                params![code, client_id, due_date],
+            )
+            .map_err(|e| AppError::internal("Falha ao registrar lembrete fiscal", e))?;
+        Ok(inserted > 0)
+    }
+}
+
+/// Hands deadlines entering the lead window to the reminders engine, one reminder
+/// per obligation and due date listing every client it applies to.
+fn schedule_reminders(app: &tauri::AppHandle) -> Result<(), AppError> {
+    let lead_days = app
+        .state::<Settings>()
+        .get::<i64>(LEAD_DAYS_KEY)
+        .unwrap_or(DEFAULT_LEAD_DAYS);
+    if lead_days <= 0 {
+        return Ok(());
+    }
+    let Some(reminders) = app.try_state::<crate::reminders::Reminders>() else {
+        return Ok(());
+    };
+
+    let calendar = app.state::<FiscalCalendar>();
+    let mut scheduled = false;
+    for obligation in calendar.upcoming(lead_days as u32, None)? {
+        let mut pending = Vec::new();
+        for client in &obligation.clients {
+            if calendar.claim_reminder(&obligation.code, client.id, &obligation.due_date)? {
+                pending.push(client.name.as_str());
+            }
+        }
+        if pending.is_empty() {
+            continue;
+        }
+
+        let due = NaiveDate::parse_from_str(&obligation.due_date, "%Y-%m-%d")
+            .map_err(|e| AppError::internal("Data de vencimento inválida", e))?;
+        let fire_at = (due - Duration::days(lead_days))
+            .and_hms_opt(REMINDER_HOUR, 0, 0)
+            .and_then(|time| Local.from_local_datetime(&time).earliest())
+            .map(|time| time.with_timezone(&Utc))
+            .unwrap_or_else(Utc::now)
+            .max(Utc::now());
+        let title = format!("{} vence em {}", obligation.name, due.format("%d/%m/%Y"));
+        let body = format!(
+            "Competência {}: {}",
+            obligation.competencia,
+            pending.join(", ")
+        );
+        reminders.schedule_once(&title, &body, fire_at)?;
+        scheduled = true;
+    }
+
+    if scheduled {
+        let _ = app.emit("reminders-changed", ());
+    }
+    Ok(())
+}
+
+/// Checks hourly so deadlines are picked up the day they enter the lead window.
+pub fn start_scheduler(app: tauri::AppHandle) {
+    std::thread::spawn(move || loop {
+        if let Err(e) = schedule_reminders(&app) {
+            crate::app_log::error(format!("Falha ao agendar lembretes fiscais: {}", e));
+        }
+        std::thread::sleep(CHECK_INTERVAL);
+    });
+}
+
+#[tauri::command]
+pub fn list_fiscal_obligations() -> Vec<FiscalObligation> {
+    SEEDS.iter().map(to_obligation).collect()
+}
+
+#[tauri::command]
+pub fn list_client_obligations(
+    calendar: tauri::State<'_, FiscalCalendar>,
+    client_id: i64,
+) -> Result<Vec<ClientObligation>, AppError> {
+    let client = calendar
+        .clients(Some(client_id))?
+        .into_iter()
+        .next()
+        .ok_or_else(|| AppError::NotFound("Cliente não encontrado".into()))?;
+    let flags = calendar.flags()?;
+
+    Ok(SEEDS
+        .iter()
+        .map(|seed| {
+            let flag = flags
+                .iter()
+                .find(|(id, code, _)| *id == client_id && code == seed.code)
+                .map(|(_, _, applies)| *applies);
+            ClientObligation {
+                obligation: to_obligation(seed),
+                applies: flag.unwrap_or_else(|| applies_by_regime(seed, client.regime.as_deref())),
+                explicit: flag.is_some(),
+            }
+        })
+        .collect())
+}
+
+/// Overrides whether an obligation applies to a client; `None` goes back to the
+/// default for the client's regime.
+#[tauri::command]
+pub fn set_client_obligation(
+    calendar: tauri::State<'_, FiscalCalendar>,
+    client_id: i64,
+    obligation_code: String,
+    applies: Option<bool>,
+) -> Result<(), AppError> {
+    let seed = seed(&obligation_code)?;
+    let conn = calendar.conn.lock().unwrap();
+    match applies {
+        Some(applies) => conn.execute(
+            "INSERT OR REPLACE INTO client_obligations (client_id, obligation_code, applies)
+             VALUES (?1, ?2, ?3)",
+            params![client_id, seed.code, applies],
+        ),
+        None => conn.execute(
+            "DELETE FROM client_obligations WHERE client_id = ?1 AND obligation_code = ?2",
+            params![client_id, seed.code],
+        ),
+    }
+    .map_err(|e| AppError::internal("Falha ao salvar obrigação do cliente", e))?;
+    Ok(())
+}
+
+/// Deadlines in the next `days` (30 by default), optionally for a single client.
+#[tauri::command]
+pub fn list_upcoming_obligations(
+    calendar: tauri::State<'_, FiscalCalendar>,
+    days: Option<u32>,
+    client_id: Option<i64>,
+) -> Result<Vec<UpcomingObligation>, AppError> {
+    let days = days.unwrap_or(DEFAULT_UPCOMING_DAYS).min(MAX_UPCOMING_DAYS);
+    calendar.upcoming(days, client_id)
+}
@@ -0,0 +1,192 @@
+// ── Hashing Module ─────────────────────────────────────────────
+use crate::error::AppError;
+use crate::jobs::Jobs;
+use sha2::Digest;
+use std::io::Read;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+const CHUNK_SIZE: usize = 1024 * 1024;
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum HashAlgorithm {
+    Md5,
+    Sha1,
+    Sha256,
+}
+
+impl HashAlgorithm {
+    /// Told apart by the length of the hex digest.
+    fn from_hex(hash: &str) -> Option<Self> {
+        match hash.len() {
+            32 => Some(HashAlgorithm::Md5),
+            40 => Some(HashAlgorithm::Sha1),
+            64 => Some(HashAlgorithm::Sha256),
+            _ => None,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            HashAlgorithm::Md5 => "MD5",
+            HashAlgorithm::Sha1 => "SHA-1",
+            HashAlgorithm::Sha256 => "SHA-256",
+        }
+    }
+}
+
+fn digest<D: Digest>(
+    reader: &mut impl Read,
+    total: u64,
+    on_progress: &mut impl FnMut(u64, u64) -> Result<(), AppError>,
+) -> Result<String, AppError> {
+    let mut hasher = D::new();
+    let mut buffer = vec![0u8; CHUNK_SIZE];
+    let mut done = 0;
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(AppError::io("Falha ao ler o arquivo", e)),
+        };
+        hasher.update(&buffer[..read]);
+        done += read as u64;
+        on_progress(done, total)?;
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Lowercase hex digest of the file, read in 1 MiB chunks so multi-GB files
+/// never sit in memory. `on_progress` gets the bytes read and the file size;
+/// returning an error stops the read.
+pub(crate) fn hash_path(
+    path: &Path,
+    algorithm: HashAlgorithm,
+    mut on_progress: impl FnMut(u64, u64) -> Result<(), AppError>,
+) -> Result<String, AppError> {
+    let mut file = std::fs::File::open(path)
+        .map_err(|e| AppError::io(format!("Falha ao abrir {}", path.display()), e))?;
+    let total = file.metadata().map(|m| m.len()).unwrap_or(0);
+    let progress = &mut on_progress;
+    match algorithm {
+        HashAlgorithm::Md5 => digest::<md5::Md5>(&mut file, total, progress),
+        HashAlgorithm::Sha1 => digest::<sha1::Sha1>(&mut file, total, progress),
+        HashAlgorithm::Sha256 => digest::<sha2::Sha256>(&mut file, total, progress),
+    }
+}
+
+/// Accepts a bare digest or a `sha256sum`-style line (`<hash>  <file>`).
+fn normalize_expected(expected: &str) -> Result<(String, HashAlgorithm), AppError> {
+    let hash = expected
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    if !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(AppError::InvalidInput(
+            "Hash deve estar em hexadecimal".into(),
+        ));
+    }
+    let algorithm = HashAlgorithm::from_hex(&hash).ok_or_else(|| {
+        AppError::InvalidInput(
+            "Hash com tamanho inválido: use MD5 (32), SHA-1 (40) ou SHA-256 (64 caracteres)".into(),
+        )
+    })?;
+    Ok((hash, algorithm))
+}
+
+/// Runs the hash as a job, so large files show up in the task list and can be cancelled.
+fn hash_as_job(
+    app: &tauri::AppHandle,
+    path: &Path,
+    algorithm: HashAlgorithm,
+) -> Result<String, AppError> {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let job = Jobs::start(
+        app,
+        "hash_file",
+        format!("{} de {}", algorithm.label(), name),
+    );
+    let mut last_report = Instant::now();
+    let result = hash_path(path, algorithm, |done, total| {
+        job.check_cancelled()?;
+        // One event per chunk would flood the frontend on multi-GB files
+        if done == total || last_report.elapsed() >= PROGRESS_INTERVAL {
+            job.progress(done, total, name.as_str());
+            last_report = Instant::now();
+        }
+        Ok(())
+    });
+    job.finish(&result);
+    result
+}
+
+#[derive(serde::Serialize)]
+pub struct FileHash {
+    pub path: String,
+    pub algorithm: HashAlgorithm,
+    pub hash: String,
+    pub size: u64,
+}
+
+#[derive(serde::Serialize)]
+pub struct HashVerification {
+    pub algorithm: HashAlgorithm,
+    pub expected: String,
+    pub actual: String,
+    pub matches: bool,
+}
+
+#[tauri::command]
+pub async fn hash_file(
+    app: tauri::AppHandle,
+    path: String,
+    algorithm: HashAlgorithm,
+) -> Result<FileHash, AppError> {
+    crate::metrics::timed("hash_file", async move {
+        tauri::async_runtime::spawn_blocking(move || {
+            let file_path = Path::new(&path);
+            let hash = hash_as_job(&app, file_path, algorithm)?;
+            let size = std::fs::metadata(file_path).map(|m| m.len()).unwrap_or(0);
+            Ok(FileHash {
+                path,
+                algorithm,
+                hash,
+                size,
+            })
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao calcular o hash", e))?
+    })
+    .await
+}
+
+/// Compares the file against `expected`; the algorithm follows from its length.
+#[tauri::command]
+pub async fn verify_hash(
+    app: tauri::AppHandle,
+    path: String,
+    expected: String,
+) -> Result<HashVerification, AppError> {
+    crate::metrics::timed("verify_hash", async move {
+        let (expected, algorithm) = normalize_expected(&expected)?;
+        tauri::async_runtime::spawn_blocking(move || {
+            let actual = hash_as_job(&app, Path::new(&path), algorithm)?;
+            Ok(HashVerification {
+                algorithm,
+                matches: actual == expected,
+                expected,
+                actual,
+            })
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao verificar o hash", e))?
+    })
+    .await
+}
@@ -0,0 +1,350 @@
+// ── Images Module ──────────────────────────────────────────────
+use crate::error::AppError;
+use crate::jobs::Jobs;
+use crate::workers;
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::{CompressionType, FilterType as PngFilter, PngEncoder};
+use image::codecs::webp::WebPEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageDecoder, ImageEncoder, ImageReader};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+pub(crate) const DEFAULT_QUALITY: u8 = 85;
+
+#[derive(serde::Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageFormat {
+    Png,
+    Jpeg,
+    Webp,
+    Bmp,
+}
+
+/// Bounding box the image is shrunk to fit; the aspect ratio is kept.
+#[derive(serde::Deserialize, Clone, Copy, Default)]
+pub struct MaxDimensions {
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+}
+
+#[derive(serde::Serialize)]
+pub struct ConvertedImage {
+    pub output_path: String,
+    pub width: u32,
+    pub height: u32,
+    pub original_size: u64,
+    pub size: u64,
+}
+
+/// Decodes by content rather than extension, and applies the EXIF rotation so
+/// phone photos come out upright once the metadata is gone.
+pub(crate) fn load(path: &Path) -> Result<DynamicImage, AppError> {
+    let invalid = |e: image::ImageError| {
+        AppError::InvalidInput(format!("Imagem inválida: {}", path.display())).with_details(e)
+    };
+    let mut decoder = ImageReader::open(path)
+        .map_err(|e| AppError::io(format!("Falha ao abrir {}", path.display()), e))?
+        .with_guessed_format()
+        .map_err(|e| AppError::io(format!("Falha ao ler {}", path.display()), e))?
+        .into_decoder()
+        .map_err(invalid)?;
+    let orientation = decoder
+        .orientation()
+        .unwrap_or(image::metadata::Orientation::NoTransforms);
+    let mut image = DynamicImage::from_decoder(decoder).map_err(invalid)?;
+    image.apply_orientation(orientation);
+    Ok(image)
+}
+
+/// Only ever shrinks: an image already inside the box is returned as is.
+pub(crate) fn downscale(image: DynamicImage, max: MaxDimensions) -> DynamicImage {
+    let width = max.width.filter(|&w| w > 0).unwrap_or(u32::MAX);
+    let height = max.height.filter(|&h| h > 0).unwrap_or(u32::MAX);
+    if image.width() <= width && image.height() <= height {
+        return image;
+    }
+    image.resize(width, height, FilterType::Lanczos3)
+}
+
+/// JPEG has no transparency: transparent areas (logos) become white instead of black.
+fn flatten_on_white(image: &DynamicImage) -> image::RgbImage {
+    if !image.color().has_alpha() {
+        return image.to_rgb8();
+    }
+    let rgba = image.to_rgba8();
+    image::RgbImage::from_fn(rgba.width(), rgba.height(), |x, y| {
+        let [r, g, b, a] = rgba.get_pixel(x, y).0;
+        let blend = |c: u8| ((c as u16 * a as u16 + 255 * (255 - a as u16)) / 255) as u8;
+        image::Rgb([blend(r), blend(g), blend(b)])
+    })
+}
+
+/// `quality` (1-100) applies to JPEG; PNG and WEBP are lossless and BMP is uncompressed.
+pub(crate) fn encode(
+    image: &DynamicImage,
+    format: ImageFormat,
+    quality: u8,
+) -> Result<Vec<u8>, AppError> {
+    let mut bytes = Vec::new();
+    let result = match format {
+        ImageFormat::Jpeg => {
+            let rgb = flatten_on_white(image);
+            JpegEncoder::new_with_quality(&mut bytes, quality.clamp(1, 100)).write_image(
+                rgb.as_raw(),
+                rgb.width(),
+                rgb.height(),
+                image::ExtendedColorType::Rgb8,
+            )
+        }
+        ImageFormat::Png => {
+            PngEncoder::new_with_quality(&mut bytes, CompressionType::Best, PngFilter::Adaptive)
+                .write_image(
+                    image.as_bytes(),
+                    image.width(),
+                    image.height(),
+                    image.color().into(),
+                )
+        }
+        ImageFormat::Webp => {
+            let rgba = image.to_rgba8();
+            WebPEncoder::new_lossless(&mut bytes).write_image(
+                rgba.as_raw(),
+                rgba.width(),
+                rgba.height(),
+                image::ExtendedColorType::Rgba8,
+            )
+        }
+        ImageFormat::Bmp => {
+            let image = if image.color().has_alpha() {
+                DynamicImage::ImageRgba8(image.to_rgba8())
+            } else {
+                DynamicImage::ImageRgb8(image.to_rgb8())
+            };
+            image.write_to(
+                &mut std::io::Cursor::new(&mut bytes),
+                image::ImageFormat::Bmp,
+            )
+        }
+    };
+    result.map_err(|e| AppError::internal("Falha ao gerar imagem", e))?;
+    Ok(bytes)
+}
+
+fn convert_image_impl(
+    input: &Path,
+    output: &Path,
+    format: ImageFormat,
+    max_dimensions: MaxDimensions,
+    quality: u8,
+) -> Result<ConvertedImage, AppError> {
+    let original_size = std::fs::metadata(input)
+        .map_err(|e| AppError::io(format!("Falha ao abrir {}", input.display()), e))?
+        .len();
+    let image = downscale(load(input)?, max_dimensions);
+    let bytes = encode(&image, format, quality)?;
+    std::fs::write(output, &bytes).map_err(|e| AppError::io("Falha ao salvar imagem", e))?;
+
+    Ok(ConvertedImage {
+        output_path: output.to_string_lossy().to_string(),
+        width: image.width(),
+        height: image.height(),
+        original_size,
+        size: bytes.len() as u64,
+    })
+}
+
+/// Converts between PNG, JPEG, WEBP and BMP, optionally shrinking to fit
+/// `max_dimensions` for portals with upload limits. `input` and `output` may be
+/// the same file.
+#[tauri::command]
+pub async fn convert_image(
+    input: String,
+    output: String,
+    format: ImageFormat,
+    max_dimensions: Option<MaxDimensions>,
+    quality: Option<u8>,
+) -> Result<ConvertedImage, AppError> {
+    crate::metrics::timed("convert_image", async move {
+        tauri::async_runtime::spawn_blocking(move || {
+            convert_image_impl(
+                Path::new(&input),
+                Path::new(&output),
+                format,
+                max_dimensions.unwrap_or_default(),
+                quality.unwrap_or(DEFAULT_QUALITY),
+            )
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao converter imagem", e))?
+    })
+    .await
+}
+
+// ── Batch Compression ──────────────────────────────────────────
+
+#[derive(serde::Serialize)]
+pub struct CompressedImage {
+    pub input_path: String,
+    pub output_path: String,
+    pub original_size: u64,
+    /// `None` when this file failed; see `error`.
+    pub compressed_size: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+pub struct ImageCompressionReport {
+    pub images: Vec<CompressedImage>,
+    /// Totals over the images that succeeded.
+    pub original_total: u64,
+    pub compressed_total: u64,
+    pub saved_bytes: u64,
+}
+
+/// Recompressed in the format the file already has, detected by content.
+fn source_format(path: &Path) -> Result<ImageFormat, AppError> {
+    let format = ImageReader::open(path)
+        .and_then(|reader| reader.with_guessed_format())
+        .map_err(|e| AppError::io(format!("Falha ao abrir {}", path.display()), e))?
+        .format();
+    match format {
+        Some(image::ImageFormat::Jpeg) => Ok(ImageFormat::Jpeg),
+        Some(image::ImageFormat::Png) => Ok(ImageFormat::Png),
+        Some(image::ImageFormat::WebP) => Ok(ImageFormat::Webp),
+        Some(image::ImageFormat::Bmp) => Ok(ImageFormat::Bmp),
+        _ => Err(AppError::Unsupported(format!(
+            "Formato de imagem não suportado: {}",
+            path.display()
+        ))),
+    }
+}
+
+/// Returns the size of the result. When recompressing does not make the file
+/// smaller, the original is kept (and copied to `output` if that is elsewhere).
+fn compress_image_impl(
+    input: &Path,
+    output: &Path,
+    quality: u8,
+    max_width: Option<u32>,
+) -> Result<u64, AppError> {
+    let original_size = std::fs::metadata(input)
+        .map_err(|e| AppError::io(format!("Falha ao abrir {}", input.display()), e))?
+        .len();
+    let format = source_format(input)?;
+    let image = downscale(
+        load(input)?,
+        MaxDimensions {
+            width: max_width,
+            height: None,
+        },
+    );
+    let bytes = encode(&image, format, quality)?;
+
+    if bytes.len() as u64 >= original_size {
+        if input != output {
+            std::fs::copy(input, output).map_err(|e| AppError::io("Falha ao copiar imagem", e))?;
+        }
+        return Ok(original_size);
+    }
+
+    // Written aside first so a failure never leaves a truncated photo in place
+    let mut partial = output.as_os_str().to_owned();
+    partial.push(".partial");
+    let partial = PathBuf::from(partial);
+    std::fs::write(&partial, &bytes)
+        .and_then(|_| std::fs::rename(&partial, output))
+        .map_err(|e| {
+            let _ = std::fs::remove_file(&partial);
+            AppError::io("Falha ao salvar imagem", e)
+        })?;
+    Ok(bytes.len() as u64)
+}
+
+/// Runs as a job, several images at a time. Without `output_dir` the files are
+/// replaced in place; otherwise they are written there under the same name.
+pub(crate) fn compress_batch(
+    app: &tauri::AppHandle,
+    paths: Vec<String>,
+    quality: u8,
+    max_width: Option<u32>,
+    output_dir: Option<&Path>,
+) -> ImageCompressionReport {
+    let job = Jobs::start(app, "compress_images", "Comprimir imagens");
+    let total = paths.len() as u64;
+    let done = AtomicU64::new(0);
+
+    let images = workers::map(workers::concurrency(app), paths, |input_path| {
+        let input = Path::new(&input_path);
+        let output = match (output_dir, input.file_name()) {
+            (Some(output_dir), Some(name)) => output_dir.join(name),
+            _ => input.to_path_buf(),
+        };
+        let original_size = std::fs::metadata(input).map(|m| m.len()).unwrap_or(0);
+
+        let outcome = job
+            .check_cancelled()
+            .and_then(|_| compress_image_impl(input, &output, quality, max_width));
+        job.progress(
+            done.fetch_add(1, Ordering::Relaxed) + 1,
+            total,
+            input_path.as_str(),
+        );
+        CompressedImage {
+            output_path: output.to_string_lossy().to_string(),
+            input_path,
+            original_size,
+            compressed_size: outcome.as_ref().ok().copied(),
+            error: outcome.err().map(|e| e.to_string()),
+        }
+    });
+
+    let outcome = job.check_cancelled();
+    job.finish(&outcome);
+
+    let (original_total, compressed_total) = images
+        .iter()
+        .filter_map(|image| Some((image.original_size, image.compressed_size?)))
+        .fold((0, 0), |(original, compressed), (o, c)| {
+            (original + o, compressed + c)
+        });
+    ImageCompressionReport {
+        images,
+        original_total,
+        compressed_total,
+        saved_bytes: original_total.saturating_sub(compressed_total),
+    }
+}
+
+/// Recompresses photos several at a time, shrinking them to `max_width` if given.
+/// Without `output_dir` the files are replaced in place; otherwise they are written
+/// there under the same name. A file that fails does not stop the others.
+#[tauri::command]
+pub async fn compress_images(
+    app: tauri::AppHandle,
+    paths: Vec<String>,
+    quality: Option<u8>,
+    max_width: Option<u32>,
+    output_dir: Option<String>,
+) -> Result<ImageCompressionReport, AppError> {
+    crate::metrics::timed("compress_images", async move {
+        if paths.is_empty() {
+            return Err(AppError::InvalidInput(
+                "Selecione pelo menos 1 imagem.".into(),
+            ));
+        }
+        let output_dir = output_dir.map(PathBuf::from);
+        if let Some(output_dir) = &output_dir {
+            std::fs::create_dir_all(output_dir)
+                .map_err(|e| AppError::io("Falha ao criar a pasta de destino", e))?;
+        }
+        let quality = quality.unwrap_or(DEFAULT_QUALITY);
+
+        tauri::async_runtime::spawn_blocking(move || {
+            compress_batch(&app, paths, quality, max_width, output_dir.as_deref())
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao comprimir imagens", e))
+    })
+    .await
+}
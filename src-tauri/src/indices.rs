@@ -0,0 +1,420 @@
+// ── Economic Indices Module ────────────────────────────────────
+use crate::error::AppError;
+use chrono::{Datelike, Local, NaiveDate};
+use rusqlite::{params, Connection};
+use serde_json::Value;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::Manager;
+
+const SGS_URL: &str = "https://api.bcb.gov.br/dados/serie/bcdata.sgs.";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(20);
+/// The Banco Central publishes each month once, so a fetch a few times a day is
+/// enough to pick up the latest rate.
+const REFETCH_AFTER_HOURS: i64 = 6;
+/// Late payment fine for federal taxes (Lei 9.430/96, art. 61): 0,33% a day up to 20%.
+const FINE_PER_DAY: f64 = 0.33;
+const FINE_CAP: f64 = 20.0;
+/// Interest for the month the overdue federal tax is paid.
+const PAYMENT_MONTH_INTEREST: f64 = 1.0;
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum IndexKind {
+    /// SELIC accumulated in the month (SGS 4390).
+    Selic,
+    /// IPCA monthly variation (SGS 433).
+    Ipca,
+}
+
+impl IndexKind {
+    fn series(self) -> u32 {
+        match self {
+            IndexKind::Selic => 4390,
+            IndexKind::Ipca => 433,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            IndexKind::Selic => "SELIC",
+            IndexKind::Ipca => "IPCA",
+        }
+    }
+}
+
+#[derive(serde::Serialize, Clone)]
+pub struct IndexRate {
+    /// `YYYY-MM`.
+    pub month: String,
+    /// Percent for the month, e.g. 0.83.
+    pub rate: f64,
+}
+
+#[derive(serde::Serialize)]
+pub struct Correction {
+    pub index: IndexKind,
+    pub principal: f64,
+    pub days_late: i64,
+    /// Accumulated index over the period, in percent.
+    pub index_percent: f64,
+    /// Principal after IPCA correction; equals `principal` for SELIC, which is interest.
+    pub corrected_principal: f64,
+    pub correction: f64,
+    /// SELIC interest plus the extra `juros`, if any.
+    pub interest: f64,
+    pub fine_percent: f64,
+    pub fine: f64,
+    pub total: f64,
+    pub rates: Vec<IndexRate>,
+    /// Months in the period the Banco Central has not published yet.
+    pub missing_months: Vec<String>,
+}
+
+/// Local copy of the monthly series, so corrections work offline and the API is
+/// not queried for every calculation.
+pub struct Indices {
+    conn: Mutex<Connection>,
+}
+
+fn month_key(date: NaiveDate) -> String {
+    date.format("%Y-%m").to_string()
+}
+
+/// Months from `from` to `to`, both inclusive, as `YYYY-MM`.
+fn months_between(from: NaiveDate, to: NaiveDate) -> Vec<String> {
+    let mut months = Vec::new();
+    let (mut year, mut month) = (from.year(), from.month());
+    while (year, month) <= (to.year(), to.month()) {
+        months.push(format!("{:04}-{:02}", year, month));
+        (year, month) = if month == 12 {
+            (year + 1, 1)
+        } else {
+            (year, month + 1)
+        };
+    }
+    months
+}
+
+fn first_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap()
+}
+
+fn previous_month(date: NaiveDate) -> NaiveDate {
+    first_of_month(date).pred_opt().unwrap()
+}
+
+fn next_month(date: NaiveDate) -> NaiveDate {
+    let (year, month) = if date.month() == 12 {
+        (date.year() + 1, 1)
+    } else {
+        (date.year(), date.month() + 1)
+    };
+    NaiveDate::from_ymd_opt(year, month, 1).unwrap()
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate, AppError> {
+    NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+        .map_err(|_| AppError::InvalidInput(format!("Data inválida: {}", value)))
+}
+
+fn round_cents(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+/// SGS answers `[{"data": "01/01/2024", "valor": "0.97"}, ...]`.
+async fn fetch_series(
+    index: IndexKind,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<IndexRate>, AppError> {
+    let client = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .user_agent(concat!("AdcontecUtil/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .map_err(|e| AppError::internal("Falha ao criar cliente HTTP", e))?;
+    let url = format!(
+        "{}{}/dados?formato=json&dataInicial={}&dataFinal={}",
+        SGS_URL,
+        index.series(),
+        from.format("%d/%m/%Y"),
+        to.format("%d/%m/%Y")
+    );
+
+    let response = client.get(&url).send().await.map_err(|e| {
+        AppError::ServiceUnavailable(format!(
+            "Falha ao consultar {} no Banco Central",
+            index.name()
+        ))
+        .with_details(e)
+    })?;
+    let status = response.status();
+    let body = response.text().await.map_err(|e| {
+        AppError::ServiceUnavailable("Falha ao ler resposta do Banco Central".into())
+            .with_details(e)
+    })?;
+    // No data in the range comes back as 404
+    if status == reqwest::StatusCode::NOT_FOUND {
+        return Ok(Vec::new());
+    }
+    if !status.is_success() {
+        return Err(AppError::ServiceUnavailable(format!(
+            "Banco Central indisponível ({})",
+            status
+        ))
+        .with_details(body));
+    }
+
+    let items: Vec<Value> = serde_json::from_str(&body)
+        .map_err(|e| AppError::internal("Resposta inválida do Banco Central", e))?;
+    Ok(items
+        .iter()
+        .filter_map(|item| {
+            let date = NaiveDate::parse_from_str(item.get("data")?.as_str()?, "%d/%m/%Y").ok()?;
+            let rate = item.get("valor")?.as_str()?.trim().parse().ok()?;
+            Some(IndexRate {
+                month: month_key(date),
+                rate,
+            })
+        })
+        .collect())
+}
+
+impl Indices {
+    pub fn open(app: &tauri::AppHandle) -> Result<Self, String> {
+        let conn = crate::db::open(app)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS index_rates (
+                series TEXT NOT NULL,
+                month TEXT NOT NULL,
+                rate REAL NOT NULL,
+                PRIMARY KEY (series, month)
+            );
+            CREATE TABLE IF NOT EXISTS index_fetches (
+                series TEXT PRIMARY KEY,
+                fetched_at TEXT NOT NULL
+            );",
+        )
+        .map_err(|e| format!("Falha ao criar cache de índices: {}", e))?;
+
+        Ok(Indices {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    fn cached(&self, index: IndexKind, from: &str, to: &str) -> Result<Vec<IndexRate>, AppError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare(
+                "SELECT month, rate FROM index_rates
+                 WHERE series = ?1 AND month BETWEEN ?2 AND ?3 ORDER BY month",
+            )
+            .map_err(|e| AppError::internal("Falha ao consultar cache de índices", e))?;
+        let rows = stmt
+            .query_map(params![index.name(), from, to], |row| {
+                Ok(IndexRate {
+                    month: row.get(0)?,
+                    rate: row.get(1)?,
+                })
+            })
+            .map_err(|e| AppError::internal("Falha ao consultar cache de índices", e))?;
+        rows.collect::<Result<_, _>>()
+            .map_err(|e| AppError::internal("Falha ao ler cache de índices", e))
+    }
+
+    fn recently_fetched(&self, index: IndexKind) -> bool {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            "SELECT COUNT(*) > 0 FROM index_fetches
+             WHERE series = ?1 AND fetched_at >= datetime('now', ?2)",
+            params![index.name(), format!("-{} hours", REFETCH_AFTER_HOURS)],
+            |row| row.get(0),
+        )
+        .unwrap_or(false)
+    }
+
+    fn store(&self, index: IndexKind, rates: &[IndexRate]) -> Result<(), AppError> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn
+            .transaction()
+            .map_err(|e| AppError::internal("Falha ao salvar índices", e))?;
+        for rate in rates {
+            tx.execute(
+                "INSERT OR REPLACE INTO index_rates (series, month, rate) VALUES (?1, ?2, ?3)",
+                params![index.name(), rate.month, rate.rate],
+            )
+            .map_err(|e| AppError::internal("Falha ao salvar índices", e))?;
+        }
+        tx.execute(
+            "INSERT OR REPLACE INTO index_fetches (series, fetched_at) VALUES (?1, datetime('now'))",
+            params![index.name()],
+        )
+        .map_err(|e| AppError::internal("Falha ao salvar índices", e))?;
+        tx.commit()
+            .map_err(|e| AppError::internal("Falha ao salvar índices", e))
+    }
+}
+
+/// Monthly rates from `from` to `to`. Goes to the Banco Central when a month is
+/// missing from the cache or on `refresh`, except for the last couple of months
+/// if it was asked in the last hours, as those may simply not be published yet.
+/// If it is unreachable, whatever is cached is used.
+async fn load_rates(
+    indices: &Indices,
+    index: IndexKind,
+    from: NaiveDate,
+    to: NaiveDate,
+    refresh: bool,
+) -> Result<Vec<IndexRate>, AppError> {
+    let (first, last) = (month_key(from), month_key(to));
+    let cached = indices.cached(index, &first, &last)?;
+    let recent = month_key(Local::now().date_naive() - chrono::Duration::days(62));
+    let missing: Vec<String> = months_between(from, to)
+        .into_iter()
+        .filter(|month| !cached.iter().any(|rate| &rate.month == month))
+        .collect();
+    let only_unpublished = missing.iter().all(|month| *month >= recent);
+    if !refresh && (missing.is_empty() || only_unpublished && indices.recently_fetched(index)) {
+        return Ok(cached);
+    }
+
+    match fetch_series(index, first_of_month(from), to).await {
+        Ok(fetched) => {
+            indices.store(index, &fetched)?;
+            indices.cached(index, &first, &last)
+        }
+        Err(e) if cached.is_empty() => Err(e),
+        Err(_) => Ok(cached),
+    }
+}
+
+fn indices_state(app: &tauri::AppHandle) -> Result<tauri::State<'_, Indices>, AppError> {
+    app.try_state::<Indices>()
+        .ok_or_else(|| AppError::Unsupported("Índices econômicos indisponíveis".into()))
+}
+
+/// Monthly SELIC or IPCA between two dates (`YYYY-MM-DD`, `to` defaults to today).
+#[tauri::command]
+pub async fn get_index_rates(
+    app: tauri::AppHandle,
+    index: IndexKind,
+    from: String,
+    to: Option<String>,
+    refresh: Option<bool>,
+) -> Result<Vec<IndexRate>, AppError> {
+    crate::metrics::timed("get_index_rates", async move {
+        let from = parse_date(&from)?;
+        let to = match to {
+            Some(to) => parse_date(&to)?,
+            None => Local::now().date_naive(),
+        };
+        if from > to {
+            return Err(AppError::InvalidInput(
+                "A data inicial é posterior à final".into(),
+            ));
+        }
+        let indices = indices_state(&app)?;
+        load_rates(&indices, index, from, to, refresh.unwrap_or(false)).await
+    })
+    .await
+}
+
+/// Updates an overdue amount due on `from` and paid on `to` (`YYYY-MM-DD`).
+///
+/// With SELIC it follows the federal rule: interest is the SELIC of each month
+/// after the due date up to the month before payment, plus 1% for the payment
+/// month. With IPCA the principal is corrected by the months from the due date
+/// to the month before payment. `multa` is the fine in percent and defaults to
+/// the federal 0,33% a day capped at 20%; `juros` adds simple monthly interest in
+/// percent, pro rata by day, on top of the index.
+#[tauri::command]
+pub async fn calculate_correction(
+    app: tauri::AppHandle,
+    value: f64,
+    from: String,
+    to: String,
+    index: IndexKind,
+    multa: Option<f64>,
+    juros: Option<f64>,
+) -> Result<Correction, AppError> {
+    crate::metrics::timed("calculate_correction", async move {
+        if !value.is_finite() || value <= 0.0 {
+            return Err(AppError::InvalidInput("Valor inválido".into()));
+        }
+        let due = parse_date(&from)?;
+        let paid = parse_date(&to)?;
+        for (name, rate) in [("multa", multa), ("juros", juros)] {
+            if rate.is_some_and(|rate| !rate.is_finite() || rate < 0.0) {
+                return Err(AppError::InvalidInput(format!(
+                    "Percentual de {} inválido",
+                    name
+                )));
+            }
+        }
+
+        let days_late = (paid - due).num_days().max(0);
+        let same_month = (due.year(), due.month()) == (paid.year(), paid.month());
+        // Empty range when paid on time or within the due month
+        let period = match index {
+            IndexKind::Selic => (next_month(due), previous_month(paid)),
+            IndexKind::Ipca => (first_of_month(due), previous_month(paid)),
+        };
+        let expected = if days_late > 0 && period.0 <= period.1 {
+            months_between(period.0, period.1)
+        } else {
+            Vec::new()
+        };
+
+        let rates = if expected.is_empty() {
+            Vec::new()
+        } else {
+            let indices = indices_state(&app)?;
+            load_rates(&indices, index, period.0, period.1, false).await?
+        };
+        let missing_months: Vec<String> = expected
+            .into_iter()
+            .filter(|month| !rates.iter().any(|rate| &rate.month == month))
+            .collect();
+
+        let (index_percent, corrected_principal, index_interest) = match index {
+            IndexKind::Selic => {
+                let mut percent: f64 = rates.iter().map(|rate| rate.rate).sum();
+                if days_late > 0 && !same_month {
+                    percent += PAYMENT_MONTH_INTEREST;
+                }
+                (percent, value, value * percent / 100.0)
+            }
+            IndexKind::Ipca => {
+                let factor: f64 = rates.iter().map(|rate| 1.0 + rate.rate / 100.0).product();
+                ((factor - 1.0) * 100.0, value * factor, 0.0)
+            }
+        };
+        let corrected_principal = round_cents(corrected_principal);
+        let extra_interest =
+            corrected_principal * juros.unwrap_or(0.0) / 100.0 * days_late as f64 / 30.0;
+        let interest = round_cents(index_interest + extra_interest);
+
+        let fine_percent = if days_late == 0 {
+            0.0
+        } else {
+            multa.unwrap_or_else(|| (FINE_PER_DAY * days_late as f64).min(FINE_CAP))
+        };
+        let fine = round_cents(corrected_principal * fine_percent / 100.0);
+
+        Ok(Correction {
+            index,
+            principal: value,
+            days_late,
+            index_percent,
+            correction: round_cents(corrected_principal - value),
+            corrected_principal,
+            interest,
+            fine_percent,
+            fine,
+            total: round_cents(corrected_principal + interest + fine),
+            rates,
+            missing_months,
+        })
+    })
+    .await
+}
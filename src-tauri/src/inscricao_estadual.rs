@@ -0,0 +1,331 @@
+// ── Inscrição Estadual Module ──────────────────────────────────
+use crate::error::AppError;
+
+/// SEFAZ CadConsultaCadastro4 endpoints. UFs missing here do not offer the web
+/// service and are checked through the CCC portal instead.
+const CONSULTA_CADASTRO: &[(&str, &str)] = &[
+    (
+        "AM",
+        "https://nfe.sefaz.am.gov.br/services2/services/CadConsultaCadastro4",
+    ),
+    (
+        "BA",
+        "https://nfe.sefaz.ba.gov.br/webservices/CadConsultaCadastro4/CadConsultaCadastro4.asmx",
+    ),
+    (
+        "GO",
+        "https://nfe.sefaz.go.gov.br/nfe/services/CadConsultaCadastro4",
+    ),
+    (
+        "MG",
+        "https://nfe.fazenda.mg.gov.br/nfe2/services/CadConsultaCadastro4",
+    ),
+    ("MS", "https://nfe.sefaz.ms.gov.br/ws/CadConsultaCadastro4"),
+    (
+        "MT",
+        "https://nfe.sefaz.mt.gov.br/nfews/v2/services/CadConsultaCadastro4",
+    ),
+    (
+        "PE",
+        "https://nfe.sefaz.pe.gov.br/nfe-service/services/CadConsultaCadastro4",
+    ),
+    ("PR", "https://nfe.sefa.pr.gov.br/nfe/CadConsultaCadastro4"),
+    (
+        "RS",
+        "https://cad.sefazrs.rs.gov.br/ws/cadconsultacadastro/cadconsultacadastro4.asmx",
+    ),
+    (
+        "SP",
+        "https://nfe.fazenda.sp.gov.br/ws/cadconsultacadastro4.asmx",
+    ),
+    (
+        "AC",
+        "https://cad.svrs.rs.gov.br/ws/cadconsultacadastro/cadconsultacadastro4.asmx",
+    ),
+    (
+        "PB",
+        "https://cad.svrs.rs.gov.br/ws/cadconsultacadastro/cadconsultacadastro4.asmx",
+    ),
+    (
+        "RN",
+        "https://cad.svrs.rs.gov.br/ws/cadconsultacadastro/cadconsultacadastro4.asmx",
+    ),
+    (
+        "SC",
+        "https://cad.svrs.rs.gov.br/ws/cadconsultacadastro/cadconsultacadastro4.asmx",
+    ),
+];
+
+/// Cadastro Centralizado de Contribuintes: public consultation covering every UF.
+const CCC_URL: &str = "https://dfe-portal.svrs.rs.gov.br/Nfe/Ccc";
+
+const UFS: &[&str] = &[
+    "AC", "AL", "AM", "AP", "BA", "CE", "DF", "ES", "GO", "MA", "MG", "MS", "MT", "PA", "PB", "PE",
+    "PI", "PR", "RJ", "RN", "RO", "RR", "RS", "SC", "SE", "SP", "TO",
+];
+
+#[derive(serde::Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum IeCheckMode {
+    /// Answered by the SEFAZ web service.
+    Direct,
+    /// The CCC portal was opened for the user to finish the consultation.
+    Assisted,
+}
+
+#[derive(serde::Serialize)]
+pub struct IeRegistration {
+    pub ie: String,
+    pub uf: String,
+    pub habilitado: bool,
+    /// "Habilitado" or "Não habilitado".
+    pub situacao: String,
+    /// Regime de apuração as reported by the SEFAZ, e.g. "SIMPLES NACIONAL".
+    pub regime: String,
+    pub razao_social: String,
+    pub nome_fantasia: String,
+    pub cnae: String,
+    /// `YYYY-MM-DD`.
+    pub inicio_atividade: Option<String>,
+    pub ultima_situacao: Option<String>,
+    pub baixa: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+pub struct IeCheck {
+    pub cnpj: String,
+    pub uf: String,
+    pub mode: IeCheckMode,
+    /// Empty in assisted mode.
+    pub registrations: Vec<IeRegistration>,
+    /// Why the web service was not used, when it was not.
+    pub note: Option<String>,
+}
+
+fn endpoint(uf: &str) -> Option<&'static str> {
+    CONSULTA_CADASTRO
+        .iter()
+        .find(|(code, _)| *code == uf)
+        .map(|(_, url)| *url)
+}
+
+fn build_request(cnpj: &str, uf: &str) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?><soap12:Envelope xmlns:soap12="http://www.w3.org/2003/05/soap-envelope" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xmlns:xsd="http://www.w3.org/2001/XMLSchema"><soap12:Body><nfeDadosMsg xmlns="http://www.portalfiscal.inf.br/nfe/wsdl/CadConsultaCadastro4"><ConsCad xmlns="http://www.portalfiscal.inf.br/nfe" versao="2.00"><infCons><xServ>CONS-CAD</xServ><UF>{uf}</UF><CNPJ>{cnpj}</CNPJ></infCons></ConsCad></nfeDadosMsg></soap12:Body></soap12:Envelope>"#,
+        uf = uf,
+        cnpj = cnpj,
+    )
+}
+
+/// Every `<infCad>` block; companies with branches in the UF return several.
+fn cad_blocks(xml: &str) -> Vec<&str> {
+    let mut blocks = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find("<infCad>") {
+        let Some(end) = rest[start..].find("</infCad>") else {
+            break;
+        };
+        blocks.push(&rest[start..start + end]);
+        rest = &rest[start + end..];
+    }
+    blocks
+}
+
+fn parse_response(xml: &str) -> Result<Vec<IeRegistration>, AppError> {
+    let tag = |block: &str, name: &str| {
+        crate::nfe::extract_tag_content(block, name)
+            .unwrap_or_default()
+            .trim()
+            .to_string()
+    };
+    let date = |block: &str, name: &str| Some(tag(block, name)).filter(|value| !value.is_empty());
+
+    let cstat = tag(xml, "cStat");
+    match cstat.as_str() {
+        // 111: one registration, 112: several
+        "111" | "112" => {}
+        "259" | "264" => {
+            return Err(AppError::NotFound(
+                "CNPJ sem inscrição estadual na UF consultada".into(),
+            ))
+        }
+        _ => {
+            return Err(AppError::ServiceRejected(format!(
+                "SEFAZ: {} - {}",
+                cstat,
+                tag(xml, "xMotivo")
+            )))
+        }
+    }
+
+    Ok(cad_blocks(xml)
+        .into_iter()
+        .map(|block| {
+            let habilitado = tag(block, "cSit") == "1";
+            IeRegistration {
+                ie: tag(block, "IE"),
+                uf: tag(block, "UF"),
+                habilitado,
+                situacao: if habilitado {
+                    "Habilitado"
+                } else {
+                    "Não habilitado"
+                }
+                .into(),
+                regime: tag(block, "xRegApur"),
+                razao_social: tag(block, "xNome"),
+                nome_fantasia: tag(block, "xFant"),
+                cnae: tag(block, "CNAE"),
+                inicio_atividade: date(block, "dIniAtiv"),
+                ultima_situacao: date(block, "dUltSit"),
+                baixa: date(block, "dBaixa"),
+            }
+        })
+        .collect())
+}
+
+#[cfg(any(windows, target_os = "linux"))]
+async fn query_direct(
+    thumbprint: &str,
+    cnpj: &str,
+    uf: &str,
+    url: &str,
+) -> Result<Vec<IeRegistration>, AppError> {
+    let (mut pfx_bytes, password, _) = crate::nfe::export_cert_pfx(thumbprint)?;
+    let identity = reqwest::Identity::from_pkcs12_der(&pfx_bytes, &password)
+        .map_err(|e| AppError::internal("Falha ao criar identidade TLS", e));
+    pfx_bytes.fill(0);
+
+    let client = reqwest::Client::builder()
+        .identity(identity?)
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .map_err(|e| AppError::internal("Falha ao criar cliente HTTP", e))?;
+    let response = client
+        .post(url)
+        .header("Content-Type", "application/soap+xml; charset=utf-8")
+        .body(build_request(cnpj, uf))
+        .send()
+        .await
+        .map_err(|e| {
+            AppError::ServiceUnavailable(format!("Falha na comunicação com a SEFAZ-{}", uf))
+                .with_details(e)
+        })?;
+    let status = response.status();
+    let body = response.text().await.map_err(|e| {
+        AppError::ServiceUnavailable("Falha ao ler resposta da SEFAZ".into()).with_details(e)
+    })?;
+    if !status.is_success() {
+        return Err(AppError::ServiceUnavailable(format!(
+            "SEFAZ-{} retornou status {}",
+            uf, status
+        ))
+        .with_details(body.chars().take(500).collect::<String>()));
+    }
+    parse_response(&body)
+}
+
+#[cfg(not(any(windows, target_os = "linux")))]
+async fn query_direct(
+    _thumbprint: &str,
+    _cnpj: &str,
+    _uf: &str,
+    _url: &str,
+) -> Result<Vec<IeRegistration>, AppError> {
+    Err(AppError::Unsupported(
+        "Consulta direta disponível apenas no Windows e Linux".into(),
+    ))
+}
+
+/// Selects the UF and fills the CNPJ on the CCC form; the captcha is left to the user.
+fn build_assisted_script(cnpj: &str, uf: &str) -> String {
+    format!(
+        r#"(function() {{
+    'use strict';
+    var CNPJ = '{cnpj}';
+    var UF = '{uf}';
+    function fill() {{
+        document.querySelectorAll('select').forEach(function(el) {{
+            if (el.value === UF) return;
+            for (var i = 0; i < el.options.length; i++) {{
+                if (el.options[i].value === UF || el.options[i].text.trim() === UF) {{
+                    el.selectedIndex = i;
+                    el.dispatchEvent(new Event('change', {{ bubbles: true }}));
+                    return;
+                }}
+            }}
+        }});
+        document.querySelectorAll("input[name*='cnpj' i], input[id*='cnpj' i], input[id*='CodInscrMf' i]").forEach(function(el) {{
+            if (el.value) return;
+            el.value = CNPJ;
+            el.dispatchEvent(new Event('input', {{ bubbles: true }}));
+        }});
+    }}
+    setTimeout(fill, 500);
+    setTimeout(fill, 1500);
+}})();"#,
+        cnpj = cnpj,
+        uf = uf,
+    )
+}
+
+/// Situação and regime of the CNPJ's inscrições estaduais in `uf`. Uses the SEFAZ
+/// web service with the given e-CNPJ certificate where the UF offers one; without
+/// a certificate, in other UFs or when the service is down, opens the CCC portal
+/// prefilled and returns `mode: assisted`.
+#[tauri::command]
+pub async fn check_ie(
+    app: tauri::AppHandle,
+    cnpj: String,
+    uf: String,
+    thumbprint: Option<String>,
+) -> Result<IeCheck, AppError> {
+    crate::metrics::timed("check_ie", async move {
+        let cnpj = crate::clients::normalize_cnpj(&cnpj)?;
+        let uf = uf.trim().to_uppercase();
+        if !UFS.contains(&uf.as_str()) {
+            return Err(AppError::InvalidInput(format!("UF inválida: {}", uf)));
+        }
+
+        let note = match (endpoint(&uf), thumbprint.as_deref()) {
+            (Some(url), Some(thumbprint)) => {
+                match query_direct(thumbprint, &cnpj, &uf, url).await {
+                    Ok(registrations) => {
+                        return Ok(IeCheck {
+                            cnpj,
+                            uf,
+                            mode: IeCheckMode::Direct,
+                            registrations,
+                            note: None,
+                        })
+                    }
+                    Err(e @ (AppError::ServiceUnavailable(_) | AppError::Unsupported(_))) => {
+                        e.to_string()
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+            (Some(_), None) => "Selecione um certificado para a consulta direta".into(),
+            (None, _) => format!(
+                "SEFAZ-{} não oferece consulta cadastral por web service",
+                uf
+            ),
+        };
+
+        crate::portal::open_window(
+            &app,
+            "ie-ccc",
+            &format!("Consulta Cadastral (CCC) - {}", uf),
+            CCC_URL,
+            &build_assisted_script(&cnpj, &uf),
+        )?;
+        Ok(IeCheck {
+            cnpj,
+            uf,
+            mode: IeCheckMode::Assisted,
+            registrations: Vec::new(),
+            note: Some(note),
+        })
+    })
+    .await
+}
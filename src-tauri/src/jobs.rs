@@ -0,0 +1,233 @@
+// ── Jobs Module ────────────────────────────────────────────────
+use crate::error::AppError;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{Emitter, Manager};
+
+/// Finished jobs kept for the task list; older ones are dropped.
+const MAX_FINISHED_JOBS: usize = 50;
+
+#[derive(serde::Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Running,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+/// Sent as-is on the `job-progress` event whenever a job changes.
+#[derive(serde::Serialize, Clone)]
+pub struct Job {
+    pub id: u64,
+    /// Operation that registered the job, e.g. "merge_pdfs" or "zip".
+    pub kind: String,
+    pub title: String,
+    pub status: JobStatus,
+    pub completed: u64,
+    /// Unit depends on the job (files, bytes, operations); 0 while unknown.
+    pub total: u64,
+    /// Item being processed, or the error once the job failed.
+    pub message: String,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+}
+
+struct Entry {
+    job: Job,
+    cancel: Arc<AtomicBool>,
+}
+
+/// Long-running operations register here so the frontend has a single task
+/// list instead of one progress event per feature.
+#[derive(Default)]
+pub struct Jobs {
+    next_id: AtomicU64,
+    entries: Mutex<BTreeMap<u64, Entry>>,
+}
+
+impl Jobs {
+    pub fn start(app: &tauri::AppHandle, kind: &str, title: impl Into<String>) -> JobHandle {
+        let jobs = app.state::<Jobs>();
+        let id = jobs.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let cancel = Arc::new(AtomicBool::new(false));
+        let job = Job {
+            id,
+            kind: kind.to_string(),
+            title: title.into(),
+            status: JobStatus::Running,
+            completed: 0,
+            total: 0,
+            message: String::new(),
+            started_at: chrono::Local::now().to_rfc3339(),
+            finished_at: None,
+        };
+        jobs.entries.lock().unwrap().insert(
+            id,
+            Entry {
+                job: job.clone(),
+                cancel: cancel.clone(),
+            },
+        );
+        let _ = app.emit("job-progress", job);
+
+        JobHandle {
+            app: app.clone(),
+            id,
+            cancel,
+        }
+    }
+
+    /// Asks every running job to stop and waits up to `timeout` for them to
+    /// clean up (remove partial outputs) before the app exits.
+    pub fn cancel_all_and_wait(&self, timeout: Duration) {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let running = {
+                let entries = self.entries.lock().unwrap();
+                let mut running = 0;
+                for entry in entries.values() {
+                    if entry.job.status == JobStatus::Running {
+                        entry.cancel.store(true, Ordering::Relaxed);
+                        running += 1;
+                    }
+                }
+                running
+            };
+            if running == 0 || Instant::now() >= deadline {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+    }
+
+    fn prune(entries: &mut BTreeMap<u64, Entry>) {
+        let finished: Vec<u64> = entries
+            .values()
+            .filter(|entry| entry.job.status != JobStatus::Running)
+            .map(|entry| entry.job.id)
+            .collect();
+        // Ids grow with start time, so the first ones are the oldest
+        for id in finished
+            .iter()
+            .take(finished.len().saturating_sub(MAX_FINISHED_JOBS))
+        {
+            entries.remove(id);
+        }
+    }
+}
+
+/// Held by the running operation to report progress and notice cancellation.
+/// Dropping it without `finish` (an early return or a panic) marks the job failed.
+pub struct JobHandle {
+    app: tauri::AppHandle,
+    id: u64,
+    cancel: Arc<AtomicBool>,
+}
+
+impl JobHandle {
+    fn update(&self, change: impl FnOnce(&mut Job)) {
+        let jobs = self.app.state::<Jobs>();
+        let mut entries = jobs.entries.lock().unwrap();
+        let Some(entry) = entries.get_mut(&self.id) else {
+            return;
+        };
+        if entry.job.status != JobStatus::Running {
+            return;
+        }
+        change(&mut entry.job);
+        let job = entry.job.clone();
+        if job.status != JobStatus::Running {
+            Jobs::prune(&mut entries);
+        }
+        drop(entries);
+        let _ = self.app.emit("job-progress", job);
+    }
+
+    pub fn progress(&self, completed: u64, total: u64, message: impl Into<String>) {
+        let message = message.into();
+        self.update(|job| {
+            job.completed = completed;
+            job.total = total;
+            job.message = message;
+        });
+    }
+
+    /// Call between items; returns the error to stop with once `cancel_job` was called.
+    pub fn check_cancelled(&self) -> Result<(), AppError> {
+        if self.cancel.load(Ordering::Relaxed) {
+            Err(AppError::Cancelled("Operação cancelada".into()))
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn finish<T>(self, result: &Result<T, AppError>) {
+        self.update(|job| {
+            job.finished_at = Some(chrono::Local::now().to_rfc3339());
+            match result {
+                Ok(_) => {
+                    job.status = JobStatus::Completed;
+                    job.completed = job.total.max(job.completed);
+                    job.message.clear();
+                }
+                Err(AppError::Cancelled(_)) => job.status = JobStatus::Cancelled,
+                Err(e) => {
+                    job.status = JobStatus::Failed;
+                    job.message = e.to_string();
+                }
+            }
+        });
+    }
+}
+
+impl Drop for JobHandle {
+    fn drop(&mut self) {
+        self.update(|job| {
+            job.status = JobStatus::Failed;
+            job.message = "Operação interrompida".into();
+            job.finished_at = Some(chrono::Local::now().to_rfc3339());
+        });
+    }
+}
+
+/// Running jobs first, then the most recent.
+#[tauri::command]
+pub fn list_jobs(jobs: tauri::State<'_, Jobs>) -> Vec<Job> {
+    let mut list: Vec<Job> = jobs
+        .entries
+        .lock()
+        .unwrap()
+        .values()
+        .rev()
+        .map(|entry| entry.job.clone())
+        .collect();
+    list.sort_by_key(|job| job.status != JobStatus::Running);
+    list
+}
+
+#[tauri::command]
+pub fn get_job(jobs: tauri::State<'_, Jobs>, id: u64) -> Result<Job, AppError> {
+    jobs.entries
+        .lock()
+        .unwrap()
+        .get(&id)
+        .map(|entry| entry.job.clone())
+        .ok_or_else(|| AppError::NotFound("Tarefa não encontrada".into()))
+}
+
+/// Asks the job to stop; it reports `cancelled` once it reaches a safe point.
+#[tauri::command]
+pub fn cancel_job(jobs: tauri::State<'_, Jobs>, id: u64) -> Result<(), AppError> {
+    let entries = jobs.entries.lock().unwrap();
+    let entry = entries
+        .get(&id)
+        .ok_or_else(|| AppError::NotFound("Tarefa não encontrada".into()))?;
+    if entry.job.status != JobStatus::Running {
+        return Err(AppError::InvalidInput("A tarefa já foi finalizada".into()));
+    }
+    entry.cancel.store(true, Ordering::Relaxed);
+    Ok(())
+}
@@ -1,29 +1,194 @@
+mod app_log;
+mod archive;
+mod backup;
+mod barcode;
+mod boleto;
+mod browsers;
+mod cert_backup;
+mod cert_check;
+mod cert_defaults;
+mod cert_details;
+mod cert_duplicates;
+mod cert_passwords;
+mod cert_store;
 mod client_folders;
+mod clients;
+mod clipboard_history;
+mod cnpj;
+mod crash_report;
+mod db;
+mod deep_link;
+mod duplicates;
+mod email;
+mod encoding;
+mod error;
+mod favicons;
+mod fiscal_calendar;
+mod hashing;
+mod images;
+mod indices;
+mod inscricao_estadual;
+mod jobs;
+mod link_history;
+mod metrics;
+mod ncm;
 mod nfe;
+mod notes;
+mod ocr;
 mod pdf_utils;
-
-use std::{fs, sync::Mutex};
+mod pix;
+mod portal;
+mod printers;
+mod quick_links;
+mod reminders;
+mod renamer;
+mod scheduler;
+mod screenshot;
+mod search;
+mod settings;
+mod snippets;
+mod sped;
+mod sync;
+mod system_report;
+mod system_stats;
+mod text_diff;
+mod todos;
+mod updater;
+mod whatsapp;
+mod workers;
+mod xml_inbox;
+
+use error::AppError;
+use std::{
+    fs,
+    sync::atomic::{AtomicU64, Ordering},
+};
 use tauri::{
-    menu::{MenuBuilder, MenuItemBuilder},
+    menu::{Menu, MenuBuilder, MenuItemBuilder, SubmenuBuilder},
     tray::TrayIconBuilder,
-    Manager, WindowEvent,
+    Emitter, Listener, Manager, WindowEvent,
 };
 use tauri_plugin_autostart::MacosLauncher;
 use tauri_plugin_clipboard_manager;
 
-// ── Managed State ───────────────────────────────────────────────
-struct AppState {
-    movable_mode: Mutex<bool>,
+// ── Window Mode ─────────────────────────────────────────────────
+const MOVABLE_MODE_KEY: &str = "movable_mode";
+
+#[tauri::command]
+fn set_movable_mode(
+    settings: tauri::State<'_, settings::Settings>,
+    enabled: bool,
+) -> Result<(), AppError> {
+    Ok(settings.set(MOVABLE_MODE_KEY, enabled)?)
 }
 
+pub(crate) const WINDOW_GEOMETRY_KEY: &str = "window_geometry";
+const DEFAULT_WINDOW_SIZE: (f64, f64) = (420.0, 600.0);
+
+/// Moves and resizes arrive in bursts while dragging; only the last one is written.
+const GEOMETRY_SAVE_DELAY: std::time::Duration = std::time::Duration::from_millis(500);
+static GEOMETRY_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Clone, Copy, serde::Serialize, serde::Deserialize)]
+struct WindowGeometry {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+}
+
+fn movable_mode(app: &tauri::AppHandle) -> bool {
+    app.try_state::<settings::Settings>()
+        .and_then(|s| s.get::<bool>(MOVABLE_MODE_KEY))
+        .unwrap_or(false)
+}
+
+fn schedule_geometry_save(window: &tauri::Window) {
+    let app = window.app_handle();
+    if !movable_mode(app) {
+        return;
+    }
+    let (Ok(position), Ok(size)) = (window.outer_position(), window.inner_size()) else {
+        return;
+    };
+    // Minimizing reports a zero size, which must not overwrite the real one
+    if size.width == 0 || size.height == 0 {
+        return;
+    }
+
+    let geometry = WindowGeometry {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+    };
+    let generation = GEOMETRY_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let app = app.clone();
+
+    std::thread::spawn(move || {
+        std::thread::sleep(GEOMETRY_SAVE_DELAY);
+        if GEOMETRY_GENERATION.load(Ordering::SeqCst) == generation {
+            let _ = app
+                .state::<settings::Settings>()
+                .set(WINDOW_GEOMETRY_KEY, geometry);
+        }
+    });
+}
+
+fn restore_window_geometry(app: &tauri::AppHandle) {
+    if !movable_mode(app) {
+        return;
+    }
+    let Some(geometry) = app
+        .state::<settings::Settings>()
+        .get::<WindowGeometry>(WINDOW_GEOMETRY_KEY)
+    else {
+        return;
+    };
+    let Some(window) = app.get_webview_window("main") else {
+        return;
+    };
+
+    let _ = window.set_size(tauri::PhysicalSize::new(geometry.width, geometry.height));
+
+    // Skip positions left on a monitor that is no longer connected
+    let on_screen = window
+        .available_monitors()
+        .unwrap_or_default()
+        .iter()
+        .any(|monitor| {
+            let (pos, size) = (monitor.position(), monitor.size());
+            (pos.x..pos.x + size.width as i32).contains(&geometry.x)
+                && (pos.y..pos.y + size.height as i32).contains(&geometry.y)
+        });
+    if on_screen {
+        let _ = window.set_position(tauri::PhysicalPosition::new(geometry.x, geometry.y));
+    }
+}
+
+/// Forgets the saved geometry and brings the window back to its default size, centered.
 #[tauri::command]
-fn set_movable_mode(state: tauri::State<'_, AppState>, enabled: bool) {
-    *state.movable_mode.lock().unwrap() = enabled;
+fn reset_window_position(
+    app: tauri::AppHandle,
+    settings: tauri::State<'_, settings::Settings>,
+) -> Result<(), AppError> {
+    settings.set(WINDOW_GEOMETRY_KEY, serde_json::Value::Null)?;
+
+    if let Some(window) = app.get_webview_window("main") {
+        let (width, height) = DEFAULT_WINDOW_SIZE;
+        window
+            .set_size(tauri::LogicalSize::new(width, height))
+            .map_err(|e| AppError::internal("Falha ao redimensionar janela", e))?;
+        window
+            .center()
+            .map_err(|e| AppError::internal("Falha ao centralizar janela", e))?;
+    }
+    Ok(())
 }
 
 #[tauri::command]
-fn save_binary_file(output_path: String, bytes: Vec<u8>) -> Result<(), String> {
-    fs::write(output_path, bytes).map_err(|e| format!("Falha ao salvar arquivo: {}", e))
+fn save_binary_file(output_path: String, bytes: Vec<u8>) -> Result<(), AppError> {
+    fs::write(output_path, bytes).map_err(|e| AppError::io("Falha ao salvar arquivo", e))
 }
 
 // ── Certificate Types ───────────────────────────────────────────
@@ -33,22 +198,149 @@ pub struct CertInfo {
     issuer: String,
     not_after: String,
     thumbprint: String,
+    thumbprint_sha256: String,
     cnpj: String,
+    has_private_key: bool,
+    /// False for A3 tokens/smart cards and keys imported as non-exportable, which
+    /// cannot be used for the SEFAZ queries.
+    is_exportable: bool,
+    /// From the ICP-Brasil policy and otherName fields; `None` when not recognized.
+    cert_type: Option<cert_details::CertType>,
+}
+
+/// Narrows `get_certificates` on large stores; without it every certificate is listed.
+#[derive(serde::Deserialize)]
+#[serde(default)]
+struct CertificateFilter {
+    include_expired: bool,
+    /// Only certificates that can sign, hiding the chain and third-party certificates.
+    only_with_private_key: bool,
+    /// Part of the CNPJ, with or without punctuation.
+    cnpj: Option<String>,
+}
+
+impl Default for CertificateFilter {
+    fn default() -> Self {
+        Self {
+            include_expired: true,
+            only_with_private_key: false,
+            cnpj: None,
+        }
+    }
+}
+
+impl CertificateFilter {
+    /// Returns a predicate with the date and CNPJ digits worked out once.
+    fn matcher(&self) -> impl Fn(&CertInfo) -> bool + '_ {
+        // ISO dates compare correctly as strings; "N/A" is never treated as expired
+        let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+        let cnpj: String = self
+            .cnpj
+            .as_deref()
+            .unwrap_or_default()
+            .chars()
+            .filter(char::is_ascii_digit)
+            .collect();
+        move |cert: &CertInfo| {
+            (self.include_expired || cert.not_after == "N/A" || cert.not_after >= today)
+                && (!self.only_with_private_key || cert.has_private_key)
+                && (cnpj.is_empty() || cert.cnpj.contains(&cnpj))
+        }
+    }
 }
 
+/// Each matching certificate is also emitted to the calling window as a
+/// `certificate-found` event while the store is enumerated, so the list can fill
+/// in while A3 tokens are slowly read. Resolves with the full list at the end.
 #[tauri::command]
-fn get_certificates() -> Result<Vec<CertInfo>, String> {
-    certs_impl()
+async fn get_certificates(
+    window: tauri::Window,
+    filter: Option<CertificateFilter>,
+) -> Result<Vec<CertInfo>, AppError> {
+    metrics::timed("get_certificates", async move {
+        let filter = filter.unwrap_or_default();
+        tauri::async_runtime::spawn_blocking(move || {
+            let matches = filter.matcher();
+            let mut found = Vec::new();
+            enum_certs_impl(&mut |cert| {
+                if matches(&cert) {
+                    let _ = window.emit_to(window.label(), "certificate-found", &cert);
+                    found.push(cert);
+                }
+            })?;
+            Ok(found)
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao listar certificados", e))?
+    })
+    .await
+}
+
+/// Hex digits of a thumbprint in upper case, dropping colons and spaces.
+fn thumbprint_hex(thumbprint: &str) -> String {
+    thumbprint
+        .chars()
+        .filter(|c| c.is_ascii_hexdigit())
+        .collect::<String>()
+        .to_uppercase()
+}
+
+/// SHA-1 thumbprint, as the stores look certificates up by, for a thumbprint given
+/// as SHA-1 or SHA-256, with or without colons.
+pub(crate) fn sha1_thumbprint(thumbprint: &str) -> Result<String, AppError> {
+    let hex = thumbprint_hex(thumbprint);
+    match hex.len() {
+        40 => Ok(hex
+            .as_bytes()
+            .chunks(2)
+            .map(|pair| String::from_utf8_lossy(pair).to_string())
+            .collect::<Vec<_>>()
+            .join(":")),
+        64 => certs_impl()?
+            .into_iter()
+            .find(|cert| cert.thumbprint_sha256.replace(':', "") == hex)
+            .map(|cert| cert.thumbprint)
+            .ok_or_else(|| AppError::NotFound("Certificado não encontrado".into())),
+        _ => Err(AppError::InvalidInput(format!(
+            "Impressão digital inválida: {}",
+            thumbprint
+        ))),
+    }
 }
 
-// NOVO COMANDO: Excluir certificados
+/// Removes certificates given by SHA-1 or SHA-256 thumbprint, with the passwords
+/// saved for them.
 #[tauri::command]
-fn delete_certificates(thumbprints: Vec<String>) -> Result<(), String> {
-    delete_certs_impl(thumbprints)
+async fn delete_certificates(thumbprints: Vec<String>) -> Result<(), AppError> {
+    metrics::timed("delete_certificates", async move {
+        tauri::async_runtime::spawn_blocking(move || {
+            let wanted: Vec<String> = thumbprints.iter().map(|t| thumbprint_hex(t)).collect();
+            // A single listing resolves every SHA-256 thumbprint and gives both vault keys.
+            // Unknown or malformed thumbprints are skipped, like certificates already gone
+            let targets: Vec<CertInfo> = certs_impl()?
+                .into_iter()
+                .filter(|cert| {
+                    let sha1 = thumbprint_hex(&cert.thumbprint);
+                    let sha256 = thumbprint_hex(&cert.thumbprint_sha256);
+                    wanted.iter().any(|hex| *hex == sha1 || *hex == sha256)
+                })
+                .collect();
+            delete_certs_impl(targets.iter().map(|cert| cert.thumbprint.clone()).collect())?;
+            // A saved password is useless once its certificate is gone
+            for cert in &targets {
+                let _ = cert_passwords::delete_password(&cert.thumbprint);
+                let _ = cert_passwords::delete_password(&cert.thumbprint_sha256);
+            }
+            Ok(())
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao excluir certificados", e))?
+    })
+    .await
 }
 
 #[cfg(windows)]
-fn delete_certs_impl(thumbprints: Vec<String>) -> Result<(), String> {
+fn delete_certs_impl(thumbprints: Vec<String>) -> Result<(), AppError> {
     use std::ptr;
     use windows_sys::Win32::Security::Cryptography::*;
 
@@ -58,7 +350,9 @@ fn delete_certs_impl(thumbprints: Vec<String>) -> Result<(), String> {
         // Abre o store com permissão padrão (que permite exclusão no repositório do usuário)
         let store = CertOpenSystemStoreW(0, store_wide.as_ptr());
         if store.is_null() {
-            return Err("Falha ao abrir repositório de certificados".into());
+            return Err(AppError::Internal(
+                "Falha ao abrir repositório de certificados".into(),
+            ));
         }
 
         for thumb_str in thumbprints {
@@ -102,21 +396,235 @@ fn delete_certs_impl(thumbprints: Vec<String>) -> Result<(), String> {
 }
 
 #[cfg(not(windows))]
-fn delete_certs_impl(_thumbprints: Vec<String>) -> Result<(), String> {
-    Err("Exclusão de certificados disponível apenas no Windows".into())
+fn delete_certs_impl(_thumbprints: Vec<String>) -> Result<(), AppError> {
+    Err(AppError::Unsupported(
+        "Exclusão de certificados disponível apenas no Windows".into(),
+    ))
+}
+
+/// Saves the certificate with its private key as a .pfx protected by `password`,
+/// to move an A1 certificate to another machine without certmgr.
+#[tauri::command]
+async fn export_certificate_pfx(
+    thumbprint: String,
+    password: String,
+    output_path: String,
+) -> Result<(), AppError> {
+    metrics::timed("export_certificate_pfx", async move {
+        if password.is_empty() {
+            return Err(AppError::InvalidInput(
+                "Informe uma senha para proteger o arquivo PFX".into(),
+            ));
+        }
+        tauri::async_runtime::spawn_blocking(move || {
+            export_pfx_impl(&thumbprint, &password, std::path::Path::new(&output_path))
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao exportar certificado", e))?
+    })
+    .await
+}
+
+#[cfg(any(windows, target_os = "linux"))]
+fn export_pfx_impl(
+    thumbprint: &str,
+    password: &str,
+    output_path: &std::path::Path,
+) -> Result<(), AppError> {
+    let (mut pfx, _) = nfe::export_cert_pfx_with_password(thumbprint, password)?;
+    let written =
+        fs::write(output_path, &pfx).map_err(|e| AppError::io("Falha ao salvar arquivo PFX", e));
+    // The key material should not linger in memory
+    pfx.fill(0);
+    written
+}
+
+#[cfg(not(any(windows, target_os = "linux")))]
+fn export_pfx_impl(
+    _thumbprint: &str,
+    _password: &str,
+    _output_path: &std::path::Path,
+) -> Result<(), AppError> {
+    Err(AppError::Unsupported(
+        "Exportação de certificados disponível apenas no Windows e Linux".into(),
+    ))
+}
+
+/// Installs a .pfx (certificate and private key) into the current user's personal
+/// store, where `get_certificates` lists it. Intermediate certificates in the file
+/// go to the user's intermediate store so the chain validates. With
+/// `remember_password`, the password is kept in the credential vault.
+#[tauri::command]
+async fn install_certificate(
+    path: String,
+    password: String,
+    remember_password: Option<bool>,
+) -> Result<CertInfo, AppError> {
+    metrics::timed("install_certificate", async move {
+        tauri::async_runtime::spawn_blocking(move || {
+            let mut pfx =
+                fs::read(&path).map_err(|e| AppError::io("Falha ao ler arquivo PFX", e))?;
+            let installed = install_pfx_impl(&mut pfx, &password);
+            pfx.fill(0);
+            let installed = installed?;
+            // The certificate is already installed; a failed save only means asking again.
+            // Both thumbprints are keys, since callers may know the file by either.
+            if remember_password.unwrap_or(false) {
+                for thumbprint in [&installed.thumbprint, &installed.thumbprint_sha256] {
+                    if let Err(e) = cert_passwords::store_password(thumbprint, &password) {
+                        app_log::error(e);
+                    }
+                }
+            }
+            Ok(installed)
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao instalar certificado", e))?
+    })
+    .await
 }
 
 #[cfg(windows)]
-fn certs_impl() -> Result<Vec<CertInfo>, String> {
+fn install_pfx_impl(pfx: &mut [u8], password: &str) -> Result<CertInfo, AppError> {
+    use windows_sys::Win32::Security::Cryptography::*;
+
+    const ERROR_INVALID_PASSWORD: i32 = 86;
+
+    let blob = CRYPT_INTEGER_BLOB {
+        cbData: pfx.len() as u32,
+        pbData: pfx.as_mut_ptr(),
+    };
+    let password_wide: Vec<u16> = password.encode_utf16().chain(std::iter::once(0)).collect();
+
+    unsafe {
+        if PFXIsPFXBlob(&blob) == 0 {
+            return Err(AppError::InvalidInput(
+                "O arquivo não é um certificado PFX válido".into(),
+            ));
+        }
+        // Exportable, so it can be moved again later with `export_certificate_pfx`
+        let pfx_store = PFXImportCertStore(
+            &blob,
+            password_wide.as_ptr(),
+            CRYPT_USER_KEYSET | CRYPT_EXPORTABLE,
+        );
+        if pfx_store.is_null() {
+            let error = std::io::Error::last_os_error();
+            if error.raw_os_error() == Some(ERROR_INVALID_PASSWORD) {
+                return Err(AppError::InvalidInput(
+                    "Senha do certificado incorreta".into(),
+                ));
+            }
+            return Err(AppError::internal("Falha ao importar certificado", error));
+        }
+
+        let my_wide: Vec<u16> = "MY\0".encode_utf16().collect();
+        let ca_wide: Vec<u16> = "CA\0".encode_utf16().collect();
+        let my_store = CertOpenSystemStoreW(0, my_wide.as_ptr());
+        let ca_store = CertOpenSystemStoreW(0, ca_wide.as_ptr());
+        let close = || {
+            CertCloseStore(pfx_store, 0);
+            if !my_store.is_null() {
+                CertCloseStore(my_store, 0);
+            }
+            if !ca_store.is_null() {
+                CertCloseStore(ca_store, 0);
+            }
+        };
+        if my_store.is_null() {
+            close();
+            return Err(AppError::Internal(
+                "Falha ao abrir repositório de certificados".into(),
+            ));
+        }
+
+        let mut installed: Option<CertInfo> = None;
+        let mut prev: *const CERT_CONTEXT = std::ptr::null();
+        loop {
+            let cert = CertEnumCertificatesInStore(pfx_store, prev);
+            if cert.is_null() {
+                break;
+            }
+            prev = cert;
+
+            // Only the certificate that owns the private key is the user's own
+            if !cert_has_private_key(cert) {
+                if !ca_store.is_null() {
+                    CertAddCertificateContextToStore(
+                        ca_store,
+                        cert,
+                        CERT_STORE_ADD_USE_EXISTING,
+                        std::ptr::null_mut(),
+                    );
+                }
+                continue;
+            }
+            if installed.is_some() {
+                continue;
+            }
+
+            if CertAddCertificateContextToStore(
+                my_store,
+                cert,
+                CERT_STORE_ADD_REPLACE_EXISTING,
+                std::ptr::null_mut(),
+            ) == 0
+            {
+                let error = std::io::Error::last_os_error();
+                CertFreeCertificateContext(cert);
+                close();
+                return Err(AppError::internal("Falha ao instalar certificado", error));
+            }
+            let subject = cert_name_string(cert, 0);
+            let rdn_subject = cert_rdn_string(cert);
+            installed = Some(CertInfo {
+                issuer: cert_name_string(cert, CERT_NAME_ISSUER_FLAG),
+                not_after: filetime_to_iso((*(*cert).pCertInfo).NotAfter),
+                thumbprint: cert_thumbprint(cert),
+                thumbprint_sha256: cert_thumbprint_sha256(cert),
+                cnpj: extract_cnpj_from_strings(&subject, &rdn_subject),
+                subject,
+                has_private_key: true,
+                is_exportable: true,
+                cert_type: cert_type(cert),
+            });
+        }
+        close();
+
+        installed.ok_or_else(|| {
+            AppError::InvalidInput("O arquivo PFX não contém uma chave privada".into())
+        })
+    }
+}
+
+#[cfg(not(windows))]
+fn install_pfx_impl(_pfx: &mut [u8], _password: &str) -> Result<CertInfo, AppError> {
+    Err(AppError::Unsupported(
+        "Instalação de certificados disponível apenas no Windows".into(),
+    ))
+}
+
+#[cfg(windows)]
+fn certs_impl() -> Result<Vec<CertInfo>, AppError> {
+    let mut certs = Vec::new();
+    enum_certs_impl(&mut |cert| certs.push(cert))?;
+    Ok(certs)
+}
+
+/// Hands each certificate over as soon as it is read; with A3 tokens plugged in
+/// every step of the enumeration can take seconds.
+#[cfg(windows)]
+fn enum_certs_impl(on_found: &mut dyn FnMut(CertInfo)) -> Result<(), AppError> {
     use windows_sys::Win32::Security::Cryptography::*;
 
-    let mut results = Vec::new();
     let store_wide: Vec<u16> = "MY\0".encode_utf16().collect();
 
     unsafe {
         let store = CertOpenSystemStoreW(0, store_wide.as_ptr());
         if store.is_null() {
-            return Err("Falha ao abrir repositório de certificados".into());
+            return Err(AppError::Internal(
+                "Falha ao abrir repositório de certificados".into(),
+            ));
         }
 
         let mut prev: *const CERT_CONTEXT = std::ptr::null();
@@ -133,14 +641,21 @@ fn certs_impl() -> Result<Vec<CertInfo>, String> {
             let info = &*(*cert).pCertInfo;
             let not_after = filetime_to_iso(info.NotAfter);
             let thumbprint = cert_thumbprint(cert);
+            let thumbprint_sha256 = cert_thumbprint_sha256(cert);
             let cnpj = extract_cnpj_from_strings(&subject, &rdn_subject);
+            let has_private_key = cert_has_private_key(cert);
+            let is_exportable = has_private_key && cert_key_is_exportable(cert);
 
-            results.push(CertInfo {
+            on_found(CertInfo {
                 subject,
                 issuer,
                 not_after,
                 thumbprint,
+                thumbprint_sha256,
                 cnpj,
+                has_private_key,
+                is_exportable,
+                cert_type: cert_type(cert),
             });
 
             prev = cert;
@@ -149,7 +664,89 @@ fn certs_impl() -> Result<Vec<CertInfo>, String> {
         let _ = CertCloseStore(store, 0);
     }
 
-    Ok(results)
+    Ok(())
+}
+
+/// Whether a private key is linked to the certificate, in software (A1) or on a
+/// token (A3). Only the size is queried, so the token PIN is never asked for.
+#[cfg(windows)]
+unsafe fn cert_has_private_key(
+    cert: *const windows_sys::Win32::Security::Cryptography::CERT_CONTEXT,
+) -> bool {
+    use windows_sys::Win32::Security::Cryptography::*;
+
+    let mut size: u32 = 0;
+    CertGetCertificateContextProperty(
+        cert,
+        CERT_KEY_PROV_INFO_PROP_ID,
+        std::ptr::null_mut(),
+        &mut size,
+    ) != 0
+}
+
+/// Whether the private key's export policy allows the PFX export used for the
+/// SEFAZ queries. Acquired silently, so a token never prompts for its PIN; a token
+/// that is not plugged in fails here and counts as not exportable.
+#[cfg(windows)]
+unsafe fn cert_key_is_exportable(
+    cert: *const windows_sys::Win32::Security::Cryptography::CERT_CONTEXT,
+) -> bool {
+    use windows_sys::Win32::Security::Cryptography::*;
+
+    let mut handle: HCRYPTPROV_OR_NCRYPT_KEY_HANDLE = 0;
+    let mut key_spec: CERT_KEY_SPEC = 0;
+    let mut must_free = 0;
+    if CryptAcquireCertificatePrivateKey(
+        cert,
+        CRYPT_ACQUIRE_SILENT_FLAG | CRYPT_ACQUIRE_ALLOW_NCRYPT_KEY_FLAG,
+        std::ptr::null(),
+        &mut handle,
+        &mut key_spec,
+        &mut must_free,
+    ) == 0
+    {
+        return false;
+    }
+
+    let exportable = if key_spec == CERT_NCRYPT_KEY_SPEC {
+        let mut policy: u32 = 0;
+        let mut size: u32 = 0;
+        NCryptGetProperty(
+            handle,
+            NCRYPT_EXPORT_POLICY_PROPERTY,
+            &mut policy as *mut u32 as *mut u8,
+            std::mem::size_of::<u32>() as u32,
+            &mut size,
+            0,
+        ) == 0
+            && policy & (NCRYPT_ALLOW_EXPORT_FLAG | NCRYPT_ALLOW_PLAINTEXT_EXPORT_FLAG) != 0
+    } else {
+        // Legacy CryptoAPI provider
+        let mut key: usize = 0;
+        let mut permissions: u32 = 0;
+        let mut size = std::mem::size_of::<u32>() as u32;
+        let ok = CryptGetUserKey(handle, key_spec, &mut key) != 0
+            && CryptGetKeyParam(
+                key,
+                KP_PERMISSIONS,
+                &mut permissions as *mut u32 as *mut u8,
+                &mut size,
+                0,
+            ) != 0;
+        if key != 0 {
+            CryptDestroyKey(key);
+        }
+        ok && permissions & CRYPT_EXPORT != 0
+    };
+
+    if must_free != 0 {
+        if key_spec == CERT_NCRYPT_KEY_SPEC {
+            NCryptFreeObject(handle);
+        } else {
+            CryptReleaseContext(handle, 0);
+        }
+    }
+    exportable
 }
 
 #[cfg(windows)]
@@ -198,6 +795,23 @@ fn filetime_to_iso(ft: windows_sys::Win32::Foundation::FILETIME) -> String {
     format!("{:04}-{:02}-{:02}", st.wYear, st.wMonth, st.wDay)
 }
 
+/// SHA-256 of the encoded certificate; Windows only caches the SHA-1 as a property.
+#[cfg(windows)]
+unsafe fn cert_thumbprint_sha256(
+    cert: *const windows_sys::Win32::Security::Cryptography::CERT_CONTEXT,
+) -> String {
+    let der = std::slice::from_raw_parts((*cert).pbCertEncoded, (*cert).cbCertEncoded as usize);
+    cert_details::der_thumbprint_sha256(der)
+}
+
+#[cfg(windows)]
+unsafe fn cert_type(
+    cert: *const windows_sys::Win32::Security::Cryptography::CERT_CONTEXT,
+) -> Option<cert_details::CertType> {
+    let der = std::slice::from_raw_parts((*cert).pbCertEncoded, (*cert).cbCertEncoded as usize);
+    cert_details::certificate_type(der)
+}
+
 #[cfg(windows)]
 unsafe fn cert_thumbprint(
     cert: *const windows_sys::Win32::Security::Cryptography::CERT_CONTEXT,
@@ -225,9 +839,91 @@ unsafe fn cert_thumbprint(
         .join(":")
 }
 
+/// Identities (certificate plus private key) in the user's keychains, which is
+/// where Keychain Access puts an imported .pfx.
+#[cfg(target_os = "macos")]
+fn certs_impl() -> Result<Vec<CertInfo>, AppError> {
+    use security_framework::item::{ItemClass, ItemSearchOptions, Limit, Reference, SearchResult};
+
+    /// errSecItemNotFound: the keychain has no identities.
+    const ITEM_NOT_FOUND: i32 = -25300;
+
+    let items = match ItemSearchOptions::new()
+        .class(ItemClass::identity())
+        .load_refs(true)
+        .limit(Limit::All)
+        .search()
+    {
+        Ok(items) => items,
+        Err(e) if e.code() == ITEM_NOT_FOUND => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(AppError::Internal("Falha ao abrir o Keychain".into()).with_details(e))
+        }
+    };
+
+    let mut results = Vec::new();
+    for item in items {
+        let SearchResult::Ref(Reference::Identity(identity)) = item else {
+            continue;
+        };
+        let Ok(cert) = identity.certificate() else {
+            continue;
+        };
+        // The PFX export used by the SEFAZ queries is not implemented for the keychain
+        if let Some(info) = cert_info_from_der(&cert.to_der(), true, false) {
+            results.push(info);
+        }
+    }
+    Ok(results)
+}
+
+/// Builds the same fields the Windows store gives: simple names (the CN) for subject
+/// and issuer, expiry date and an SHA-1 thumbprint.
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+fn cert_info_from_der(der: &[u8], has_private_key: bool, is_exportable: bool) -> Option<CertInfo> {
+    use cert_details::simple_name;
+
+    let (_, cert) = x509_parser::parse_x509_certificate(der).ok()?;
+    let subject = simple_name(cert.subject());
+    let rdn_subject = cert.subject().to_string();
+    let not_after = chrono::DateTime::from_timestamp(cert.validity().not_after.timestamp(), 0)
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|| "N/A".to_string());
+    let thumbprint = cert_details::der_thumbprint(der);
+    let thumbprint_sha256 = cert_details::der_thumbprint_sha256(der);
+
+    Some(CertInfo {
+        issuer: simple_name(cert.issuer()),
+        not_after,
+        thumbprint,
+        thumbprint_sha256,
+        cnpj: extract_cnpj_from_strings(&subject, &rdn_subject),
+        subject,
+        has_private_key,
+        is_exportable,
+        cert_type: cert_details::certificate_type(der),
+    })
+}
+
+/// Certificates from the store chosen with `set_certificate_store`.
+#[cfg(target_os = "linux")]
+fn certs_impl() -> Result<Vec<CertInfo>, AppError> {
+    cert_store::list()
+}
+
+/// Keychains and the Linux stores are read in one go, so every certificate
+/// arrives at the end.
 #[cfg(not(windows))]
-fn certs_impl() -> Result<Vec<CertInfo>, String> {
-    Err("Listagem de certificados disponível apenas no Windows".into())
+fn enum_certs_impl(on_found: &mut dyn FnMut(CertInfo)) -> Result<(), AppError> {
+    certs_impl()?.into_iter().for_each(on_found);
+    Ok(())
+}
+
+#[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
+fn certs_impl() -> Result<Vec<CertInfo>, AppError> {
+    Err(AppError::Unsupported(
+        "Listagem de certificados indisponível neste sistema".into(),
+    ))
 }
 
 // ── CNPJ Extraction from Certificate ────────────────────────────
@@ -292,14 +988,88 @@ fn find_cnpj_in_string(s: &str) -> Option<String> {
 
 // ── Screen Capture ──────────────────────────────────────────────
 #[tauri::command]
-fn start_screen_capture() -> Result<(), String> {
-    screen_capture_impl()
+fn start_screen_capture(app: tauri::AppHandle) -> Result<(), AppError> {
+    screenshot::capture_current_screen(&app)
+}
+
+// ── Link Schemes ───────────────────────────────────────────────
+const ALLOWED_LINK_SCHEMES_KEY: &str = "allowed_link_schemes";
+/// Besides http/https, which are always accepted.
+const DEFAULT_EXTRA_LINK_SCHEMES: [&str; 4] = ["mailto", "tel", "whatsapp", "ms-excel"];
+
+fn normalize_scheme(scheme: &str) -> String {
+    scheme.trim().trim_end_matches(':').to_ascii_lowercase()
+}
+
+fn is_valid_scheme(scheme: &str) -> bool {
+    let mut chars = scheme.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+}
+
+fn allowed_link_schemes(settings: &settings::Settings) -> Vec<String> {
+    settings
+        .get::<Vec<String>>(ALLOWED_LINK_SCHEMES_KEY)
+        .unwrap_or_else(|| {
+            DEFAULT_EXTRA_LINK_SCHEMES
+                .iter()
+                .map(|s| s.to_string())
+                .collect()
+        })
+        .iter()
+        .map(|s| normalize_scheme(s))
+        .collect()
+}
+
+#[tauri::command]
+fn get_allowed_link_schemes(settings: tauri::State<'_, settings::Settings>) -> Vec<String> {
+    allowed_link_schemes(&settings)
+}
+
+#[tauri::command]
+fn set_allowed_link_schemes(
+    settings: tauri::State<'_, settings::Settings>,
+    schemes: Vec<String>,
+) -> Result<(), AppError> {
+    let mut normalized: Vec<String> = Vec::new();
+    for scheme in schemes.iter().map(|s| normalize_scheme(s)) {
+        if scheme.is_empty() || scheme == "http" || scheme == "https" {
+            continue;
+        }
+        if !is_valid_scheme(&scheme) {
+            return Err(AppError::InvalidInput(format!(
+                "Esquema de link inválido: {}",
+                scheme
+            )));
+        }
+        if !normalized.contains(&scheme) {
+            normalized.push(scheme);
+        }
+    }
+    Ok(settings.set(ALLOWED_LINK_SCHEMES_KEY, normalized)?)
 }
 
+/// `browser`/`profile` pick a specific browser and profile (e.g. a client's Chrome
+/// profile); without them the link goes to the system default browser. Schemes other
+/// than http/https must be allowed in settings and always go to their default handler.
 #[tauri::command]
-fn open_external_link(url: String, mode: Option<String>) -> Result<(), String> {
-    if !url.starts_with("http://") && !url.starts_with("https://") {
-        return Err("URL inválida: use http:// ou https://".into());
+fn open_external_link(
+    app: tauri::AppHandle,
+    url: String,
+    mode: Option<String>,
+    browser: Option<browsers::Browser>,
+    profile: Option<String>,
+) -> Result<(), AppError> {
+    let url = url.trim().to_string();
+    let scheme = tauri::Url::parse(&url)
+        .map(|parsed| parsed.scheme().to_string())
+        .map_err(|_| AppError::InvalidInput("URL inválida".into()))?;
+    let is_web = scheme == "http" || scheme == "https";
+    if !is_web && !allowed_link_schemes(&app.state()).contains(&scheme) {
+        return Err(AppError::AccessDenied(format!(
+            "Links \"{}:\" não são permitidos. Adicione o esquema nas configurações.",
+            scheme
+        )));
     }
 
     let selected_mode = mode
@@ -307,44 +1077,36 @@ fn open_external_link(url: String, mode: Option<String>) -> Result<(), String> {
         .unwrap_or("normal")
         .trim()
         .to_ascii_lowercase();
-
-    match selected_mode.as_str() {
-        "incognito" | "private" => open_link_incognito_impl(&url),
-        _ => open_link_normal_impl(&url),
+    let private = is_web && matches!(selected_mode.as_str(), "incognito" | "private");
+    let browser = browser.filter(|_| is_web);
+    let profile = profile.filter(|_| is_web);
+
+    match browser {
+        Some(browser) => browsers::open_in_browser(browser, profile.as_deref(), private, &url)?,
+        None if profile.is_some() => {
+            return Err(AppError::InvalidInput(
+                "Informe o navegador do perfil selecionado".into(),
+            ))
+        }
+        None if private => open_link_incognito_impl(&url)?,
+        None => open_link_normal_impl(&url)?,
     }
-}
 
-#[cfg(windows)]
-fn open_link_normal_impl(url: &str) -> Result<(), String> {
-    std::process::Command::new("cmd")
-        .args(["/C", "start", "", url])
-        .spawn()
-        .map_err(|e| format!("Falha ao abrir link: {}", e))?;
+    if let Some(history) = app.try_state::<link_history::LinkHistory>() {
+        let mode = if private { "incognito" } else { "normal" };
+        let browser = browser.map(browsers::Browser::id);
+        if let Err(e) = history.record(&url, &scheme, mode, browser, profile.as_deref()) {
+            app_log::error(e);
+        }
+    }
     Ok(())
 }
 
-#[cfg(not(windows))]
-fn open_link_normal_impl(url: &str) -> Result<(), String> {
-    #[cfg(target_os = "macos")]
-    {
-        std::process::Command::new("open")
-            .arg(url)
-            .spawn()
-            .map_err(|e| format!("Falha ao abrir link: {}", e))?;
-        return Ok(());
-    }
-
-    #[cfg(all(unix, not(target_os = "macos")))]
-    {
-        std::process::Command::new("xdg-open")
-            .arg(url)
-            .spawn()
-            .map_err(|e| format!("Falha ao abrir link: {}", e))?;
-        return Ok(());
-    }
-
-    #[allow(unreachable_code)]
-    Err("Abertura de links não suportada neste sistema".into())
+/// Hands the link to its default handler (ShellExecute on Windows) without a shell
+/// in between, so characters like `&` in the URL are not interpreted.
+fn open_link_normal_impl(url: &str) -> Result<(), AppError> {
+    tauri_plugin_opener::open_url(url, None::<&str>)
+        .map_err(|e| AppError::internal("Falha ao abrir link", e))
 }
 
 #[cfg(windows)]
@@ -584,52 +1346,533 @@ fn open_link_incognito_impl(url: &str) -> Result<(), String> {
     Err("Não foi possível abrir em modo privado no sistema atual".into())
 }
 
+// ── System Tray Menu ────────────────────────────────────────────
+const TRAY_ID: &str = "main";
+const CLIPBOARD_MENU_PREFIX: &str = "clip:";
+const QUICK_LINK_MENU_PREFIX: &str = "link:";
+const QUICK_LINK_INCOGNITO_MENU_PREFIX: &str = "link-incognito:";
+
+struct QuickLink {
+    id: i64,
+    title: String,
+    url: String,
+}
+
+/// The table comes from the frontend migrations; before they run there are simply no links.
+/// Most used links come first.
+fn load_quick_links(app: &tauri::AppHandle) -> Vec<QuickLink> {
+    let Ok(conn) = db::open(app) else {
+        return Vec::new();
+    };
+    let Ok(mut stmt) = conn.prepare(
+        "SELECT id, title, url FROM quick_links ORDER BY click_count DESC, sort_order, id",
+    ) else {
+        return Vec::new();
+    };
+
+    stmt.query_map([], |row| {
+        Ok(QuickLink {
+            id: row.get(0)?,
+            title: row.get(1)?,
+            url: row.get(2)?,
+        })
+    })
+    .map(|rows| rows.flatten().collect())
+    .unwrap_or_default()
+}
+
+fn open_quick_link(app: &tauri::AppHandle, id: i64, incognito: bool) {
+    if let Some(link) = load_quick_links(app).into_iter().find(|link| link.id == id) {
+        let mode = if incognito { "incognito" } else { "normal" };
+        if open_external_link(app.clone(), link.url, Some(mode.to_string()), None, None).is_ok()
+            && quick_links::record_click(app, id).is_ok()
+        {
+            refresh_tray_menu(app);
+        }
+    }
+}
+
+fn build_tray_menu(app: &tauri::AppHandle) -> tauri::Result<Menu<tauri::Wry>> {
+    let show_hide = MenuItemBuilder::with_id("toggle", "Mostrar/Ocultar").build(app)?;
+    let quit = MenuItemBuilder::with_id("quit", "Sair").build(app)?;
+
+    let mut clipboard = SubmenuBuilder::new(app, "Área de transferência");
+    let recent = app
+        .try_state::<clipboard_history::ClipboardHistory>()
+        .and_then(|history| {
+            history
+                .recent(None, clipboard_history::TRAY_RECENT_COUNT)
+                .ok()
+        })
+        .unwrap_or_default();
+    if recent.is_empty() {
+        clipboard = clipboard.item(
+            &MenuItemBuilder::new("Histórico vazio")
+                .enabled(false)
+                .build(app)?,
+        );
+    }
+    for entry in &recent {
+        let id = format!("{}{}", CLIPBOARD_MENU_PREFIX, entry.id);
+        let label = clipboard_history::menu_label(entry);
+        clipboard = clipboard.item(&MenuItemBuilder::with_id(id, label).build(app)?);
+    }
+
+    let links = load_quick_links(app);
+    let mut quick_links = SubmenuBuilder::new(app, "Links rápidos");
+    if links.is_empty() {
+        quick_links = quick_links.item(
+            &MenuItemBuilder::new("Nenhum link salvo")
+                .enabled(false)
+                .build(app)?,
+        );
+    } else {
+        let mut incognito = SubmenuBuilder::new(app, "Abrir em janela anônima");
+        for link in &links {
+            let id = format!("{}{}", QUICK_LINK_MENU_PREFIX, link.id);
+            quick_links = quick_links.item(&MenuItemBuilder::with_id(id, &link.title).build(app)?);
+            let id = format!("{}{}", QUICK_LINK_INCOGNITO_MENU_PREFIX, link.id);
+            incognito = incognito.item(&MenuItemBuilder::with_id(id, &link.title).build(app)?);
+        }
+        quick_links = quick_links.separator().item(&incognito.build()?);
+    }
+
+    MenuBuilder::new(app)
+        .item(&show_hide)
+        .item(&quick_links.build()?)
+        .item(&clipboard.build()?)
+        .separator()
+        .item(&quit)
+        .build()
+}
+
+/// Rebuilds the tray menu after the data behind its dynamic items changes.
+pub(crate) fn refresh_tray_menu(app: &tauri::AppHandle) {
+    if let (Some(tray), Ok(menu)) = (app.tray_by_id(TRAY_ID), build_tray_menu(app)) {
+        let _ = tray.set_menu(Some(menu));
+    }
+}
+
+// ── Pending Todos Indicator ─────────────────────────────────────
+const TRAY_TOOLTIP: &str = "Adcontec Útil";
+
+fn pending_todo_count(app: &tauri::AppHandle) -> i64 {
+    db::open(app)
+        .and_then(|conn| {
+            conn.query_row("SELECT COUNT(*) FROM todos WHERE done = 0", [], |row| {
+                row.get(0)
+            })
+            .map_err(|e| e.to_string())
+        })
+        .unwrap_or(0)
+}
+
+/// Shows the number of open tasks in the tray tooltip and on the taskbar button.
+fn refresh_todo_indicator(app: &tauri::AppHandle) {
+    let count = pending_todo_count(app);
+
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        let tooltip = match count {
+            0 => TRAY_TOOLTIP.to_string(),
+            1 => format!("{} — 1 tarefa pendente", TRAY_TOOLTIP),
+            n => format!("{} — {} tarefas pendentes", TRAY_TOOLTIP, n),
+        };
+        let _ = tray.set_tooltip(Some(tooltip));
+    }
+
+    if let Some(window) = app.get_webview_window("main") {
+        // Windows has no numeric badge, only an overlay icon on the taskbar button
+        #[cfg(windows)]
+        let _ = window.set_overlay_icon((count > 0).then(pending_overlay_icon));
+
+        #[cfg(not(windows))]
+        let _ = window.set_badge_count((count > 0).then_some(count));
+    }
+}
+
+/// 16×16 red dot drawn in code, so no extra asset has to be bundled.
 #[cfg(windows)]
-fn screen_capture_impl() -> Result<(), String> {
-    std::process::Command::new("cmd")
-        .args(["/C", "start", "ms-screenclip:"])
-        .spawn()
-        .map_err(|e| format!("Falha ao iniciar captura de tela: {}", e))?;
-    Ok(())
+fn pending_overlay_icon() -> tauri::image::Image<'static> {
+    const SIZE: u32 = 16;
+    let center = (SIZE as f32 - 1.0) / 2.0;
+    let mut rgba = Vec::with_capacity((SIZE * SIZE * 4) as usize);
+
+    for y in 0..SIZE {
+        for x in 0..SIZE {
+            let (dx, dy) = (x as f32 - center, y as f32 - center);
+            let alpha = if dx * dx + dy * dy <= center * center {
+                255
+            } else {
+                0
+            };
+            rgba.extend_from_slice(&[220, 38, 38, alpha]);
+        }
+    }
+
+    tauri::image::Image::new_owned(rgba, SIZE, SIZE)
 }
 
-#[cfg(not(windows))]
-fn screen_capture_impl() -> Result<(), String> {
-    Err("Captura de tela disponível apenas no Windows".into())
+// ── Autostart ───────────────────────────────────────────────────
+pub(crate) const AUTOSTART_ENABLED_KEY: &str = "autostart_enabled";
+
+/// New installs start with Windows; afterwards the user's choice is kept as is.
+fn apply_autostart_preference(app: &tauri::AppHandle) {
+    let settings = app.state::<settings::Settings>();
+    if settings.get::<bool>(AUTOSTART_ENABLED_KEY).is_some() {
+        return;
+    }
+
+    let autostart_manager = app.plugin_autostart();
+    if !autostart_manager.is_enabled().unwrap_or(false) {
+        let _ = autostart_manager.enable();
+    }
+    let _ = settings.set(AUTOSTART_ENABLED_KEY, true);
+}
+
+#[tauri::command]
+fn get_autostart_status(app: tauri::AppHandle) -> Result<bool, AppError> {
+    app.plugin_autostart()
+        .is_enabled()
+        .map_err(|e| AppError::internal("Falha ao consultar inicialização automática", e))
+}
+
+#[tauri::command]
+fn set_autostart(
+    app: tauri::AppHandle,
+    settings: tauri::State<'_, settings::Settings>,
+    enabled: bool,
+) -> Result<(), AppError> {
+    let autostart_manager = app.plugin_autostart();
+    let result = if enabled {
+        autostart_manager.enable()
+    } else {
+        autostart_manager.disable()
+    };
+    result.map_err(|e| AppError::internal("Falha ao alterar inicialização automática", e))?;
+
+    Ok(settings.set(AUTOSTART_ENABLED_KEY, enabled)?)
+}
+
+// ── Launch Arguments ────────────────────────────────────────────
+const AUTOSTARTED_FLAG: &str = "--autostarted";
+const START_HIDDEN_ON_AUTOSTART_KEY: &str = "start_hidden_on_autostart";
+
+/// How this process was launched, parsed once from argv.
+struct LaunchOptions {
+    autostarted: bool,
+}
+
+impl LaunchOptions {
+    fn from_args(args: impl IntoIterator<Item = String>) -> Self {
+        LaunchOptions {
+            autostarted: args.into_iter().skip(1).any(|arg| arg == AUTOSTARTED_FLAG),
+        }
+    }
+}
+
+/// Autostarted sessions stay in the tray unless the user opted out in settings.
+#[tauri::command]
+fn should_start_hidden(
+    launch: tauri::State<'_, LaunchOptions>,
+    settings: tauri::State<'_, settings::Settings>,
+) -> bool {
+    launch.autostarted
+        && settings
+            .get::<bool>(START_HIDDEN_ON_AUTOSTART_KEY)
+            .unwrap_or(true)
+}
+
+/// Turns `--tool pdf-merge --files a.pdf b.pdf` or `--nfe <chave>` into a navigation.
+/// Values run until the next flag; any other `--key value` pair becomes a param.
+fn parse_cli_navigation(args: &[String], cwd: &std::path::Path) -> Option<deep_link::Navigation> {
+    let mut tool = None;
+    let mut params = serde_json::Map::new();
+
+    let mut args = args.iter().peekable();
+    while let Some(arg) = args.next() {
+        // Bare arguments (e.g. utilhub:// links) are left to the deep-link plugin
+        let Some(flag) = arg.strip_prefix("--") else {
+            continue;
+        };
+        let mut values = Vec::new();
+        while let Some(value) = args.next_if(|value| !value.starts_with("--")) {
+            values.push(value.clone());
+        }
+
+        match flag {
+            "tool" => tool = values.into_iter().next(),
+            "nfe" => {
+                tool = Some("nfe".to_string());
+                if let Some(chave) = values.into_iter().next() {
+                    params.insert("chave".into(), chave.into());
+                }
+            }
+            "files" => {
+                // A second instance may run from another directory
+                let files: Vec<serde_json::Value> = values
+                    .iter()
+                    .map(|file| cwd.join(file).to_string_lossy().into_owned().into())
+                    .collect();
+                params.insert("files".into(), files.into());
+            }
+            _ => {
+                if let Some(value) = values.into_iter().next() {
+                    params.insert(flag.to_string(), value.into());
+                }
+            }
+        }
+    }
+
+    tool.filter(|tool| !tool.is_empty())
+        .map(|tool| deep_link::Navigation {
+            tool: tool.to_lowercase(),
+            params,
+        })
+}
+
+/// Opens the tool requested on the command line; returns false when none was.
+fn route_cli_args(app: &tauri::AppHandle, args: &[String], cwd: &std::path::Path) -> bool {
+    match parse_cli_navigation(args, cwd) {
+        Some(navigation) => {
+            deep_link::navigate(app, navigation);
+            true
+        }
+        None => false,
+    }
 }
 
+/// How long "Sair" waits for cancelled jobs to remove their partial outputs.
+const SHUTDOWN_GRACE_PERIOD: std::time::Duration = std::time::Duration::from_secs(5);
+
 // ── App Entry ───────────────────────────────────────────────────
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    let invoke_handler: fn(tauri::ipc::Invoke) -> bool = tauri::generate_handler![
+        set_movable_mode,
+        reset_window_position,
+        should_start_hidden,
+        get_autostart_status,
+        set_autostart,
+        deep_link::take_pending_navigation,
+        crash_report::get_pending_crash_report,
+        crash_report::copy_crash_report,
+        crash_report::dismiss_crash_report,
+        settings::get_setting,
+        settings::set_setting,
+        settings::get_all_settings,
+        save_binary_file,
+        get_certificates,
+        delete_certificates,
+        export_certificate_pfx,
+        install_certificate,
+        cert_backup::backup_certificates,
+        cert_backup::restore_certificates,
+        cert_check::test_certificate,
+        cert_defaults::get_certificate_groups,
+        cert_defaults::get_default_certificate,
+        cert_defaults::set_default_certificate,
+        cert_duplicates::find_duplicate_certificates,
+        cert_passwords::save_pfx_password,
+        cert_passwords::get_pfx_password,
+        cert_passwords::delete_pfx_password,
+        cert_details::get_certificate_details,
+        cert_store::get_certificate_store,
+        cert_store::set_certificate_store,
+        clients::list_clients,
+        clients::get_client,
+        clients::create_client,
+        clients::update_client,
+        clients::delete_client,
+        clients::find_client,
+        clients::get_client_links,
+        cnpj::lookup_cnpj,
+        ncm::lookup_ncm,
+        inscricao_estadual::check_ie,
+        indices::get_index_rates,
+        indices::calculate_correction,
+        boleto::decode_boleto,
+        pix::generate_pix_qrcode,
+        barcode::generate_barcode,
+        barcode::scan_barcode_from_image,
+        ocr::ocr_clipboard,
+        start_screen_capture,
+        screenshot::list_capture_targets,
+        screenshot::capture_screenshot,
+        open_external_link,
+        browsers::list_browser_profiles,
+        get_allowed_link_schemes,
+        set_allowed_link_schemes,
+        link_history::get_link_history,
+        system_stats::get_system_stats,
+        system_stats::subscribe_system_stats,
+        system_stats::unsubscribe_system_stats,
+        system_report::generate_system_report,
+        printers::list_printers,
+        printers::get_default_printer,
+        printers::set_default_printer,
+        printers::list_print_jobs,
+        printers::cancel_print_job,
+        printers::set_print_queue_paused,
+        printers::clear_print_queue,
+        clipboard_history::list_clipboard_history,
+        clipboard_history::search_clipboard_history,
+        clipboard_history::get_clipboard_image,
+        clipboard_history::pin_clipboard_entry,
+        clipboard_history::delete_clipboard_entry,
+        clipboard_history::paste_clipboard_entry,
+        fiscal_calendar::list_fiscal_obligations,
+        fiscal_calendar::list_client_obligations,
+        fiscal_calendar::set_client_obligation,
+        fiscal_calendar::list_upcoming_obligations,
+        reminders::list_reminders,
+        reminders::create_reminder,
+        reminders::update_reminder,
+        reminders::set_reminder_enabled,
+        reminders::delete_reminder,
+        reminders::set_todo_schedule,
+        scheduler::list_schedules,
+        scheduler::create_schedule,
+        scheduler::set_schedule_enabled,
+        scheduler::delete_schedule,
+        scheduler::run_schedule_now,
+        scheduler::list_schedule_runs,
+        todos::list_todos,
+        todos::list_tags,
+        todos::create_tag,
+        todos::update_tag,
+        todos::delete_tag,
+        todos::set_todo_tags,
+        todos::list_saved_todo_filters,
+        todos::save_todo_filter,
+        todos::delete_todo_filter,
+        todos::create_subtask,
+        todos::set_todo_parent,
+        todos::reorder_todos,
+        todos::set_todo_done,
+        notes::list_notes,
+        notes::get_note,
+        notes::create_note,
+        notes::update_note,
+        notes::delete_note,
+        notes::list_note_tags,
+        snippets::list_snippets,
+        snippets::create_snippet,
+        snippets::update_snippet,
+        snippets::delete_snippet,
+        snippets::render_snippet,
+        quick_links::record_quick_link_click,
+        quick_links::reorder_quick_links,
+        quick_links::set_quick_link_group,
+        quick_links::list_quick_link_groups,
+        favicons::fetch_favicon,
+        backup::export_app_data,
+        backup::import_app_data,
+        backup::get_backup_settings,
+        backup::set_backup_settings,
+        backup::backup_now,
+        backup::list_backups,
+        backup::restore_backup,
+        search::search_all,
+        sync::get_sync_settings,
+        sync::set_sync_settings,
+        sync::sync_now,
+        xml_inbox::get_xml_inbox_settings,
+        xml_inbox::set_xml_inbox_settings,
+        xml_inbox::organize_xml_inbox,
+        xml_inbox::get_xml_inbox_report,
+        sped::summarize_sped,
+        sped::export_sped_csv,
+        sync::list_sync_conflicts,
+        sync::clear_sync_conflicts,
+        nfe::query_nfe,
+        nfe::invalidate_nfe_cache,
+        nfe::open_danfe,
+        nfe::download_danfe,
+        nfe::query_nfe_portal,
+        portal::list_portals,
+        portal::open_portal,
+        jobs::list_jobs,
+        jobs::get_job,
+        jobs::cancel_job,
+        pdf_utils::merge_pdfs,
+        pdf_utils::split_pdf,
+        pdf_utils::split_spread_pages,
+        pdf_utils::get_pdf_info,
+        pdf_utils::compress_pdf,
+        pdf_utils::compress_pdfs,
+        workers::get_worker_settings,
+        workers::set_worker_concurrency,
+        pdf_utils::find_duplicate_pages,
+        pdf_utils::render_pdf_page,
+        hashing::hash_file,
+        hashing::verify_hash,
+        encoding::encode_file,
+        encoding::decode_to_file,
+        images::convert_image,
+        images::compress_images,
+        renamer::batch_rename,
+        text_diff::diff_text,
+        text_diff::diff_files,
+        client_folders::get_network_module_enabled,
+        client_folders::set_network_module_enabled,
+        client_folders::get_network_base_path,
+        client_folders::set_network_base_path,
+        client_folders::list_network_folders,
+        client_folders::check_share_connection,
+        client_folders::connect_share,
+        client_folders::list_directory,
+        client_folders::list_directory_stream,
+        client_folders::list_directory_page,
+        client_folders::get_folder_size,
+        client_folders::find_duplicate_files,
+        duplicates::find_local_duplicates,
+        duplicates::remove_local_duplicates,
+        email::compose_email,
+        whatsapp::open_whatsapp_chat,
+        client_folders::get_storage_report,
+        client_folders::get_entry_properties,
+        client_folders::can_write,
+        client_folders::get_file_preview,
+        client_folders::rename_entry,
+        client_folders::move_entry,
+        client_folders::delete_entry,
+        client_folders::open_file,
+        client_folders::reveal_in_explorer,
+        client_folders::copy_path_to_clipboard,
+        client_folders::copy_paths_to_directory,
+        client_folders::copy_entry,
+        client_folders::set_drop_target,
+        client_folders::import_dropped_files,
+        client_folders::create_directory,
+        client_folders::create_folder,
+        client_folders::zip_entry,
+        client_folders::extract_archive,
+        archive::create_archive,
+        archive::extract_local_archive,
+        client_folders::batch_operation,
+        metrics::get_command_metrics,
+    ];
+
     tauri::Builder::default()
+        // Must come first so a second launch (or a utilhub:// link) reaches this instance
+        .plugin(tauri_plugin_single_instance::init(|app, argv, cwd| {
+            let args = argv.get(1..).unwrap_or_default();
+            if route_cli_args(app, args, std::path::Path::new(&cwd)) {
+                return;
+            }
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+        }))
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_clipboard_manager::init())
-        .manage(AppState {
-            movable_mode: Mutex::new(false),
+        .invoke_handler(move |invoke| {
+            metrics::measure(invoke, |invoke| {
+                crash_report::record_command(invoke.message.command());
+                invoke_handler(invoke)
+            })
         })
-        .invoke_handler(tauri::generate_handler![
-            set_movable_mode,
-            save_binary_file,
-            get_certificates,
-            delete_certificates,
-            start_screen_capture,
-            open_external_link,
-            nfe::query_nfe,
-            nfe::open_danfe,
-            nfe::download_danfe,
-            nfe::query_nfe_portal,
-            pdf_utils::merge_pdfs,
-            pdf_utils::split_pdf,
-            pdf_utils::get_pdf_info,
-            pdf_utils::compress_pdf,
-            client_folders::list_network_folders,
-            client_folders::list_directory,
-            client_folders::rename_entry,
-            client_folders::move_entry,
-            client_folders::delete_entry,
-            client_folders::open_file,
-            client_folders::copy_paths_to_directory,
-            client_folders::create_directory,
-        ])
         // ── Plugins ──────────────────────────────────────────────
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
@@ -690,33 +1933,349 @@ pub fn run() {
                             );",
                             kind: tauri_plugin_sql::MigrationKind::Up,
                         },
+                        tauri_plugin_sql::Migration {
+                            version: 7,
+                            description: "add due_at column",
+                            sql: "ALTER TABLE todos ADD COLUMN due_at TEXT;",
+                            kind: tauri_plugin_sql::MigrationKind::Up,
+                        },
+                        tauri_plugin_sql::Migration {
+                            version: 8,
+                            description: "add remind_at column",
+                            sql: "ALTER TABLE todos ADD COLUMN remind_at TEXT;",
+                            kind: tauri_plugin_sql::MigrationKind::Up,
+                        },
+                        tauri_plugin_sql::Migration {
+                            version: 9,
+                            description: "add parent_id column",
+                            sql: "ALTER TABLE todos ADD COLUMN parent_id INTEGER REFERENCES todos(id) ON DELETE CASCADE;",
+                            kind: tauri_plugin_sql::MigrationKind::Up,
+                        },
+                        tauri_plugin_sql::Migration {
+                            version: 10,
+                            description: "create notes table",
+                            sql: "CREATE TABLE IF NOT EXISTS notes (
+                                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                                title TEXT NOT NULL,
+                                body TEXT NOT NULL DEFAULT '',
+                                tags TEXT NOT NULL DEFAULT '[]',
+                                created_at TEXT NOT NULL DEFAULT (datetime('now')),
+                                updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+                            );",
+                            kind: tauri_plugin_sql::MigrationKind::Up,
+                        },
+                        tauri_plugin_sql::Migration {
+                            version: 11,
+                            description: "add quick_links group_name column",
+                            sql: "ALTER TABLE quick_links ADD COLUMN group_name TEXT;",
+                            kind: tauri_plugin_sql::MigrationKind::Up,
+                        },
+                        tauri_plugin_sql::Migration {
+                            version: 12,
+                            description: "add quick_links sort_order column",
+                            sql: "ALTER TABLE quick_links ADD COLUMN sort_order INTEGER NOT NULL DEFAULT 0;",
+                            kind: tauri_plugin_sql::MigrationKind::Up,
+                        },
+                        tauri_plugin_sql::Migration {
+                            version: 13,
+                            description: "initialize quick_links sort_order from id",
+                            sql: "UPDATE quick_links SET sort_order = id;",
+                            kind: tauri_plugin_sql::MigrationKind::Up,
+                        },
+                        tauri_plugin_sql::Migration {
+                            version: 14,
+                            description: "add quick_links click_count column",
+                            sql: "ALTER TABLE quick_links ADD COLUMN click_count INTEGER NOT NULL DEFAULT 0;",
+                            kind: tauri_plugin_sql::MigrationKind::Up,
+                        },
+                        tauri_plugin_sql::Migration {
+                            version: 15,
+                            description: "create search_index table",
+                            sql: "CREATE VIRTUAL TABLE IF NOT EXISTS search_index USING fts5(
+                                kind UNINDEXED,
+                                ref_id UNINDEXED,
+                                title,
+                                body,
+                                tokenize = 'unicode61 remove_diacritics 2'
+                            );
+                            INSERT INTO search_index (kind, ref_id, title, body)
+                                SELECT 'todo', id, title, '' FROM todos;
+                            INSERT INTO search_index (kind, ref_id, title, body)
+                                SELECT 'note', id, title, body || ' ' || tags FROM notes;
+                            INSERT INTO search_index (kind, ref_id, title, body)
+                                SELECT 'quick_link', id, title, url FROM quick_links;
+
+                            CREATE TRIGGER IF NOT EXISTS todos_search_insert AFTER INSERT ON todos BEGIN
+                                INSERT INTO search_index (kind, ref_id, title, body)
+                                VALUES ('todo', new.id, new.title, '');
+                            END;
+                            CREATE TRIGGER IF NOT EXISTS todos_search_update AFTER UPDATE OF title ON todos BEGIN
+                                UPDATE search_index SET title = new.title
+                                WHERE kind = 'todo' AND ref_id = new.id;
+                            END;
+                            CREATE TRIGGER IF NOT EXISTS todos_search_delete AFTER DELETE ON todos BEGIN
+                                DELETE FROM search_index WHERE kind = 'todo' AND ref_id = old.id;
+                            END;
+
+                            CREATE TRIGGER IF NOT EXISTS notes_search_insert AFTER INSERT ON notes BEGIN
+                                INSERT INTO search_index (kind, ref_id, title, body)
+                                VALUES ('note', new.id, new.title, new.body || ' ' || new.tags);
+                            END;
+                            CREATE TRIGGER IF NOT EXISTS notes_search_update AFTER UPDATE OF title, body, tags ON notes BEGIN
+                                UPDATE search_index SET title = new.title, body = new.body || ' ' || new.tags
+                                WHERE kind = 'note' AND ref_id = new.id;
+                            END;
+                            CREATE TRIGGER IF NOT EXISTS notes_search_delete AFTER DELETE ON notes BEGIN
+                                DELETE FROM search_index WHERE kind = 'note' AND ref_id = old.id;
+                            END;
+
+                            CREATE TRIGGER IF NOT EXISTS quick_links_search_insert AFTER INSERT ON quick_links BEGIN
+                                INSERT INTO search_index (kind, ref_id, title, body)
+                                VALUES ('quick_link', new.id, new.title, new.url);
+                            END;
+                            CREATE TRIGGER IF NOT EXISTS quick_links_search_update AFTER UPDATE OF title, url ON quick_links BEGIN
+                                UPDATE search_index SET title = new.title, body = new.url
+                                WHERE kind = 'quick_link' AND ref_id = new.id;
+                            END;
+                            CREATE TRIGGER IF NOT EXISTS quick_links_search_delete AFTER DELETE ON quick_links BEGIN
+                                DELETE FROM search_index WHERE kind = 'quick_link' AND ref_id = old.id;
+                            END;",
+                            kind: tauri_plugin_sql::MigrationKind::Up,
+                        },
+                        tauri_plugin_sql::Migration {
+                            version: 16,
+                            description: "add uid columns and sync journal",
+                            sql: "ALTER TABLE todos ADD COLUMN uid TEXT;
+                            ALTER TABLE notes ADD COLUMN uid TEXT;
+                            ALTER TABLE quick_links ADD COLUMN uid TEXT;
+                            UPDATE todos SET uid = lower(hex(randomblob(16)));
+                            UPDATE notes SET uid = lower(hex(randomblob(16)));
+                            UPDATE quick_links SET uid = lower(hex(randomblob(16)));
+                            CREATE UNIQUE INDEX IF NOT EXISTS idx_todos_uid ON todos (uid);
+                            CREATE UNIQUE INDEX IF NOT EXISTS idx_notes_uid ON notes (uid);
+                            CREATE UNIQUE INDEX IF NOT EXISTS idx_quick_links_uid ON quick_links (uid);
+
+                            CREATE TABLE IF NOT EXISTS sync_control (
+                                id INTEGER PRIMARY KEY CHECK (id = 1),
+                                enabled INTEGER NOT NULL DEFAULT 0,
+                                applying INTEGER NOT NULL DEFAULT 0
+                            );
+                            INSERT OR IGNORE INTO sync_control (id) VALUES (1);
+                            CREATE TABLE IF NOT EXISTS sync_journal (
+                                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                                table_name TEXT NOT NULL,
+                                uid TEXT NOT NULL,
+                                changed_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+                            );
+
+                            CREATE TRIGGER IF NOT EXISTS todos_uid AFTER INSERT ON todos WHEN new.uid IS NULL BEGIN
+                                UPDATE todos SET uid = lower(hex(randomblob(16))) WHERE id = new.id;
+                            END;
+                            CREATE TRIGGER IF NOT EXISTS todos_sync_insert AFTER INSERT ON todos
+                            WHEN new.uid IS NOT NULL AND (SELECT enabled AND NOT applying FROM sync_control) BEGIN
+                                INSERT INTO sync_journal (table_name, uid) VALUES ('todos', new.uid);
+                            END;
+                            CREATE TRIGGER IF NOT EXISTS todos_sync_update AFTER UPDATE ON todos
+                            WHEN new.uid IS NOT NULL AND (SELECT enabled AND NOT applying FROM sync_control) BEGIN
+                                INSERT INTO sync_journal (table_name, uid) VALUES ('todos', new.uid);
+                            END;
+                            CREATE TRIGGER IF NOT EXISTS todos_sync_delete AFTER DELETE ON todos
+                            WHEN old.uid IS NOT NULL AND (SELECT enabled AND NOT applying FROM sync_control) BEGIN
+                                INSERT INTO sync_journal (table_name, uid) VALUES ('todos', old.uid);
+                            END;
+
+                            CREATE TRIGGER IF NOT EXISTS notes_uid AFTER INSERT ON notes WHEN new.uid IS NULL BEGIN
+                                UPDATE notes SET uid = lower(hex(randomblob(16))) WHERE id = new.id;
+                            END;
+                            CREATE TRIGGER IF NOT EXISTS notes_sync_insert AFTER INSERT ON notes
+                            WHEN new.uid IS NOT NULL AND (SELECT enabled AND NOT applying FROM sync_control) BEGIN
+                                INSERT INTO sync_journal (table_name, uid) VALUES ('notes', new.uid);
+                            END;
+                            CREATE TRIGGER IF NOT EXISTS notes_sync_update AFTER UPDATE ON notes
+                            WHEN new.uid IS NOT NULL AND (SELECT enabled AND NOT applying FROM sync_control) BEGIN
+                                INSERT INTO sync_journal (table_name, uid) VALUES ('notes', new.uid);
+                            END;
+                            CREATE TRIGGER IF NOT EXISTS notes_sync_delete AFTER DELETE ON notes
+                            WHEN old.uid IS NOT NULL AND (SELECT enabled AND NOT applying FROM sync_control) BEGIN
+                                INSERT INTO sync_journal (table_name, uid) VALUES ('notes', old.uid);
+                            END;
+
+                            CREATE TRIGGER IF NOT EXISTS quick_links_uid AFTER INSERT ON quick_links WHEN new.uid IS NULL BEGIN
+                                UPDATE quick_links SET uid = lower(hex(randomblob(16))) WHERE id = new.id;
+                            END;
+                            CREATE TRIGGER IF NOT EXISTS quick_links_sync_insert AFTER INSERT ON quick_links
+                            WHEN new.uid IS NOT NULL AND (SELECT enabled AND NOT applying FROM sync_control) BEGIN
+                                INSERT INTO sync_journal (table_name, uid) VALUES ('quick_links', new.uid);
+                            END;
+                            CREATE TRIGGER IF NOT EXISTS quick_links_sync_update AFTER UPDATE ON quick_links
+                            WHEN new.uid IS NOT NULL AND (SELECT enabled AND NOT applying FROM sync_control) BEGIN
+                                INSERT INTO sync_journal (table_name, uid) VALUES ('quick_links', new.uid);
+                            END;
+                            CREATE TRIGGER IF NOT EXISTS quick_links_sync_delete AFTER DELETE ON quick_links
+                            WHEN old.uid IS NOT NULL AND (SELECT enabled AND NOT applying FROM sync_control) BEGIN
+                                INSERT INTO sync_journal (table_name, uid) VALUES ('quick_links', old.uid);
+                            END;",
+                            kind: tauri_plugin_sql::MigrationKind::Up,
+                        },
+                        tauri_plugin_sql::Migration {
+                            version: 17,
+                            description: "stop journaling quick link clicks",
+                            sql: "DROP TRIGGER IF EXISTS quick_links_sync_update;
+                            CREATE TRIGGER quick_links_sync_update
+                            AFTER UPDATE OF uid, title, url, created_at, group_name, sort_order ON quick_links
+                            WHEN new.uid IS NOT NULL AND (SELECT enabled AND NOT applying FROM sync_control) BEGIN
+                                INSERT INTO sync_journal (table_name, uid) VALUES ('quick_links', new.uid);
+                            END;",
+                            kind: tauri_plugin_sql::MigrationKind::Up,
+                        },
                     ],
                 )
                 .build(),
         )
         .plugin(tauri_plugin_autostart::init(
             MacosLauncher::LaunchAgent,
-            Some(vec!["--autostarted"]),
+            Some(vec![AUTOSTARTED_FLAG]),
         ))
         .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_updater::Builder::new().build())
         // ── System Tray Setup ────────────────────────────────────
         .setup(|app| {
-            // ... (setup existente)
-            // Menu items
-            let show_hide = MenuItemBuilder::with_id("toggle", "Mostrar/Ocultar")
-                .build(app)?;
-            let quit = MenuItemBuilder::with_id("quit", "Sair").build(app)?;
+            app_log::init(app.handle());
+            crash_report::install(app.handle());
+            app.manage(settings::Settings::load(app.handle()));
+            cert_store::load(&app.state::<settings::Settings>());
+            app.manage(LaunchOptions::from_args(std::env::args()));
+            restore_window_geometry(app.handle());
+            app.manage(client_folders::DropTarget::default());
+            app.manage(client_folders::StorageReportCache::default());
+            app.manage(system_stats::SystemMonitor::default());
+            app.manage(xml_inbox::XmlInbox::default());
+            app.manage(jobs::Jobs::default());
+            xml_inbox::start_watcher(app.handle().clone());
+
+            match clipboard_history::ClipboardHistory::open(app.handle()) {
+                Ok(history) => {
+                    app.manage(history);
+                    clipboard_history::start_watcher(app.handle().clone());
+                }
+                Err(e) => app_log::error(format!("Histórico da área de transferência indisponível: {}", e)),
+            }
+
+            match link_history::LinkHistory::open(app.handle()) {
+                Ok(history) => {
+                    app.manage(history);
+                }
+                Err(e) => app_log::error(format!("Histórico de links indisponível: {}", e)),
+            }
+
+            match todos::Todos::open(app.handle()) {
+                Ok(store) => {
+                    app.manage(store);
+                }
+                Err(e) => app_log::error(format!("Etiquetas de tarefas indisponíveis: {}", e)),
+            }
 
-            let menu = MenuBuilder::new(app)
-                .item(&show_hide)
-                .separator()
-                .item(&quit)
-                .build()?;
+            match clients::Clients::open(app.handle()) {
+                Ok(store) => {
+                    app.manage(store);
+                }
+                Err(e) => app_log::error(format!("Cadastro de clientes indisponível: {}", e)),
+            }
+
+            match cnpj::CnpjLookup::open(app.handle()) {
+                Ok(lookup) => {
+                    app.manage(lookup);
+                }
+                Err(e) => app_log::error(format!("Consulta de CNPJ indisponível: {}", e)),
+            }
+
+            match cert_defaults::CertDefaults::open(app.handle()) {
+                Ok(defaults) => {
+                    app.manage(defaults);
+                }
+                Err(e) => app_log::error(format!("Certificados padrão indisponíveis: {}", e)),
+            }
+
+            match nfe::NfeCache::open(app.handle()) {
+                Ok(cache) => {
+                    app.manage(cache);
+                }
+                Err(e) => app_log::error(format!("Cache de NFe indisponível: {}", e)),
+            }
+
+            match ncm::NcmTable::open(app.handle()) {
+                Ok(table) => {
+                    app.manage(table);
+                }
+                Err(e) => app_log::error(format!("Tabela NCM indisponível: {}", e)),
+            }
+
+            match indices::Indices::open(app.handle()) {
+                Ok(indices) => {
+                    app.manage(indices);
+                }
+                Err(e) => app_log::error(format!("Índices econômicos indisponíveis: {}", e)),
+            }
+
+            match notes::Notes::open(app.handle()) {
+                Ok(store) => {
+                    app.manage(store);
+                }
+                Err(e) => app_log::error(format!("Notas indisponíveis: {}", e)),
+            }
+
+            match snippets::Snippets::open(app.handle()) {
+                Ok(store) => {
+                    app.manage(store);
+                }
+                Err(e) => app_log::error(format!("Textos prontos indisponíveis: {}", e)),
+            }
+
+            backup::start_backup_scheduler(app.handle().clone());
 
-            let _tray = TrayIconBuilder::new()
+            match sync::SyncJournal::open(app.handle()) {
+                Ok(journal) => {
+                    app.manage(journal);
+                    sync::start_sync_scheduler(app.handle().clone());
+                }
+                Err(e) => app_log::error(format!("Sincronização indisponível: {}", e)),
+            }
+
+            match reminders::Reminders::open(app.handle()) {
+                Ok(store) => {
+                    app.manage(store);
+                    reminders::start_scheduler(app.handle().clone());
+                }
+                Err(e) => app_log::error(format!("Lembretes indisponíveis: {}", e)),
+            }
+
+            match scheduler::Scheduler::open(app.handle()) {
+                Ok(scheduler) => {
+                    app.manage(scheduler);
+                    scheduler::start_scheduler(app.handle().clone());
+                }
+                Err(e) => app_log::error(format!("Agendamentos indisponíveis: {}", e)),
+            }
+
+            match fiscal_calendar::FiscalCalendar::open(app.handle()) {
+                Ok(calendar) => {
+                    app.manage(calendar);
+                    fiscal_calendar::start_scheduler(app.handle().clone());
+                }
+                Err(e) => app_log::error(format!("Calendário fiscal indisponível: {}", e)),
+            }
+
+            // The frontend owns the quick_links table and announces edits
+            let handle = app.handle().clone();
+            app.listen("quick-links-changed", move |_| refresh_tray_menu(&handle));
+
+            // ... (setup existente)
+            let menu = build_tray_menu(app.handle())?;
+
+            let _tray = TrayIconBuilder::with_id(TRAY_ID)
                 .icon(app.default_window_icon().unwrap().clone())
                 .menu(&menu)
-                .tooltip("Adcontec Útil")
+                .tooltip(TRAY_TOOLTIP)
                 .on_menu_event(|app_handle, event| match event.id().as_ref() {
                     "toggle" => {
                         if let Some(window) = app_handle.get_webview_window("main") {
@@ -729,9 +2288,29 @@ pub fn run() {
                         }
                     }
                     "quit" => {
-                        app_handle.exit(0);
+                        // Off the event loop, so jobs can still emit while they wind down
+                        let app_handle = app_handle.clone();
+                        std::thread::spawn(move || {
+                            app_handle
+                                .state::<jobs::Jobs>()
+                                .cancel_all_and_wait(SHUTDOWN_GRACE_PERIOD);
+                            app_handle.exit(0);
+                        });
+                    }
+                    id => {
+                        let parse_id = |prefix: &str| {
+                            id.strip_prefix(prefix)
+                                .and_then(|rest| rest.parse::<i64>().ok())
+                        };
+
+                        if let Some(entry_id) = parse_id(CLIPBOARD_MENU_PREFIX) {
+                            let _ = clipboard_history::paste_entry(app_handle, entry_id);
+                        } else if let Some(link_id) = parse_id(QUICK_LINK_INCOGNITO_MENU_PREFIX) {
+                            open_quick_link(app_handle, link_id, true);
+                        } else if let Some(link_id) = parse_id(QUICK_LINK_MENU_PREFIX) {
+                            open_quick_link(app_handle, link_id, false);
+                        }
                     }
-                    _ => {}
                 })
                 .on_tray_icon_event(|tray, event| {
                     if let tauri::tray::TrayIconEvent::Click {
@@ -747,12 +2326,7 @@ pub fn run() {
                                 let _ = window.hide();
                             } else {
                                 // Check movable mode
-                                let movable = app_handle
-                                    .try_state::<AppState>()
-                                    .map(|s| *s.movable_mode.lock().unwrap())
-                                    .unwrap_or(false);
-
-                                if !movable {
+                                if !movable_mode(app_handle) {
                                     // Position window centered above the tray icon
                                     if let Ok(win_size) = window.outer_size() {
                                         let scale = window
@@ -777,20 +2351,37 @@ pub fn run() {
                 })
                 .build(app)?;
 
-            // Enable autostart
-            let autostart_manager = app.handle().plugin_autostart();
-            if !autostart_manager.is_enabled().unwrap_or(false) {
-                let _ = autostart_manager.enable();
-            }
+            refresh_todo_indicator(app.handle());
+            let handle = app.handle().clone();
+            app.listen("todos-changed", move |_| refresh_todo_indicator(&handle));
+
+            deep_link::init(app.handle());
+            let args: Vec<String> = std::env::args().skip(1).collect();
+            route_cli_args(
+                app.handle(),
+                &args,
+                &std::env::current_dir().unwrap_or_default(),
+            );
+            app.manage(updater::PendingUpdate::default());
+            updater::check_on_startup(app.handle());
+
+            apply_autostart_preference(app.handle());
 
             Ok(())
         })
         // ... (restante do código existente)
-        .on_window_event(|window, event| {
-            if let WindowEvent::CloseRequested { api, .. } = event {
+        .on_window_event(|window, event| match event {
+            WindowEvent::CloseRequested { api, .. } => {
                 api.prevent_close();
                 let _ = window.hide();
             }
+            WindowEvent::Moved(_) | WindowEvent::Resized(_) => {
+                schedule_geometry_save(window);
+            }
+            WindowEvent::DragDrop(tauri::DragDropEvent::Drop { paths, .. }) => {
+                client_folders::handle_file_drop(window.app_handle(), paths.clone());
+            }
+            _ => {}
         })
         .plugin(tauri_plugin_clipboard_manager::init())
         .run(tauri::generate_context!())
@@ -0,0 +1,100 @@
+// ── Link History Module ────────────────────────────────────────
+use crate::error::AppError;
+use rusqlite::{params, Connection};
+use std::sync::Mutex;
+
+const DEFAULT_LIST_LIMIT: u32 = 100;
+/// Older entries are pruned on insert so the table never grows unbounded.
+const MAX_ENTRIES: u32 = 1000;
+
+pub struct LinkHistory {
+    conn: Mutex<Connection>,
+}
+
+#[derive(serde::Serialize)]
+pub struct LinkHistoryEntry {
+    pub id: i64,
+    pub url: String,
+    pub scheme: String,
+    pub mode: String,
+    pub browser: Option<String>,
+    pub profile: Option<String>,
+    pub opened_at: String,
+}
+
+impl LinkHistory {
+    pub fn open(app: &tauri::AppHandle) -> Result<Self, String> {
+        let conn = crate::db::open(app)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS link_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                url TEXT NOT NULL,
+                scheme TEXT NOT NULL,
+                mode TEXT NOT NULL,
+                browser TEXT,
+                profile TEXT,
+                opened_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%d %H:%M:%f', 'now'))
+            );",
+        )
+        .map_err(|e| format!("Falha ao criar tabela do histórico de links: {}", e))?;
+
+        Ok(LinkHistory {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    pub fn record(
+        &self,
+        url: &str,
+        scheme: &str,
+        mode: &str,
+        browser: Option<&str>,
+        profile: Option<&str>,
+    ) -> Result<(), AppError> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO link_history (url, scheme, mode, browser, profile)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![url, scheme, mode, browser, profile],
+        )
+        .map_err(|e| AppError::internal("Falha ao registrar link aberto", e))?;
+
+        conn.execute(
+            "DELETE FROM link_history WHERE id NOT IN (
+                SELECT id FROM link_history ORDER BY id DESC LIMIT ?1
+             )",
+            params![MAX_ENTRIES],
+        )
+        .map_err(|e| AppError::internal("Falha ao limpar histórico de links", e))?;
+        Ok(())
+    }
+}
+
+#[tauri::command]
+pub fn get_link_history(
+    history: tauri::State<'_, LinkHistory>,
+    limit: Option<u32>,
+) -> Result<Vec<LinkHistoryEntry>, AppError> {
+    let conn = history.conn.lock().unwrap();
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, url, scheme, mode, browser, profile, opened_at
+             FROM link_history ORDER BY id DESC LIMIT ?1",
+        )
+        .map_err(|e| AppError::internal("Falha ao consultar histórico de links", e))?;
+    let rows = stmt
+        .query_map(params![limit.unwrap_or(DEFAULT_LIST_LIMIT)], |row| {
+            Ok(LinkHistoryEntry {
+                id: row.get(0)?,
+                url: row.get(1)?,
+                scheme: row.get(2)?,
+                mode: row.get(3)?,
+                browser: row.get(4)?,
+                profile: row.get(5)?,
+                opened_at: row.get(6)?,
+            })
+        })
+        .map_err(|e| AppError::internal("Falha ao consultar histórico de links", e))?;
+    rows.collect::<Result<_, _>>()
+        .map_err(|e| AppError::internal("Falha ao ler histórico de links", e))
+}
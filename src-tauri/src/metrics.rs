@@ -0,0 +1,181 @@
+// ── Command Metrics Module ─────────────────────────────────────
+use std::cell::RefCell;
+use std::collections::{BTreeMap, VecDeque};
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::ipc::Invoke;
+
+/// Durations kept per command for the percentiles; older samples are dropped.
+const SAMPLE_LIMIT: usize = 500;
+/// Errors kept for the support report.
+const RECENT_ERRORS_LIMIT: usize = 50;
+
+/// Per-command response times since the app started.
+static STATS: Mutex<BTreeMap<String, Stats>> = Mutex::new(BTreeMap::new());
+static RECENT_ERRORS: Mutex<VecDeque<RecentError>> = Mutex::new(VecDeque::new());
+
+thread_local! {
+    /// Set while a command is dispatched on this thread; holds the error its
+    /// answer was serialized with, if any.
+    static DISPATCH_ERROR: RefCell<Option<Option<String>>> = const { RefCell::new(None) };
+}
+
+#[derive(Default)]
+struct Stats {
+    count: u64,
+    errors: u64,
+    samples: VecDeque<Duration>,
+    max: Duration,
+    last_error: Option<String>,
+    /// Async command timed by `timed`; its dispatches are not samples.
+    timed_body: bool,
+}
+
+#[derive(serde::Serialize)]
+pub struct CommandMetric {
+    pub command: String,
+    pub count: u64,
+    pub errors: u64,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub max_ms: f64,
+    pub last_error: Option<String>,
+}
+
+#[derive(serde::Serialize, Clone)]
+pub struct RecentError {
+    pub at: String,
+    pub command: String,
+    pub message: String,
+}
+
+fn record(command: &str, elapsed: Duration, error: Option<String>, from_body: bool) {
+    if let Some(message) = &error {
+        note_error(command, message);
+    }
+
+    let mut stats = STATS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let entry = stats.entry(command.to_string()).or_default();
+    if from_body && !entry.timed_body {
+        // Drops the hand-off times recorded before the first body finished
+        *entry = Stats {
+            timed_body: true,
+            ..Stats::default()
+        };
+    } else if !from_body && entry.timed_body {
+        return;
+    }
+    entry.count += 1;
+    if entry.samples.len() == SAMPLE_LIMIT {
+        entry.samples.pop_front();
+    }
+    entry.samples.push_back(elapsed);
+    entry.max = entry.max.max(elapsed);
+    if let Some(error) = error {
+        entry.errors += 1;
+        entry.last_error = Some(error);
+    }
+}
+
+fn note_error(command: &str, message: &str) {
+    let mut recent = RECENT_ERRORS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if recent.len() == RECENT_ERRORS_LIMIT {
+        recent.pop_front();
+    }
+    recent.push_back(RecentError {
+        at: chrono::Local::now().to_rfc3339(),
+        command: command.to_string(),
+        message: message.to_string(),
+    });
+}
+
+/// Runs `invoke` through `handler` and records how long the dispatch took. Sync
+/// commands run to completion inside it, answer included, so this is their full
+/// duration and their error; async commands are only handed off here and time
+/// their own bodies with `timed`.
+pub fn measure<R: tauri::Runtime>(
+    invoke: Invoke<R>,
+    handler: impl FnOnce(Invoke<R>) -> bool,
+) -> bool {
+    let command = invoke.message.command().to_string();
+    DISPATCH_ERROR.with(|error| *error.borrow_mut() = Some(None));
+    let started = Instant::now();
+    let handled = handler(invoke);
+    let elapsed = started.elapsed();
+    let error = DISPATCH_ERROR
+        .with(|error| error.borrow_mut().take())
+        .flatten();
+    record(&command, elapsed, error, false);
+    handled
+}
+
+/// Called when an `AppError` is serialized: during a dispatch on this thread, that
+/// is the sync command answering with it. Errors serialized elsewhere (events,
+/// async commands) are not attributed here.
+pub fn note_dispatch_error(message: impl FnOnce() -> String) {
+    DISPATCH_ERROR.with(|error| {
+        if let Some(error) = error.borrow_mut().as_mut() {
+            *error = Some(message());
+        }
+    });
+}
+
+/// Body of an async command, timed until it answers and recorded with the error
+/// it answered with.
+pub async fn timed<T, E: std::fmt::Display>(
+    command: &str,
+    body: impl Future<Output = Result<T, E>>,
+) -> Result<T, E> {
+    let started = Instant::now();
+    let result = body.await;
+    let error = result.as_ref().err().map(|e| e.to_string());
+    record(command, started.elapsed(), error, true);
+    result
+}
+
+/// Errors answered to the frontend since the app started, newest first.
+pub fn recent_errors() -> Vec<RecentError> {
+    let recent = RECENT_ERRORS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    recent.iter().rev().cloned().collect()
+}
+
+fn percentile(sorted: &[Duration], fraction: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let index = ((sorted.len() - 1) as f64 * fraction).round() as usize;
+    sorted[index].as_secs_f64() * 1000.0
+}
+
+/// Slowest commands (by p95) first.
+#[tauri::command]
+pub fn get_command_metrics() -> Vec<CommandMetric> {
+    let stats = STATS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let mut list: Vec<CommandMetric> = stats
+        .iter()
+        .map(|(command, stats)| {
+            let mut sorted: Vec<Duration> = stats.samples.iter().copied().collect();
+            sorted.sort();
+            CommandMetric {
+                command: command.clone(),
+                count: stats.count,
+                errors: stats.errors,
+                p50_ms: percentile(&sorted, 0.5),
+                p95_ms: percentile(&sorted, 0.95),
+                max_ms: stats.max.as_secs_f64() * 1000.0,
+                last_error: stats.last_error.clone(),
+            }
+        })
+        .collect();
+    list.sort_by(|a, b| b.p95_ms.total_cmp(&a.p95_ms));
+    list
+}
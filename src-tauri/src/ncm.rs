@@ -0,0 +1,416 @@
+// ── NCM Module ─────────────────────────────────────────────────
+use crate::error::AppError;
+use chrono::{Datelike, NaiveDate};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde_json::Value;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::Manager;
+
+/// Full Nomenclatura Comum do Mercosul as published by Siscomex (a few MB of JSON).
+const TABLE_URL: &str =
+    "https://portalunico.siscomex.gov.br/classif/api/publico/nomenclatura/download/json";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+/// Camex changes the table a few times a year.
+const REFRESH_AFTER_DAYS: i64 = 30;
+const MAX_RESULTS: usize = 50;
+
+/// Local copy of the NCM table, downloaded the first time it is needed. Also
+/// remembers the IPI rates seen on queried NFes, the only rate source at hand.
+pub struct NcmTable {
+    conn: Mutex<Connection>,
+    downloading: AtomicBool,
+}
+
+#[derive(serde::Serialize)]
+pub struct NcmInfo {
+    /// Formatted as `0000.00.00`.
+    pub code: String,
+    pub description: String,
+    /// Chapter, heading and subheading descriptions down to this code.
+    pub full_description: String,
+    /// `YYYY-MM-DD`.
+    pub valid_from: Option<String>,
+    /// `None` while the code is in force.
+    pub valid_until: Option<String>,
+    pub valid: bool,
+    pub hints: Vec<String>,
+}
+
+fn digits(code: &str) -> String {
+    code.chars().filter(char::is_ascii_digit).collect()
+}
+
+fn format_code(code: &str) -> String {
+    if code.len() == 8 {
+        format!("{}.{}.{}", &code[..4], &code[4..6], &code[6..])
+    } else {
+        code.to_string()
+    }
+}
+
+/// Siscomex dates are `dd/mm/yyyy`, with 31/12/9999 for "no end".
+fn parse_date(value: Option<&str>) -> Option<String> {
+    let date = NaiveDate::parse_from_str(value?.trim(), "%d/%m/%Y").ok()?;
+    (date.year() != 9999).then(|| date.format("%Y-%m-%d").to_string())
+}
+
+/// Descriptions carry their level as leading dashes ("-- Reprodutores de raça pura").
+fn clean_description(value: &str) -> String {
+    value
+        .trim_start_matches(|c: char| c == '-' || c == '–' || c.is_whitespace())
+        .trim()
+        .to_string()
+}
+
+/// NCM as written on an NFe: 8 digits, 00000000 for services and items outside
+/// the nomenclature.
+fn check_format(ncm: &str) -> Option<String> {
+    if ncm.is_empty() {
+        return Some("NCM não informado".into());
+    }
+    if ncm.len() != 8 || !ncm.chars().all(|c| c.is_ascii_digit()) {
+        return Some(format!("NCM {} fora do formato de 8 dígitos", ncm));
+    }
+    None
+}
+
+async fn download() -> Result<Vec<(String, String, Option<String>, Option<String>)>, AppError> {
+    let client = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .user_agent(concat!("AdcontecUtil/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .map_err(|e| AppError::internal("Falha ao criar cliente HTTP", e))?;
+    let response = client.get(TABLE_URL).send().await.map_err(|e| {
+        AppError::ServiceUnavailable("Falha ao baixar a tabela NCM".into()).with_details(e)
+    })?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(AppError::ServiceUnavailable(format!(
+            "Siscomex indisponível ({})",
+            status
+        )));
+    }
+    let body = response.text().await.map_err(|e| {
+        AppError::ServiceUnavailable("Falha ao baixar a tabela NCM".into()).with_details(e)
+    })?;
+
+    let json: Value = serde_json::from_str(body.trim_start_matches('\u{feff}'))
+        .map_err(|e| AppError::internal("Tabela NCM inválida", e))?;
+    let entries: Vec<_> = json
+        .get("Nomenclaturas")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|item| {
+            let code = digits(item.get("Codigo")?.as_str()?);
+            let description = clean_description(item.get("Descricao")?.as_str()?);
+            (!code.is_empty()).then(|| {
+                (
+                    code,
+                    description,
+                    parse_date(item.get("Data_Inicio").and_then(Value::as_str)),
+                    parse_date(item.get("Data_Fim").and_then(Value::as_str)),
+                )
+            })
+        })
+        .collect();
+    if entries.is_empty() {
+        return Err(AppError::ServiceRejected(
+            "A tabela NCM baixada está vazia".into(),
+        ));
+    }
+    Ok(entries)
+}
+
+impl NcmTable {
+    pub fn open(app: &tauri::AppHandle) -> Result<Self, String> {
+        let conn = crate::db::open(app)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS ncm (
+                code TEXT PRIMARY KEY,
+                description TEXT NOT NULL,
+                valid_from TEXT,
+                valid_until TEXT
+            );
+            CREATE TABLE IF NOT EXISTS ncm_meta (
+                id INTEGER PRIMARY KEY CHECK (id = 1),
+                fetched_at TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS ncm_ipi_seen (
+                code TEXT NOT NULL,
+                rate TEXT NOT NULL,
+                times INTEGER NOT NULL DEFAULT 1,
+                PRIMARY KEY (code, rate)
+            );",
+        )
+        .map_err(|e| format!("Falha ao criar tabela NCM: {}", e))?;
+
+        Ok(NcmTable {
+            conn: Mutex::new(conn),
+            downloading: AtomicBool::new(false),
+        })
+    }
+
+    /// `None` when never downloaded, `Some(true)` when due for a refresh.
+    fn staleness(&self) -> Option<bool> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            "SELECT fetched_at < datetime('now', ?1) FROM ncm_meta WHERE id = 1",
+            params![format!("-{} days", REFRESH_AFTER_DAYS)],
+            |row| row.get(0),
+        )
+        .optional()
+        .ok()
+        .flatten()
+    }
+
+    fn replace(
+        &self,
+        entries: &[(String, String, Option<String>, Option<String>)],
+    ) -> Result<(), AppError> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn
+            .transaction()
+            .map_err(|e| AppError::internal("Falha ao salvar tabela NCM", e))?;
+        tx.execute("DELETE FROM ncm", [])
+            .map_err(|e| AppError::internal("Falha ao salvar tabela NCM", e))?;
+        {
+            let mut stmt = tx
+                .prepare(
+                    "INSERT OR REPLACE INTO ncm (code, description, valid_from, valid_until)
+                     VALUES (?1, ?2, ?3, ?4)",
+                )
+                .map_err(|e| AppError::internal("Falha ao salvar tabela NCM", e))?;
+            for (code, description, valid_from, valid_until) in entries {
+                stmt.execute(params![code, description, valid_from, valid_until])
+                    .map_err(|e| AppError::internal("Falha ao salvar tabela NCM", e))?;
+            }
+        }
+        tx.execute(
+            "INSERT OR REPLACE INTO ncm_meta (id, fetched_at) VALUES (1, datetime('now'))",
+            [],
+        )
+        .map_err(|e| AppError::internal("Falha ao salvar tabela NCM", e))?;
+        tx.commit()
+            .map_err(|e| AppError::internal("Falha ao salvar tabela NCM", e))
+    }
+
+    /// Downloads the table when missing or old. An old copy keeps being used if
+    /// Siscomex is unreachable; only a missing table is an error.
+    async fn ensure_loaded(&self) -> Result<(), AppError> {
+        let staleness = self.staleness();
+        if staleness == Some(false) {
+            return Ok(());
+        }
+        if self.downloading.swap(true, Ordering::SeqCst) {
+            return match staleness {
+                Some(_) => Ok(()),
+                None => Err(AppError::ServiceUnavailable(
+                    "A tabela NCM está sendo baixada, tente novamente em instantes".into(),
+                )),
+            };
+        }
+
+        let result = match download().await {
+            Ok(entries) => self.replace(&entries),
+            Err(e) => Err(e),
+        };
+        self.downloading.store(false, Ordering::SeqCst);
+        match (result, staleness) {
+            (Err(e), None) => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    fn find(
+        &self,
+        code: &str,
+    ) -> Result<Option<(String, Option<String>, Option<String>)>, AppError> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            "SELECT description, valid_from, valid_until FROM ncm WHERE code = ?1",
+            params![code],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .optional()
+        .map_err(|e| AppError::internal("Falha ao consultar tabela NCM", e))
+    }
+
+    /// Chapter (2), heading (4) and subheading (5, 6, 7) descriptions above `code`.
+    fn full_description(&self, code: &str) -> Result<String, AppError> {
+        let mut parts = Vec::new();
+        for len in [2, 4, 5, 6, 7, 8] {
+            if len > code.len() {
+                break;
+            }
+            if let Some((description, _, _)) = self.find(&code[..len])? {
+                parts.push(description);
+            }
+        }
+        Ok(parts.join(" › "))
+    }
+
+    fn hints(&self, code: &str) -> Result<Vec<String>, AppError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare("SELECT rate, times FROM ncm_ipi_seen WHERE code = ?1 ORDER BY times DESC")
+            .map_err(|e| AppError::internal("Falha ao consultar tabela NCM", e))?;
+        let seen: Vec<(String, i64)> = stmt
+            .query_map(params![code], |row| Ok((row.get(0)?, row.get(1)?)))
+            .and_then(Iterator::collect)
+            .map_err(|e| AppError::internal("Falha ao consultar tabela NCM", e))?;
+
+        let mut hints: Vec<String> = seen
+            .iter()
+            .map(|(rate, times)| {
+                format!(
+                    "IPI de {}% visto em {} item(ns) de NFe consultadas",
+                    rate, times
+                )
+            })
+            .collect();
+        if seen.is_empty() {
+            hints.push("Alíquota de IPI: consulte a TIPI".into());
+        }
+        hints.push("Imposto de Importação: consulte a TEC (Tarifa Externa Comum)".into());
+        Ok(hints)
+    }
+
+    fn info(&self, code: &str) -> Result<Option<NcmInfo>, AppError> {
+        let Some((description, valid_from, valid_until)) = self.find(code)? else {
+            return Ok(None);
+        };
+        let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+        Ok(Some(NcmInfo {
+            code: format_code(code),
+            full_description: self.full_description(code)?,
+            valid: valid_from.as_deref().map_or(true, |from| *from <= *today)
+                && valid_until
+                    .as_deref()
+                    .map_or(true, |until| *until >= *today),
+            hints: self.hints(code)?,
+            description,
+            valid_from,
+            valid_until,
+        }))
+    }
+
+    fn search(&self, query: &str) -> Result<Vec<String>, AppError> {
+        let is_code = query
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == '.' || c.is_whitespace());
+        let conn = self.conn.lock().unwrap();
+        let (sql, pattern) = if is_code {
+            (
+                "SELECT code FROM ncm WHERE code LIKE ?1 ORDER BY code LIMIT ?2",
+                format!("{}%", digits(query)),
+            )
+        } else {
+            (
+                "SELECT code FROM ncm WHERE length(code) = 8 AND description LIKE ?1
+                 ORDER BY code LIMIT ?2",
+                format!("%{}%", query.trim()),
+            )
+        };
+        let mut stmt = conn
+            .prepare(sql)
+            .map_err(|e| AppError::internal("Falha ao consultar tabela NCM", e))?;
+        let codes = stmt
+            .query_map(params![pattern, MAX_RESULTS as i64], |row| row.get(0))
+            .and_then(Iterator::collect)
+            .map_err(|e| AppError::internal("Falha ao consultar tabela NCM", e))?;
+        Ok(codes)
+    }
+
+    fn record_ipi(&self, code: &str, rate: &str) {
+        let conn = self.conn.lock().unwrap();
+        let _ = conn.execute(
+            "INSERT INTO ncm_ipi_seen (code, rate) VALUES (?1, ?2)
+             ON CONFLICT(code, rate) DO UPDATE SET times = times + 1",
+            params![code, rate],
+        );
+    }
+}
+
+/// Checks the NCM of every product against the table in force on the issue date
+/// and sets `ncm_warning` on the ones that fail. The table is downloaded or
+/// refreshed in the background so the DANFE is not held up; until then only the
+/// format is checked.
+pub(crate) fn validate_products(app: &tauri::AppHandle, data: &mut crate::nfe::NfeData) {
+    let table = app.try_state::<NcmTable>();
+    let loaded = match &table {
+        Some(table) => {
+            let staleness = table.staleness();
+            if staleness != Some(false) {
+                let app = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = app.state::<NcmTable>().ensure_loaded().await {
+                        crate::app_log::error(format!("Falha ao atualizar tabela NCM: {}", e));
+                    }
+                });
+            }
+            staleness.is_some()
+        }
+        None => false,
+    };
+    let issued = data.data_emissao.get(..10).unwrap_or_default().to_string();
+
+    for product in &mut data.produtos {
+        if let Some(warning) = check_format(&product.ncm) {
+            product.ncm_warning = Some(warning);
+            continue;
+        }
+        let Some(table) = table.as_ref().filter(|_| loaded) else {
+            continue;
+        };
+        if product.ncm == "00000000" {
+            continue;
+        }
+        product.ncm_warning = match table.find(&product.ncm) {
+            Ok(None) => Some(format!("NCM {} não existe na tabela vigente", product.ncm)),
+            Ok(Some((_, _, Some(until)))) if !issued.is_empty() && until < issued => {
+                Some(format!("NCM {} extinto desde {}", product.ncm, until))
+            }
+            Ok(Some((_, Some(from), _))) if !issued.is_empty() && from > issued => Some(format!(
+                "NCM {} só vigora a partir de {}",
+                product.ncm, from
+            )),
+            _ => None,
+        };
+        if product.ncm_warning.is_none() && !product.aliq_ipi.is_empty() {
+            table.record_ipi(&product.ncm, &product.aliq_ipi);
+        }
+    }
+}
+
+/// Looks up an NCM by code (full or prefix, with or without dots) or by words of
+/// its description. Downloads the table on first use.
+#[tauri::command]
+pub async fn lookup_ncm(
+    app: tauri::AppHandle,
+    code_or_description: String,
+) -> Result<Vec<NcmInfo>, AppError> {
+    crate::metrics::timed("lookup_ncm", async move {
+        let query = code_or_description.trim();
+        if query.chars().count() < 2 {
+            return Err(AppError::InvalidInput(
+                "Informe ao menos 2 caracteres do código ou da descrição".into(),
+            ));
+        }
+        let table = app
+            .try_state::<NcmTable>()
+            .ok_or_else(|| AppError::Unsupported("Tabela NCM indisponível".into()))?;
+        table.ensure_loaded().await?;
+
+        let mut results = Vec::new();
+        for code in table.search(query)? {
+            if let Some(info) = table.info(&code)? {
+                results.push(info);
+            }
+        }
+        Ok(results)
+    })
+    .await
+}
@@ -1,7 +1,15 @@
 // ── NFe Query Module ───────────────────────────────────────────
+use crate::barcode::{self, Symbology};
+use crate::error::AppError;
+use rusqlite::{params, Connection, OptionalExtension};
 use std::io::Write;
+use std::sync::Mutex;
 use tauri::Manager;
 
+/// A note only changes through events (e.g. cancellation), so a week-old copy is
+/// still good; repeated queries of the same key make SEFAZ answer cStat 656.
+const CACHE_TTL_DAYS: i64 = 7;
+
 #[derive(serde::Serialize, Clone, Default)]
 pub struct NfeParty {
     pub name: String,
@@ -39,6 +47,8 @@ pub struct NfeProduto {
     pub aliq_icms: String,
     pub aliq_ipi: String,
     pub v_tot_trib: String,
+    /// Set when the NCM is malformed, unknown or not in force on the issue date.
+    pub ncm_warning: Option<String>,
 }
 
 #[derive(serde::Serialize, Clone, Default)]
@@ -112,33 +122,184 @@ pub struct NfeData {
     pub protocolo: String,
 }
 
-#[tauri::command]
-pub async fn query_nfe(thumbprint: String, access_key: String) -> Result<String, String> {
-    query_nfe_impl(thumbprint, access_key).await
+/// Note XMLs and their DANFEs by access key and the company whose certificate
+/// fetched them, so consulting the same note again does not hit SEFAZ. Another
+/// company asking for the note still goes through SEFAZ's authorization.
+pub struct NfeCache {
+    conn: Mutex<Connection>,
 }
 
-#[cfg(windows)]
-async fn query_nfe_impl(thumbprint: String, access_key: String) -> Result<String, String> {
+struct CachedNfe {
+    raw_xml: String,
+    html: String,
+}
+
+impl NfeCache {
+    pub fn open(app: &tauri::AppHandle) -> Result<Self, String> {
+        let conn = crate::db::open(app)?;
+        // Tables from before entries were keyed by CNPJ can't be told apart per
+        // company; being only a cache, they are dropped instead of migrated
+        let keyed_by_cnpj: bool = conn
+            .query_row(
+                "SELECT COUNT(*) = 2 FROM pragma_table_info('nfe_cache') WHERE pk > 0",
+                [],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("Falha ao verificar cache de NFe: {}", e))?;
+        if !keyed_by_cnpj {
+            conn.execute_batch("DROP TABLE IF EXISTS nfe_cache;")
+                .map_err(|e| format!("Falha ao atualizar cache de NFe: {}", e))?;
+        }
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS nfe_cache (
+                chave TEXT NOT NULL,
+                cnpj TEXT NOT NULL,
+                raw_xml TEXT NOT NULL,
+                html TEXT NOT NULL,
+                fetched_at TEXT NOT NULL DEFAULT (datetime('now')),
+                PRIMARY KEY (chave, cnpj)
+            );",
+        )
+        .map_err(|e| format!("Falha ao criar cache de NFe: {}", e))?;
+        Ok(NfeCache {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    fn cached(&self, chave: &str, cnpj: &str) -> Option<CachedNfe> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            "SELECT raw_xml, html FROM nfe_cache
+             WHERE chave = ?1 AND cnpj = ?2 AND fetched_at >= datetime('now', ?3)",
+            params![chave, cnpj, format!("-{} days", CACHE_TTL_DAYS)],
+            |row| {
+                Ok(CachedNfe {
+                    raw_xml: row.get(0)?,
+                    html: row.get(1)?,
+                })
+            },
+        )
+        .optional()
+        .ok()?
+    }
+
+    fn store(&self, chave: &str, cnpj: &str, raw_xml: &str, html: &str) -> Result<(), AppError> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT OR REPLACE INTO nfe_cache (chave, cnpj, raw_xml, html, fetched_at)
+             VALUES (?1, ?2, ?3, ?4, datetime('now'))",
+            params![chave, cnpj, raw_xml, html],
+        )
+        .map_err(|e| AppError::internal("Falha ao salvar consulta de NFe", e))?;
+        Ok(())
+    }
+
+    /// Drops one key for every company, or every entry without one. Returns how many were removed.
+    fn invalidate(&self, chave: Option<&str>) -> Result<usize, AppError> {
+        let conn = self.conn.lock().unwrap();
+        match chave {
+            Some(chave) => conn.execute("DELETE FROM nfe_cache WHERE chave = ?1", [chave]),
+            None => conn.execute("DELETE FROM nfe_cache", []),
+        }
+        .map_err(|e| AppError::internal("Falha ao limpar cache de NFe", e))
+    }
+}
+
+fn validate_access_key(access_key: &str) -> Result<(), AppError> {
     if access_key.len() != 44 || !access_key.chars().all(|c| c.is_ascii_digit()) {
-        return Err("Chave de acesso deve conter exatamente 44 dígitos numéricos".into());
+        return Err(AppError::InvalidInput(
+            "Chave de acesso deve conter exatamente 44 dígitos numéricos".into(),
+        ));
     }
+    Ok(())
+}
+
+/// Answers from the local cache for 7 days unless `refresh` is set; the DANFE is
+/// written to a new temp file either way. Without a `thumbprint`, the certificate
+/// is the default set for `cnpj`.
+#[tauri::command]
+pub async fn query_nfe(
+    app: tauri::AppHandle,
+    thumbprint: Option<String>,
+    cnpj: Option<String>,
+    access_key: String,
+    refresh: Option<bool>,
+) -> Result<String, AppError> {
+    crate::metrics::timed("query_nfe", async move {
+        validate_access_key(&access_key)?;
+        // Resolved before the cache is read: a cached note is only served to the company
+        // whose certificate SEFAZ authorized for it. The store lookup may list every
+        // certificate to match a SHA-256 thumbprint or a CNPJ
+        let handle = app.clone();
+        let (thumbprint, owner) = tauri::async_runtime::spawn_blocking(move || {
+            match (thumbprint.filter(|t| !t.trim().is_empty()), cnpj) {
+                (Some(thumbprint), _) => {
+                    let thumbprint = crate::sha1_thumbprint(&thumbprint)?;
+                    let owner = crate::certs_impl()?
+                        .into_iter()
+                        .find(|cert| cert.thumbprint == thumbprint)
+                        .map(|cert| cert.cnpj)
+                        .unwrap_or_default();
+                    Ok((thumbprint, owner))
+                }
+                (None, Some(cnpj)) => Ok((
+                    crate::cert_defaults::resolve_thumbprint(&handle, &cnpj)?,
+                    crate::clients::normalize_cnpj(&cnpj)?,
+                )),
+                (None, None) => Err(AppError::InvalidInput(
+                    "Informe o certificado ou o CNPJ da empresa".into(),
+                )),
+            }
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao localizar certificado", e))??;
+
+        if !refresh.unwrap_or(false) && !owner.is_empty() {
+            let cached = app
+                .try_state::<NfeCache>()
+                .and_then(|cache| cache.cached(&access_key, &owner));
+            if let Some(cached) = cached {
+                let path = save_files_to_temp(&cached.html, &cached.raw_xml, &access_key)?;
+                crate::clients::record_nfe_query(&app, &access_key, &owner);
+                return Ok(path);
+            }
+        }
+        query_nfe_impl(&app, thumbprint, access_key).await
+    })
+    .await
+}
 
+/// Forgets the cached note for `access_key`, or all cached notes without one.
+#[tauri::command]
+pub fn invalidate_nfe_cache(
+    cache: tauri::State<'_, NfeCache>,
+    access_key: Option<String>,
+) -> Result<usize, AppError> {
+    cache.invalidate(access_key.as_deref())
+}
+
+#[cfg(any(windows, target_os = "linux"))]
+async fn query_nfe_impl(
+    app: &tauri::AppHandle,
+    thumbprint: String,
+    access_key: String,
+) -> Result<String, AppError> {
     let uf_code: u32 = access_key[..2]
         .parse()
-        .map_err(|_| "Código UF inválido na chave de acesso".to_string())?;
+        .map_err(|_| AppError::InvalidInput("Código UF inválido na chave de acesso".into()))?;
 
     let (mut pfx_bytes, password, cnpj) = export_cert_pfx(&thumbprint)?;
 
     if cnpj.is_empty() {
         pfx_bytes.fill(0);
-        return Err("Não foi possível extrair o CNPJ do certificado selecionado. Verifique se é um e-CNPJ (A1).".into());
+        return Err(AppError::InvalidInput("Não foi possível extrair o CNPJ do certificado selecionado. Verifique se é um e-CNPJ (A1).".into()));
     }
 
     let soap_xml = build_soap_request(&access_key, &cnpj, uf_code, "1");
     let endpoint = "https://www1.nfe.fazenda.gov.br/NFeDistribuicaoDFe/NFeDistribuicaoDFe.asmx";
 
     let identity = reqwest::Identity::from_pkcs12_der(&pfx_bytes, &password)
-        .map_err(|e| format!("Falha ao criar identidade TLS: {}", e))?;
+        .map_err(|e| AppError::internal("Falha ao criar identidade TLS", e))?;
 
     pfx_bytes.fill(0);
 
@@ -146,7 +307,7 @@ async fn query_nfe_impl(thumbprint: String, access_key: String) -> Result<String
         .identity(identity)
         .timeout(std::time::Duration::from_secs(30))
         .build()
-        .map_err(|e| format!("Falha ao criar cliente HTTP: {}", e))?;
+        .map_err(|e| AppError::internal("Falha ao criar cliente HTTP", e))?;
 
     let response = client
         .post(endpoint)
@@ -154,13 +315,14 @@ async fn query_nfe_impl(thumbprint: String, access_key: String) -> Result<String
         .body(soap_xml)
         .send()
         .await
-        .map_err(|e| format!("Falha na comunicação com SEFAZ: {}", e))?;
+        .map_err(|e| {
+            AppError::ServiceUnavailable("Falha na comunicação com SEFAZ".into()).with_details(e)
+        })?;
 
     let status = response.status();
-    let body = response
-        .text()
-        .await
-        .map_err(|e| format!("Falha ao ler resposta: {}", e))?;
+    let body = response.text().await.map_err(|e| {
+        AppError::ServiceUnavailable("Falha ao ler resposta da SEFAZ".into()).with_details(e)
+    })?;
 
     if !status.is_success() {
         let preview = if body.len() > 500 {
@@ -168,59 +330,76 @@ async fn query_nfe_impl(thumbprint: String, access_key: String) -> Result<String
         } else {
             &body
         };
-        return Err(format!("SEFAZ retornou status {}: {}", status, preview));
+        return Err(
+            AppError::ServiceUnavailable(format!("SEFAZ retornou status {}", status))
+                .with_details(preview),
+        );
     }
 
-    let (nfe_data, raw_xml) = parse_sefaz_response(&body, &access_key)?;
+    let (mut nfe_data, raw_xml) = parse_sefaz_response(&body, &access_key)?;
+    crate::ncm::validate_products(app, &mut nfe_data);
     let html = generate_danfe_html(&nfe_data);
     let path = save_files_to_temp(&html, &raw_xml, &access_key)?;
+    if let Some(cache) = app.try_state::<NfeCache>() {
+        // The DANFE is already on disk; a failed cache write only costs a new query
+        if let Err(e) = cache.store(&access_key, &cnpj, &raw_xml, &html) {
+            crate::app_log::error(e);
+        }
+    }
+    crate::clients::record_nfe_query(app, &access_key, &cnpj);
 
     Ok(path)
 }
 
-#[cfg(not(windows))]
-async fn query_nfe_impl(_thumbprint: String, _access_key: String) -> Result<String, String> {
-    Err("Consulta NFe disponível apenas no Windows".into())
+#[cfg(not(any(windows, target_os = "linux")))]
+async fn query_nfe_impl(
+    _app: &tauri::AppHandle,
+    _thumbprint: String,
+    _access_key: String,
+) -> Result<String, AppError> {
+    Err(AppError::Unsupported(
+        "Consulta NFe disponível apenas no Windows e Linux".into(),
+    ))
 }
 
 #[tauri::command]
-pub fn open_danfe(file_path: String) -> Result<(), String> {
+pub fn open_danfe(file_path: String) -> Result<(), AppError> {
     open_danfe_impl(&file_path)
 }
 
 #[cfg(windows)]
-fn open_danfe_impl(file_path: &str) -> Result<(), String> {
+fn open_danfe_impl(file_path: &str) -> Result<(), AppError> {
     std::process::Command::new("cmd")
         .args(["/C", "start", "", file_path])
         .spawn()
-        .map_err(|e| format!("Falha ao abrir navegador: {}", e))?;
+        .map_err(|e| AppError::io("Falha ao abrir navegador", e))?;
     Ok(())
 }
 
 #[cfg(not(windows))]
-fn open_danfe_impl(file_path: &str) -> Result<(), String> {
+fn open_danfe_impl(file_path: &str) -> Result<(), AppError> {
     std::process::Command::new("xdg-open")
         .arg(file_path)
         .spawn()
-        .map_err(|e| format!("Falha ao abrir navegador: {}", e))?;
+        .map_err(|e| AppError::io("Falha ao abrir navegador", e))?;
     Ok(())
 }
 
 #[tauri::command]
-pub fn download_danfe(source_path: String, access_key: String) -> Result<String, String> {
+pub fn download_danfe(source_path: String, access_key: String) -> Result<String, AppError> {
     let home = std::env::var("USERPROFILE")
         .or_else(|_| std::env::var("HOME"))
-        .map_err(|_| "Não foi possível localizar a pasta do usuário".to_string())?;
+        .map_err(|_| AppError::NotFound("Não foi possível localizar a pasta do usuário".into()))?;
     let downloads = std::path::PathBuf::from(home).join("Downloads");
     if !downloads.exists() {
         std::fs::create_dir_all(&downloads)
-            .map_err(|e| format!("Falha ao criar pasta Downloads: {}", e))?;
+            .map_err(|e| AppError::io("Falha ao criar pasta Downloads", e))?;
     }
 
     let filename_html = format!("DANFE_{}.html", &access_key[..20.min(access_key.len())]);
     let dest_html = downloads.join(filename_html);
     std::fs::copy(&source_path, &dest_html)
-        .map_err(|e| format!("Falha ao salvar arquivo HTML: {}", e))?;
+        .map_err(|e| AppError::io("Falha ao salvar arquivo HTML", e))?;
 
     let source_xml = std::path::PathBuf::from(&source_path).with_extension("xml");
     if source_xml.exists() {
@@ -233,27 +412,20 @@ pub fn download_danfe(source_path: String, access_key: String) -> Result<String,
 }
 
 #[tauri::command]
-pub async fn query_nfe_portal(app: tauri::AppHandle, access_key: String) -> Result<(), String> {
-    if let Some(existing) = app.get_webview_window("sefaz-nfe") {
-        let _: Result<(), _> = existing.close();
-    }
-
+pub async fn query_nfe_portal(app: tauri::AppHandle, access_key: String) -> Result<(), AppError> {
+    crate::metrics::timed("query_nfe_portal", async move {
     let url = "https://www.nfe.fazenda.gov.br/portal/consultaRecaptcha.aspx?tipoConsulta=resumo&tipoConteudo=7PhJ+gAVw2g=";
     let init_script = build_portal_init_script(&access_key);
 
-    tauri::WebviewWindowBuilder::new(
+    crate::portal::open_window(
         &app,
         "sefaz-nfe",
-        tauri::WebviewUrl::External(url.parse().unwrap()),
+        "Consulta NFe - SEFAZ (Resumo)",
+        url,
+        &init_script,
     )
-    .title("Consulta NFe - SEFAZ (Resumo)")
-    .inner_size(1024.0, 800.0)
-    .center()
-    .initialization_script(&init_script)
-    .build()
-    .map_err(|e| format!("Falha ao abrir janela de consulta: {}", e))?;
-
-    Ok(())
+})
+.await
 }
 
 fn build_portal_init_script(access_key: &str) -> String {
@@ -275,28 +447,40 @@ fn build_portal_init_script(access_key: &str) -> String {
 
 // ── Cert & Parser Helpers ─────────────────────────────────────
 
-#[cfg(windows)]
-fn export_cert_pfx(thumbprint: &str) -> Result<(Vec<u8>, String, String), String> {
+/// PFX with a throwaway random password, for handing the certificate to reqwest.
+#[cfg(any(windows, target_os = "linux"))]
+pub(crate) fn export_cert_pfx(thumbprint: &str) -> Result<(Vec<u8>, String, String), AppError> {
     use rand::Rng;
-    use windows_sys::Win32::Security::Cryptography::*;
 
     let password: String = rand::thread_rng()
         .sample_iter(&rand::distributions::Alphanumeric)
         .take(16)
         .map(char::from)
         .collect();
+    let (pfx, cnpj) = export_cert_pfx_with_password(thumbprint, &password)?;
+    Ok((pfx, password, cnpj))
+}
+
+/// Certificate and private key as PFX bytes protected by `password`, plus the
+/// CNPJ found in the certificate (empty if none).
+#[cfg(windows)]
+pub(crate) fn export_cert_pfx_with_password(
+    thumbprint: &str,
+    password: &str,
+) -> Result<(Vec<u8>, String), AppError> {
+    use windows_sys::Win32::Security::Cryptography::*;
 
     unsafe {
         let store_name: Vec<u16> = "MY\0".encode_utf16().collect();
         let store = CertOpenSystemStoreW(0, store_name.as_ptr());
         if store.is_null() {
-            return Err("Falha ao abrir repositório".into());
+            return Err(AppError::Internal("Falha ao abrir repositório".into()));
         }
 
         let cert = find_cert_by_thumbprint_raw(store, thumbprint);
         if cert.is_null() {
             CertCloseStore(store, 0);
-            return Err("Certificado não encontrado".into());
+            return Err(AppError::NotFound("Certificado não encontrado".into()));
         }
 
         let cnpj = extract_cnpj_from_cert(cert);
@@ -325,17 +509,27 @@ fn export_cert_pfx(thumbprint: &str) -> Result<(Vec<u8>, String, String), String
             std::ptr::null_mut(),
             0x0004 | 0x0002,
         );
+        let error = std::io::Error::last_os_error();
 
         CertCloseStore(mem_store, 0);
         CertCloseStore(store, 0);
 
         if ok == 0 {
-            return Err("Falha ao exportar PFX".into());
+            // Usually a key marked as non-exportable, or an A3 token
+            return Err(AppError::Internal("Falha ao exportar PFX".into()).with_details(error));
         }
-        Ok((pfx_data, password, cnpj))
+        Ok((pfx_data, cnpj))
     }
 }
 
+#[cfg(target_os = "linux")]
+pub(crate) fn export_cert_pfx_with_password(
+    thumbprint: &str,
+    password: &str,
+) -> Result<(Vec<u8>, String), AppError> {
+    crate::cert_store::export_pfx(thumbprint, password)
+}
+
 #[cfg(windows)]
 unsafe fn find_cert_by_thumbprint_raw(
     store: *mut std::ffi::c_void,
@@ -433,19 +627,24 @@ fn build_soap_request(access_key: &str, cnpj: &str, uf_code: u32, tp_amb: &str)
     )
 }
 
-fn parse_sefaz_response(soap_xml: &str, access_key: &str) -> Result<(NfeData, String), String> {
+fn parse_sefaz_response(soap_xml: &str, access_key: &str) -> Result<(NfeData, String), AppError> {
     let cstat = extract_tag_content(soap_xml, "cStat")
         .unwrap_or_default()
         .trim()
         .to_string();
     if cstat != "138" {
         let xmotivo = extract_tag_content(soap_xml, "xMotivo").unwrap_or_default();
-        return Err(format!("SEFAZ: {} - {}", cstat, xmotivo));
+        return Err(AppError::ServiceRejected(format!(
+            "SEFAZ: {} - {}",
+            cstat, xmotivo
+        )));
     }
 
     let doc_zips = extract_all_doc_zips(soap_xml);
     if doc_zips.is_empty() {
-        return Err("Nenhum documento encontrado na resposta da SEFAZ".into());
+        return Err(AppError::NotFound(
+            "Nenhum documento encontrado na resposta da SEFAZ".into(),
+        ));
     }
 
     let mut nfe_xml_raw = String::new();
@@ -471,7 +670,7 @@ fn parse_sefaz_response(soap_xml: &str, access_key: &str) -> Result<(NfeData, St
     Ok((data, nfe_xml_raw))
 }
 
-fn extract_tag_content(xml: &str, tag: &str) -> Option<String> {
+pub(crate) fn extract_tag_content(xml: &str, tag: &str) -> Option<String> {
     let open = format!("<{}", tag);
     let close = format!("</{}>", tag);
     let start = xml.find(&open)?;
@@ -480,7 +679,7 @@ fn extract_tag_content(xml: &str, tag: &str) -> Option<String> {
     Some(xml[tag_end..end].to_string())
 }
 
-fn extract_block(xml: &str, tag: &str) -> Option<String> {
+pub(crate) fn extract_block(xml: &str, tag: &str) -> Option<String> {
     let open = format!("<{}", tag);
     let close = format!("</{}>", tag);
     let start = xml.find(&open)?;
@@ -781,7 +980,7 @@ fn generate_danfe_html(data: &NfeData) -> String {
     // CSS Paisagem com Largura Total (~277mm no A4 Landscape)
     let css = r#"
     <style>
-        @import url('https://fonts.googleapis.com/css2?family=Libre+Barcode+128&family=Roboto+Condensed:wght@400;700&display=swap');
+        @import url('https://fonts.googleapis.com/css2?family=Roboto+Condensed:wght@400;700&display=swap');
         @page { size: A4 landscape; margin: 6mm; }
         
         * { box-sizing: border-box; -webkit-print-color-adjust: exact; }
@@ -841,13 +1040,8 @@ fn generate_danfe_html(data: &NfeData) -> String {
 
         /* Código de Barras */
         .barcode-container { flex: 1; display: flex; flex-direction: column; justify-content: center; align-items: center; padding: 2px; }
-        .barcode { 
-            font-family: 'Libre Barcode 128', cursive; 
-            font-size: 38pt; 
-            white-space: nowrap; 
-            transform: scaleX(1.1); /* Estica levemente para leitura */
-            transform-origin: center;
-        }
+        .barcode { width: 100%; }
+        .barcode svg { display: block; width: 100%; height: 13mm; }
 
         /* Tabela de Produtos */
         table { width: 100%; border-collapse: collapse; font-size: 7pt; margin-top: 0; table-layout: fixed; }
@@ -858,6 +1052,9 @@ fn generate_danfe_html(data: &NfeData) -> String {
         .t-left { text-align: left; }
         .t-right { text-align: right; }
         .t-center { text-align: center; }
+        .ncm-warning { background: #fff3cd; }
+        .warning-box { border: 1px solid #d39e00; background: #fff3cd; font-size: 8pt; padding: 3px 6px; margin-top: 4px; }
+        .warning-box ul { margin: 2px 0 0 16px; padding: 0; }
 
         /* Larguras Específicas (%) */
         .w-5 { width: 5%; } .w-10 { width: 10%; } .w-15 { width: 15%; } 
@@ -874,12 +1071,23 @@ fn generate_danfe_html(data: &NfeData) -> String {
     "#;
 
     let mut prods = String::new();
+    let mut ncm_warnings = String::new();
     for p in &data.produtos {
+        let ncm_cell = match &p.ncm_warning {
+            Some(warning) => {
+                ncm_warnings.push_str(&format!("<li>Item {}: {}</li>", p.num, warning));
+                format!(
+                    "<td class='t-center ncm-warning' title='{}'>{}</td>",
+                    warning, p.ncm
+                )
+            }
+            None => format!("<td class='t-center'>{}</td>", p.ncm),
+        };
         prods.push_str(&format!(
             "<tr>
                 <td class='t-center'>{}</td>
                 <td class='t-left content-wrap' style='white-space:normal'>{}</td>
-                <td class='t-center'>{}</td>
+                {}
                 <td class='t-center'>{}</td>
                 <td class='t-center'>{}</td>
                 <td class='t-center'>{}</td>
@@ -894,7 +1102,7 @@ fn generate_danfe_html(data: &NfeData) -> String {
             </tr>",
             p.code,
             p.description,
-            p.ncm,
+            ncm_cell,
             p.cst,
             p.cfop,
             p.unit,
@@ -909,6 +1117,19 @@ fn generate_danfe_html(data: &NfeData) -> String {
         ));
     }
 
+    // Shown on screen only; the printed DANFE stays as issued
+    let ncm_warning_box = if ncm_warnings.is_empty() {
+        String::new()
+    } else {
+        format!(
+            "<div class=\"warning-box no-print\"><b>Avisos de NCM</b><ul>{}</ul></div>",
+            ncm_warnings
+        )
+    };
+
+    // Code 128 (set C) of the chave; the DANFE still shows the key in full below it
+    let barcode_svg = barcode::svg(Symbology::Code128, &data.chave).unwrap_or_default();
+
     let barcode_script = r#"
     <script>
     (function() {
@@ -959,7 +1180,7 @@ fn generate_danfe_html(data: &NfeData) -> String {
 
             <div class="col flex-1">
                 <div class="barcode-container">
-                    <div class="barcode">{chave}</div>
+                    <div class="barcode">{barcode}</div>
                 </div>
                 <div class="row row-no-border">
                    <div class="col flex-1 row-no-border" style="padding-top:2px">
@@ -1040,6 +1261,7 @@ fn generate_danfe_html(data: &NfeData) -> String {
             <div class="col flex-1"><div class="label">PESO LÍQUIDO</div><div class="content right">{peso_l}</div></div>
         </div>
 
+        {ncm_warnings}
         <div class="section-header">DADOS DO PRODUTO / SERVIÇO</div>
         <div class="row row-no-border">
             <table>
@@ -1091,7 +1313,7 @@ fn generate_danfe_html(data: &NfeData) -> String {
         numero = data.numero,
         serie = data.serie,
         tipo_nf = data.tipo_nf,
-        chave = data.chave,
+        barcode = barcode_svg,
         chave_fmt = chave_formatada,
         emit_nome = data.emitente.name,
         emit_addr = emit_addr,
@@ -1134,6 +1356,7 @@ fn generate_danfe_html(data: &NfeData) -> String {
         peso_l = data.transporte.vol_peso_l,
         inf_cpl = data.info_adicional.inf_cpl,
         products = prods,
+        ncm_warnings = ncm_warning_box,
         scripts = barcode_script
     )
 }
@@ -1161,7 +1384,7 @@ fn format_cnpj_cpf(value: &str) -> String {
     }
 }
 
-fn save_files_to_temp(html: &str, raw_xml: &str, access_key: &str) -> Result<String, String> {
+fn save_files_to_temp(html: &str, raw_xml: &str, access_key: &str) -> Result<String, AppError> {
     use rand::Rng;
     let random: u64 = rand::thread_rng().gen();
     let temp_dir = std::env::temp_dir();
@@ -1169,18 +1392,18 @@ fn save_files_to_temp(html: &str, raw_xml: &str, access_key: &str) -> Result<Str
     let xml_filename = format!("danfe_{}_{}.xml", access_key, random);
     let xml_path = temp_dir.join(xml_filename);
     let mut xml_file =
-        std::fs::File::create(&xml_path).map_err(|e| format!("Erro ao criar XML: {}", e))?;
+        std::fs::File::create(&xml_path).map_err(|e| AppError::io("Erro ao criar XML", e))?;
     xml_file
         .write_all(raw_xml.as_bytes())
-        .map_err(|e| format!("Erro ao escrever XML: {}", e))?;
+        .map_err(|e| AppError::io("Erro ao escrever XML", e))?;
 
     let html_filename = format!("danfe_{}_{}.html", access_key, random);
     let html_path = temp_dir.join(html_filename);
     let mut html_file =
-        std::fs::File::create(&html_path).map_err(|e| format!("Erro ao criar HTML: {}", e))?;
+        std::fs::File::create(&html_path).map_err(|e| AppError::io("Erro ao criar HTML", e))?;
     html_file
         .write_all(html.as_bytes())
-        .map_err(|e| format!("Erro ao escrever HTML: {}", e))?;
+        .map_err(|e| AppError::io("Erro ao escrever HTML", e))?;
 
     Ok(html_path.to_string_lossy().to_string())
 }
@@ -0,0 +1,192 @@
+// ── Notes Module ───────────────────────────────────────────────
+use crate::error::AppError;
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension};
+use std::sync::Mutex;
+
+/// Markdown notes; the table comes from the plugin migrations like `todos`.
+pub struct Notes {
+    conn: Mutex<Connection>,
+}
+
+#[derive(serde::Serialize)]
+pub struct Note {
+    pub id: i64,
+    pub title: String,
+    /// Markdown source, rendered by the frontend.
+    pub body: String,
+    pub tags: Vec<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(serde::Deserialize)]
+pub struct NoteInput {
+    pub title: String,
+    #[serde(default)]
+    pub body: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+const SELECT_COLUMNS: &str = "SELECT id, title, body, tags, created_at, updated_at FROM notes";
+
+fn row_to_note(row: &rusqlite::Row) -> rusqlite::Result<Note> {
+    let tags: String = row.get(3)?;
+    Ok(Note {
+        id: row.get(0)?,
+        title: row.get(1)?,
+        body: row.get(2)?,
+        tags: serde_json::from_str(&tags).unwrap_or_default(),
+        created_at: row.get(4)?,
+        updated_at: row.get(5)?,
+    })
+}
+
+/// Trims the title and tags, dropping empty or repeated tags (case-insensitive).
+fn normalize_input(input: &NoteInput) -> Result<(String, String), AppError> {
+    let title = input.title.trim();
+    if title.is_empty() {
+        return Err(AppError::InvalidInput("A nota precisa de um título".into()));
+    }
+
+    let mut tags: Vec<String> = Vec::new();
+    for tag in input.tags.iter().map(|tag| tag.trim()) {
+        if !tag.is_empty() && !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            tags.push(tag.to_string());
+        }
+    }
+    let tags = serde_json::to_string(&tags)
+        .map_err(|e| AppError::internal("Falha ao salvar etiquetas da nota", e))?;
+    Ok((title.to_string(), tags))
+}
+
+impl Notes {
+    pub fn open(app: &tauri::AppHandle) -> Result<Self, String> {
+        Ok(Notes {
+            conn: Mutex::new(crate::db::open(app)?),
+        })
+    }
+
+    fn get(&self, id: i64) -> Result<Note, AppError> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            &format!("{} WHERE id = ?1", SELECT_COLUMNS),
+            params![id],
+            row_to_note,
+        )
+        .optional()
+        .map_err(|e| AppError::internal("Falha ao consultar nota", e))?
+        .ok_or_else(|| AppError::NotFound("Nota não encontrada".into()))
+    }
+}
+
+/// `query` matches title or body; `tag` keeps only notes carrying that tag.
+#[tauri::command]
+pub fn list_notes(
+    notes: tauri::State<'_, Notes>,
+    query: Option<String>,
+    tag: Option<String>,
+) -> Result<Vec<Note>, AppError> {
+    let mut conditions: Vec<&str> = Vec::new();
+    let mut values: Vec<Value> = Vec::new();
+
+    if let Some(query) = query.as_deref().map(str::trim).filter(|q| !q.is_empty()) {
+        let pattern = format!("%{}%", query);
+        conditions.push("(title LIKE ? OR body LIKE ?)");
+        values.push(Value::Text(pattern.clone()));
+        values.push(Value::Text(pattern));
+    }
+    if let Some(tag) = tag.as_deref().map(str::trim).filter(|t| !t.is_empty()) {
+        conditions
+            .push("EXISTS (SELECT 1 FROM json_each(notes.tags) WHERE value = ? COLLATE NOCASE)");
+        values.push(Value::Text(tag.to_string()));
+    }
+
+    let where_clause = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", conditions.join(" AND "))
+    };
+
+    let conn = notes.conn.lock().unwrap();
+    let mut stmt = conn
+        .prepare(&format!(
+            "{} {} ORDER BY updated_at DESC",
+            SELECT_COLUMNS, where_clause
+        ))
+        .map_err(|e| AppError::internal("Falha ao consultar notas", e))?;
+    let rows = stmt
+        .query_map(params_from_iter(values), row_to_note)
+        .map_err(|e| AppError::internal("Falha ao consultar notas", e))?;
+    rows.collect::<Result<_, _>>()
+        .map_err(|e| AppError::internal("Falha ao ler notas", e))
+}
+
+#[tauri::command]
+pub fn get_note(notes: tauri::State<'_, Notes>, id: i64) -> Result<Note, AppError> {
+    notes.get(id)
+}
+
+#[tauri::command]
+pub fn create_note(notes: tauri::State<'_, Notes>, input: NoteInput) -> Result<Note, AppError> {
+    let (title, tags) = normalize_input(&input)?;
+    let id = {
+        let conn = notes.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO notes (title, body, tags) VALUES (?1, ?2, ?3)",
+            params![title, input.body, tags],
+        )
+        .map_err(|e| AppError::internal("Falha ao salvar nota", e))?;
+        conn.last_insert_rowid()
+    };
+    notes.get(id)
+}
+
+#[tauri::command]
+pub fn update_note(
+    notes: tauri::State<'_, Notes>,
+    id: i64,
+    input: NoteInput,
+) -> Result<Note, AppError> {
+    let (title, tags) = normalize_input(&input)?;
+    {
+        let conn = notes.conn.lock().unwrap();
+        let changed = conn
+            .execute(
+                "UPDATE notes SET title = ?1, body = ?2, tags = ?3, updated_at = datetime('now')
+                 WHERE id = ?4",
+                params![title, input.body, tags, id],
+            )
+            .map_err(|e| AppError::internal("Falha ao salvar nota", e))?;
+        if changed == 0 {
+            return Err(AppError::NotFound("Nota não encontrada".into()));
+        }
+    }
+    notes.get(id)
+}
+
+#[tauri::command]
+pub fn delete_note(notes: tauri::State<'_, Notes>, id: i64) -> Result<(), AppError> {
+    let conn = notes.conn.lock().unwrap();
+    conn.execute("DELETE FROM notes WHERE id = ?1", params![id])
+        .map_err(|e| AppError::internal("Falha ao excluir nota", e))?;
+    Ok(())
+}
+
+/// Every tag in use, for autocompletion and the tag filter.
+#[tauri::command]
+pub fn list_note_tags(notes: tauri::State<'_, Notes>) -> Result<Vec<String>, AppError> {
+    let conn = notes.conn.lock().unwrap();
+    let mut stmt = conn
+        .prepare(
+            "SELECT DISTINCT tag.value FROM notes, json_each(notes.tags) AS tag
+             ORDER BY tag.value COLLATE NOCASE",
+        )
+        .map_err(|e| AppError::internal("Falha ao consultar etiquetas", e))?;
+    let rows = stmt
+        .query_map([], |row| row.get(0))
+        .map_err(|e| AppError::internal("Falha ao consultar etiquetas", e))?;
+    rows.collect::<Result<_, _>>()
+        .map_err(|e| AppError::internal("Falha ao ler etiquetas", e))
+}
@@ -0,0 +1,121 @@
+// ── OCR Module ─────────────────────────────────────────────────
+use crate::error::AppError;
+use image::RgbaImage;
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+#[derive(serde::Serialize)]
+pub struct OcrText {
+    /// Recognized lines joined with line breaks, in reading order.
+    pub text: String,
+    pub lines: Vec<String>,
+    pub copied: bool,
+}
+
+/// Windows' built-in OCR engine, in the languages of the user profile (Portuguese
+/// on the office machines), so no model has to ship with the app.
+#[cfg(windows)]
+fn recognize(image: RgbaImage) -> Result<Vec<String>, AppError> {
+    use crate::images::{downscale, MaxDimensions};
+    use image::DynamicImage;
+    use windows::Graphics::Imaging::{BitmapPixelFormat, SoftwareBitmap};
+    use windows::Media::Ocr::OcrEngine;
+    use windows::Storage::Streams::DataWriter;
+
+    let ocr_error = |e: windows::core::Error| AppError::internal("Falha ao reconhecer o texto", e);
+
+    let engine = OcrEngine::TryCreateFromUserProfileLanguages().map_err(|e| {
+        AppError::ServiceUnavailable(
+            "Nenhum idioma com reconhecimento de texto instalado no Windows".into(),
+        )
+        .with_details(e)
+    })?;
+
+    // The engine refuses images past this size on either side
+    let max = OcrEngine::MaxImageDimension().map_err(ocr_error)?;
+    let image = downscale(
+        DynamicImage::ImageRgba8(image),
+        MaxDimensions {
+            width: Some(max),
+            height: Some(max),
+        },
+    )
+    .to_rgba8();
+
+    let (width, height) = (image.width() as i32, image.height() as i32);
+    let mut bgra = image.into_raw();
+    for pixel in bgra.chunks_exact_mut(4) {
+        pixel.swap(0, 2);
+    }
+    let writer = DataWriter::new().map_err(ocr_error)?;
+    writer.WriteBytes(&bgra).map_err(ocr_error)?;
+    let buffer = writer.DetachBuffer().map_err(ocr_error)?;
+    let bitmap =
+        SoftwareBitmap::CreateCopyFromBuffer(&buffer, BitmapPixelFormat::Bgra8, width, height)
+            .map_err(ocr_error)?;
+
+    let result = engine
+        .RecognizeAsync(&bitmap)
+        .and_then(|operation| operation.get())
+        .map_err(ocr_error)?;
+    let lines = result.Lines().map_err(ocr_error)?;
+    let mut text_lines = Vec::new();
+    for index in 0..lines.Size().map_err(ocr_error)? {
+        let line = lines
+            .GetAt(index)
+            .and_then(|line| line.Text())
+            .map_err(ocr_error)?;
+        text_lines.push(line.to_string_lossy());
+    }
+    Ok(text_lines)
+}
+
+#[cfg(not(windows))]
+fn recognize(_image: RgbaImage) -> Result<Vec<String>, AppError> {
+    Err(AppError::Unsupported(
+        "Reconhecimento de texto disponível apenas no Windows".into(),
+    ))
+}
+
+fn clipboard_image(app: &tauri::AppHandle) -> Result<RgbaImage, AppError> {
+    let image = app.clipboard().read_image().map_err(|_| {
+        AppError::InvalidInput("A área de transferência não contém uma imagem".into())
+    })?;
+    RgbaImage::from_raw(image.width(), image.height(), image.rgba().to_vec())
+        .ok_or_else(|| AppError::InvalidInput("Imagem inválida na área de transferência".into()))
+}
+
+/// Reads the text in the image currently on the clipboard (e.g. a screenshot
+/// pasted from Teams). With `copy_to_clipboard` the image is replaced by the
+/// recognized text, ready to paste.
+#[tauri::command]
+pub async fn ocr_clipboard(
+    app: tauri::AppHandle,
+    copy_to_clipboard: Option<bool>,
+) -> Result<OcrText, AppError> {
+    crate::metrics::timed("ocr_clipboard", async move {
+        let handle = app.clone();
+        let lines =
+            tauri::async_runtime::spawn_blocking(move || recognize(clipboard_image(&handle)?))
+                .await
+                .map_err(|e| AppError::internal("Falha ao reconhecer o texto", e))??;
+        if lines.is_empty() {
+            return Err(AppError::InvalidInput(
+                "Nenhum texto encontrado na imagem".into(),
+            ));
+        }
+
+        let text = lines.join("\n");
+        let copied = copy_to_clipboard.unwrap_or(false);
+        if copied {
+            app.clipboard().write_text(text.clone()).map_err(|e| {
+                AppError::internal("Falha ao copiar para a área de transferência", e)
+            })?;
+        }
+        Ok(OcrText {
+            text,
+            lines,
+            copied,
+        })
+    })
+    .await
+}
@@ -1,26 +1,88 @@
+use crate::error::AppError;
+use crate::jobs::{JobHandle, Jobs};
+use crate::workers;
 use chrono::{DateTime, Utc};
-use lopdf::{Document, Object, ObjectId};
-use std::collections::BTreeMap;
+use lopdf::{Dictionary, Document, Object, ObjectId};
+use pdfium_render::prelude::{PdfRenderConfig, Pdfium};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tauri::Manager;
+
+// ── Errors ──────────────────────────────────────────────────────
+
+fn ensure_exists(path: &str) -> Result<(), AppError> {
+    if !Path::new(path).exists() {
+        return Err(AppError::NotFound(format!(
+            "Arquivo não encontrado: {}",
+            path
+        )));
+    }
+    Ok(())
+}
+
+/// lopdf wraps filesystem failures; anything else means the file is not a usable PDF.
+fn pdf_error(message: String, error: lopdf::Error) -> AppError {
+    match error {
+        lopdf::Error::IO(error) => AppError::io(message, error),
+        other => AppError::InvalidInput(message).with_details(other),
+    }
+}
 
 // ── Merge PDFs ──────────────────────────────────────────────────
 
 #[tauri::command]
-pub fn merge_pdfs(input_paths: Vec<String>, output_path: String) -> Result<String, String> {
+pub async fn merge_pdfs(
+    app: tauri::AppHandle,
+    input_paths: Vec<String>,
+    output_path: String,
+) -> Result<String, AppError> {
+    crate::metrics::timed("merge_pdfs", async move {
+        tauri::async_runtime::spawn_blocking(move || {
+            let job = Jobs::start(&app, "merge_pdfs", "Unir PDFs");
+            let result =
+                merge_pdfs_impl(&job, workers::concurrency(&app), input_paths, output_path);
+            job.finish(&result);
+            result
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao unir PDFs", e))?
+    })
+    .await
+}
+
+fn merge_pdfs_impl(
+    job: &JobHandle,
+    concurrency: usize,
+    input_paths: Vec<String>,
+    output_path: String,
+) -> Result<String, AppError> {
     if input_paths.len() < 2 {
-        return Err("Selecione pelo menos 2 arquivos PDF para unir.".into());
+        return Err(AppError::InvalidInput(
+            "Selecione pelo menos 2 arquivos PDF para unir.".into(),
+        ));
     }
 
     for p in &input_paths {
-        if !Path::new(p).exists() {
-            return Err(format!("Arquivo não encontrado: {}", p));
-        }
+        ensure_exists(p)?;
     }
 
-    let documents: Vec<Document> = input_paths
-        .iter()
-        .map(|p| Document::load(p).map_err(|e| format!("Erro ao abrir '{}': {}", p, e)))
-        .collect::<Result<Vec<_>, _>>()?;
+    // Loading is where the time goes on large files, so files load in parallel
+    let total = input_paths.len() as u64;
+    let loaded = AtomicU64::new(0);
+    let documents: Vec<Document> = workers::map(concurrency, input_paths.iter().collect(), |p| {
+        job.check_cancelled()?;
+        let doc = Document::load(p).map_err(|e| pdf_error(format!("Erro ao abrir '{}'", p), e))?;
+        job.progress(
+            loaded.fetch_add(1, Ordering::Relaxed) + 1,
+            total,
+            p.as_str(),
+        );
+        Ok::<_, AppError>(doc)
+    })
+    .into_iter()
+    .collect::<Result<_, AppError>>()?;
 
     let mut max_id = 1;
     let mut documents_pages: Vec<Vec<(ObjectId, Object)>> = Vec::new();
@@ -134,7 +196,7 @@ pub fn merge_pdfs(input_paths: Vec<String>, output_path: String) -> Result<Strin
 
     merged
         .save(&output_path)
-        .map_err(|e| format!("Erro ao salvar o PDF: {}", e))?;
+        .map_err(|e| AppError::io("Erro ao salvar o PDF", e))?;
 
     Ok(output_path)
 }
@@ -151,21 +213,36 @@ pub enum SplitStrategy {
 }
 
 #[tauri::command]
-pub fn split_pdf(
+pub async fn split_pdf(
     input_path: String,
     output_dir: String,
     prefix: String,
     strategy: SplitStrategy,
-) -> Result<Vec<String>, String> {
-    if !Path::new(&input_path).exists() {
-        return Err(format!("Arquivo não encontrado: {}", input_path));
-    }
+) -> Result<Vec<String>, AppError> {
+    crate::metrics::timed("split_pdf", async move {
+        tauri::async_runtime::spawn_blocking(move || {
+            split_pdf_impl(input_path, output_dir, prefix, strategy)
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao dividir PDF", e))?
+    })
+    .await
+}
 
-    let doc = Document::load(&input_path).map_err(|e| format!("Erro ao abrir o PDF: {}", e))?;
+fn split_pdf_impl(
+    input_path: String,
+    output_dir: String,
+    prefix: String,
+    strategy: SplitStrategy,
+) -> Result<Vec<String>, AppError> {
+    ensure_exists(&input_path)?;
+
+    let doc =
+        Document::load(&input_path).map_err(|e| pdf_error("Erro ao abrir o PDF".into(), e))?;
 
     let page_count = doc.get_pages().len() as u32;
     if page_count == 0 {
-        return Err("O PDF não contém páginas.".into());
+        return Err(AppError::InvalidInput("O PDF não contém páginas.".into()));
     }
 
     // Build groups of page numbers (1-indexed) for each output file
@@ -201,10 +278,10 @@ pub fn split_pdf(
             // Validate
             for &sp in &split_points {
                 if sp < 1 || sp > page_count {
-                    return Err(format!(
+                    return Err(AppError::InvalidInput(format!(
                         "Página {} fora do intervalo (1-{}).",
                         sp, page_count
-                    ));
+                    )));
                 }
             }
             let mut groups = Vec::new();
@@ -222,7 +299,9 @@ pub fn split_pdf(
         }
         SplitStrategy::EveryNPages(n) => {
             if n == 0 {
-                return Err("O número de páginas por grupo deve ser maior que 0.".into());
+                return Err(AppError::InvalidInput(
+                    "O número de páginas por grupo deve ser maior que 0.".into(),
+                ));
             }
             (1..=page_count)
                 .collect::<Vec<u32>>()
@@ -235,7 +314,7 @@ pub fn split_pdf(
     let out_dir = Path::new(&output_dir);
     if !out_dir.exists() {
         std::fs::create_dir_all(out_dir)
-            .map_err(|e| format!("Erro ao criar diretório de saída: {}", e))?;
+            .map_err(|e| AppError::io("Erro ao criar diretório de saída", e))?;
     }
 
     let mut output_files = Vec::new();
@@ -251,7 +330,7 @@ pub fn split_pdf(
     Ok(output_files)
 }
 
-fn extract_pages(source: &Document, pages: &[u32], output_path: &Path) -> Result<(), String> {
+fn extract_pages(source: &Document, pages: &[u32], output_path: &Path) -> Result<(), AppError> {
     let all_pages = source.get_pages();
     let page_count = all_pages.len() as u32;
 
@@ -268,11 +347,180 @@ fn extract_pages(source: &Document, pages: &[u32], output_path: &Path) -> Result
 
     new_doc
         .save(output_path)
-        .map_err(|e| format!("Erro ao salvar '{}': {}", output_path.display(), e))?;
+        .map_err(|e| AppError::io(format!("Erro ao salvar '{}'", output_path.display()), e))?;
 
     Ok(())
 }
 
+// ── Split Spread Pages ───────────────────────────────────────────
+
+#[tauri::command]
+pub async fn split_spread_pages(
+    input_path: String,
+    output_path: String,
+) -> Result<usize, AppError> {
+    crate::metrics::timed("split_spread_pages", async move {
+        tauri::async_runtime::spawn_blocking(move || {
+            split_spread_pages_impl(input_path, output_path)
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao dividir páginas", e))?
+    })
+    .await
+}
+
+fn split_spread_pages_impl(input_path: String, output_path: String) -> Result<usize, AppError> {
+    ensure_exists(&input_path)?;
+
+    let mut doc =
+        Document::load(&input_path).map_err(|e| pdf_error("Erro ao abrir o PDF".into(), e))?;
+
+    let page_ids: Vec<ObjectId> = doc.get_pages().into_values().collect();
+    if page_ids.is_empty() {
+        return Err(AppError::InvalidInput("O PDF não contém páginas.".into()));
+    }
+
+    let pages_root = doc
+        .catalog()
+        .and_then(|c| c.get(b"Pages"))
+        .and_then(Object::as_reference)
+        .map_err(|_| {
+            AppError::InvalidInput("Não foi possível encontrar o objeto de páginas do PDF.".into())
+        })?;
+
+    let mut new_kids: Vec<ObjectId> = Vec::new();
+
+    for page_id in page_ids {
+        // The page tree is flattened below, so inherited attributes must be
+        // copied onto each page before its parent is replaced.
+        let media_box = inherited_attribute(&doc, page_id, b"MediaBox")
+            .and_then(|o| parse_rect(&doc, &o))
+            .ok_or_else(|| AppError::InvalidInput("Página sem MediaBox válida.".into()))?;
+        let visible_box = inherited_attribute(&doc, page_id, b"CropBox")
+            .and_then(|o| parse_rect(&doc, &o))
+            .unwrap_or(media_box);
+        let resources = inherited_attribute(&doc, page_id, b"Resources");
+        let rotate = inherited_attribute(&doc, page_id, b"Rotate")
+            .and_then(|o| o.as_i64().ok())
+            .unwrap_or(0)
+            .rem_euclid(360);
+
+        {
+            let dict = doc
+                .get_dictionary_mut(page_id)
+                .map_err(|e| pdf_error("Erro ao ler página".into(), e))?;
+            if let Some(resources) = resources {
+                dict.set("Resources", resources);
+            }
+            dict.set("Rotate", Object::Integer(rotate));
+        }
+
+        let [x0, y0, x1, y1] = visible_box;
+        let (w, h) = (x1 - x0, y1 - y0);
+        let landscape = if rotate % 180 == 0 { w > h } else { h > w };
+
+        if !landscape {
+            set_page_box(&mut doc, page_id, visible_box)?;
+            new_kids.push(page_id);
+            continue;
+        }
+
+        // Left/right halves as seen on screen, mapped back to user space
+        let (mx, my) = (x0 + w / 2.0, y0 + h / 2.0);
+        let (first, second) = match rotate {
+            90 => ([x0, y0, x1, my], [x0, my, x1, y1]),
+            180 => ([mx, y0, x1, y1], [x0, y0, mx, y1]),
+            270 => ([x0, my, x1, y1], [x0, y0, x1, my]),
+            _ => ([x0, y0, mx, y1], [mx, y0, x1, y1]),
+        };
+
+        let mut second_page = doc
+            .get_dictionary(page_id)
+            .map_err(|e| pdf_error("Erro ao ler página".into(), e))?
+            .clone();
+        // Annotations belong to a single page; keep them on the first half only
+        second_page.remove(b"Annots");
+        let second_id = doc.add_object(second_page);
+
+        set_page_box(&mut doc, page_id, first)?;
+        set_page_box(&mut doc, second_id, second)?;
+
+        new_kids.push(page_id);
+        new_kids.push(second_id);
+    }
+
+    for page_id in &new_kids {
+        if let Ok(dict) = doc.get_dictionary_mut(*page_id) {
+            dict.set("Parent", Object::Reference(pages_root));
+        }
+    }
+
+    let root = doc
+        .get_dictionary_mut(pages_root)
+        .map_err(|e| pdf_error("Erro ao atualizar páginas".into(), e))?;
+    root.set(
+        "Kids",
+        new_kids
+            .iter()
+            .map(|id| Object::Reference(*id))
+            .collect::<Vec<Object>>(),
+    );
+    root.set("Count", Object::Integer(new_kids.len() as i64));
+    root.remove(b"MediaBox");
+    root.remove(b"CropBox");
+
+    doc.prune_objects();
+    doc.renumber_objects();
+    doc.compress();
+
+    doc.save(&output_path)
+        .map_err(|e| AppError::io("Erro ao salvar o PDF", e))?;
+
+    Ok(new_kids.len())
+}
+
+fn inherited_attribute(doc: &Document, page_id: ObjectId, key: &[u8]) -> Option<Object> {
+    let mut current = doc.get_dictionary(page_id).ok()?;
+    // Bounded walk up the page tree in case of malformed Parent cycles
+    for _ in 0..32 {
+        if let Ok(value) = current.get(key) {
+            return Some(value.clone());
+        }
+        let parent = current.get(b"Parent").and_then(Object::as_reference).ok()?;
+        current = doc.get_dictionary(parent).ok()?;
+    }
+    None
+}
+
+fn parse_rect(doc: &Document, object: &Object) -> Option<[f32; 4]> {
+    let (_, object) = doc.dereference(object).ok()?;
+    let values: Vec<f32> = object
+        .as_array()
+        .ok()?
+        .iter()
+        .filter_map(|v| v.as_float().ok())
+        .collect();
+    if values.len() != 4 {
+        return None;
+    }
+    Some([
+        values[0].min(values[2]),
+        values[1].min(values[3]),
+        values[0].max(values[2]),
+        values[1].max(values[3]),
+    ])
+}
+
+fn set_page_box(doc: &mut Document, page_id: ObjectId, rect: [f32; 4]) -> Result<(), AppError> {
+    let dict = doc
+        .get_dictionary_mut(page_id)
+        .map_err(|e| pdf_error("Erro ao ler página".into(), e))?;
+    let rect_object = || Object::Array(rect.iter().map(|v| Object::Real(*v)).collect());
+    dict.set("MediaBox", rect_object());
+    dict.set("CropBox", rect_object());
+    Ok(())
+}
+
 // ── Get PDF Info ─────────────────────────────────────────────────
 
 #[derive(serde::Serialize)]
@@ -283,21 +531,19 @@ pub struct PdfInfo {
 }
 
 #[tauri::command]
-pub fn get_pdf_info(path: String) -> Result<PdfInfo, String> {
-    if !Path::new(&path).exists() {
-        return Err(format!("Arquivo não encontrado: {}", path));
-    }
+pub fn get_pdf_info(path: String) -> Result<PdfInfo, AppError> {
+    ensure_exists(&path)?;
 
-    let doc = Document::load(&path).map_err(|e| format!("Erro ao abrir o PDF: {}", e))?;
+    let doc = Document::load(&path).map_err(|e| pdf_error("Erro ao abrir o PDF".into(), e))?;
     let page_count = doc.get_pages().len();
     let metadata =
-        std::fs::metadata(&path).map_err(|e| format!("Erro ao obter metadados: {}", e))?;
+        std::fs::metadata(&path).map_err(|e| AppError::io("Erro ao obter metadados", e))?;
     let size = metadata.len();
     let created = metadata
         .created()
-        .map_err(|e| format!("Erro ao obter data de criação: {}", e))?
+        .map_err(|e| AppError::io("Erro ao obter data de criação", e))?
         .duration_since(std::time::UNIX_EPOCH)
-        .map_err(|e| format!("Erro na conversão de tempo: {}", e))?
+        .map_err(|e| AppError::internal("Erro na conversão de tempo", e))?
         .as_secs();
 
     // Formatar data
@@ -315,12 +561,30 @@ pub fn get_pdf_info(path: String) -> Result<PdfInfo, String> {
 // ── Compress PDF ─────────────────────────────────────────────────
 
 #[tauri::command]
-pub fn compress_pdf(input_path: String, output_path: String, level: String) -> Result<u64, String> {
-    if !Path::new(&input_path).exists() {
-        return Err(format!("Arquivo não encontrado: {}", input_path));
-    }
+pub async fn compress_pdf(
+    input_path: String,
+    output_path: String,
+    level: String,
+) -> Result<u64, AppError> {
+    crate::metrics::timed("compress_pdf", async move {
+        tauri::async_runtime::spawn_blocking(move || {
+            compress_pdf_impl(input_path, output_path, level)
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao comprimir PDF", e))?
+    })
+    .await
+}
 
-    let mut doc = Document::load(&input_path).map_err(|e| format!("Erro ao abrir o PDF: {}", e))?;
+fn compress_pdf_impl(
+    input_path: String,
+    output_path: String,
+    level: String,
+) -> Result<u64, AppError> {
+    ensure_exists(&input_path)?;
+
+    let mut doc =
+        Document::load(&input_path).map_err(|e| pdf_error("Erro ao abrir o PDF".into(), e))?;
 
     // Compress based on level
     match level.as_str() {
@@ -339,15 +603,344 @@ pub fn compress_pdf(input_path: String, output_path: String, level: String) -> R
             doc.renumber_objects();
             // Additional optimization could be added here
         }
-        _ => return Err("Nível de compressão inválido".into()),
+        _ => {
+            return Err(AppError::InvalidInput(
+                "Nível de compressão inválido".into(),
+            ))
+        }
     }
 
     doc.save(&output_path)
-        .map_err(|e| format!("Erro ao salvar o PDF: {}", e))?;
+        .map_err(|e| AppError::io("Erro ao salvar o PDF", e))?;
 
     let new_size = std::fs::metadata(&output_path)
-        .map_err(|e| format!("Erro ao obter tamanho do arquivo comprimido: {}", e))?
+        .map_err(|e| AppError::io("Erro ao obter tamanho do arquivo comprimido", e))?
         .len();
 
     Ok(new_size)
 }
+
+#[derive(serde::Serialize)]
+pub struct CompressedPdf {
+    pub input_path: String,
+    pub output_path: String,
+    pub original_size: u64,
+    /// `None` when this file failed; see `error`.
+    pub compressed_size: Option<u64>,
+    pub error: Option<String>,
+}
+
+/// Compresses every PDF into `output_dir` under the same file name, several at a
+/// time. A file that fails does not stop the others.
+#[tauri::command]
+pub async fn compress_pdfs(
+    app: tauri::AppHandle,
+    input_paths: Vec<String>,
+    output_dir: String,
+    level: String,
+) -> Result<Vec<CompressedPdf>, AppError> {
+    crate::metrics::timed("compress_pdfs", async move {
+        if input_paths.is_empty() {
+            return Err(AppError::InvalidInput(
+                "Selecione pelo menos 1 arquivo PDF.".into(),
+            ));
+        }
+        let output_dir = std::path::PathBuf::from(output_dir);
+        std::fs::create_dir_all(&output_dir)
+            .map_err(|e| AppError::io("Falha ao criar a pasta de destino", e))?;
+
+        tauri::async_runtime::spawn_blocking(move || {
+            let job = Jobs::start(&app, "compress_pdfs", "Comprimir PDFs");
+            let total = input_paths.len() as u64;
+            let done = AtomicU64::new(0);
+
+            let results = workers::map(workers::concurrency(&app), input_paths, |input_path| {
+                let name = Path::new(&input_path)
+                    .file_name()
+                    .map(|name| name.to_os_string())
+                    .unwrap_or_else(|| "documento.pdf".into());
+                let output_path = output_dir.join(name).to_string_lossy().to_string();
+                let original_size = std::fs::metadata(&input_path).map(|m| m.len()).unwrap_or(0);
+
+                let outcome = job.check_cancelled().and_then(|_| {
+                    compress_pdf_impl(input_path.clone(), output_path.clone(), level.clone())
+                });
+                job.progress(
+                    done.fetch_add(1, Ordering::Relaxed) + 1,
+                    total,
+                    input_path.as_str(),
+                );
+                CompressedPdf {
+                    input_path,
+                    output_path,
+                    original_size,
+                    compressed_size: outcome.as_ref().ok().copied(),
+                    error: outcome.err().map(|e| e.to_string()),
+                }
+            });
+
+            let outcome = job.check_cancelled();
+            job.finish(&outcome);
+            results
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao comprimir PDFs", e))
+    })
+    .await
+}
+
+// ── Find Duplicate Pages ─────────────────────────────────────────
+
+#[derive(serde::Serialize)]
+pub struct PageLocation {
+    pub path: String,
+    pub page: u32,
+}
+
+#[derive(serde::Serialize)]
+pub struct DuplicatePageGroup {
+    pub hash: String,
+    pub pages: Vec<PageLocation>,
+}
+
+#[tauri::command]
+pub async fn find_duplicate_pages(
+    app: tauri::AppHandle,
+    paths: Vec<String>,
+) -> Result<Vec<DuplicatePageGroup>, AppError> {
+    crate::metrics::timed("find_duplicate_pages", async move {
+        tauri::async_runtime::spawn_blocking(move || {
+            find_duplicate_pages_impl(workers::concurrency(&app), paths)
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao procurar páginas duplicadas", e))?
+    })
+    .await
+}
+
+fn find_duplicate_pages_impl(
+    concurrency: usize,
+    paths: Vec<String>,
+) -> Result<Vec<DuplicatePageGroup>, AppError> {
+    if paths.is_empty() {
+        return Err(AppError::InvalidInput(
+            "Selecione pelo menos 1 arquivo PDF.".into(),
+        ));
+    }
+
+    for p in &paths {
+        ensure_exists(p)?;
+    }
+
+    // Keep groups in the order their first page appears
+    let mut order: Vec<String> = Vec::new();
+    let mut groups: HashMap<String, Vec<PageLocation>> = HashMap::new();
+
+    // Fingerprints per file in parallel; grouping stays in file order
+    let fingerprints = workers::map(concurrency, paths.iter().collect(), |p| {
+        let doc = Document::load(p).map_err(|e| pdf_error(format!("Erro ao abrir '{}'", p), e))?;
+        Ok::<_, AppError>(
+            doc.get_pages()
+                .into_iter()
+                .map(|(page_num, page_id)| (page_num, page_fingerprint(&doc, page_id)))
+                .collect::<Vec<_>>(),
+        )
+    });
+
+    for (p, pages) in paths.iter().zip(fingerprints) {
+        for (page_num, hash) in pages? {
+            groups
+                .entry(hash.clone())
+                .or_insert_with(|| {
+                    order.push(hash);
+                    Vec::new()
+                })
+                .push(PageLocation {
+                    path: p.clone(),
+                    page: page_num,
+                });
+        }
+    }
+
+    let duplicates = order
+        .into_iter()
+        .filter_map(|hash| {
+            let pages = groups.remove(&hash)?;
+            if pages.len() > 1 {
+                Some(DuplicatePageGroup { hash, pages })
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    Ok(duplicates)
+}
+
+fn page_fingerprint(doc: &Document, page_id: ObjectId) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(doc.get_page_content(page_id).unwrap_or_default());
+
+    // Scanned pages usually share the same content stream ("/Im0 Do"),
+    // so the referenced images must be part of the fingerprint.
+    if let Ok((resources, resource_ids)) = doc.get_page_resources(page_id) {
+        let mut dicts: Vec<&Dictionary> = resources.into_iter().collect();
+        dicts.extend(
+            resource_ids
+                .iter()
+                .filter_map(|id| doc.get_dictionary(*id).ok()),
+        );
+        for dict in dicts {
+            hash_xobjects(doc, dict, &mut hasher, 0);
+        }
+    }
+
+    hex::encode(hasher.finalize())
+}
+
+fn hash_xobjects(doc: &Document, resources: &Dictionary, hasher: &mut Sha256, depth: u32) {
+    // Form XObjects can nest; guard against cyclic or absurdly deep trees
+    if depth > 8 {
+        return;
+    }
+
+    let xobjects = match resources.get(b"XObject").and_then(|o| doc.dereference(o)) {
+        Ok((_, Object::Dictionary(dict))) => dict,
+        _ => return,
+    };
+
+    for (name, value) in xobjects.iter() {
+        hasher.update(name);
+        if let Ok((_, Object::Stream(stream))) = doc.dereference(value) {
+            hasher.update(&stream.content);
+            if let Ok((_, Object::Dictionary(inner))) = stream
+                .dict
+                .get(b"Resources")
+                .and_then(|o| doc.dereference(o))
+            {
+                hash_xobjects(doc, inner, hasher, depth + 1);
+            }
+        }
+    }
+}
+
+// ── Render PDF Page ──────────────────────────────────────────────
+
+/// Binds Pdfium from the app resources, next to the executable or, as a last
+/// resort, from the system library path.
+fn load_pdfium(app: &tauri::AppHandle) -> Result<Pdfium, AppError> {
+    let mut search_dirs = Vec::new();
+    if let Ok(dir) = app.path().resource_dir() {
+        search_dirs.push(dir);
+    }
+    if let Some(dir) = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf))
+    {
+        search_dirs.push(dir);
+    }
+
+    for dir in search_dirs {
+        let library = Pdfium::pdfium_platform_library_name_at_path(&dir);
+        if library.exists() {
+            if let Ok(bindings) = Pdfium::bind_to_library(&library) {
+                return Ok(Pdfium::new(bindings));
+            }
+        }
+    }
+
+    Pdfium::bind_to_system_library()
+        .map(Pdfium::new)
+        .map_err(|e| {
+            AppError::Unsupported("Biblioteca Pdfium não encontrada".into()).with_details(e)
+        })
+}
+
+pub(crate) fn encode_png_base64(image: &image::DynamicImage) -> Result<String, AppError> {
+    let mut png = std::io::Cursor::new(Vec::new());
+    image
+        .write_to(&mut png, image::ImageFormat::Png)
+        .map_err(|e| AppError::internal("Erro ao gerar PNG", e))?;
+    Ok(base64::Engine::encode(
+        &base64::engine::general_purpose::STANDARD,
+        png.into_inner(),
+    ))
+}
+
+#[tauri::command]
+pub async fn render_pdf_page(
+    app: tauri::AppHandle,
+    path: String,
+    page: u32,
+    zoom: f32,
+) -> Result<String, AppError> {
+    crate::metrics::timed("render_pdf_page", async move {
+        tauri::async_runtime::spawn_blocking(move || render_pdf_page_impl(&app, &path, page, zoom))
+            .await
+            .map_err(|e| AppError::internal("Falha ao renderizar a página", e))?
+    })
+    .await
+}
+
+fn render_pdf_page_impl(
+    app: &tauri::AppHandle,
+    path: &str,
+    page: u32,
+    zoom: f32,
+) -> Result<String, AppError> {
+    ensure_exists(path)?;
+    if !(0.1..=5.0).contains(&zoom) {
+        return Err(AppError::InvalidInput(
+            "O zoom deve estar entre 0.1 e 5.0.".into(),
+        ));
+    }
+
+    let pdfium = load_pdfium(app)?;
+    let document = pdfium
+        .load_pdf_from_file(path, None)
+        .map_err(|e| AppError::InvalidInput("Erro ao abrir o PDF".into()).with_details(e))?;
+
+    let pages = document.pages();
+    let page_count = pages.len() as u32;
+    if page < 1 || page > page_count {
+        return Err(AppError::InvalidInput(format!(
+            "Página {} fora do intervalo (1-{}).",
+            page, page_count
+        )));
+    }
+
+    let pdf_page = pages
+        .get((page - 1) as u16)
+        .map_err(|e| AppError::internal(format!("Erro ao carregar a página {}", page), e))?;
+    let config = PdfRenderConfig::new().scale_page_by_factor(zoom);
+    let bitmap = pdf_page
+        .render_with_config(&config)
+        .map_err(|e| AppError::internal(format!("Erro ao renderizar a página {}", page), e))?;
+
+    encode_png_base64(&bitmap.as_image())
+}
+
+/// Renders the first page scaled to fit a `max_size` square, for gallery thumbnails.
+pub(crate) fn render_pdf_thumbnail(
+    app: &tauri::AppHandle,
+    path: &Path,
+    max_size: u32,
+) -> Result<String, AppError> {
+    let pdfium = load_pdfium(app)?;
+    let document = pdfium
+        .load_pdf_from_file(path, None)
+        .map_err(|e| AppError::InvalidInput("Erro ao abrir o PDF".into()).with_details(e))?;
+
+    let first_page = document
+        .pages()
+        .first()
+        .map_err(|e| AppError::internal("Erro ao carregar a primeira página", e))?;
+    let config = PdfRenderConfig::new()
+        .set_maximum_width(max_size as i32)
+        .set_maximum_height(max_size as i32);
+    let bitmap = first_page
+        .render_with_config(&config)
+        .map_err(|e| AppError::internal("Erro ao renderizar a primeira página", e))?;
+
+    encode_png_base64(&bitmap.as_image())
+}
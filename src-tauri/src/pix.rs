@@ -0,0 +1,170 @@
+// ── PIX Module ─────────────────────────────────────────────────
+use crate::barcode::{self, Symbology};
+use crate::error::AppError;
+use base64::Engine;
+
+const GUI: &str = "br.gov.bcb.pix";
+const MAX_MERCHANT_LEN: usize = 25;
+const MAX_CITY_LEN: usize = 15;
+const MAX_TXID_LEN: usize = 25;
+
+#[derive(serde::Serialize)]
+pub struct PixQrCode {
+    /// "Pix copia e cola" string.
+    pub payload: String,
+    /// Ready to use as an `<img src>`.
+    pub png_data_url: String,
+}
+
+/// EMV field: two-digit id, two-digit length, value.
+fn field(id: &str, value: &str) -> String {
+    format!("{}{:02}{}", id, value.len(), value)
+}
+
+/// CRC16-CCITT (polynomial 0x1021, initial value 0xFFFF), as the BR Code requires.
+fn crc16(payload: &str) -> u16 {
+    let mut crc: u16 = 0xFFFF;
+    for byte in payload.bytes() {
+        crc ^= (byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+/// Banking apps reject accents and some symbols in the name and city fields.
+fn to_ascii(text: &str, max_len: usize) -> String {
+    text.trim()
+        .chars()
+        .map(|c| match c {
+            'á' | 'à' | 'â' | 'ã' | 'ä' => 'a',
+            'Á' | 'À' | 'Â' | 'Ã' | 'Ä' => 'A',
+            'é' | 'è' | 'ê' | 'ë' => 'e',
+            'É' | 'È' | 'Ê' | 'Ë' => 'E',
+            'í' | 'ì' | 'î' | 'ï' => 'i',
+            'Í' | 'Ì' | 'Î' | 'Ï' => 'I',
+            'ó' | 'ò' | 'ô' | 'õ' | 'ö' => 'o',
+            'Ó' | 'Ò' | 'Ô' | 'Õ' | 'Ö' => 'O',
+            'ú' | 'ù' | 'û' | 'ü' => 'u',
+            'Ú' | 'Ù' | 'Û' | 'Ü' => 'U',
+            'ç' => 'c',
+            'Ç' => 'C',
+            'ñ' => 'n',
+            'Ñ' => 'N',
+            c if c.is_ascii_alphanumeric() || " .-&/".contains(c) => c,
+            _ => ' ',
+        })
+        .take(max_len)
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+/// Keys are stored by the DICT without formatting: CPF/CNPJ as digits, phone
+/// as +55..., e-mail and random (EVP) keys in lower case.
+fn normalize_key(key: &str) -> Result<String, AppError> {
+    let key = key.trim();
+    let normalized = if key.contains('@') {
+        key.to_lowercase()
+    } else if let Some(phone) = key.strip_prefix('+') {
+        format!(
+            "+{}",
+            phone
+                .chars()
+                .filter(char::is_ascii_digit)
+                .collect::<String>()
+        )
+    } else if key
+        .chars()
+        .all(|c| c.is_ascii_digit() || " .-/()".contains(c))
+    {
+        key.chars().filter(char::is_ascii_digit).collect()
+    } else {
+        key.to_lowercase()
+    };
+
+    if normalized.is_empty() || normalized.len() > 77 {
+        return Err(AppError::InvalidInput("Chave PIX inválida".into()));
+    }
+    Ok(normalized)
+}
+
+fn build_payload(key: &str, amount: Option<f64>, merchant: &str, city: &str, txid: &str) -> String {
+    let account = format!("{}{}", field("00", GUI), field("01", key));
+    let mut payload = String::new();
+    payload.push_str(&field("00", "01"));
+    payload.push_str(&field("26", &account));
+    payload.push_str(&field("52", "0000"));
+    payload.push_str(&field("53", "986"));
+    if let Some(amount) = amount {
+        payload.push_str(&field("54", &format!("{:.2}", amount)));
+    }
+    payload.push_str(&field("58", "BR"));
+    payload.push_str(&field("59", merchant));
+    payload.push_str(&field("60", city));
+    payload.push_str(&field("62", &field("05", txid)));
+    // The CRC covers its own id and length
+    payload.push_str("6304");
+    let crc = crc16(&payload);
+    payload.push_str(&format!("{:04X}", crc));
+    payload
+}
+
+/// Static PIX BR Code for charging honorários. Without `amount` the payer types
+/// the value; `txid` identifies the charge on the bank statement.
+#[tauri::command]
+pub fn generate_pix_qrcode(
+    key: String,
+    amount: Option<f64>,
+    merchant: String,
+    city: String,
+    txid: Option<String>,
+) -> Result<PixQrCode, AppError> {
+    let key = normalize_key(&key)?;
+
+    let amount = amount.filter(|amount| *amount != 0.0);
+    if let Some(amount) = amount {
+        if !amount.is_finite() || amount < 0.01 || amount >= 1e10 {
+            return Err(AppError::InvalidInput("Valor inválido".into()));
+        }
+    }
+
+    let merchant = to_ascii(&merchant, MAX_MERCHANT_LEN);
+    if merchant.is_empty() {
+        return Err(AppError::InvalidInput("Informe o nome do recebedor".into()));
+    }
+    let city = to_ascii(&city, MAX_CITY_LEN);
+    if city.is_empty() {
+        return Err(AppError::InvalidInput(
+            "Informe a cidade do recebedor".into(),
+        ));
+    }
+
+    // Only letters and digits are accepted; "***" means no identifier
+    let txid: String = txid
+        .unwrap_or_default()
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .take(MAX_TXID_LEN)
+        .collect();
+    let txid = if txid.is_empty() {
+        "***".to_string()
+    } else {
+        txid
+    };
+
+    let payload = build_payload(&key, amount, &merchant, &city, &txid);
+    let png = barcode::png(Symbology::Qr, &payload)?;
+    Ok(PixQrCode {
+        png_data_url: format!(
+            "data:image/png;base64,{}",
+            base64::engine::general_purpose::STANDARD.encode(png)
+        ),
+        payload,
+    })
+}
@@ -0,0 +1,227 @@
+// ── Portal Module ──────────────────────────────────────────────
+use crate::error::AppError;
+use tauri::Manager;
+
+/// A government portal opened in its own webview window.
+pub struct Portal {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub url: &'static str,
+    /// Link or button starting the digital certificate login. Clicking it sends the
+    /// webview through the TLS client-certificate handshake, so Windows offers the
+    /// installed certificates instead of asking for a password.
+    pub certificate_login: &'static [&'static str],
+    /// Inputs that take the client's CNPJ.
+    pub cnpj_inputs: &'static [&'static str],
+}
+
+const GENERIC_CNPJ_INPUTS: &[&str] = &["input[name*='cnpj' i]", "input[id*='cnpj' i]"];
+
+const PORTALS: &[Portal] = &[
+    Portal {
+        id: "ecac",
+        name: "e-CAC (Receita Federal)",
+        url: "https://cav.receita.fazenda.gov.br/autenticacao/login",
+        certificate_login: &[
+            "#login-certificate",
+            "a[href*='certificado' i]",
+            "input[alt*='certificado' i]",
+        ],
+        cnpj_inputs: GENERIC_CNPJ_INPUTS,
+    },
+    Portal {
+        id: "simples_nacional",
+        name: "Portal do Simples Nacional",
+        url: "https://www8.receita.fazenda.gov.br/SimplesNacional/",
+        certificate_login: &["a[href*='certificado' i]"],
+        cnpj_inputs: GENERIC_CNPJ_INPUTS,
+    },
+    Portal {
+        id: "pgdas",
+        name: "PGDAS-D (Simples Nacional)",
+        url: "https://www8.receita.fazenda.gov.br/SimplesNacional/Aplicacoes/ATSPO/pgdasd2018.app/",
+        certificate_login: &["a[href*='certificado' i]"],
+        cnpj_inputs: GENERIC_CNPJ_INPUTS,
+    },
+    Portal {
+        id: "sefaz_sp",
+        name: "SEFAZ-SP (Posto Fiscal Eletrônico)",
+        url: "https://www.fazenda.sp.gov.br/pfe/",
+        certificate_login: &["a[href*='certificado' i]", "input[value*='certificado' i]"],
+        cnpj_inputs: GENERIC_CNPJ_INPUTS,
+    },
+    Portal {
+        id: "sefaz_mg",
+        name: "SEFAZ-MG (SIARE)",
+        url: "https://www2.fazenda.mg.gov.br/sol/",
+        certificate_login: &["a[href*='certificado' i]"],
+        cnpj_inputs: GENERIC_CNPJ_INPUTS,
+    },
+    Portal {
+        id: "sefaz_pr",
+        name: "SEFAZ-PR (Receita/PR)",
+        url: "https://receita.pr.gov.br/",
+        certificate_login: &["a[href*='certificado' i]"],
+        cnpj_inputs: GENERIC_CNPJ_INPUTS,
+    },
+    Portal {
+        id: "sefaz_rs",
+        name: "SEFAZ-RS (Receita RS)",
+        url: "https://www.sefaz.rs.gov.br/",
+        certificate_login: &["a[href*='certificado' i]"],
+        cnpj_inputs: GENERIC_CNPJ_INPUTS,
+    },
+];
+
+#[derive(serde::Serialize)]
+pub struct PortalInfo {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub url: &'static str,
+    pub certificate_login: bool,
+}
+
+/// What the injected helpers know about the client the window was opened for.
+#[derive(serde::Serialize)]
+struct ClientContext {
+    name: String,
+    cnpj: Option<String>,
+    ie: Option<String>,
+}
+
+/// Opens `url` in a window of its own, replacing any window already using `label`
+/// so the initialization script always matches the latest request.
+pub fn open_window(
+    app: &tauri::AppHandle,
+    label: &str,
+    title: &str,
+    url: &str,
+    init_script: &str,
+) -> Result<(), AppError> {
+    if let Some(existing) = app.get_webview_window(label) {
+        let _: Result<(), _> = existing.close();
+    }
+
+    let url = url
+        .parse()
+        .map_err(|e| AppError::internal("Endereço do portal inválido", e))?;
+    tauri::WebviewWindowBuilder::new(app, label, tauri::WebviewUrl::External(url))
+        .title(title)
+        .inner_size(1024.0, 800.0)
+        .center()
+        .initialization_script(init_script)
+        .build()
+        .map_err(|e| AppError::internal("Falha ao abrir janela do portal", e))?;
+
+    Ok(())
+}
+
+fn to_json<T: serde::Serialize + ?Sized>(value: &T) -> Result<String, AppError> {
+    serde_json::to_string(value).map_err(|e| AppError::internal("Falha ao preparar portal", e))
+}
+
+/// Exposes the client as `window.__adcontec.client`, fills empty CNPJ fields and
+/// starts the certificate login once per session. Pages load their forms late, so
+/// the helpers run a few times after load.
+fn build_init_script(portal: &Portal, client: Option<&ClientContext>) -> Result<String, AppError> {
+    Ok(format!(
+        r#"(function() {{
+    'use strict';
+    var CLIENT = {client};
+    var CERT_LOGIN = {certificate_login};
+    var CNPJ_INPUTS = {cnpj_inputs};
+    var LOGIN_FLAG = 'adcontec-cert-login-{id}';
+    window.__adcontec = {{ portal: '{id}', client: CLIENT }};
+
+    function fill() {{
+        if (!CLIENT || !CLIENT.cnpj) return;
+        CNPJ_INPUTS.forEach(function(selector) {{
+            document.querySelectorAll(selector).forEach(function(el) {{
+                if (el.value) return;
+                el.value = CLIENT.cnpj;
+                el.dispatchEvent(new Event('input', {{ bubbles: true }}));
+                el.dispatchEvent(new Event('change', {{ bubbles: true }}));
+            }});
+        }});
+    }}
+    function certificateLogin() {{
+        if (sessionStorage.getItem(LOGIN_FLAG)) return;
+        for (var i = 0; i < CERT_LOGIN.length; i++) {{
+            var el = document.querySelector(CERT_LOGIN[i]);
+            if (el) {{
+                sessionStorage.setItem(LOGIN_FLAG, '1');
+                el.click();
+                return;
+            }}
+        }}
+    }}
+    function run() {{ fill(); certificateLogin(); }}
+    setTimeout(run, 500);
+    setTimeout(run, 1500);
+    setTimeout(run, 3000);
+}})();"#,
+        client = to_json(&client)?,
+        certificate_login = to_json(portal.certificate_login)?,
+        cnpj_inputs = to_json(portal.cnpj_inputs)?,
+        id = portal.id,
+    ))
+}
+
+#[tauri::command]
+pub fn list_portals() -> Vec<PortalInfo> {
+    PORTALS
+        .iter()
+        .map(|portal| PortalInfo {
+            id: portal.id,
+            name: portal.name,
+            url: portal.url,
+            certificate_login: !portal.certificate_login.is_empty(),
+        })
+        .collect()
+}
+
+/// Opens a portal, optionally for a client from the registry so its CNPJ is filled
+/// in and the title says whose session it is. Async because creating a window from a
+/// synchronous command deadlocks on Windows.
+#[tauri::command]
+pub async fn open_portal(
+    app: tauri::AppHandle,
+    portal_id: String,
+    client_id: Option<i64>,
+) -> Result<(), AppError> {
+    crate::metrics::timed("open_portal", async move {
+        let portal = PORTALS
+            .iter()
+            .find(|portal| portal.id == portal_id)
+            .ok_or_else(|| AppError::NotFound(format!("Portal desconhecido: {}", portal_id)))?;
+
+        let client = match client_id {
+            Some(id) => {
+                let clients = app.try_state::<crate::clients::Clients>().ok_or_else(|| {
+                    AppError::Unsupported("Cadastro de clientes indisponível".into())
+                })?;
+                let client = clients.get(id)?;
+                Some(ClientContext {
+                    name: client.name,
+                    cnpj: client.cnpj,
+                    ie: client.ie,
+                })
+            }
+            None => None,
+        };
+
+        let title = match &client {
+            Some(client) => format!("{} - {}", portal.name, client.name),
+            None => portal.name.to_string(),
+        };
+        let script = build_init_script(portal, client.as_ref())?;
+        open_window(
+            &app,
+            &format!("portal-{}", portal.id),
+            &title,
+            portal.url,
+            &script,
+        )
+    })
+    .await
+}
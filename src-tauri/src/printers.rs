@@ -0,0 +1,539 @@
+// ── Printers Module ────────────────────────────────────────────
+use crate::error::AppError;
+
+#[derive(serde::Serialize)]
+pub struct PrinterInfo {
+    pub name: String,
+    pub driver: String,
+    pub port: String,
+    pub location: String,
+    pub comment: String,
+    pub is_default: bool,
+    pub is_network: bool,
+    pub is_offline: bool,
+    /// Jobs waiting in the queue.
+    pub jobs: u32,
+}
+
+#[derive(serde::Serialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum PrintJobState {
+    Paused,
+    Error,
+    Deleting,
+    Spooling,
+    Printing,
+    Offline,
+    PaperOut,
+    Printed,
+    Blocked,
+    UserIntervention,
+}
+
+#[derive(serde::Serialize)]
+pub struct PrintJob {
+    pub id: u32,
+    pub document: String,
+    pub user: String,
+    pub machine: String,
+    /// Flags reported by the spooler; empty while simply waiting.
+    pub states: Vec<PrintJobState>,
+    /// Free text from the driver or port monitor, e.g. "Toner baixo".
+    pub status_text: String,
+    pub position: u32,
+    pub total_pages: u32,
+    pub pages_printed: u32,
+    pub submitted_at: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct PrintQueue {
+    pub printer: String,
+    pub paused: bool,
+    pub jobs: Vec<PrintJob>,
+}
+
+#[cfg(windows)]
+fn wide_ptr_to_string(ptr: *const u16) -> String {
+    if ptr.is_null() {
+        return String::new();
+    }
+    unsafe {
+        let len = (0..).take_while(|&i| *ptr.add(i) != 0).count();
+        String::from_utf16_lossy(std::slice::from_raw_parts(ptr, len))
+    }
+}
+
+#[cfg(windows)]
+fn default_printer_impl() -> Option<String> {
+    use windows_sys::Win32::Graphics::Printing::GetDefaultPrinterW;
+
+    let mut len: u32 = 0;
+    unsafe { GetDefaultPrinterW(std::ptr::null_mut(), &mut len) };
+    if len == 0 {
+        return None;
+    }
+
+    let mut buf = vec![0u16; len as usize];
+    if unsafe { GetDefaultPrinterW(buf.as_mut_ptr(), &mut len) } == 0 {
+        return None;
+    }
+    Some(String::from_utf16_lossy(&buf[..len as usize - 1]))
+}
+
+#[cfg(windows)]
+fn list_printers_impl() -> Result<Vec<PrinterInfo>, AppError> {
+    use windows_sys::Win32::Graphics::Printing::{
+        EnumPrintersW, PRINTER_ATTRIBUTE_NETWORK, PRINTER_ATTRIBUTE_WORK_OFFLINE,
+        PRINTER_ENUM_CONNECTIONS, PRINTER_ENUM_LOCAL, PRINTER_INFO_2W, PRINTER_STATUS_OFFLINE,
+    };
+
+    let flags = PRINTER_ENUM_LOCAL | PRINTER_ENUM_CONNECTIONS;
+    let mut needed: u32 = 0;
+    let mut returned: u32 = 0;
+    unsafe {
+        EnumPrintersW(
+            flags,
+            std::ptr::null(),
+            2,
+            std::ptr::null_mut(),
+            0,
+            &mut needed,
+            &mut returned,
+        )
+    };
+    if needed == 0 {
+        return Ok(Vec::new());
+    }
+
+    // u64 storage keeps the PRINTER_INFO_2W array properly aligned
+    let mut buf = vec![0u64; (needed as usize).div_ceil(8)];
+    let ok = unsafe {
+        EnumPrintersW(
+            flags,
+            std::ptr::null(),
+            2,
+            buf.as_mut_ptr() as *mut u8,
+            needed,
+            &mut needed,
+            &mut returned,
+        )
+    };
+    if ok == 0 {
+        return Err(AppError::internal(
+            "Falha ao listar impressoras",
+            std::io::Error::last_os_error(),
+        ));
+    }
+
+    let default = default_printer_impl();
+    let infos = unsafe {
+        std::slice::from_raw_parts(buf.as_ptr() as *const PRINTER_INFO_2W, returned as usize)
+    };
+    let mut printers: Vec<PrinterInfo> = infos
+        .iter()
+        .map(|info| {
+            let name = wide_ptr_to_string(info.pPrinterName);
+            PrinterInfo {
+                is_default: default.as_deref() == Some(name.as_str()),
+                driver: wide_ptr_to_string(info.pDriverName),
+                port: wide_ptr_to_string(info.pPortName),
+                location: wide_ptr_to_string(info.pLocation),
+                comment: wide_ptr_to_string(info.pComment),
+                is_network: info.Attributes & PRINTER_ATTRIBUTE_NETWORK != 0,
+                is_offline: info.Status & PRINTER_STATUS_OFFLINE != 0
+                    || info.Attributes & PRINTER_ATTRIBUTE_WORK_OFFLINE != 0,
+                jobs: info.cJobs,
+                name,
+            }
+        })
+        .collect();
+    printers.sort_by_key(|printer| printer.name.to_lowercase());
+    Ok(printers)
+}
+
+#[cfg(windows)]
+fn set_default_printer_impl(name: &str) -> Result<(), AppError> {
+    use windows_sys::Win32::Graphics::Printing::SetDefaultPrinterW;
+
+    let wide: Vec<u16> = name.encode_utf16().chain(std::iter::once(0)).collect();
+    if unsafe { SetDefaultPrinterW(wide.as_ptr()) } == 0 {
+        let error = std::io::Error::last_os_error();
+        // ERROR_INVALID_PRINTER_NAME
+        if error.raw_os_error() == Some(1801) {
+            return Err(AppError::NotFound(format!(
+                "Impressora '{}' não encontrada",
+                name
+            )));
+        }
+        return Err(AppError::internal(
+            "Falha ao definir impressora padrão",
+            error,
+        ));
+    }
+    Ok(())
+}
+
+/// Printer opened through the spooler, closed on drop.
+#[cfg(windows)]
+struct PrinterHandle(windows_sys::Win32::Graphics::Printing::PRINTER_HANDLE);
+
+#[cfg(windows)]
+impl PrinterHandle {
+    /// Asks for administer rights (needed to pause or purge the queue and to cancel
+    /// other users' jobs) and falls back to plain use for everything else.
+    fn open(name: &str) -> Result<Self, AppError> {
+        use windows_sys::Win32::Graphics::Printing::{
+            OpenPrinterW, PRINTER_ACCESS_ADMINISTER, PRINTER_ACCESS_USE, PRINTER_DEFAULTSW,
+        };
+
+        let wide: Vec<u16> = name.encode_utf16().chain(std::iter::once(0)).collect();
+        let mut last_error = None;
+        for access in [PRINTER_ACCESS_ADMINISTER, PRINTER_ACCESS_USE] {
+            let defaults = PRINTER_DEFAULTSW {
+                pDatatype: std::ptr::null_mut(),
+                pDevMode: std::ptr::null_mut(),
+                DesiredAccess: access,
+            };
+            let mut handle = std::ptr::null_mut();
+            if unsafe { OpenPrinterW(wide.as_ptr(), &mut handle, &defaults) } != 0 {
+                return Ok(PrinterHandle(handle));
+            }
+            last_error = Some(std::io::Error::last_os_error());
+        }
+        Err(spooler_error(
+            name,
+            "Falha ao abrir a fila de impressão",
+            last_error.unwrap_or_else(std::io::Error::last_os_error),
+        ))
+    }
+}
+
+#[cfg(windows)]
+impl Drop for PrinterHandle {
+    fn drop(&mut self) {
+        unsafe { windows_sys::Win32::Graphics::Printing::ClosePrinter(self.0) };
+    }
+}
+
+#[cfg(windows)]
+fn spooler_error(printer: &str, message: &str, error: std::io::Error) -> AppError {
+    match error.raw_os_error() {
+        // ERROR_ACCESS_DENIED
+        Some(5) => AppError::AccessDenied(
+            "Sem permissão para gerenciar esta fila; é preciso ser administrador da impressora"
+                .into(),
+        ),
+        // ERROR_INVALID_PRINTER_NAME
+        Some(1801) => AppError::NotFound(format!("Impressora '{}' não encontrada", printer)),
+        // ERROR_INVALID_PARAMETER: the job finished or was removed meanwhile
+        Some(87) => AppError::NotFound("O trabalho não está mais na fila".into()),
+        _ => AppError::internal(message, error),
+    }
+}
+
+#[cfg(windows)]
+fn job_states(status: u32) -> Vec<PrintJobState> {
+    use windows_sys::Win32::Graphics::Printing::{
+        JOB_STATUS_BLOCKED_DEVQ, JOB_STATUS_DELETING, JOB_STATUS_ERROR, JOB_STATUS_OFFLINE,
+        JOB_STATUS_PAPEROUT, JOB_STATUS_PAUSED, JOB_STATUS_PRINTED, JOB_STATUS_PRINTING,
+        JOB_STATUS_SPOOLING, JOB_STATUS_USER_INTERVENTION,
+    };
+
+    [
+        (JOB_STATUS_PAUSED, PrintJobState::Paused),
+        (JOB_STATUS_ERROR, PrintJobState::Error),
+        (JOB_STATUS_DELETING, PrintJobState::Deleting),
+        (JOB_STATUS_SPOOLING, PrintJobState::Spooling),
+        (JOB_STATUS_PRINTING, PrintJobState::Printing),
+        (JOB_STATUS_OFFLINE, PrintJobState::Offline),
+        (JOB_STATUS_PAPEROUT, PrintJobState::PaperOut),
+        (JOB_STATUS_PRINTED, PrintJobState::Printed),
+        (JOB_STATUS_BLOCKED_DEVQ, PrintJobState::Blocked),
+        (
+            JOB_STATUS_USER_INTERVENTION,
+            PrintJobState::UserIntervention,
+        ),
+    ]
+    .into_iter()
+    .filter(|(flag, _)| status & flag != 0)
+    .map(|(_, state)| state)
+    .collect()
+}
+
+/// The spooler reports submission times in UTC.
+#[cfg(windows)]
+fn system_time_to_rfc3339(time: &windows_sys::Win32::Foundation::SYSTEMTIME) -> String {
+    chrono::NaiveDate::from_ymd_opt(time.wYear as i32, time.wMonth as u32, time.wDay as u32)
+        .and_then(|date| {
+            date.and_hms_opt(time.wHour as u32, time.wMinute as u32, time.wSecond as u32)
+        })
+        .map(|naive| naive.and_utc().with_timezone(&chrono::Local).to_rfc3339())
+        .unwrap_or_default()
+}
+
+#[cfg(windows)]
+fn queue_paused(printer: &PrinterHandle) -> bool {
+    use windows_sys::Win32::Graphics::Printing::{
+        GetPrinterW, PRINTER_INFO_6, PRINTER_STATUS_PAUSED,
+    };
+
+    let mut info = PRINTER_INFO_6 { dwStatus: 0 };
+    let mut needed: u32 = 0;
+    let ok = unsafe {
+        GetPrinterW(
+            printer.0,
+            6,
+            &mut info as *mut PRINTER_INFO_6 as *mut u8,
+            std::mem::size_of::<PRINTER_INFO_6>() as u32,
+            &mut needed,
+        )
+    };
+    ok != 0 && info.dwStatus & PRINTER_STATUS_PAUSED != 0
+}
+
+#[cfg(windows)]
+fn list_print_jobs_impl(name: &str) -> Result<PrintQueue, AppError> {
+    use windows_sys::Win32::Graphics::Printing::{EnumJobsW, JOB_INFO_1W};
+
+    let printer = PrinterHandle::open(name)?;
+    let mut needed: u32 = 0;
+    let mut returned: u32 = 0;
+    unsafe {
+        EnumJobsW(
+            printer.0,
+            0,
+            u32::MAX,
+            1,
+            std::ptr::null_mut(),
+            0,
+            &mut needed,
+            &mut returned,
+        )
+    };
+
+    let mut jobs = Vec::new();
+    if needed > 0 {
+        // u64 storage keeps the JOB_INFO_1W array properly aligned
+        let mut buf = vec![0u64; (needed as usize).div_ceil(8)];
+        let ok = unsafe {
+            EnumJobsW(
+                printer.0,
+                0,
+                u32::MAX,
+                1,
+                buf.as_mut_ptr() as *mut u8,
+                needed,
+                &mut needed,
+                &mut returned,
+            )
+        };
+        if ok == 0 {
+            return Err(spooler_error(
+                name,
+                "Falha ao listar a fila de impressão",
+                std::io::Error::last_os_error(),
+            ));
+        }
+        let infos = unsafe {
+            std::slice::from_raw_parts(buf.as_ptr() as *const JOB_INFO_1W, returned as usize)
+        };
+        jobs = infos
+            .iter()
+            .map(|info| PrintJob {
+                id: info.JobId,
+                document: wide_ptr_to_string(info.pDocument),
+                user: wide_ptr_to_string(info.pUserName),
+                machine: wide_ptr_to_string(info.pMachineName),
+                states: job_states(info.Status),
+                status_text: wide_ptr_to_string(info.pStatus),
+                position: info.Position,
+                total_pages: info.TotalPages,
+                pages_printed: info.PagesPrinted,
+                submitted_at: system_time_to_rfc3339(&info.Submitted),
+            })
+            .collect();
+        jobs.sort_by_key(|job: &PrintJob| job.position);
+    }
+
+    Ok(PrintQueue {
+        printer: name.to_string(),
+        paused: queue_paused(&printer),
+        jobs,
+    })
+}
+
+#[cfg(windows)]
+fn cancel_print_job_impl(name: &str, job_id: u32) -> Result<(), AppError> {
+    use windows_sys::Win32::Graphics::Printing::{SetJobW, JOB_CONTROL_DELETE};
+
+    let printer = PrinterHandle::open(name)?;
+    // DELETE rather than CANCEL: it also removes jobs stuck in "Excluindo"
+    if unsafe { SetJobW(printer.0, job_id, 0, std::ptr::null(), JOB_CONTROL_DELETE) } == 0 {
+        return Err(spooler_error(
+            name,
+            "Falha ao cancelar o trabalho",
+            std::io::Error::last_os_error(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(windows)]
+fn control_queue_impl(name: &str, command: u32, message: &str) -> Result<(), AppError> {
+    use windows_sys::Win32::Graphics::Printing::SetPrinterW;
+
+    let printer = PrinterHandle::open(name)?;
+    if unsafe { SetPrinterW(printer.0, 0, std::ptr::null(), command) } == 0 {
+        return Err(spooler_error(
+            name,
+            message,
+            std::io::Error::last_os_error(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(windows)]
+fn set_queue_paused_impl(name: &str, paused: bool) -> Result<(), AppError> {
+    use windows_sys::Win32::Graphics::Printing::{PRINTER_CONTROL_PAUSE, PRINTER_CONTROL_RESUME};
+
+    if paused {
+        control_queue_impl(name, PRINTER_CONTROL_PAUSE, "Falha ao pausar a fila")
+    } else {
+        control_queue_impl(name, PRINTER_CONTROL_RESUME, "Falha ao retomar a fila")
+    }
+}
+
+#[cfg(windows)]
+fn clear_queue_impl(name: &str) -> Result<(), AppError> {
+    use windows_sys::Win32::Graphics::Printing::PRINTER_CONTROL_PURGE;
+
+    control_queue_impl(name, PRINTER_CONTROL_PURGE, "Falha ao limpar a fila")
+}
+
+#[cfg(not(windows))]
+fn list_printers_impl() -> Result<Vec<PrinterInfo>, AppError> {
+    Err(AppError::Unsupported(
+        "Listagem de impressoras disponível apenas no Windows".into(),
+    ))
+}
+
+#[cfg(not(windows))]
+fn default_printer_impl() -> Option<String> {
+    None
+}
+
+#[cfg(not(windows))]
+fn set_default_printer_impl(_name: &str) -> Result<(), AppError> {
+    Err(AppError::Unsupported(
+        "Impressora padrão só pode ser alterada no Windows".into(),
+    ))
+}
+
+#[cfg(not(windows))]
+fn list_print_jobs_impl(_name: &str) -> Result<PrintQueue, AppError> {
+    Err(AppError::Unsupported(
+        "Fila de impressão disponível apenas no Windows".into(),
+    ))
+}
+
+#[cfg(not(windows))]
+fn cancel_print_job_impl(_name: &str, _job_id: u32) -> Result<(), AppError> {
+    Err(AppError::Unsupported(
+        "Fila de impressão disponível apenas no Windows".into(),
+    ))
+}
+
+#[cfg(not(windows))]
+fn set_queue_paused_impl(_name: &str, _paused: bool) -> Result<(), AppError> {
+    Err(AppError::Unsupported(
+        "Fila de impressão disponível apenas no Windows".into(),
+    ))
+}
+
+#[cfg(not(windows))]
+fn clear_queue_impl(_name: &str) -> Result<(), AppError> {
+    Err(AppError::Unsupported(
+        "Fila de impressão disponível apenas no Windows".into(),
+    ))
+}
+
+fn printer_name(name: &str) -> Result<&str, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::InvalidInput(
+            "Informe o nome da impressora".into(),
+        ));
+    }
+    Ok(name)
+}
+
+#[tauri::command]
+pub async fn list_printers() -> Result<Vec<PrinterInfo>, AppError> {
+    crate::metrics::timed("list_printers", async move {
+        // Network printers can take a while to answer
+        tauri::async_runtime::spawn_blocking(list_printers_impl)
+            .await
+            .map_err(|e| AppError::internal("Falha ao listar impressoras", e))?
+    })
+    .await
+}
+
+#[tauri::command]
+pub fn get_default_printer() -> Option<String> {
+    default_printer_impl()
+}
+
+#[tauri::command]
+pub fn set_default_printer(name: String) -> Result<(), AppError> {
+    set_default_printer_impl(printer_name(&name)?)
+}
+
+/// Jobs waiting in `printer`'s queue, in printing order.
+#[tauri::command]
+pub async fn list_print_jobs(printer: String) -> Result<PrintQueue, AppError> {
+    crate::metrics::timed("list_print_jobs", async move {
+        // Shared printers answer through the print server, which can be slow
+        tauri::async_runtime::spawn_blocking(move || list_print_jobs_impl(printer_name(&printer)?))
+            .await
+            .map_err(|e| AppError::internal("Falha ao listar a fila de impressão", e))?
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn cancel_print_job(printer: String, job_id: u32) -> Result<(), AppError> {
+    crate::metrics::timed("cancel_print_job", async move {
+        tauri::async_runtime::spawn_blocking(move || {
+            cancel_print_job_impl(printer_name(&printer)?, job_id)
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao cancelar o trabalho", e))?
+    })
+    .await
+}
+
+/// Pausing holds new jobs in the queue without losing them; resuming sends them on.
+#[tauri::command]
+pub async fn set_print_queue_paused(printer: String, paused: bool) -> Result<(), AppError> {
+    crate::metrics::timed("set_print_queue_paused", async move {
+        tauri::async_runtime::spawn_blocking(move || {
+            set_queue_paused_impl(printer_name(&printer)?, paused)
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao alterar a fila de impressão", e))?
+    })
+    .await
+}
+
+/// Removes every job from the queue, including ones stuck while being deleted.
+#[tauri::command]
+pub async fn clear_print_queue(printer: String) -> Result<(), AppError> {
+    crate::metrics::timed("clear_print_queue", async move {
+        tauri::async_runtime::spawn_blocking(move || clear_queue_impl(printer_name(&printer)?))
+            .await
+            .map_err(|e| AppError::internal("Falha ao limpar a fila de impressão", e))?
+    })
+    .await
+}
@@ -0,0 +1,84 @@
+// ── Quick Links Module ─────────────────────────────────────────
+use crate::error::AppError;
+use rusqlite::params;
+
+/// Counts a link as used; the tray submenu lists the most used links first.
+#[tauri::command]
+pub fn record_quick_link_click(app: tauri::AppHandle, id: i64) -> Result<(), AppError> {
+    record_click(&app, id)?;
+    crate::refresh_tray_menu(&app);
+    Ok(())
+}
+
+pub(crate) fn record_click(app: &tauri::AppHandle, id: i64) -> Result<(), AppError> {
+    let conn = crate::db::open(app)?;
+    conn.execute(
+        "UPDATE quick_links SET click_count = click_count + 1 WHERE id = ?1",
+        params![id],
+    )
+    .map_err(|e| AppError::internal("Falha ao registrar acesso ao link", e))?;
+    Ok(())
+}
+
+/// Applies the order of `ordered_ids`; links left out keep their position.
+#[tauri::command]
+pub fn reorder_quick_links(app: tauri::AppHandle, ordered_ids: Vec<i64>) -> Result<(), AppError> {
+    let mut conn = crate::db::open(&app)?;
+    let tx = conn
+        .transaction()
+        .map_err(|e| AppError::internal("Falha ao reordenar links", e))?;
+    for (index, id) in ordered_ids.iter().enumerate() {
+        tx.execute(
+            "UPDATE quick_links SET sort_order = ?1 WHERE id = ?2",
+            params![index as i64 + 1, id],
+        )
+        .map_err(|e| AppError::internal("Falha ao reordenar links", e))?;
+    }
+    tx.commit()
+        .map_err(|e| AppError::internal("Falha ao reordenar links", e))?;
+
+    crate::refresh_tray_menu(&app);
+    Ok(())
+}
+
+/// Moves a link into `group`, or out of any group with `None`/empty.
+#[tauri::command]
+pub fn set_quick_link_group(
+    app: tauri::AppHandle,
+    id: i64,
+    group: Option<String>,
+) -> Result<(), AppError> {
+    let group = group
+        .map(|group| group.trim().to_string())
+        .filter(|group| !group.is_empty());
+
+    let conn = crate::db::open(&app)?;
+    let changed = conn
+        .execute(
+            "UPDATE quick_links SET group_name = ?1 WHERE id = ?2",
+            params![group, id],
+        )
+        .map_err(|e| AppError::internal("Falha ao agrupar link", e))?;
+    if changed == 0 {
+        return Err(AppError::NotFound("Link não encontrado".into()));
+    }
+
+    crate::refresh_tray_menu(&app);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_quick_link_groups(app: tauri::AppHandle) -> Result<Vec<String>, AppError> {
+    let conn = crate::db::open(&app)?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT DISTINCT group_name FROM quick_links
+             WHERE group_name IS NOT NULL ORDER BY group_name COLLATE NOCASE",
+        )
+        .map_err(|e| AppError::internal("Falha ao consultar grupos", e))?;
+    let rows = stmt
+        .query_map([], |row| row.get(0))
+        .map_err(|e| AppError::internal("Falha ao consultar grupos", e))?;
+    rows.collect::<Result<_, _>>()
+        .map_err(|e| AppError::internal("Falha ao ler grupos", e))
+}
@@ -0,0 +1,481 @@
+// ── Reminders Module ───────────────────────────────────────────
+use crate::error::AppError;
+use crate::settings::Settings;
+use chrono::{DateTime, Duration, Local, NaiveDateTime, TimeZone, Utc};
+use croner::Cron;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::sync::Mutex;
+use tauri::{Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+const TICK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Reminders fired this much after their time mention the original time in the alert.
+const LATE_THRESHOLD_SECS: i64 = 120;
+
+/// How long before a todo's deadline it is announced when no `remind_at` is given.
+const TODO_LEAD_MINUTES_KEY: &str = "todo_reminder_lead_minutes";
+const DEFAULT_TODO_LEAD_MINUTES: i64 = 60;
+
+pub struct Reminders {
+    conn: Mutex<Connection>,
+}
+
+#[derive(serde::Serialize)]
+pub struct Reminder {
+    pub id: i64,
+    pub title: String,
+    pub body: String,
+    pub next_run: Option<String>,
+    pub recurrence: Option<String>,
+    pub enabled: bool,
+    pub last_fired_at: Option<String>,
+    /// Set when the reminder was scheduled from a todo's due date.
+    pub todo_id: Option<i64>,
+}
+
+#[derive(serde::Deserialize)]
+pub struct ReminderInput {
+    pub title: String,
+    #[serde(default)]
+    pub body: String,
+    /// RFC 3339, or local time as sent by `<input type="datetime-local">`.
+    pub due_at: Option<String>,
+    /// Five-field cron rule (`min hour day month weekday`), e.g. `0 9 * * 1-5`.
+    pub recurrence: Option<String>,
+}
+
+/// Timestamps are stored as UTC RFC 3339 so they sort and compare as text.
+pub(crate) fn to_db_time(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+pub(crate) fn parse_due_at(value: &str) -> Result<DateTime<Utc>, AppError> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .and_then(|naive| Local.from_local_datetime(&naive).earliest())
+        .map(|time| time.with_timezone(&Utc))
+        .ok_or_else(|| AppError::InvalidInput(format!("Data/hora inválida: {}", value)))
+}
+
+pub(crate) fn parse_rule(rule: &str) -> Result<Cron, AppError> {
+    Cron::new(rule.trim()).parse().map_err(|e| {
+        AppError::InvalidInput(format!("Regra de recorrência inválida '{}'", rule)).with_details(e)
+    })
+}
+
+/// Next occurrence strictly after `after`, evaluated in local time.
+pub(crate) fn next_occurrence(rule: &str, after: DateTime<Utc>) -> Result<DateTime<Utc>, AppError> {
+    parse_rule(rule)?
+        .find_next_occurrence(&after.with_timezone(&Local), false)
+        .map(|time| time.with_timezone(&Utc))
+        .map_err(|e| {
+            AppError::InvalidInput("Regra de recorrência sem próxima ocorrência".into())
+                .with_details(e)
+        })
+}
+
+/// Validates the input and works out when it should fire first.
+fn first_run(input: &ReminderInput) -> Result<DateTime<Utc>, AppError> {
+    if input.title.trim().is_empty() {
+        return Err(AppError::InvalidInput(
+            "O lembrete precisa de um título".into(),
+        ));
+    }
+
+    let due_at = input.due_at.as_deref().map(parse_due_at).transpose()?;
+    match (input.recurrence.as_deref(), due_at) {
+        (Some(rule), Some(due_at)) => {
+            parse_rule(rule)?;
+            Ok(due_at)
+        }
+        (Some(rule), None) => next_occurrence(rule, Utc::now()),
+        (None, Some(due_at)) => Ok(due_at),
+        (None, None) => Err(AppError::InvalidInput(
+            "Informe a data do lembrete ou uma regra de recorrência".into(),
+        )),
+    }
+}
+
+/// The todos table belongs to the frontend migrations, which only run once the
+/// webview opens the database.
+fn todos_table_exists(conn: &Connection) -> bool {
+    conn.query_row(
+        "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type = 'table' AND name = 'todos'",
+        [],
+        |row| row.get(0),
+    )
+    .unwrap_or(false)
+}
+
+fn row_to_reminder(row: &rusqlite::Row) -> rusqlite::Result<Reminder> {
+    Ok(Reminder {
+        id: row.get(0)?,
+        title: row.get(1)?,
+        body: row.get(2)?,
+        next_run: row.get(3)?,
+        recurrence: row.get(4)?,
+        enabled: row.get(5)?,
+        last_fired_at: row.get(6)?,
+        todo_id: row.get(7)?,
+    })
+}
+
+const SELECT_COLUMNS: &str =
+    "SELECT id, title, body, next_run, recurrence, enabled, last_fired_at, todo_id FROM reminders";
+
+impl Reminders {
+    pub fn open(app: &tauri::AppHandle) -> Result<Self, String> {
+        let conn = crate::db::open(app)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS reminders (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                title TEXT NOT NULL,
+                body TEXT NOT NULL DEFAULT '',
+                next_run TEXT,
+                recurrence TEXT,
+                enabled INTEGER NOT NULL DEFAULT 1,
+                last_fired_at TEXT,
+                todo_id INTEGER,
+                created_at TEXT NOT NULL DEFAULT (datetime('now'))
+            );",
+        )
+        .map_err(|e| format!("Falha ao criar tabela de lembretes: {}", e))?;
+
+        // Tables created before todo deadlines existed lack the link column
+        let has_todo_id: bool = conn
+            .query_row(
+                "SELECT COUNT(*) > 0 FROM pragma_table_info('reminders') WHERE name = 'todo_id'",
+                [],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("Falha ao verificar tabela de lembretes: {}", e))?;
+        if !has_todo_id {
+            conn.execute_batch("ALTER TABLE reminders ADD COLUMN todo_id INTEGER;")
+                .map_err(|e| format!("Falha ao atualizar tabela de lembretes: {}", e))?;
+        }
+
+        Ok(Reminders {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    fn get(&self, id: i64) -> Result<Reminder, AppError> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            &format!("{} WHERE id = ?1", SELECT_COLUMNS),
+            params![id],
+            row_to_reminder,
+        )
+        .optional()
+        .map_err(|e| AppError::internal("Falha ao consultar lembrete", e))?
+        .ok_or_else(|| AppError::NotFound("Lembrete não encontrado".into()))
+    }
+
+    /// Todo reminders are held back while the todo is completed.
+    fn due(&self, now: DateTime<Utc>) -> Result<Vec<Reminder>, AppError> {
+        let conn = self.conn.lock().unwrap();
+        let todo_filter = if todos_table_exists(&conn) {
+            "AND (todo_id IS NULL OR todo_id IN (SELECT id FROM todos WHERE done = 0))"
+        } else {
+            ""
+        };
+        let mut stmt = conn
+            .prepare(&format!(
+                "{} WHERE enabled = 1 AND next_run IS NOT NULL AND next_run <= ?1 {}",
+                SELECT_COLUMNS, todo_filter
+            ))
+            .map_err(|e| AppError::internal("Falha ao consultar lembretes", e))?;
+        let rows = stmt
+            .query_map(params![to_db_time(now)], row_to_reminder)
+            .map_err(|e| AppError::internal("Falha ao consultar lembretes", e))?;
+        rows.collect::<Result<_, _>>()
+            .map_err(|e| AppError::internal("Falha ao ler lembretes", e))
+    }
+
+    /// Recurring reminders move to their next occurrence; one-off ones are disabled.
+    /// Occurrences missed while the app was closed collapse into a single alert.
+    fn mark_fired(&self, reminder: &Reminder, now: DateTime<Utc>) -> Result<(), AppError> {
+        let next_run = match &reminder.recurrence {
+            Some(rule) => Some(to_db_time(next_occurrence(rule, now)?)),
+            None => None,
+        };
+
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE reminders
+             SET next_run = ?1, enabled = ?2, last_fired_at = ?3
+             WHERE id = ?4",
+            params![next_run, next_run.is_some(), to_db_time(now), reminder.id],
+        )
+        .map_err(|e| AppError::internal("Falha ao atualizar lembrete", e))?;
+        Ok(())
+    }
+
+    /// Drops reminders whose todo was deleted.
+    fn prune_todo_reminders(&self) -> Result<(), AppError> {
+        let conn = self.conn.lock().unwrap();
+        if !todos_table_exists(&conn) {
+            return Ok(());
+        }
+        conn.execute(
+            "DELETE FROM reminders
+             WHERE todo_id IS NOT NULL AND todo_id NOT IN (SELECT id FROM todos)",
+            [],
+        )
+        .map_err(|e| AppError::internal("Falha ao limpar lembretes de tarefas", e))?;
+        Ok(())
+    }
+
+    /// One-off reminder scheduled by another module, e.g. a fiscal deadline.
+    pub(crate) fn schedule_once(
+        &self,
+        title: &str,
+        body: &str,
+        at: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO reminders (title, body, next_run) VALUES (?1, ?2, ?3)",
+            params![title, body, to_db_time(at)],
+        )
+        .map_err(|e| AppError::internal("Falha ao agendar lembrete", e))?;
+        Ok(())
+    }
+
+    /// Stores the todo's dates and replaces its reminder, if any.
+    fn schedule_todo(
+        &self,
+        todo_id: i64,
+        due_at: Option<DateTime<Utc>>,
+        remind_at: Option<DateTime<Utc>>,
+        lead_minutes: i64,
+    ) -> Result<(), AppError> {
+        let conn = self.conn.lock().unwrap();
+        let title: String = conn
+            .query_row(
+                "SELECT title FROM todos WHERE id = ?1",
+                params![todo_id],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| AppError::internal("Falha ao consultar tarefa", e))?
+            .ok_or_else(|| AppError::NotFound("Tarefa não encontrada".into()))?;
+
+        conn.execute(
+            "UPDATE todos SET due_at = ?1, remind_at = ?2 WHERE id = ?3",
+            params![due_at.map(to_db_time), remind_at.map(to_db_time), todo_id],
+        )
+        .map_err(|e| AppError::internal("Falha ao salvar prazo da tarefa", e))?;
+        conn.execute("DELETE FROM reminders WHERE todo_id = ?1", params![todo_id])
+            .map_err(|e| AppError::internal("Falha ao atualizar lembrete da tarefa", e))?;
+
+        let fire_at = remind_at.or_else(|| due_at.map(|due| due - Duration::minutes(lead_minutes)));
+        let Some(fire_at) = fire_at else {
+            return Ok(());
+        };
+        let body = match due_at {
+            Some(due) => format!(
+                "Prazo: {}",
+                due.with_timezone(&Local).format("%d/%m/%Y %H:%M")
+            ),
+            None => String::new(),
+        };
+        conn.execute(
+            "INSERT INTO reminders (title, body, next_run, todo_id) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                format!("Tarefa: {}", title),
+                body,
+                to_db_time(fire_at),
+                todo_id
+            ],
+        )
+        .map_err(|e| AppError::internal("Falha ao agendar lembrete da tarefa", e))?;
+        Ok(())
+    }
+}
+
+fn fire(app: &tauri::AppHandle, reminder: &Reminder, now: DateTime<Utc>) {
+    let scheduled = reminder
+        .next_run
+        .as_deref()
+        .and_then(|time| DateTime::parse_from_rfc3339(time).ok());
+
+    let mut body = reminder.body.clone();
+    if let Some(scheduled) = scheduled {
+        if (now - scheduled.with_timezone(&Utc)).num_seconds() > LATE_THRESHOLD_SECS {
+            let local = scheduled.with_timezone(&Local);
+            if !body.is_empty() {
+                body.push('\n');
+            }
+            body.push_str(&format!("Previsto para {}", local.format("%d/%m/%Y %H:%M")));
+        }
+    }
+
+    let _ = app
+        .notification()
+        .builder()
+        .title(&reminder.title)
+        .body(body)
+        .show();
+}
+
+fn tick(app: &tauri::AppHandle) {
+    let reminders = app.state::<Reminders>();
+    if let Err(e) = reminders.prune_todo_reminders() {
+        crate::app_log::error(e);
+    }
+    let now = Utc::now();
+    let Ok(due) = reminders.due(now) else {
+        return;
+    };
+    if due.is_empty() {
+        return;
+    }
+
+    for reminder in &due {
+        fire(app, reminder, now);
+        if let Err(e) = reminders.mark_fired(reminder, now) {
+            crate::app_log::error(e);
+        }
+    }
+    let _ = app.emit("reminders-changed", ());
+}
+
+/// The first tick runs immediately, catching up on reminders due while the app was closed.
+pub fn start_scheduler(app: tauri::AppHandle) {
+    std::thread::spawn(move || loop {
+        tick(&app);
+        std::thread::sleep(TICK_INTERVAL);
+    });
+}
+
+#[tauri::command]
+pub fn list_reminders(reminders: tauri::State<'_, Reminders>) -> Result<Vec<Reminder>, AppError> {
+    let conn = reminders.conn.lock().unwrap();
+    let mut stmt = conn
+        .prepare(&format!(
+            "{} ORDER BY enabled DESC, next_run IS NULL, next_run",
+            SELECT_COLUMNS
+        ))
+        .map_err(|e| AppError::internal("Falha ao consultar lembretes", e))?;
+    let rows = stmt
+        .query_map([], row_to_reminder)
+        .map_err(|e| AppError::internal("Falha ao consultar lembretes", e))?;
+    rows.collect::<Result<_, _>>()
+        .map_err(|e| AppError::internal("Falha ao ler lembretes", e))
+}
+
+#[tauri::command]
+pub fn create_reminder(
+    reminders: tauri::State<'_, Reminders>,
+    input: ReminderInput,
+) -> Result<Reminder, AppError> {
+    let next_run = first_run(&input)?;
+    let id = {
+        let conn = reminders.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO reminders (title, body, next_run, recurrence) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                input.title.trim(),
+                input.body,
+                to_db_time(next_run),
+                input.recurrence.as_deref().map(str::trim)
+            ],
+        )
+        .map_err(|e| AppError::internal("Falha ao salvar lembrete", e))?;
+        conn.last_insert_rowid()
+    };
+    reminders.get(id)
+}
+
+#[tauri::command]
+pub fn update_reminder(
+    reminders: tauri::State<'_, Reminders>,
+    id: i64,
+    input: ReminderInput,
+) -> Result<Reminder, AppError> {
+    let next_run = first_run(&input)?;
+    {
+        let conn = reminders.conn.lock().unwrap();
+        let changed = conn
+            .execute(
+                "UPDATE reminders
+                 SET title = ?1, body = ?2, next_run = ?3, recurrence = ?4, enabled = 1
+                 WHERE id = ?5",
+                params![
+                    input.title.trim(),
+                    input.body,
+                    to_db_time(next_run),
+                    input.recurrence.as_deref().map(str::trim),
+                    id
+                ],
+            )
+            .map_err(|e| AppError::internal("Falha ao salvar lembrete", e))?;
+        if changed == 0 {
+            return Err(AppError::NotFound("Lembrete não encontrado".into()));
+        }
+    }
+    reminders.get(id)
+}
+
+#[tauri::command]
+pub fn set_reminder_enabled(
+    reminders: tauri::State<'_, Reminders>,
+    id: i64,
+    enabled: bool,
+) -> Result<Reminder, AppError> {
+    let reminder = reminders.get(id)?;
+
+    // Re-enabling a recurring reminder resumes from now instead of replaying the past
+    let next_run = match (&reminder.recurrence, enabled) {
+        (Some(rule), true) => Some(to_db_time(next_occurrence(rule, Utc::now())?)),
+        _ => reminder.next_run,
+    };
+
+    {
+        let conn = reminders.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE reminders SET enabled = ?1, next_run = ?2 WHERE id = ?3",
+            params![enabled, next_run, id],
+        )
+        .map_err(|e| AppError::internal("Falha ao atualizar lembrete", e))?;
+    }
+    reminders.get(id)
+}
+
+#[tauri::command]
+pub fn delete_reminder(reminders: tauri::State<'_, Reminders>, id: i64) -> Result<(), AppError> {
+    let conn = reminders.conn.lock().unwrap();
+    conn.execute("DELETE FROM reminders WHERE id = ?1", params![id])
+        .map_err(|e| AppError::internal("Falha ao excluir lembrete", e))?;
+    Ok(())
+}
+
+/// `due_at`/`remind_at` follow the same formats as `ReminderInput::due_at`; without
+/// `remind_at` the alert goes out `todo_reminder_lead_minutes` before the deadline.
+/// Passing neither clears the todo's schedule.
+#[tauri::command]
+pub fn set_todo_schedule(
+    app: tauri::AppHandle,
+    todo_id: i64,
+    due_at: Option<String>,
+    remind_at: Option<String>,
+) -> Result<(), AppError> {
+    let due_at = due_at.as_deref().map(parse_due_at).transpose()?;
+    let remind_at = remind_at.as_deref().map(parse_due_at).transpose()?;
+    let lead_minutes = app
+        .state::<Settings>()
+        .get::<i64>(TODO_LEAD_MINUTES_KEY)
+        .unwrap_or(DEFAULT_TODO_LEAD_MINUTES)
+        .max(0);
+
+    app.state::<Reminders>()
+        .schedule_todo(todo_id, due_at, remind_at, lead_minutes)?;
+    let _ = app.emit("reminders-changed", ());
+    Ok(())
+}
@@ -0,0 +1,300 @@
+// ── Batch Renamer Module ───────────────────────────────────────
+use crate::error::AppError;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+/// Characters Windows refuses in file names.
+const FORBIDDEN_CHARS: &[char] = &['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+
+#[derive(serde::Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum CaseTransform {
+    Lower,
+    Upper,
+    /// First letter of every word upper-cased.
+    Title,
+}
+
+/// How new names are built. Steps run in order: the template is expanded, then
+/// `find` is replaced, then the case is changed. The extension is always kept.
+#[derive(serde::Deserialize)]
+pub struct RenamePattern {
+    /// Name without extension. Tokens: `{original}` (current name without
+    /// extension), `{n}` (counter), `{date}` (file modified date), `{today}`.
+    #[serde(default = "default_template")]
+    pub template: String,
+    #[serde(default)]
+    pub find: Option<String>,
+    #[serde(default)]
+    pub replace: String,
+    #[serde(default)]
+    pub case: Option<CaseTransform>,
+    /// First value of `{n}`.
+    #[serde(default = "default_start")]
+    pub start: u64,
+    /// Zero-padded width of `{n}`; by default wide enough for the last number.
+    #[serde(default)]
+    pub padding: Option<usize>,
+    /// chrono format for `{date}` and `{today}`.
+    #[serde(default)]
+    pub date_format: Option<String>,
+}
+
+fn default_template() -> String {
+    "{original}".into()
+}
+
+fn default_start() -> u64 {
+    1
+}
+
+#[derive(serde::Serialize)]
+pub struct RenameResult {
+    pub path: String,
+    pub new_name: String,
+    pub new_path: String,
+    pub renamed: bool,
+    pub error: Option<String>,
+}
+
+fn title_case(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut word_start = true;
+    for c in text.chars() {
+        if word_start {
+            result.extend(c.to_uppercase());
+        } else {
+            result.extend(c.to_lowercase());
+        }
+        word_start = !c.is_alphanumeric();
+    }
+    result
+}
+
+/// Single pass, so braces inside the original name are never read as tokens.
+/// Unknown tokens are kept literally.
+fn expand_template(template: &str, tokens: &[(&str, &str)]) -> String {
+    let mut result = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        result.push_str(&rest[..open]);
+        let after = &rest[open..];
+        let value = after.find('}').and_then(|close| {
+            let name = &after[1..close];
+            tokens
+                .iter()
+                .find(|(token, _)| *token == name)
+                .map(|(_, value)| (*value, close))
+        });
+        match value {
+            Some((value, close)) => {
+                result.push_str(value);
+                rest = &after[close + 1..];
+            }
+            None => {
+                result.push('{');
+                rest = &after[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+fn validate_file_name(name: &str) -> Result<(), String> {
+    if name.trim().is_empty() || name == "." || name == ".." {
+        return Err("O novo nome ficou vazio".into());
+    }
+    if let Some(c) = name
+        .chars()
+        .find(|c| FORBIDDEN_CHARS.contains(c) || c.is_control())
+    {
+        return Err(format!("O novo nome contém o caractere inválido '{}'", c));
+    }
+    if name.ends_with(['.', ' ']) {
+        return Err("O novo nome não pode terminar com ponto ou espaço".into());
+    }
+    Ok(())
+}
+
+fn new_name(
+    pattern: &RenamePattern,
+    path: &Path,
+    counter: &str,
+    today: &str,
+) -> Result<String, String> {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .ok_or("Não foi possível determinar o nome do arquivo")?;
+    let date_format = pattern
+        .date_format
+        .as_deref()
+        .unwrap_or(DEFAULT_DATE_FORMAT);
+    let modified = std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .map(|time| {
+            chrono::DateTime::<chrono::Local>::from(time)
+                .format(date_format)
+                .to_string()
+        })
+        .map_err(|e| format!("Falha ao ler o arquivo: {}", e))?;
+
+    let mut name = expand_template(
+        &pattern.template,
+        &[
+            ("original", &stem),
+            ("n", counter),
+            ("date", &modified),
+            ("today", today),
+        ],
+    );
+    if let Some(find) = pattern.find.as_deref().filter(|find| !find.is_empty()) {
+        name = name.replace(find, &pattern.replace);
+    }
+    name = match pattern.case {
+        Some(CaseTransform::Lower) => name.to_lowercase(),
+        Some(CaseTransform::Upper) => name.to_uppercase(),
+        Some(CaseTransform::Title) => title_case(&name),
+        None => name,
+    };
+    let name = name.trim().to_string();
+    let name = match path.extension() {
+        Some(extension) => format!("{}.{}", name, extension.to_string_lossy()),
+        None => name,
+    };
+    validate_file_name(&name)?;
+    Ok(name)
+}
+
+/// Computes every new name and flags the ones that can't be applied: invalid
+/// names, two files getting the same name, or a name taken by a file outside the batch.
+fn plan(paths: &[String], pattern: &RenamePattern) -> Vec<RenameResult> {
+    // {n} is zero-padded to the width of the last number, so names sort correctly
+    let last = pattern.start + paths.len().saturating_sub(1) as u64;
+    let width = pattern.padding.unwrap_or(last.to_string().len());
+    let date_format = pattern
+        .date_format
+        .as_deref()
+        .unwrap_or(DEFAULT_DATE_FORMAT);
+    let today = chrono::Local::now().format(date_format).to_string();
+
+    // Names are compared case-insensitively, as on Windows
+    let sources: HashSet<String> = paths.iter().map(|path| path.to_lowercase()).collect();
+    let mut targets = HashSet::new();
+
+    paths
+        .iter()
+        .enumerate()
+        .map(|(index, path)| {
+            let source = Path::new(path);
+            let counter = format!("{:0width$}", pattern.start + index as u64, width = width);
+            let outcome = if source.is_file() {
+                new_name(pattern, source, &counter, &today)
+            } else {
+                Err("Arquivo não encontrado".into())
+            };
+            let (new_name, new_path, error) = match outcome {
+                Ok(new_name) => {
+                    let new_path = source.with_file_name(&new_name);
+                    let key = new_path.to_string_lossy().to_lowercase();
+                    let error = if !targets.insert(key.clone()) {
+                        Some(format!(
+                            "Outro arquivo do lote também ficaria como '{}'",
+                            new_name
+                        ))
+                    } else if new_path.exists() && !sources.contains(&key) {
+                        Some(format!("Já existe um arquivo chamado '{}'", new_name))
+                    } else {
+                        None
+                    };
+                    (new_name, new_path, error)
+                }
+                Err(error) => (String::new(), PathBuf::new(), Some(error)),
+            };
+            RenameResult {
+                path: path.clone(),
+                new_name,
+                new_path: new_path.to_string_lossy().to_string(),
+                renamed: false,
+                error,
+            }
+        })
+        .collect()
+}
+
+/// Renames in two steps, through temporary names, so swaps and renumbering
+/// (`1.pdf` → `2.pdf`, `2.pdf` → `3.pdf`) don't trip over each other. A failure
+/// puts the files already moved back under their original names.
+fn apply(results: &mut [RenameResult]) -> Result<(), AppError> {
+    let stamp = chrono::Utc::now().timestamp_millis();
+    let mut staged: Vec<(usize, PathBuf)> = Vec::new();
+
+    let rollback = |staged: &[(usize, PathBuf)], results: &[RenameResult]| {
+        for (index, temp) in staged {
+            let _ = std::fs::rename(temp, &results[*index].path);
+        }
+    };
+
+    for (index, result) in results.iter().enumerate() {
+        let temp =
+            Path::new(&result.path).with_file_name(format!(".renomeando-{}-{}", stamp, index));
+        if let Err(e) = std::fs::rename(&result.path, &temp) {
+            rollback(&staged, results);
+            return Err(AppError::io(
+                format!("Falha ao renomear {}", result.path),
+                e,
+            ));
+        }
+        staged.push((index, temp));
+    }
+
+    for position in 0..staged.len() {
+        let (index, temp) = &staged[position];
+        if let Err(e) = std::fs::rename(temp, &results[*index].new_path) {
+            // Files already at their new name go back as well
+            for (index, _) in &staged[..position] {
+                let _ = std::fs::rename(&results[*index].new_path, &results[*index].path);
+            }
+            rollback(&staged[position..], results);
+            return Err(AppError::io(
+                format!("Falha ao renomear {}", results[*index].path),
+                e,
+            ));
+        }
+    }
+
+    for result in results.iter_mut() {
+        result.renamed = true;
+    }
+    Ok(())
+}
+
+/// Renames local files by `pattern`. With `dry_run` (or when any new name has a
+/// problem) nothing is touched and the result is the preview; otherwise every
+/// file is renamed or, on failure, none is.
+#[tauri::command]
+pub async fn batch_rename(
+    paths: Vec<String>,
+    pattern: RenamePattern,
+    dry_run: bool,
+) -> Result<Vec<RenameResult>, AppError> {
+    crate::metrics::timed("batch_rename", async move {
+        if paths.is_empty() {
+            return Err(AppError::InvalidInput(
+                "Selecione pelo menos 1 arquivo.".into(),
+            ));
+        }
+        tauri::async_runtime::spawn_blocking(move || {
+            let mut results = plan(&paths, &pattern);
+            if !dry_run && results.iter().all(|result| result.error.is_none()) {
+                apply(&mut results)?;
+            }
+            Ok(results)
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao renomear arquivos", e))?
+    })
+    .await
+}
@@ -0,0 +1,529 @@
+// ── Scheduler Module ───────────────────────────────────────────
+use crate::error::AppError;
+use crate::reminders::{next_occurrence, parse_rule, to_db_time};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Mutex;
+use tauri::{Emitter, Manager};
+
+const TICK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+/// Runs kept per schedule; older history is pruned after each run.
+const RUNS_KEPT_PER_SCHEDULE: u32 = 50;
+const DEFAULT_RUNS_LIMIT: u32 = 100;
+const IMAGE_EXTENSIONS: [&str; 5] = ["jpg", "jpeg", "png", "webp", "bmp"];
+
+const STATUS_SUCCEEDED: &str = "succeeded";
+const STATUS_FAILED: &str = "failed";
+
+/// Internal job a schedule triggers. Stored as JSON, tagged by `kind`.
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ScheduledTask {
+    /// Files the XMLs waiting in the DF-e inbox folder, as `organize_xml_inbox`.
+    XmlInbox,
+    /// Database backup to the backup folder, as `backup_now`.
+    Backup,
+    /// Recompresses, in place, the images directly inside `folder` that changed
+    /// since the last successful run, so photos are not recompressed every time.
+    CompressImages {
+        folder: String,
+        #[serde(default)]
+        quality: Option<u8>,
+        #[serde(default)]
+        max_width: Option<u32>,
+    },
+}
+
+impl ScheduledTask {
+    fn validate(&self) -> Result<(), AppError> {
+        match self {
+            ScheduledTask::CompressImages { folder, .. } if !Path::new(folder).is_dir() => Err(
+                AppError::NotFound(format!("Pasta não encontrada: {}", folder)),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the summary shown in the execution history.
+    fn run(
+        &self,
+        app: &tauri::AppHandle,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<String, AppError> {
+        match self {
+            ScheduledTask::XmlInbox => {
+                let report = crate::xml_inbox::organize(app, false, true)?;
+                if report.moved > 0 || report.failed > 0 {
+                    let _ = app.emit("xml-inbox-organized", &report);
+                }
+                Ok(format!(
+                    "{} XML(s) organizado(s), {} ignorado(s), {} com falha",
+                    report.moved, report.skipped, report.failed
+                ))
+            }
+            ScheduledTask::Backup => {
+                let backup = crate::backup::run_backup(app)?;
+                Ok(format!("Backup {} criado", backup.file_name))
+            }
+            ScheduledTask::CompressImages {
+                folder,
+                quality,
+                max_width,
+            } => {
+                let paths = changed_images(Path::new(folder), since)?;
+                if paths.is_empty() {
+                    return Ok("Nenhuma imagem nova na pasta".into());
+                }
+                let report = crate::images::compress_batch(
+                    app,
+                    paths,
+                    quality.unwrap_or(crate::images::DEFAULT_QUALITY),
+                    *max_width,
+                    None,
+                );
+                let failed = report
+                    .images
+                    .iter()
+                    .filter(|image| image.error.is_some())
+                    .count();
+                Ok(format!(
+                    "{} imagem(ns) processada(s), {:.1} MB economizados, {} com falha",
+                    report.images.len() - failed,
+                    report.saved_bytes as f64 / (1024.0 * 1024.0),
+                    failed
+                ))
+            }
+        }
+    }
+}
+
+/// Images directly inside `folder` modified after `since` (all of them without it).
+fn changed_images(folder: &Path, since: Option<DateTime<Utc>>) -> Result<Vec<String>, AppError> {
+    let entries = std::fs::read_dir(folder)
+        .map_err(|e| AppError::io(format!("Falha ao listar {}", folder.display()), e))?;
+    let mut paths: Vec<String> = entries
+        .flatten()
+        .filter(|entry| {
+            entry.path().extension().is_some_and(|extension| {
+                IMAGE_EXTENSIONS.contains(&extension.to_string_lossy().to_lowercase().as_str())
+            })
+        })
+        .filter(|entry| {
+            let Ok(metadata) = entry.metadata() else {
+                return false;
+            };
+            metadata.is_file()
+                && match (since, metadata.modified()) {
+                    // Whole seconds, as stored; files the last run rewrote are not newer
+                    (Some(since), Ok(modified)) => {
+                        DateTime::<Utc>::from(modified).timestamp() > since.timestamp()
+                    }
+                    _ => true,
+                }
+        })
+        .map(|entry| entry.path().to_string_lossy().to_string())
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+#[derive(serde::Serialize)]
+pub struct Schedule {
+    pub id: i64,
+    pub name: String,
+    pub task: ScheduledTask,
+    /// Five-field cron rule (`min hour day month weekday`), e.g. `0 2 * * *`.
+    pub rule: String,
+    pub enabled: bool,
+    pub next_run: Option<String>,
+    pub last_run_at: Option<String>,
+    /// `succeeded` or `failed`.
+    pub last_status: Option<String>,
+    pub last_success_at: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+pub struct ScheduleInput {
+    pub name: String,
+    pub task: ScheduledTask,
+    pub rule: String,
+}
+
+#[derive(serde::Serialize, Clone)]
+pub struct ScheduleRun {
+    pub id: i64,
+    pub schedule_id: i64,
+    pub schedule_name: String,
+    pub started_at: String,
+    pub finished_at: String,
+    /// `succeeded` or `failed`.
+    pub status: String,
+    pub message: String,
+}
+
+fn db_error(message: &str, e: rusqlite::Error) -> AppError {
+    AppError::internal(message, e)
+}
+
+fn row_to_schedule(row: &rusqlite::Row) -> rusqlite::Result<Schedule> {
+    let task: String = row.get(2)?;
+    let task = serde_json::from_str(&task).map_err(|e| {
+        rusqlite::Error::FromSqlConversionFailure(2, rusqlite::types::Type::Text, Box::new(e))
+    })?;
+    Ok(Schedule {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        task,
+        rule: row.get(3)?,
+        enabled: row.get(4)?,
+        next_run: row.get(5)?,
+        last_run_at: row.get(6)?,
+        last_status: row.get(7)?,
+        last_success_at: row.get(8)?,
+    })
+}
+
+fn row_to_run(row: &rusqlite::Row) -> rusqlite::Result<ScheduleRun> {
+    Ok(ScheduleRun {
+        id: row.get(0)?,
+        schedule_id: row.get(1)?,
+        schedule_name: row.get(2)?,
+        started_at: row.get(3)?,
+        finished_at: row.get(4)?,
+        status: row.get(5)?,
+        message: row.get(6)?,
+    })
+}
+
+const SELECT_COLUMNS: &str = "SELECT id, name, task, rule, enabled, next_run, last_run_at, \
+     last_status, last_success_at FROM schedules";
+
+const SELECT_RUN_COLUMNS: &str = "SELECT r.id, r.schedule_id, s.name, r.started_at, \
+     r.finished_at, r.status, r.message \
+     FROM schedule_runs r JOIN schedules s ON s.id = r.schedule_id";
+
+/// Recurring internal jobs (DF-e inbox, backups, folder compression) with cron
+/// rules, checked every 30 seconds like the reminders.
+pub struct Scheduler {
+    conn: Mutex<Connection>,
+    /// Schedules executing right now, so "run now" never overlaps a scheduled run.
+    running: Mutex<HashSet<i64>>,
+}
+
+impl Scheduler {
+    pub fn open(app: &tauri::AppHandle) -> Result<Self, String> {
+        let conn = crate::db::open(app)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS schedules (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL,
+                task TEXT NOT NULL,
+                rule TEXT NOT NULL,
+                enabled INTEGER NOT NULL DEFAULT 1,
+                next_run TEXT,
+                last_run_at TEXT,
+                last_status TEXT,
+                last_success_at TEXT,
+                created_at TEXT NOT NULL DEFAULT (datetime('now'))
+            );
+            CREATE TABLE IF NOT EXISTS schedule_runs (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                schedule_id INTEGER NOT NULL,
+                started_at TEXT NOT NULL,
+                finished_at TEXT NOT NULL,
+                status TEXT NOT NULL,
+                message TEXT NOT NULL
+            );",
+        )
+        .map_err(|e| format!("Falha ao criar tabelas de agendamentos: {}", e))?;
+
+        Ok(Scheduler {
+            conn: Mutex::new(conn),
+            running: Mutex::new(HashSet::new()),
+        })
+    }
+
+    fn get(&self, id: i64) -> Result<Schedule, AppError> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            &format!("{} WHERE id = ?1", SELECT_COLUMNS),
+            params![id],
+            row_to_schedule,
+        )
+        .optional()
+        .map_err(|e| db_error("Falha ao consultar agendamento", e))?
+        .ok_or_else(|| AppError::NotFound("Agendamento não encontrado".into()))
+    }
+
+    fn due(&self, now: DateTime<Utc>) -> Result<Vec<Schedule>, AppError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare(&format!(
+                "{} WHERE enabled = 1 AND next_run IS NOT NULL AND next_run <= ?1 ORDER BY next_run",
+                SELECT_COLUMNS
+            ))
+            .map_err(|e| db_error("Falha ao consultar agendamentos", e))?;
+        let rows = stmt
+            .query_map(params![to_db_time(now)], row_to_schedule)
+            .map_err(|e| db_error("Falha ao consultar agendamentos", e))?;
+        rows.collect::<Result<_, _>>()
+            .map_err(|e| db_error("Falha ao ler agendamentos", e))
+    }
+
+    /// Moves the schedule to its next occurrence before it runs, so a long run is
+    /// not picked up again by the next tick. Occurrences missed while the app was
+    /// closed collapse into a single run.
+    fn advance(&self, schedule: &Schedule, now: DateTime<Utc>) -> Result<(), AppError> {
+        let next_run = next_occurrence(&schedule.rule, now)?;
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE schedules SET next_run = ?1 WHERE id = ?2",
+            params![to_db_time(next_run), schedule.id],
+        )
+        .map_err(|e| db_error("Falha ao atualizar agendamento", e))?;
+        Ok(())
+    }
+
+    fn record_run(
+        &self,
+        schedule: &Schedule,
+        started_at: DateTime<Utc>,
+        finished_at: DateTime<Utc>,
+        outcome: &Result<String, AppError>,
+    ) -> Result<ScheduleRun, AppError> {
+        let (status, message) = match outcome {
+            Ok(message) => (STATUS_SUCCEEDED, message.clone()),
+            Err(e) => (STATUS_FAILED, e.to_string()),
+        };
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO schedule_runs (schedule_id, started_at, finished_at, status, message)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                schedule.id,
+                to_db_time(started_at),
+                to_db_time(finished_at),
+                status,
+                message
+            ],
+        )
+        .map_err(|e| db_error("Falha ao registrar execução", e))?;
+        let run_id = conn.last_insert_rowid();
+
+        // The finish time is the cutoff for "changed since the last run"; files the
+        // run itself rewrote are older than it
+        conn.execute(
+            "UPDATE schedules
+             SET last_run_at = ?1, last_status = ?2,
+                 last_success_at = CASE WHEN ?2 = ?3 THEN ?1 ELSE last_success_at END
+             WHERE id = ?4",
+            params![
+                to_db_time(finished_at),
+                status,
+                STATUS_SUCCEEDED,
+                schedule.id
+            ],
+        )
+        .map_err(|e| db_error("Falha ao atualizar agendamento", e))?;
+        conn.execute(
+            "DELETE FROM schedule_runs WHERE schedule_id = ?1 AND id NOT IN (
+                SELECT id FROM schedule_runs WHERE schedule_id = ?1 ORDER BY id DESC LIMIT ?2
+             )",
+            params![schedule.id, RUNS_KEPT_PER_SCHEDULE],
+        )
+        .map_err(|e| db_error("Falha ao limpar histórico de execuções", e))?;
+
+        conn.query_row(
+            &format!("{} WHERE r.id = ?1", SELECT_RUN_COLUMNS),
+            params![run_id],
+            row_to_run,
+        )
+        .map_err(|e| db_error("Falha ao consultar execução", e))
+    }
+}
+
+fn execute(app: &tauri::AppHandle, schedule: &Schedule) -> Result<ScheduleRun, AppError> {
+    let scheduler = app.state::<Scheduler>();
+    if !scheduler.running.lock().unwrap().insert(schedule.id) {
+        return Err(AppError::AlreadyExists(format!(
+            "'{}' já está em execução",
+            schedule.name
+        )));
+    }
+    let since = schedule
+        .last_success_at
+        .as_deref()
+        .and_then(|time| DateTime::parse_from_rfc3339(time).ok())
+        .map(|time| time.with_timezone(&Utc));
+
+    let started_at = Utc::now();
+    let outcome = schedule.task.run(app, since);
+    let finished_at = Utc::now();
+    scheduler.running.lock().unwrap().remove(&schedule.id);
+
+    let run = scheduler.record_run(schedule, started_at, finished_at, &outcome)?;
+    if outcome.is_err() {
+        let _ = app.emit("scheduled-task-failed", &run);
+    }
+    let _ = app.emit("schedules-changed", ());
+    Ok(run)
+}
+
+/// Due schedules run one after the other on this thread.
+fn tick(app: &tauri::AppHandle) {
+    let scheduler = app.state::<Scheduler>();
+    let now = Utc::now();
+    let due = match scheduler.due(now) {
+        Ok(due) => due,
+        Err(e) => {
+            crate::app_log::error(e);
+            return;
+        }
+    };
+    for schedule in &due {
+        if let Err(e) = scheduler.advance(schedule, now) {
+            crate::app_log::error(e);
+            continue;
+        }
+        if let Err(e) = execute(app, schedule) {
+            crate::app_log::error(format!(
+                "Tarefa agendada '{}' não executada: {}",
+                schedule.name, e
+            ));
+        }
+    }
+}
+
+/// The first tick runs immediately, catching up on runs due while the app was closed.
+pub fn start_scheduler(app: tauri::AppHandle) {
+    std::thread::spawn(move || loop {
+        tick(&app);
+        std::thread::sleep(TICK_INTERVAL);
+    });
+}
+
+#[tauri::command]
+pub fn list_schedules(scheduler: tauri::State<'_, Scheduler>) -> Result<Vec<Schedule>, AppError> {
+    let conn = scheduler.conn.lock().unwrap();
+    let mut stmt = conn
+        .prepare(&format!(
+            "{} ORDER BY enabled DESC, next_run IS NULL, next_run",
+            SELECT_COLUMNS
+        ))
+        .map_err(|e| db_error("Falha ao consultar agendamentos", e))?;
+    let rows = stmt
+        .query_map([], row_to_schedule)
+        .map_err(|e| db_error("Falha ao consultar agendamentos", e))?;
+    rows.collect::<Result<_, _>>()
+        .map_err(|e| db_error("Falha ao ler agendamentos", e))
+}
+
+#[tauri::command]
+pub fn create_schedule(
+    scheduler: tauri::State<'_, Scheduler>,
+    input: ScheduleInput,
+) -> Result<Schedule, AppError> {
+    let name = input.name.trim();
+    if name.is_empty() {
+        return Err(AppError::InvalidInput(
+            "O agendamento precisa de um nome".into(),
+        ));
+    }
+    let rule = input.rule.trim();
+    parse_rule(rule)?;
+    input.task.validate()?;
+    let next_run = next_occurrence(rule, Utc::now())?;
+    let task = serde_json::to_string(&input.task)
+        .map_err(|e| AppError::internal("Falha ao salvar agendamento", e))?;
+
+    let id = {
+        let conn = scheduler.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO schedules (name, task, rule, next_run) VALUES (?1, ?2, ?3, ?4)",
+            params![name, task, rule, to_db_time(next_run)],
+        )
+        .map_err(|e| db_error("Falha ao salvar agendamento", e))?;
+        conn.last_insert_rowid()
+    };
+    scheduler.get(id)
+}
+
+#[tauri::command]
+pub fn set_schedule_enabled(
+    scheduler: tauri::State<'_, Scheduler>,
+    id: i64,
+    enabled: bool,
+) -> Result<Schedule, AppError> {
+    let schedule = scheduler.get(id)?;
+
+    // Re-enabling resumes from now instead of catching up on the time it was off
+    let next_run = if enabled {
+        Some(to_db_time(next_occurrence(&schedule.rule, Utc::now())?))
+    } else {
+        schedule.next_run
+    };
+
+    {
+        let conn = scheduler.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE schedules SET enabled = ?1, next_run = ?2 WHERE id = ?3",
+            params![enabled, next_run, id],
+        )
+        .map_err(|e| db_error("Falha ao atualizar agendamento", e))?;
+    }
+    scheduler.get(id)
+}
+
+#[tauri::command]
+pub fn delete_schedule(scheduler: tauri::State<'_, Scheduler>, id: i64) -> Result<(), AppError> {
+    let conn = scheduler.conn.lock().unwrap();
+    conn.execute(
+        "DELETE FROM schedule_runs WHERE schedule_id = ?1",
+        params![id],
+    )
+    .map_err(|e| db_error("Falha ao excluir agendamento", e))?;
+    conn.execute("DELETE FROM schedules WHERE id = ?1", params![id])
+        .map_err(|e| db_error("Falha ao excluir agendamento", e))?;
+    Ok(())
+}
+
+/// Runs the schedule's task right away, even if disabled; its next scheduled
+/// run is not affected.
+#[tauri::command]
+pub async fn run_schedule_now(app: tauri::AppHandle, id: i64) -> Result<ScheduleRun, AppError> {
+    crate::metrics::timed("run_schedule_now", async move {
+        tauri::async_runtime::spawn_blocking(move || {
+            let schedule = app.state::<Scheduler>().get(id)?;
+            execute(&app, &schedule)
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao executar agendamento", e))?
+    })
+    .await
+}
+
+/// Execution history, newest first; `schedule_id` narrows it to one schedule.
+#[tauri::command]
+pub fn list_schedule_runs(
+    scheduler: tauri::State<'_, Scheduler>,
+    schedule_id: Option<i64>,
+    limit: Option<u32>,
+) -> Result<Vec<ScheduleRun>, AppError> {
+    let conn = scheduler.conn.lock().unwrap();
+    let mut stmt = conn
+        .prepare(&format!(
+            "{} WHERE ?1 IS NULL OR r.schedule_id = ?1 ORDER BY r.id DESC LIMIT ?2",
+            SELECT_RUN_COLUMNS
+        ))
+        .map_err(|e| db_error("Falha ao consultar execuções", e))?;
+    let rows = stmt
+        .query_map(
+            params![schedule_id, limit.unwrap_or(DEFAULT_RUNS_LIMIT)],
+            row_to_run,
+        )
+        .map_err(|e| db_error("Falha ao consultar execuções", e))?;
+    rows.collect::<Result<_, _>>()
+        .map_err(|e| db_error("Falha ao ler execuções", e))
+}
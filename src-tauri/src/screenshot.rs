@@ -0,0 +1,231 @@
+// ── Screenshot Module ──────────────────────────────────────────
+use crate::error::AppError;
+use image::RgbaImage;
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use xcap::{Monitor, Window};
+
+#[derive(serde::Serialize)]
+pub struct CaptureMonitor {
+    pub id: u32,
+    pub name: String,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub is_primary: bool,
+}
+
+#[derive(serde::Serialize)]
+pub struct CaptureWindow {
+    pub id: u32,
+    pub title: String,
+    pub app_name: String,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(serde::Serialize)]
+pub struct CaptureTargets {
+    pub monitors: Vec<CaptureMonitor>,
+    pub windows: Vec<CaptureWindow>,
+}
+
+/// Region coordinates are physical pixels in virtual-desktop space.
+#[derive(serde::Deserialize)]
+pub enum CaptureTarget {
+    Screen {
+        monitor_id: Option<u32>,
+    },
+    Window {
+        window_id: u32,
+    },
+    Region {
+        x: i32,
+        y: i32,
+        width: u32,
+        height: u32,
+    },
+}
+
+#[derive(serde::Serialize)]
+pub struct CaptureResult {
+    pub width: u32,
+    pub height: u32,
+    pub saved_path: Option<String>,
+    pub png_base64: Option<String>,
+}
+
+fn monitor_by_id(monitor_id: Option<u32>) -> Result<Monitor, AppError> {
+    let monitors =
+        Monitor::all().map_err(|e| AppError::internal("Falha ao listar monitores", e))?;
+    let found = match monitor_id {
+        Some(id) => monitors.into_iter().find(|m| m.id().ok() == Some(id)),
+        None => monitors
+            .into_iter()
+            .find(|m| m.is_primary().unwrap_or(false)),
+    };
+    found.ok_or_else(|| AppError::NotFound("Monitor não encontrado".into()))
+}
+
+fn capture_region(x: i32, y: i32, width: u32, height: u32) -> Result<RgbaImage, AppError> {
+    if width == 0 || height == 0 {
+        return Err(AppError::InvalidInput(
+            "A região selecionada está vazia".into(),
+        ));
+    }
+
+    let monitor = Monitor::from_point(x, y).map_err(|e| {
+        AppError::NotFound(format!("Nenhum monitor na posição ({}, {})", x, y)).with_details(e)
+    })?;
+    let (origin_x, origin_y) = (
+        monitor.x().unwrap_or_default(),
+        monitor.y().unwrap_or_default(),
+    );
+    let (monitor_width, monitor_height) = (
+        monitor.width().unwrap_or_default(),
+        monitor.height().unwrap_or_default(),
+    );
+
+    // Regions spanning two monitors are clipped to the one where they start
+    let rel_x = (x - origin_x) as u32;
+    let rel_y = (y - origin_y) as u32;
+    let width = width.min(monitor_width.saturating_sub(rel_x));
+    let height = height.min(monitor_height.saturating_sub(rel_y));
+
+    monitor
+        .capture_region(rel_x, rel_y, width, height)
+        .map_err(|e| AppError::internal("Falha ao capturar região", e))
+}
+
+fn capture(target: &CaptureTarget) -> Result<RgbaImage, AppError> {
+    match target {
+        CaptureTarget::Screen { monitor_id } => monitor_by_id(*monitor_id)?
+            .capture_image()
+            .map_err(|e| AppError::internal("Falha ao capturar tela", e)),
+        CaptureTarget::Window { window_id } => Window::all()
+            .map_err(|e| AppError::internal("Falha ao listar janelas", e))?
+            .into_iter()
+            .find(|w| w.id().ok() == Some(*window_id))
+            .ok_or_else(|| AppError::NotFound("Janela não encontrada".into()))?
+            .capture_image()
+            .map_err(|e| AppError::internal("Falha ao capturar janela", e)),
+        CaptureTarget::Region {
+            x,
+            y,
+            width,
+            height,
+        } => capture_region(*x, *y, *width, *height),
+    }
+}
+
+fn copy_image_to_clipboard(app: &tauri::AppHandle, image: &RgbaImage) -> Result<(), AppError> {
+    let (width, height) = image.dimensions();
+    let image = tauri::image::Image::new(image.as_raw(), width, height);
+    app.clipboard()
+        .write_image(&image)
+        .map_err(|e| AppError::internal("Falha ao copiar para a área de transferência", e))
+}
+
+#[tauri::command]
+pub fn list_capture_targets() -> Result<CaptureTargets, AppError> {
+    let monitors = Monitor::all()
+        .map_err(|e| AppError::internal("Falha ao listar monitores", e))?
+        .into_iter()
+        .map(|m| CaptureMonitor {
+            id: m.id().unwrap_or_default(),
+            name: m.name().unwrap_or_default(),
+            x: m.x().unwrap_or_default(),
+            y: m.y().unwrap_or_default(),
+            width: m.width().unwrap_or_default(),
+            height: m.height().unwrap_or_default(),
+            is_primary: m.is_primary().unwrap_or(false),
+        })
+        .collect();
+
+    let windows = Window::all()
+        .map_err(|e| AppError::internal("Falha ao listar janelas", e))?
+        .into_iter()
+        .filter(|w| !w.is_minimized().unwrap_or(false))
+        .filter(|w| !w.title().unwrap_or_default().is_empty())
+        .map(|w| CaptureWindow {
+            id: w.id().unwrap_or_default(),
+            title: w.title().unwrap_or_default(),
+            app_name: w.app_name().unwrap_or_default(),
+            x: w.x().unwrap_or_default(),
+            y: w.y().unwrap_or_default(),
+            width: w.width().unwrap_or_default(),
+            height: w.height().unwrap_or_default(),
+        })
+        .collect();
+
+    Ok(CaptureTargets { monitors, windows })
+}
+
+/// Captures the target and saves it to `save_dir` or, without one, returns it as base64 PNG.
+#[tauri::command]
+pub async fn capture_screenshot(
+    app: tauri::AppHandle,
+    target: CaptureTarget,
+    save_dir: Option<String>,
+    copy_to_clipboard: bool,
+) -> Result<CaptureResult, AppError> {
+    crate::metrics::timed("capture_screenshot", async move {
+        tauri::async_runtime::spawn_blocking(move || {
+            let image = capture(&target)?;
+            let (width, height) = image.dimensions();
+
+            if copy_to_clipboard {
+                copy_image_to_clipboard(&app, &image)?;
+            }
+
+            let mut result = CaptureResult {
+                width,
+                height,
+                saved_path: None,
+                png_base64: None,
+            };
+
+            match save_dir {
+                Some(dir) => {
+                    let file_name = format!(
+                        "captura_{}.png",
+                        chrono::Local::now().format("%Y-%m-%d_%H-%M-%S")
+                    );
+                    let path = std::path::Path::new(&dir).join(file_name);
+                    image
+                        .save_with_format(&path, image::ImageFormat::Png)
+                        .map_err(|e| AppError::internal("Falha ao salvar captura", e))?;
+                    result.saved_path = Some(path.to_string_lossy().to_string());
+                }
+                None => {
+                    let image = image::DynamicImage::ImageRgba8(image);
+                    result.png_base64 = Some(crate::pdf_utils::encode_png_base64(&image)?);
+                }
+            }
+
+            Ok(result)
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao capturar tela", e))?
+    })
+    .await
+}
+
+/// Quick capture used by the dashboard button: the screen under the cursor, straight to the clipboard.
+pub fn capture_current_screen(app: &tauri::AppHandle) -> Result<(), AppError> {
+    let monitor = match app.cursor_position() {
+        Ok(pos) => Monitor::from_point(pos.x as i32, pos.y as i32).ok(),
+        Err(_) => None,
+    };
+    let monitor = match monitor {
+        Some(monitor) => monitor,
+        None => monitor_by_id(None)?,
+    };
+
+    let image = monitor
+        .capture_image()
+        .map_err(|e| AppError::internal("Falha ao capturar tela", e))?;
+    copy_image_to_clipboard(app, &image)
+}
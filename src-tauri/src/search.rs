@@ -0,0 +1,94 @@
+// ── Search Module ──────────────────────────────────────────────
+use crate::error::AppError;
+use rusqlite::params;
+
+const DEFAULT_LIMIT: u32 = 30;
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchKind {
+    Todo,
+    Note,
+    QuickLink,
+}
+
+impl SearchKind {
+    fn from_db(kind: &str) -> Option<Self> {
+        match kind {
+            "todo" => Some(SearchKind::Todo),
+            "note" => Some(SearchKind::Note),
+            "quick_link" => Some(SearchKind::QuickLink),
+            _ => None,
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+pub struct SearchHit {
+    pub kind: SearchKind,
+    /// Row id in the table matching `kind`.
+    pub id: i64,
+    pub title: String,
+    /// Excerpt of the note body or link URL around the match; empty for todos.
+    pub snippet: String,
+}
+
+/// Turns free text into an FTS5 query: every word must match as a prefix, and
+/// quoting keeps operators like `OR`, `-` or `*` typed by the user literal.
+fn to_match_query(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .map(|term| format!("\"{}\"*", term.replace('"', "\"\"")))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+/// Searches todos, notes and quick links at once, best matches first.
+/// The `search_index` table is kept in sync by triggers from the migrations.
+#[tauri::command]
+pub fn search_all(
+    app: tauri::AppHandle,
+    query: String,
+    limit: Option<u32>,
+) -> Result<Vec<SearchHit>, AppError> {
+    let Some(match_query) = to_match_query(&query) else {
+        return Ok(Vec::new());
+    };
+
+    let conn = crate::db::open(&app)?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT kind, ref_id, title, snippet(search_index, 3, '', '', '…', 12)
+             FROM search_index WHERE search_index MATCH ?1
+             ORDER BY rank LIMIT ?2",
+        )
+        .map_err(|e| AppError::internal("Falha ao pesquisar", e))?;
+    let rows = stmt
+        .query_map(
+            params![match_query, limit.unwrap_or(DEFAULT_LIMIT)],
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                ))
+            },
+        )
+        .map_err(|e| AppError::internal("Falha ao pesquisar", e))?;
+
+    let mut hits = Vec::new();
+    for row in rows {
+        let (kind, id, title, snippet) =
+            row.map_err(|e| AppError::internal("Falha ao ler resultados da pesquisa", e))?;
+        if let Some(kind) = SearchKind::from_db(&kind) {
+            hits.push(SearchHit {
+                kind,
+                id,
+                title,
+                snippet,
+            });
+        }
+    }
+    Ok(hits)
+}
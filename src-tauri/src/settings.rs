@@ -0,0 +1,153 @@
+// ── Settings Module ────────────────────────────────────────────
+use crate::error::AppError;
+use serde::{de::DeserializeOwned, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{Emitter, Manager};
+
+const SETTINGS_FILE: &str = "settings.json";
+
+/// Keys owned by commands that validate the value before saving it, e.g. the base
+/// path every file command is confined to. `set_setting` must not bypass them.
+const RESERVED_KEYS: &[&str] = &[
+    crate::client_folders::NETWORK_BASE_PATH_KEY,
+    crate::client_folders::NETWORK_MODULE_ENABLED_KEY,
+    crate::ALLOWED_LINK_SCHEMES_KEY,
+    crate::cert_store::SETTING_KEY,
+    crate::workers::CONCURRENCY_KEY,
+    crate::backup::AUTO_BACKUP_ENABLED_KEY,
+    crate::backup::AUTO_BACKUP_FOLDER_KEY,
+    crate::backup::AUTO_BACKUP_KEEP_KEY,
+    crate::backup::AUTO_BACKUP_LAST_KEY,
+    crate::sync::SYNC_ENABLED_KEY,
+    crate::sync::SYNC_FOLDER_KEY,
+    crate::sync::SYNC_DEVICE_KEY,
+    crate::sync::SYNC_LAST_KEY,
+    crate::xml_inbox::INBOX_FOLDER_KEY,
+    crate::xml_inbox::INBOX_ENABLED_KEY,
+    crate::AUTOSTART_ENABLED_KEY,
+];
+
+/// Whether `key` may only be written through its own validating command.
+pub(crate) fn is_reserved(key: &str) -> bool {
+    RESERVED_KEYS.contains(&key)
+}
+
+#[derive(Clone, Serialize)]
+pub struct SettingChanged {
+    pub key: String,
+    pub value: serde_json::Value,
+}
+
+/// Key/value settings persisted as JSON in the app config directory.
+pub struct Settings {
+    app: tauri::AppHandle,
+    path: PathBuf,
+    values: Mutex<serde_json::Map<String, serde_json::Value>>,
+}
+
+impl Settings {
+    pub fn load(app: &tauri::AppHandle) -> Self {
+        let path = app
+            .path()
+            .app_config_dir()
+            .unwrap_or_else(|_| std::env::temp_dir())
+            .join(SETTINGS_FILE);
+
+        // A missing or corrupted file just means defaults everywhere
+        let values = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+
+        Settings {
+            app: app.clone(),
+            path,
+            values: Mutex::new(values),
+        }
+    }
+
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let values = self.values.lock().unwrap();
+        values
+            .get(key)
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+    }
+
+    pub fn set<T: Serialize>(&self, key: &str, value: T) -> Result<(), String> {
+        let value = serde_json::to_value(value)
+            .map_err(|e| format!("Valor inválido para '{}': {}", key, e))?;
+
+        {
+            let mut values = self.values.lock().unwrap();
+            values.insert(key.to_string(), value.clone());
+            self.persist(&values)?;
+        }
+
+        let _ = self.app.emit(
+            "settings-changed",
+            SettingChanged {
+                key: key.to_string(),
+                value,
+            },
+        );
+        Ok(())
+    }
+
+    pub fn all(&self) -> serde_json::Map<String, serde_json::Value> {
+        self.values.lock().unwrap().clone()
+    }
+
+    fn persist(&self, values: &serde_json::Map<String, serde_json::Value>) -> Result<(), String> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Falha ao criar pasta de configurações: {}", e))?;
+        }
+
+        let content = serde_json::to_string_pretty(values)
+            .map_err(|e| format!("Falha ao serializar configurações: {}", e))?;
+
+        // Write to a sibling file first so a crash never leaves a truncated settings.json
+        let tmp_path = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp_path, content)
+            .map_err(|e| format!("Falha ao salvar configurações: {}", e))?;
+        std::fs::rename(&tmp_path, &self.path)
+            .map_err(|e| format!("Falha ao salvar configurações: {}", e))?;
+
+        Ok(())
+    }
+}
+
+// ── Settings Commands ──────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_setting(settings: tauri::State<'_, Settings>, key: String) -> Option<serde_json::Value> {
+    settings.get(&key)
+}
+
+#[tauri::command]
+pub fn set_setting(
+    settings: tauri::State<'_, Settings>,
+    key: String,
+    value: serde_json::Value,
+) -> Result<(), AppError> {
+    if key.trim().is_empty() {
+        return Err(AppError::InvalidInput(
+            "A chave da configuração não pode ser vazia".into(),
+        ));
+    }
+    if is_reserved(&key) {
+        return Err(AppError::AccessDenied(format!(
+            "A configuração '{}' só pode ser alterada pela tela correspondente",
+            key
+        )));
+    }
+    Ok(settings.set(&key, value)?)
+}
+
+#[tauri::command]
+pub fn get_all_settings(
+    settings: tauri::State<'_, Settings>,
+) -> serde_json::Map<String, serde_json::Value> {
+    settings.all()
+}
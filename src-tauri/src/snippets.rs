@@ -0,0 +1,247 @@
+// ── Snippets Module ────────────────────────────────────────────
+use crate::error::AppError;
+use chrono::{Datelike, Local};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+const MONTHS: [&str; 12] = [
+    "janeiro",
+    "fevereiro",
+    "março",
+    "abril",
+    "maio",
+    "junho",
+    "julho",
+    "agosto",
+    "setembro",
+    "outubro",
+    "novembro",
+    "dezembro",
+];
+
+/// Reusable text templates with `{placeholder}`s filled in at render time.
+pub struct Snippets {
+    conn: Mutex<Connection>,
+}
+
+#[derive(serde::Serialize)]
+pub struct Snippet {
+    pub id: i64,
+    pub title: String,
+    pub content: String,
+    /// Placeholder names found in `content`, in order of first use.
+    pub placeholders: Vec<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(serde::Deserialize)]
+pub struct SnippetInput {
+    pub title: String,
+    pub content: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct RenderedSnippet {
+    pub text: String,
+    /// Placeholders with no value, left untouched in `text`.
+    pub missing: Vec<String>,
+}
+
+/// Splits `content` into literal text and `{name}` placeholders. Anything in braces
+/// that isn't a plain name (e.g. `{ }` or JSON) is kept as literal text.
+fn parse(content: &str) -> Vec<(bool, &str)> {
+    let mut parts = Vec::new();
+    let mut rest = content;
+    while let Some(open) = rest.find('{') {
+        let after = &rest[open + 1..];
+        let Some(close) = after.find(|c: char| !(c.is_alphanumeric() || c == '_')) else {
+            break;
+        };
+        if close == 0 || !after[close..].starts_with('}') {
+            parts.push((false, &rest[..open + 1]));
+            rest = after;
+            continue;
+        }
+        parts.push((false, &rest[..open]));
+        parts.push((true, &after[..close]));
+        rest = &after[close + 1..];
+    }
+    parts.push((false, rest));
+    parts
+}
+
+fn placeholders(content: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for (_, name) in parse(content).into_iter().filter(|(is_var, _)| *is_var) {
+        if !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+    }
+    names
+}
+
+/// Values every snippet can use without the caller providing them.
+fn builtin_vars() -> HashMap<String, String> {
+    let now = Local::now();
+    HashMap::from([
+        ("mes".into(), MONTHS[now.month0() as usize].into()),
+        ("ano".into(), now.year().to_string()),
+        ("data".into(), now.format("%d/%m/%Y").to_string()),
+    ])
+}
+
+fn row_to_snippet(row: &rusqlite::Row) -> rusqlite::Result<Snippet> {
+    let content: String = row.get(2)?;
+    Ok(Snippet {
+        id: row.get(0)?,
+        title: row.get(1)?,
+        placeholders: placeholders(&content),
+        content,
+        created_at: row.get(3)?,
+        updated_at: row.get(4)?,
+    })
+}
+
+const SELECT_COLUMNS: &str = "SELECT id, title, content, created_at, updated_at FROM snippets";
+
+fn validate(input: &SnippetInput) -> Result<String, AppError> {
+    let title = input.title.trim();
+    if title.is_empty() {
+        return Err(AppError::InvalidInput(
+            "O texto precisa de um título".into(),
+        ));
+    }
+    if input.content.trim().is_empty() {
+        return Err(AppError::InvalidInput(
+            "O texto não pode ficar vazio".into(),
+        ));
+    }
+    Ok(title.to_string())
+}
+
+impl Snippets {
+    pub fn open(app: &tauri::AppHandle) -> Result<Self, String> {
+        let conn = crate::db::open(app)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS snippets (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                title TEXT NOT NULL,
+                content TEXT NOT NULL,
+                created_at TEXT NOT NULL DEFAULT (datetime('now')),
+                updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+            );",
+        )
+        .map_err(|e| format!("Falha ao criar tabela de textos prontos: {}", e))?;
+
+        Ok(Snippets {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    fn get(&self, id: i64) -> Result<Snippet, AppError> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            &format!("{} WHERE id = ?1", SELECT_COLUMNS),
+            params![id],
+            row_to_snippet,
+        )
+        .optional()
+        .map_err(|e| AppError::internal("Falha ao consultar texto pronto", e))?
+        .ok_or_else(|| AppError::NotFound("Texto pronto não encontrado".into()))
+    }
+}
+
+#[tauri::command]
+pub fn list_snippets(snippets: tauri::State<'_, Snippets>) -> Result<Vec<Snippet>, AppError> {
+    let conn = snippets.conn.lock().unwrap();
+    let mut stmt = conn
+        .prepare(&format!("{} ORDER BY title COLLATE NOCASE", SELECT_COLUMNS))
+        .map_err(|e| AppError::internal("Falha ao consultar textos prontos", e))?;
+    let rows = stmt
+        .query_map([], row_to_snippet)
+        .map_err(|e| AppError::internal("Falha ao consultar textos prontos", e))?;
+    rows.collect::<Result<_, _>>()
+        .map_err(|e| AppError::internal("Falha ao ler textos prontos", e))
+}
+
+#[tauri::command]
+pub fn create_snippet(
+    snippets: tauri::State<'_, Snippets>,
+    input: SnippetInput,
+) -> Result<Snippet, AppError> {
+    let title = validate(&input)?;
+    let id = {
+        let conn = snippets.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO snippets (title, content) VALUES (?1, ?2)",
+            params![title, input.content],
+        )
+        .map_err(|e| AppError::internal("Falha ao salvar texto pronto", e))?;
+        conn.last_insert_rowid()
+    };
+    snippets.get(id)
+}
+
+#[tauri::command]
+pub fn update_snippet(
+    snippets: tauri::State<'_, Snippets>,
+    id: i64,
+    input: SnippetInput,
+) -> Result<Snippet, AppError> {
+    let title = validate(&input)?;
+    {
+        let conn = snippets.conn.lock().unwrap();
+        let changed = conn
+            .execute(
+                "UPDATE snippets SET title = ?1, content = ?2, updated_at = datetime('now')
+                 WHERE id = ?3",
+                params![title, input.content, id],
+            )
+            .map_err(|e| AppError::internal("Falha ao salvar texto pronto", e))?;
+        if changed == 0 {
+            return Err(AppError::NotFound("Texto pronto não encontrado".into()));
+        }
+    }
+    snippets.get(id)
+}
+
+#[tauri::command]
+pub fn delete_snippet(snippets: tauri::State<'_, Snippets>, id: i64) -> Result<(), AppError> {
+    let conn = snippets.conn.lock().unwrap();
+    conn.execute("DELETE FROM snippets WHERE id = ?1", params![id])
+        .map_err(|e| AppError::internal("Falha ao excluir texto pronto", e))?;
+    Ok(())
+}
+
+/// Fills `{cliente}`, `{cnpj}` and other placeholders from `vars`. `{mes}`, `{ano}`
+/// and `{data}` default to the current date unless `vars` overrides them.
+#[tauri::command]
+pub fn render_snippet(
+    snippets: tauri::State<'_, Snippets>,
+    id: i64,
+    vars: Option<HashMap<String, String>>,
+) -> Result<RenderedSnippet, AppError> {
+    let snippet = snippets.get(id)?;
+    let mut values = builtin_vars();
+    values.extend(vars.unwrap_or_default());
+
+    let mut text = String::with_capacity(snippet.content.len());
+    let mut missing: Vec<String> = Vec::new();
+    for (is_var, part) in parse(&snippet.content) {
+        if !is_var {
+            text.push_str(part);
+        } else if let Some(value) = values.get(part) {
+            text.push_str(value);
+        } else {
+            text.push('{');
+            text.push_str(part);
+            text.push('}');
+            if !missing.iter().any(|m| m == part) {
+                missing.push(part.to_string());
+            }
+        }
+    }
+    Ok(RenderedSnippet { text, missing })
+}
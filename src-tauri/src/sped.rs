@@ -0,0 +1,416 @@
+// ── SPED Module ────────────────────────────────────────────────
+use crate::error::AppError;
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+/// Issues beyond this are only counted, so a badly broken file does not produce
+/// a report larger than the file itself.
+const MAX_ISSUES: usize = 200;
+
+#[derive(serde::Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SpedKind {
+    EfdIcmsIpi,
+    EfdContribuicoes,
+}
+
+#[derive(serde::Serialize)]
+pub struct RecordCount {
+    pub record: String,
+    pub count: u64,
+    /// As declared in the 9900 records; `None` when missing there.
+    pub declared: Option<u64>,
+}
+
+#[derive(serde::Serialize)]
+pub struct SpedBlock {
+    pub block: String,
+    pub lines: u64,
+    /// From the block's X990 closing record.
+    pub declared_lines: Option<u64>,
+    pub records: Vec<RecordCount>,
+}
+
+#[derive(serde::Serialize)]
+pub struct SpedIssue {
+    /// 1-based line number, when the issue is about a single line.
+    pub line: Option<u64>,
+    pub message: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct SpedSummary {
+    pub kind: Option<SpedKind>,
+    pub company_name: String,
+    pub cnpj: String,
+    /// `YYYY-MM-DD`.
+    pub period_start: Option<String>,
+    pub period_end: Option<String>,
+    pub total_lines: u64,
+    pub blocks: Vec<SpedBlock>,
+    pub valid: bool,
+    pub issues: Vec<SpedIssue>,
+    pub omitted_issues: u64,
+}
+
+/// SPED files are ISO-8859-1; each byte maps to the code point of the same value.
+fn decode_latin1(bytes: &[u8]) -> String {
+    bytes.iter().map(|&b| b as char).collect()
+}
+
+/// `|C100|0|1|...|` → `["C100", "0", "1", ...]`; `None` without the delimiters.
+fn split_record(line: &str) -> Option<Vec<&str>> {
+    let inner = line.strip_prefix('|')?.strip_suffix('|')?;
+    Some(inner.split('|').collect())
+}
+
+/// Calls `visit` with each line (without the line break) and its 1-based number.
+fn for_each_line(
+    path: &Path,
+    mut visit: impl FnMut(u64, &str) -> Result<(), AppError>,
+) -> Result<(), AppError> {
+    let file = std::fs::File::open(path)
+        .map_err(|e| AppError::io(format!("Falha ao abrir {}", path.display()), e))?;
+    let mut reader = BufReader::new(file);
+    let mut buffer = Vec::new();
+    let mut number = 0;
+    loop {
+        buffer.clear();
+        let read = reader
+            .read_until(b'\n', &mut buffer)
+            .map_err(|e| AppError::io("Falha ao ler o arquivo SPED", e))?;
+        if read == 0 {
+            return Ok(());
+        }
+        let line = decode_latin1(&buffer);
+        let line = line.trim_end_matches(['\r', '\n']);
+        // The digital signature follows the 9999 record; nothing after it counts
+        if line.is_empty() || line.starts_with("SBRCAAEPDR") {
+            continue;
+        }
+        number += 1;
+        visit(number, line)?;
+    }
+}
+
+fn ddmmyyyy(value: &str) -> Option<String> {
+    chrono::NaiveDate::parse_from_str(value, "%d%m%Y")
+        .ok()
+        .map(|date| date.format("%Y-%m-%d").to_string())
+}
+
+fn parse_count(value: Option<&&str>) -> Option<u64> {
+    value.and_then(|value| value.trim().parse().ok())
+}
+
+fn summarize(path: &Path) -> Result<SpedSummary, AppError> {
+    let mut summary = SpedSummary {
+        kind: None,
+        company_name: String::new(),
+        cnpj: String::new(),
+        period_start: None,
+        period_end: None,
+        total_lines: 0,
+        blocks: Vec::new(),
+        valid: true,
+        issues: Vec::new(),
+        omitted_issues: 0,
+    };
+    let issue = |summary: &mut SpedSummary, line: Option<u64>, message: String| {
+        summary.valid = false;
+        if summary.issues.len() < MAX_ISSUES {
+            summary.issues.push(SpedIssue { line, message });
+        } else {
+            summary.omitted_issues += 1;
+        }
+    };
+
+    let mut record_counts: BTreeMap<String, u64> = BTreeMap::new();
+    let mut block_lines: BTreeMap<String, u64> = BTreeMap::new();
+    let mut block_declared: BTreeMap<String, u64> = BTreeMap::new();
+    let mut declared_counts: BTreeMap<String, u64> = BTreeMap::new();
+    let mut declared_total: Option<u64> = None;
+    let mut last_record = String::new();
+
+    for_each_line(path, |number, line| {
+        summary.total_lines = number;
+        let Some(fields) = split_record(line) else {
+            issue(
+                &mut summary,
+                Some(number),
+                "Linha sem os delimitadores '|' no início e no fim".into(),
+            );
+            return Ok(());
+        };
+        let record = fields[0].to_string();
+        if number == 1 && record != "0000" {
+            issue(
+                &mut summary,
+                Some(1),
+                "O arquivo deve começar pelo registro 0000".into(),
+            );
+        }
+        if record.len() != 4 || !record.is_ascii() {
+            issue(
+                &mut summary,
+                Some(number),
+                format!("Registro inválido: {}", record),
+            );
+            return Ok(());
+        }
+        let block = record[..1].to_string();
+
+        match record.as_str() {
+            "0000" => {
+                // DT_INI is the 4th field on EFD ICMS/IPI and the 6th on EFD-Contribuições
+                if let Some(start) = fields.get(3).and_then(|value| ddmmyyyy(value)) {
+                    summary.kind = Some(SpedKind::EfdIcmsIpi);
+                    summary.period_start = Some(start);
+                    summary.period_end = fields.get(4).and_then(|value| ddmmyyyy(value));
+                    summary.company_name = fields.get(5).unwrap_or(&"").to_string();
+                    summary.cnpj = fields.get(6).unwrap_or(&"").to_string();
+                } else if let Some(start) = fields.get(5).and_then(|value| ddmmyyyy(value)) {
+                    summary.kind = Some(SpedKind::EfdContribuicoes);
+                    summary.period_start = Some(start);
+                    summary.period_end = fields.get(6).and_then(|value| ddmmyyyy(value));
+                    summary.company_name = fields.get(7).unwrap_or(&"").to_string();
+                    summary.cnpj = fields.get(8).unwrap_or(&"").to_string();
+                }
+            }
+            "9900" => {
+                if let (Some(counted), Some(count)) = (fields.get(1), parse_count(fields.get(2))) {
+                    declared_counts.insert(counted.to_string(), count);
+                }
+            }
+            "9999" => declared_total = parse_count(fields.get(1)),
+            _ if record.ends_with("990") => {
+                if let Some(count) = parse_count(fields.get(1)) {
+                    block_declared.insert(block.clone(), count);
+                }
+            }
+            _ => {}
+        }
+
+        *record_counts.entry(record.clone()).or_default() += 1;
+        *block_lines.entry(block).or_default() += 1;
+        last_record = record;
+        Ok(())
+    })?;
+
+    if summary.total_lines == 0 {
+        return Err(AppError::InvalidInput("Arquivo SPED vazio".into()));
+    }
+    if summary.kind.is_none() {
+        issue(
+            &mut summary,
+            None,
+            "Registro 0000 ausente ou sem período; tipo de escrituração não identificado".into(),
+        );
+    }
+    if last_record != "9999" {
+        issue(
+            &mut summary,
+            None,
+            "O arquivo deve terminar pelo registro 9999".into(),
+        );
+    }
+
+    // Block 9 totalizers: 9999 counts every line, 9900 each record type
+    let total_lines = summary.total_lines;
+    if let Some(total) = declared_total.filter(|total| *total != total_lines) {
+        issue(
+            &mut summary,
+            None,
+            format!(
+                "Registro 9999 declara {} linhas, o arquivo tem {}",
+                total, total_lines
+            ),
+        );
+    }
+    for (record, count) in &record_counts {
+        match declared_counts.get(record) {
+            Some(declared) if declared != count => issue(
+                &mut summary,
+                None,
+                format!(
+                    "Registro 9900 declara {} ocorrência(s) de {}, o arquivo tem {}",
+                    declared, record, count
+                ),
+            ),
+            None if !declared_counts.is_empty() => issue(
+                &mut summary,
+                None,
+                format!("Registro {} sem totalizador no 9900", record),
+            ),
+            _ => {}
+        }
+    }
+    for (record, declared) in &declared_counts {
+        if !record_counts.contains_key(record) {
+            issue(
+                &mut summary,
+                None,
+                format!(
+                    "Registro 9900 declara {} ocorrência(s) de {}, ausente no arquivo",
+                    declared, record
+                ),
+            );
+        }
+    }
+
+    for (block, lines) in &block_lines {
+        let declared_lines = block_declared.get(block).copied();
+        match declared_lines {
+            Some(declared) if declared != *lines => issue(
+                &mut summary,
+                None,
+                format!(
+                    "Registro {}990 declara {} linhas no bloco, o bloco tem {}",
+                    block, declared, lines
+                ),
+            ),
+            None => issue(
+                &mut summary,
+                None,
+                format!("Bloco {} sem registro de encerramento {}990", block, block),
+            ),
+            _ => {}
+        }
+        summary.blocks.push(SpedBlock {
+            block: block.clone(),
+            lines: *lines,
+            declared_lines,
+            records: record_counts
+                .iter()
+                .filter(|(record, _)| record.starts_with(block.as_str()))
+                .map(|(record, count)| RecordCount {
+                    record: record.clone(),
+                    count: *count,
+                    declared: declared_counts.get(record).copied(),
+                })
+                .collect(),
+        });
+    }
+    // Blocks in file order: 0 first, 9 last, letters and 1 in between
+    summary
+        .blocks
+        .sort_by_key(|block| match block.block.as_str() {
+            "0" => (0, String::new()),
+            "9" => (3, String::new()),
+            "1" => (2, String::new()),
+            other => (1, other.to_string()),
+        });
+
+    Ok(summary)
+}
+
+/// CSV cell for Excel in pt-BR: `;`-separated, quoted when needed.
+fn csv_cell(value: &str) -> String {
+    if value.contains([';', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Writes one CSV per record type of the chosen blocks, since every record has
+/// its own layout. Returns the files written.
+fn export_csv(path: &Path, blocks: &[String], output_dir: &Path) -> Result<Vec<String>, AppError> {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "sped".into());
+    std::fs::create_dir_all(output_dir)
+        .map_err(|e| AppError::io(format!("Falha ao criar {}", output_dir.display()), e))?;
+
+    let mut writers: BTreeMap<String, (PathBuf, std::io::BufWriter<std::fs::File>)> =
+        BTreeMap::new();
+    for_each_line(path, |_, line| {
+        let Some(fields) = split_record(line) else {
+            return Ok(());
+        };
+        let record = fields[0];
+        if !blocks
+            .iter()
+            .any(|block| record.starts_with(block.as_str()))
+        {
+            return Ok(());
+        }
+        if !writers.contains_key(record) {
+            let file_path = output_dir.join(format!("{}_{}.csv", stem, record));
+            let file = std::fs::File::create(&file_path)
+                .map_err(|e| AppError::io(format!("Falha ao criar {}", file_path.display()), e))?;
+            let mut writer = std::io::BufWriter::new(file);
+            // BOM so Excel opens the file as UTF-8
+            writer
+                .write_all("\u{feff}".as_bytes())
+                .map_err(|e| AppError::io("Falha ao gravar CSV", e))?;
+            writers.insert(record.to_string(), (file_path, writer));
+        }
+        let (_, writer) = writers.get_mut(record).unwrap();
+        let row = fields
+            .iter()
+            .map(|field| csv_cell(field))
+            .collect::<Vec<_>>()
+            .join(";");
+        writeln!(writer, "{}", row).map_err(|e| AppError::io("Falha ao gravar CSV", e))
+    })?;
+
+    let mut written = Vec::new();
+    for (_, (file_path, mut writer)) in writers {
+        writer
+            .flush()
+            .map_err(|e| AppError::io("Falha ao gravar CSV", e))?;
+        written.push(file_path.to_string_lossy().to_string());
+    }
+    Ok(written)
+}
+
+/// Reads an EFD ICMS/IPI or EFD-Contribuições file and checks its structure: the
+/// line count of every block against its X990 record and the block 9 totals
+/// (9900 per record, 9999 for the whole file).
+#[tauri::command]
+pub async fn summarize_sped(path: String) -> Result<SpedSummary, AppError> {
+    crate::metrics::timed("summarize_sped", async move {
+        tauri::async_runtime::spawn_blocking(move || summarize(Path::new(&path)))
+            .await
+            .map_err(|e| AppError::internal("Falha ao ler o arquivo SPED", e))?
+    })
+    .await
+}
+
+/// Exports the records of `blocks` (e.g. `["C", "E"]`) to CSV, one file per record
+/// type, into `output_dir` or next to the SPED file.
+#[tauri::command]
+pub async fn export_sped_csv(
+    path: String,
+    blocks: Vec<String>,
+    output_dir: Option<String>,
+) -> Result<Vec<String>, AppError> {
+    crate::metrics::timed("export_sped_csv", async move {
+        let blocks: Vec<String> = blocks
+            .iter()
+            .map(|block| block.trim().to_uppercase())
+            .filter(|block| block.len() == 1)
+            .collect();
+        if blocks.is_empty() {
+            return Err(AppError::InvalidInput(
+                "Selecione ao menos um bloco para exportar".into(),
+            ));
+        }
+        let path = PathBuf::from(path);
+        let output_dir = match output_dir.filter(|dir| !dir.trim().is_empty()) {
+            Some(dir) => PathBuf::from(dir),
+            None => path
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| PathBuf::from(".")),
+        };
+
+        tauri::async_runtime::spawn_blocking(move || export_csv(&path, &blocks, &output_dir))
+            .await
+            .map_err(|e| AppError::internal("Falha ao exportar o arquivo SPED", e))?
+    })
+    .await
+}
@@ -0,0 +1,747 @@
+// ── Sync Module ────────────────────────────────────────────────
+//
+// Workstations share todos, notes and quick links through a folder on the office
+// network share, without a server. Triggers (see the plugin migrations) journal every
+// local change to a row's `uid`; syncing appends those rows to this device's own
+// `<device>.jsonl` in the shared folder and replays the other devices' files from
+// where it last stopped. The newest change wins; when two devices changed the same
+// row without seeing each other's change, both versions go to `sync_conflicts`.
+use crate::error::AppError;
+use crate::settings::Settings;
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension, Transaction};
+use std::collections::HashMap;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+
+pub(crate) const SYNC_ENABLED_KEY: &str = "sync_enabled";
+pub(crate) const SYNC_FOLDER_KEY: &str = "sync_folder";
+pub(crate) const SYNC_DEVICE_KEY: &str = "sync_device_id";
+pub(crate) const SYNC_LAST_KEY: &str = "sync_last_at";
+/// Subfolder of the configured share folder holding one journal file per device.
+const SYNC_DIR: &str = "adcontec-sync";
+const JOURNAL_EXTENSION: &str = "jsonl";
+const SCHEDULER_FIRST_CHECK: Duration = Duration::from_secs(30);
+const SCHEDULER_TICK: Duration = Duration::from_secs(5 * 60);
+
+struct SyncedTable {
+    name: &'static str,
+    columns: &'static [&'static str],
+    /// Column pointing at another row of the same table; synced as that row's uid.
+    parent_column: Option<&'static str>,
+}
+
+const TABLES: [SyncedTable; 3] = [
+    SyncedTable {
+        name: "todos",
+        columns: &[
+            "title",
+            "done",
+            "created_at",
+            "completed_at",
+            "sort_order",
+            "due_at",
+            "remind_at",
+            "parent_id",
+        ],
+        parent_column: Some("parent_id"),
+    },
+    SyncedTable {
+        name: "notes",
+        columns: &["title", "body", "tags", "created_at", "updated_at"],
+        parent_column: None,
+    },
+    SyncedTable {
+        name: "quick_links",
+        // `click_count` stays per workstation; it changes on every click
+        columns: &["title", "url", "created_at", "group_name", "sort_order"],
+        parent_column: None,
+    },
+];
+
+/// One line of a device journal. `data` is `None` when the row was deleted.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Change {
+    table: String,
+    uid: String,
+    data: Option<serde_json::Map<String, serde_json::Value>>,
+    changed_at: String,
+    /// `changed_at` of the version the writer had before this change, to tell
+    /// concurrent edits from sequential ones.
+    base: Option<String>,
+    device: String,
+}
+
+pub struct SyncJournal {
+    conn: Mutex<Connection>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct SyncSettings {
+    pub enabled: bool,
+    /// Folder on the network share; the same one on every workstation.
+    pub folder: Option<String>,
+    #[serde(default)]
+    pub device_id: String,
+    #[serde(default)]
+    pub last_sync_at: Option<String>,
+}
+
+#[derive(serde::Serialize, Clone, Default)]
+pub struct SyncSummary {
+    /// Local changes written to the share.
+    pub pushed: u32,
+    /// Changes from other workstations applied here.
+    pub pulled: u32,
+    pub conflicts: u32,
+}
+
+#[derive(serde::Serialize)]
+pub struct SyncConflict {
+    pub id: i64,
+    pub table_name: String,
+    pub uid: String,
+    /// Row as JSON, `None` if deleted on that side.
+    pub local_data: Option<String>,
+    pub remote_data: Option<String>,
+    pub remote_device: String,
+    /// "local" or "remote".
+    pub winner: String,
+    pub detected_at: String,
+}
+
+fn sync_error(message: &str, e: rusqlite::Error) -> AppError {
+    AppError::internal(message, e)
+}
+
+fn to_json(value: Value) -> serde_json::Value {
+    match value {
+        Value::Null | Value::Blob(_) => serde_json::Value::Null,
+        Value::Integer(n) => n.into(),
+        Value::Real(n) => n.into(),
+        Value::Text(text) => text.into(),
+    }
+}
+
+fn from_json(value: Option<&serde_json::Value>) -> Value {
+    match value {
+        Some(serde_json::Value::Bool(b)) => Value::Integer(*b as i64),
+        Some(serde_json::Value::Number(n)) => match n.as_i64() {
+            Some(n) => Value::Integer(n),
+            None => n.as_f64().map(Value::Real).unwrap_or(Value::Null),
+        },
+        Some(serde_json::Value::String(text)) => Value::Text(text.clone()),
+        _ => Value::Null,
+    }
+}
+
+fn device_id(settings: &Settings) -> Result<String, AppError> {
+    if let Some(id) = settings.get::<String>(SYNC_DEVICE_KEY) {
+        return Ok(id);
+    }
+    use rand::Rng;
+    let id = hex::encode(rand::thread_rng().gen::<[u8; 8]>());
+    settings.set(SYNC_DEVICE_KEY, &id)?;
+    Ok(id)
+}
+
+fn sync_dir(settings: &Settings) -> Result<PathBuf, AppError> {
+    let folder = settings
+        .get::<String>(SYNC_FOLDER_KEY)
+        .filter(|folder| !folder.trim().is_empty())
+        .ok_or_else(|| {
+            AppError::InvalidInput("Configure a pasta de sincronização na rede".into())
+        })?;
+    let dir = PathBuf::from(folder.trim()).join(SYNC_DIR);
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| AppError::io("Pasta de sincronização inacessível", e))?;
+    Ok(dir)
+}
+
+/// Current row as JSON, with the parent reference swapped for the parent's uid.
+fn read_row(
+    conn: &Connection,
+    table: &SyncedTable,
+    uid: &str,
+) -> Result<Option<serde_json::Map<String, serde_json::Value>>, AppError> {
+    let columns: Vec<String> = table
+        .columns
+        .iter()
+        .map(|column| match table.parent_column {
+            Some(parent) if parent == *column => format!(
+                "(SELECT p.uid FROM {t} AS p WHERE p.id = {t}.{c})",
+                t = table.name,
+                c = column
+            ),
+            _ => column.to_string(),
+        })
+        .collect();
+    let sql = format!(
+        "SELECT {} FROM {} WHERE uid = ?1",
+        columns.join(", "),
+        table.name
+    );
+
+    conn.query_row(&sql, params![uid], |row| {
+        let mut data = serde_json::Map::new();
+        for (index, column) in table.columns.iter().enumerate() {
+            data.insert(column.to_string(), to_json(row.get(index)?));
+        }
+        Ok(data)
+    })
+    .optional()
+    .map_err(|e| sync_error("Falha ao ler registro para sincronizar", e))
+}
+
+fn write_row(
+    tx: &Transaction,
+    table: &SyncedTable,
+    uid: &str,
+    data: &serde_json::Map<String, serde_json::Value>,
+) -> Result<(), AppError> {
+    let mut values: Vec<Value> = Vec::with_capacity(table.columns.len() + 1);
+    for column in table.columns {
+        let value = from_json(data.get(*column));
+        let value = match (table.parent_column, value) {
+            (Some(parent), Value::Text(parent_uid)) if parent == *column => tx
+                .query_row(
+                    &format!("SELECT id FROM {} WHERE uid = ?1", table.name),
+                    params![parent_uid],
+                    |row| row.get::<_, i64>(0),
+                )
+                .optional()
+                .map_err(|e| sync_error("Falha ao aplicar sincronização", e))?
+                .map(Value::Integer)
+                .unwrap_or(Value::Null),
+            (_, value) => value,
+        };
+        values.push(value);
+    }
+    values.push(Value::Text(uid.to_string()));
+
+    let exists = tx
+        .query_row(
+            &format!("SELECT 1 FROM {} WHERE uid = ?1", table.name),
+            params![uid],
+            |_| Ok(()),
+        )
+        .optional()
+        .map_err(|e| sync_error("Falha ao aplicar sincronização", e))?
+        .is_some();
+
+    let sql = if exists {
+        let assignments: Vec<String> = table
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(index, column)| format!("{} = ?{}", column, index + 1))
+            .collect();
+        format!(
+            "UPDATE {} SET {} WHERE uid = ?{}",
+            table.name,
+            assignments.join(", "),
+            table.columns.len() + 1
+        )
+    } else {
+        let placeholders: Vec<String> = (1..=table.columns.len() + 1)
+            .map(|index| format!("?{}", index))
+            .collect();
+        format!(
+            "INSERT INTO {} ({}, uid) VALUES ({})",
+            table.name,
+            table.columns.join(", "),
+            placeholders.join(", ")
+        )
+    };
+    tx.execute(&sql, params_from_iter(values))
+        .map_err(|e| sync_error("Falha ao aplicar sincronização", e))?;
+    Ok(())
+}
+
+fn set_applying(conn: &Connection, applying: bool) -> Result<(), AppError> {
+    conn.execute("UPDATE sync_control SET applying = ?1", params![applying])
+        .map_err(|e| sync_error("Falha ao preparar sincronização", e))?;
+    Ok(())
+}
+
+fn set_journaling(conn: &Connection, enabled: bool) -> Result<(), AppError> {
+    conn.execute("UPDATE sync_control SET enabled = ?1", params![enabled])
+        .map_err(|e| sync_error("Falha ao ativar registro de alterações", e))?;
+    Ok(())
+}
+
+/// Appends the journaled local changes to this device's file on the share.
+fn push(conn: &mut Connection, dir: &Path, device: &str) -> Result<u32, AppError> {
+    let entries: Vec<(i64, String, String, String)> = {
+        let mut stmt = conn
+            .prepare("SELECT id, table_name, uid, changed_at FROM sync_journal ORDER BY id")
+            .map_err(|e| sync_error("Falha ao ler alterações locais", e))?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+            })
+            .map_err(|e| sync_error("Falha ao ler alterações locais", e))?;
+        rows.collect::<Result<_, _>>()
+            .map_err(|e| sync_error("Falha ao ler alterações locais", e))?
+    };
+    let Some(last_id) = entries.last().map(|entry| entry.0) else {
+        return Ok(0);
+    };
+
+    // A row edited several times since the last sync is sent once, as it is now
+    let mut latest: Vec<(String, String, String)> = Vec::new();
+    let mut positions: HashMap<(String, String), usize> = HashMap::new();
+    for (_, table, uid, changed_at) in entries {
+        match positions.get(&(table.clone(), uid.clone())) {
+            Some(&index) => latest[index].2 = changed_at,
+            None => {
+                positions.insert((table.clone(), uid.clone()), latest.len());
+                latest.push((table, uid, changed_at));
+            }
+        }
+    }
+
+    let mut changes = Vec::with_capacity(latest.len());
+    for (table_name, uid, changed_at) in latest {
+        let Some(table) = TABLES.iter().find(|table| table.name == table_name) else {
+            continue;
+        };
+        let base = conn
+            .query_row(
+                "SELECT changed_at FROM sync_rows WHERE table_name = ?1 AND uid = ?2",
+                params![table_name, uid],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| sync_error("Falha ao ler alterações locais", e))?;
+        changes.push(Change {
+            data: read_row(conn, table, &uid)?,
+            table: table_name,
+            uid,
+            changed_at,
+            base,
+            device: device.to_string(),
+        });
+    }
+
+    let mut lines = String::new();
+    for change in &changes {
+        lines.push_str(&serde_json::to_string(change).map_err(|e| e.to_string())?);
+        lines.push('\n');
+    }
+    let path = dir.join(format!("{}.{}", device, JOURNAL_EXTENSION));
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| AppError::io("Falha ao abrir arquivo de sincronização", e))?;
+    file.write_all(lines.as_bytes())
+        .and_then(|_| file.sync_all())
+        .map_err(|e| AppError::io("Falha ao gravar arquivo de sincronização", e))?;
+
+    let tx = conn
+        .transaction()
+        .map_err(|e| sync_error("Falha ao registrar sincronização", e))?;
+    for change in &changes {
+        tx.execute(
+            "INSERT OR REPLACE INTO sync_rows (table_name, uid, changed_at, device)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![change.table, change.uid, change.changed_at, device],
+        )
+        .map_err(|e| sync_error("Falha ao registrar sincronização", e))?;
+    }
+    tx.execute("DELETE FROM sync_journal WHERE id <= ?1", params![last_id])
+        .map_err(|e| sync_error("Falha ao registrar sincronização", e))?;
+    tx.commit()
+        .map_err(|e| sync_error("Falha ao registrar sincronização", e))?;
+
+    Ok(changes.len() as u32)
+}
+
+enum Outcome {
+    Skipped,
+    Applied,
+    Conflict,
+}
+
+fn apply_change(tx: &Transaction, change: &Change) -> Result<Outcome, AppError> {
+    let Some(table) = TABLES.iter().find(|table| table.name == change.table) else {
+        return Ok(Outcome::Skipped);
+    };
+    let local: Option<(String, String)> = tx
+        .query_row(
+            "SELECT changed_at, device FROM sync_rows WHERE table_name = ?1 AND uid = ?2",
+            params![change.table, change.uid],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()
+        .map_err(|e| sync_error("Falha ao aplicar sincronização", e))?;
+
+    let (concurrent, remote_wins) = match &local {
+        None => (false, true),
+        Some((changed_at, device))
+            if *changed_at == change.changed_at && *device == change.device =>
+        {
+            return Ok(Outcome::Skipped);
+        }
+        Some((changed_at, _)) => (
+            change.base.as_deref() != Some(changed_at.as_str()),
+            change.changed_at > *changed_at,
+        ),
+    };
+
+    if concurrent {
+        let local_data = read_row(tx, table, &change.uid)?
+            .map(|data| serde_json::Value::Object(data).to_string());
+        let remote_data = change
+            .data
+            .as_ref()
+            .map(|data| serde_json::Value::Object(data.clone()).to_string());
+        tx.execute(
+            "INSERT INTO sync_conflicts (table_name, uid, local_data, remote_data, remote_device, winner)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                change.table,
+                change.uid,
+                local_data,
+                remote_data,
+                change.device,
+                if remote_wins { "remote" } else { "local" }
+            ],
+        )
+        .map_err(|e| sync_error("Falha ao registrar conflito", e))?;
+    }
+    if !remote_wins {
+        return Ok(if concurrent {
+            Outcome::Conflict
+        } else {
+            Outcome::Skipped
+        });
+    }
+
+    match &change.data {
+        Some(data) => write_row(tx, table, &change.uid, data)?,
+        None => {
+            tx.execute(
+                &format!("DELETE FROM {} WHERE uid = ?1", table.name),
+                params![change.uid],
+            )
+            .map_err(|e| sync_error("Falha ao aplicar sincronização", e))?;
+        }
+    }
+    tx.execute(
+        "INSERT OR REPLACE INTO sync_rows (table_name, uid, changed_at, device)
+         VALUES (?1, ?2, ?3, ?4)",
+        params![change.table, change.uid, change.changed_at, change.device],
+    )
+    .map_err(|e| sync_error("Falha ao aplicar sincronização", e))?;
+
+    Ok(if concurrent {
+        Outcome::Conflict
+    } else {
+        Outcome::Applied
+    })
+}
+
+/// Replays the part of each other device's file not read yet.
+fn pull(
+    conn: &mut Connection,
+    dir: &Path,
+    device: &str,
+    summary: &mut SyncSummary,
+) -> Result<(), AppError> {
+    let entries = std::fs::read_dir(dir)
+        .map_err(|e| AppError::io("Pasta de sincronização inacessível", e))?;
+
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.extension().and_then(|ext| ext.to_str()) != Some(JOURNAL_EXTENSION) {
+            continue;
+        }
+        let Some(peer) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        if peer == device {
+            continue;
+        }
+
+        let mut offset: u64 = conn
+            .query_row(
+                "SELECT read_offset FROM sync_peers WHERE device = ?1",
+                params![peer],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| sync_error("Falha ao ler estado da sincronização", e))?
+            .unwrap_or(0);
+
+        let mut file = std::fs::File::open(&path)
+            .map_err(|e| AppError::io("Falha ao abrir arquivo de sincronização", e))?;
+        let len = file
+            .metadata()
+            .map_err(|e| AppError::io("Falha ao ler arquivo de sincronização", e))?
+            .len();
+        if len < offset {
+            // The file was recreated (e.g. the device was reset); read it again
+            offset = 0;
+        }
+        if len == offset {
+            continue;
+        }
+        let mut buf = Vec::new();
+        file.seek(SeekFrom::Start(offset))
+            .and_then(|_| file.read_to_end(&mut buf))
+            .map_err(|e| AppError::io("Falha ao ler arquivo de sincronização", e))?;
+        // The peer may be halfway through writing its last line
+        let Some(consumed) = buf.iter().rposition(|&b| b == b'\n').map(|end| end + 1) else {
+            continue;
+        };
+
+        let tx = conn
+            .transaction()
+            .map_err(|e| sync_error("Falha ao aplicar sincronização", e))?;
+        set_applying(&tx, true)?;
+        for line in buf[..consumed].split(|&b| b == b'\n') {
+            if line.is_empty() {
+                continue;
+            }
+            let change: Change = match serde_json::from_slice(line) {
+                Ok(change) => change,
+                Err(e) => {
+                    crate::app_log::error(format!(
+                        "Linha de sincronização ignorada ({}): {}",
+                        peer, e
+                    ));
+                    continue;
+                }
+            };
+            match apply_change(&tx, &change)? {
+                Outcome::Skipped => {}
+                Outcome::Applied => summary.pulled += 1,
+                Outcome::Conflict => {
+                    summary.pulled += 1;
+                    summary.conflicts += 1;
+                }
+            }
+        }
+        tx.execute(
+            "INSERT INTO sync_peers (device, read_offset, last_pull_at) VALUES (?1, ?2, datetime('now'))
+             ON CONFLICT (device) DO UPDATE SET read_offset = excluded.read_offset,
+                last_pull_at = excluded.last_pull_at",
+            params![peer, offset + consumed as u64],
+        )
+        .map_err(|e| sync_error("Falha ao registrar sincronização", e))?;
+        set_applying(&tx, false)?;
+        tx.commit()
+            .map_err(|e| sync_error("Falha ao aplicar sincronização", e))?;
+    }
+    Ok(())
+}
+
+fn run_sync(app: &tauri::AppHandle) -> Result<SyncSummary, AppError> {
+    let settings = app.state::<Settings>();
+    let dir = sync_dir(&settings)?;
+    let device = device_id(&settings)?;
+
+    let journal = app
+        .try_state::<SyncJournal>()
+        .ok_or_else(|| AppError::Unsupported("Sincronização indisponível".into()))?;
+    let mut summary = SyncSummary::default();
+    {
+        let mut conn = journal.conn.lock().unwrap();
+        // Pushing first means pending local edits are already in `sync_rows` when
+        // remote changes to the same rows are compared against them
+        summary.pushed = push(&mut conn, &dir, &device)?;
+        pull(&mut conn, &dir, &device, &mut summary)?;
+    }
+
+    settings.set(SYNC_LAST_KEY, chrono::Utc::now().to_rfc3339())?;
+    if summary.pulled > 0 {
+        let _ = app.emit("todos-changed", ());
+        crate::refresh_tray_menu(app);
+    }
+    let _ = app.emit("sync-completed", summary.clone());
+    Ok(summary)
+}
+
+impl SyncJournal {
+    pub fn open(app: &tauri::AppHandle) -> Result<Self, String> {
+        let conn = crate::db::open(app)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS sync_rows (
+                table_name TEXT NOT NULL,
+                uid TEXT NOT NULL,
+                changed_at TEXT NOT NULL,
+                device TEXT NOT NULL,
+                PRIMARY KEY (table_name, uid)
+            );
+            CREATE TABLE IF NOT EXISTS sync_peers (
+                device TEXT PRIMARY KEY,
+                read_offset INTEGER NOT NULL DEFAULT 0,
+                last_pull_at TEXT
+            );
+            CREATE TABLE IF NOT EXISTS sync_conflicts (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                table_name TEXT NOT NULL,
+                uid TEXT NOT NULL,
+                local_data TEXT,
+                remote_data TEXT,
+                remote_device TEXT NOT NULL,
+                winner TEXT NOT NULL,
+                detected_at TEXT NOT NULL DEFAULT (datetime('now'))
+            );",
+        )
+        .map_err(|e| format!("Falha ao criar tabelas de sincronização: {}", e))?;
+
+        Ok(SyncJournal {
+            conn: Mutex::new(conn),
+        })
+    }
+}
+
+/// Syncs every few minutes while enabled. Also keeps the journaling triggers in
+/// line with the setting, e.g. after a database restore.
+pub fn start_sync_scheduler(app: tauri::AppHandle) {
+    std::thread::spawn(move || {
+        std::thread::sleep(SCHEDULER_FIRST_CHECK);
+        loop {
+            let enabled = app
+                .state::<Settings>()
+                .get::<bool>(SYNC_ENABLED_KEY)
+                .unwrap_or(false);
+            if let Some(journal) = app.try_state::<SyncJournal>() {
+                // sync_control comes from the plugin migrations, which may not have run yet
+                let _ = set_journaling(&journal.conn.lock().unwrap(), enabled);
+            }
+            if enabled {
+                if let Err(e) = run_sync(&app) {
+                    // Usually the share being offline; the next tick tries again
+                    crate::app_log::error(format!("Sincronização falhou: {}", e));
+                    let _ = app.emit("sync-failed", e);
+                }
+            }
+            std::thread::sleep(SCHEDULER_TICK);
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_sync_settings(settings: tauri::State<'_, Settings>) -> Result<SyncSettings, AppError> {
+    Ok(SyncSettings {
+        enabled: settings.get(SYNC_ENABLED_KEY).unwrap_or(false),
+        folder: settings.get(SYNC_FOLDER_KEY),
+        device_id: device_id(&settings)?,
+        last_sync_at: settings.get(SYNC_LAST_KEY),
+    })
+}
+
+/// Turning sync on queues every existing row, so the first sync shares everything
+/// this workstation already has.
+/// Runs off the main thread: checking the folder can hang on an unreachable share.
+#[tauri::command]
+pub async fn set_sync_settings(app: tauri::AppHandle, sync: SyncSettings) -> Result<(), AppError> {
+    crate::metrics::timed("set_sync_settings", async move {
+        tauri::async_runtime::spawn_blocking(move || {
+            set_sync_settings_impl(&app.state::<Settings>(), &app.state::<SyncJournal>(), sync)
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao salvar configurações de sincronização", e))?
+    })
+    .await
+}
+
+fn set_sync_settings_impl(
+    settings: &Settings,
+    journal: &SyncJournal,
+    sync: SyncSettings,
+) -> Result<(), AppError> {
+    let folder = sync
+        .folder
+        .map(|folder| folder.trim().to_string())
+        .filter(|folder| !folder.is_empty());
+    if sync.enabled {
+        let Some(folder) = &folder else {
+            return Err(AppError::InvalidInput(
+                "Informe a pasta de sincronização na rede".into(),
+            ));
+        };
+        if !Path::new(folder).is_dir() {
+            return Err(AppError::NotFound(format!(
+                "Pasta não encontrada: {}",
+                folder
+            )));
+        }
+    }
+
+    let was_enabled = settings.get::<bool>(SYNC_ENABLED_KEY).unwrap_or(false);
+    {
+        let conn = journal.conn.lock().unwrap();
+        set_journaling(&conn, sync.enabled)?;
+        if sync.enabled && !was_enabled {
+            for table in &TABLES {
+                conn.execute(
+                    &format!(
+                        "INSERT INTO sync_journal (table_name, uid)
+                         SELECT '{0}', uid FROM {0} WHERE uid IS NOT NULL",
+                        table.name
+                    ),
+                    [],
+                )
+                .map_err(|e| sync_error("Falha ao preparar sincronização", e))?;
+            }
+        }
+    }
+
+    settings.set(SYNC_ENABLED_KEY, sync.enabled)?;
+    settings.set(SYNC_FOLDER_KEY, folder)?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn sync_now(app: tauri::AppHandle) -> Result<SyncSummary, AppError> {
+    crate::metrics::timed("sync_now", async move {
+        tauri::async_runtime::spawn_blocking(move || run_sync(&app))
+            .await
+            .map_err(|e| AppError::internal("Falha ao sincronizar", e))?
+    })
+    .await
+}
+
+#[tauri::command]
+pub fn list_sync_conflicts(
+    journal: tauri::State<'_, SyncJournal>,
+) -> Result<Vec<SyncConflict>, AppError> {
+    let conn = journal.conn.lock().unwrap();
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, table_name, uid, local_data, remote_data, remote_device, winner, detected_at
+             FROM sync_conflicts ORDER BY id DESC",
+        )
+        .map_err(|e| sync_error("Falha ao consultar conflitos", e))?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(SyncConflict {
+                id: row.get(0)?,
+                table_name: row.get(1)?,
+                uid: row.get(2)?,
+                local_data: row.get(3)?,
+                remote_data: row.get(4)?,
+                remote_device: row.get(5)?,
+                winner: row.get(6)?,
+                detected_at: row.get(7)?,
+            })
+        })
+        .map_err(|e| sync_error("Falha ao consultar conflitos", e))?;
+    rows.collect::<Result<_, _>>()
+        .map_err(|e| sync_error("Falha ao ler conflitos", e))
+}
+
+#[tauri::command]
+pub fn clear_sync_conflicts(journal: tauri::State<'_, SyncJournal>) -> Result<(), AppError> {
+    let conn = journal.conn.lock().unwrap();
+    conn.execute("DELETE FROM sync_conflicts", [])
+        .map_err(|e| sync_error("Falha ao limpar conflitos", e))?;
+    Ok(())
+}
@@ -0,0 +1,357 @@
+// ── System Report Module ───────────────────────────────────────
+use crate::error::AppError;
+use crate::metrics::{self, RecentError};
+use crate::system_stats::{self, DiskStats};
+use crate::CertInfo;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use sysinfo::System;
+
+/// Certificates expiring within this many days are flagged in the report.
+const EXPIRING_SOON_DAYS: i64 = 30;
+/// Only the latest crash reports are listed; the files themselves stay in the log folder.
+const CRASH_REPORTS_LIMIT: usize = 10;
+/// Tail of the app log included in the report.
+const LOG_LINES_LIMIT: usize = 100;
+
+#[derive(serde::Serialize)]
+pub struct MappedDrive {
+    pub letter: String,
+    pub remote: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct CertificateSummary {
+    pub total: usize,
+    pub expired: usize,
+    pub expiring_soon: usize,
+    pub certificates: Vec<CertInfo>,
+    /// Set when the certificate store could not be read; the counts are then 0.
+    pub error: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+pub struct SystemReport {
+    pub output_path: String,
+    pub generated_at: String,
+    pub app_version: String,
+    pub os: String,
+    pub kernel_version: String,
+    pub arch: String,
+    pub hostname: String,
+    pub user: String,
+    pub memory_total_bytes: u64,
+    pub memory_used_bytes: u64,
+    pub disks: Vec<DiskStats>,
+    pub mapped_drives: Vec<MappedDrive>,
+    pub certificates: CertificateSummary,
+    /// Newest first.
+    pub recent_errors: Vec<RecentError>,
+    pub crash_reports: Vec<String>,
+    /// Last lines of the app log, oldest first.
+    pub log_lines: Vec<String>,
+}
+
+#[cfg(windows)]
+fn mapped_drives() -> Vec<MappedDrive> {
+    use windows_sys::Win32::NetworkManagement::WNet::WNetGetConnectionW;
+
+    let mut drives = Vec::new();
+    for letter in 'A'..='Z' {
+        let local: Vec<u16> = format!("{}:", letter)
+            .encode_utf16()
+            .chain(std::iter::once(0))
+            .collect();
+        let mut buffer = [0u16; 1024];
+        let mut len = buffer.len() as u32;
+        // Fails with ERROR_NOT_CONNECTED for local and unused letters
+        let status = unsafe { WNetGetConnectionW(local.as_ptr(), buffer.as_mut_ptr(), &mut len) };
+        if status != 0 {
+            continue;
+        }
+        let end = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+        drives.push(MappedDrive {
+            letter: format!("{}:", letter),
+            remote: String::from_utf16_lossy(&buffer[..end]),
+        });
+    }
+    drives
+}
+
+#[cfg(not(windows))]
+fn mapped_drives() -> Vec<MappedDrive> {
+    Vec::new()
+}
+
+fn certificate_summary() -> CertificateSummary {
+    let certificates = match crate::certs_impl() {
+        Ok(certificates) => certificates,
+        Err(e) => {
+            return CertificateSummary {
+                total: 0,
+                expired: 0,
+                expiring_soon: 0,
+                certificates: Vec::new(),
+                error: Some(e.to_string()),
+            }
+        }
+    };
+
+    let today = chrono::Local::now().date_naive();
+    let (mut expired, mut expiring_soon) = (0, 0);
+    for cert in &certificates {
+        // "N/A" when the store did not report a date
+        let Ok(not_after) = chrono::NaiveDate::parse_from_str(&cert.not_after, "%Y-%m-%d") else {
+            continue;
+        };
+        let days_left = (not_after - today).num_days();
+        if days_left < 0 {
+            expired += 1;
+        } else if days_left <= EXPIRING_SOON_DAYS {
+            expiring_soon += 1;
+        }
+    }
+    CertificateSummary {
+        total: certificates.len(),
+        expired,
+        expiring_soon,
+        certificates,
+        error: None,
+    }
+}
+
+fn collect(app: &tauri::AppHandle, output_path: &Path) -> SystemReport {
+    let mut system = System::new();
+    system.refresh_memory();
+    let user = std::env::var("USERNAME")
+        .or_else(|_| std::env::var("USER"))
+        .unwrap_or_default();
+    let mut crash_reports = crate::crash_report::report_file_names();
+    crash_reports.truncate(CRASH_REPORTS_LIMIT);
+
+    SystemReport {
+        output_path: output_path.to_string_lossy().to_string(),
+        generated_at: chrono::Local::now().to_rfc3339(),
+        app_version: app.package_info().version.to_string(),
+        os: System::long_os_version().unwrap_or_else(|| std::env::consts::OS.to_string()),
+        kernel_version: System::kernel_version().unwrap_or_default(),
+        arch: std::env::consts::ARCH.to_string(),
+        hostname: System::host_name().unwrap_or_default(),
+        user,
+        memory_total_bytes: system.total_memory(),
+        memory_used_bytes: system.used_memory(),
+        disks: system_stats::disks(),
+        mapped_drives: mapped_drives(),
+        certificates: certificate_summary(),
+        recent_errors: metrics::recent_errors(),
+        crash_reports,
+        log_lines: crate::app_log::recent_lines(LOG_LINES_LIMIT),
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn format_gb(bytes: u64) -> String {
+    format!("{:.1} GB", bytes as f64 / (1024.0 * 1024.0 * 1024.0))
+}
+
+/// Table with a header row; every cell is escaped.
+fn html_table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    if rows.is_empty() {
+        return "<p class=\"empty\">Nenhum item.</p>".into();
+    }
+    let mut html = String::from("<table><tr>");
+    for header in headers {
+        let _ = write!(html, "<th>{}</th>", escape_html(header));
+    }
+    html.push_str("</tr>");
+    for row in rows {
+        html.push_str("<tr>");
+        for cell in row {
+            let _ = write!(html, "<td>{}</td>", escape_html(cell));
+        }
+        html.push_str("</tr>");
+    }
+    html.push_str("</table>");
+    html
+}
+
+fn render_html(report: &SystemReport) -> String {
+    let overview = html_table(
+        &["Item", "Valor"],
+        &[
+            vec!["Gerado em".into(), report.generated_at.clone()],
+            vec!["Versão do app".into(), report.app_version.clone()],
+            vec!["Sistema".into(), report.os.clone()],
+            vec!["Kernel".into(), report.kernel_version.clone()],
+            vec!["Arquitetura".into(), report.arch.clone()],
+            vec!["Computador".into(), report.hostname.clone()],
+            vec!["Usuário".into(), report.user.clone()],
+            vec![
+                "Memória".into(),
+                format!(
+                    "{} usados de {}",
+                    format_gb(report.memory_used_bytes),
+                    format_gb(report.memory_total_bytes)
+                ),
+            ],
+        ],
+    );
+
+    let disks = html_table(
+        &["Unidade", "Nome", "Sistema de arquivos", "Livre", "Total"],
+        &report
+            .disks
+            .iter()
+            .map(|disk| {
+                vec![
+                    disk.mount_point.clone(),
+                    disk.name.clone(),
+                    disk.file_system.clone(),
+                    format_gb(disk.free_bytes),
+                    format_gb(disk.total_bytes),
+                ]
+            })
+            .collect::<Vec<_>>(),
+    );
+
+    let mapped_drives = html_table(
+        &["Letra", "Caminho de rede"],
+        &report
+            .mapped_drives
+            .iter()
+            .map(|drive| vec![drive.letter.clone(), drive.remote.clone()])
+            .collect::<Vec<_>>(),
+    );
+
+    let summary = &report.certificates;
+    let certificates = match &summary.error {
+        Some(error) => format!("<p class=\"empty\">{}</p>", escape_html(error)),
+        None => format!(
+            "<p>{} certificado(s), {} vencido(s), {} vencendo em até {} dias.</p>{}",
+            summary.total,
+            summary.expired,
+            summary.expiring_soon,
+            EXPIRING_SOON_DAYS,
+            html_table(
+                &["Titular", "Emissor", "Validade", "CNPJ"],
+                &summary
+                    .certificates
+                    .iter()
+                    .map(|cert| {
+                        vec![
+                            cert.subject.clone(),
+                            cert.issuer.clone(),
+                            cert.not_after.clone(),
+                            cert.cnpj.clone(),
+                        ]
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        ),
+    };
+
+    let errors = html_table(
+        &["Data", "Comando", "Mensagem"],
+        &report
+            .recent_errors
+            .iter()
+            .map(|error| {
+                vec![
+                    error.at.clone(),
+                    error.command.clone(),
+                    error.message.clone(),
+                ]
+            })
+            .collect::<Vec<_>>(),
+    );
+
+    let crashes = html_table(
+        &["Arquivo"],
+        &report
+            .crash_reports
+            .iter()
+            .map(|name| vec![name.clone()])
+            .collect::<Vec<_>>(),
+    );
+
+    let log = html_table(
+        &["Linha"],
+        &report
+            .log_lines
+            .iter()
+            .map(|line| vec![line.clone()])
+            .collect::<Vec<_>>(),
+    );
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="pt-BR">
+<head><meta charset="UTF-8"><title>Relatório do sistema - {hostname}</title>
+<style>
+body {{ font-family: Segoe UI, sans-serif; font-size: 10pt; margin: 24px; color: #222; }}
+h1 {{ font-size: 16pt; }}
+h2 {{ font-size: 12pt; margin-top: 24px; border-bottom: 1px solid #ccc; }}
+table {{ border-collapse: collapse; width: 100%; }}
+th, td {{ border: 1px solid #ddd; padding: 4px 8px; text-align: left; vertical-align: top; }}
+th {{ background: #f3f3f3; }}
+.empty {{ color: #777; }}
+</style></head>
+<body>
+<h1>Relatório do sistema</h1>
+<h2>Resumo</h2>{overview}
+<h2>Discos</h2>{disks}
+<h2>Unidades mapeadas</h2>{mapped_drives}
+<h2>Certificados digitais</h2>{certificates}
+<h2>Erros recentes do app</h2>{errors}
+<h2>Relatórios de falha</h2>{crashes}
+<h2>Log do app</h2>{log}
+</body>
+</html>"#,
+        hostname = escape_html(&report.hostname),
+    )
+}
+
+/// Collects what support usually asks for (OS, machine, user, disks, mapped drives,
+/// certificates, app version and recent errors) and saves it to `output_path`, as
+/// JSON when the extension is `.json` and as HTML otherwise, ready to attach to a ticket.
+#[tauri::command]
+pub async fn generate_system_report(
+    app: tauri::AppHandle,
+    output_path: String,
+) -> Result<SystemReport, AppError> {
+    crate::metrics::timed("generate_system_report", async move {
+        tauri::async_runtime::spawn_blocking(move || {
+            let output_path = PathBuf::from(output_path);
+            let report = collect(&app, &output_path);
+            let is_json = output_path
+                .extension()
+                .is_some_and(|extension| extension.eq_ignore_ascii_case("json"));
+            let content = if is_json {
+                serde_json::to_string_pretty(&report)
+                    .map_err(|e| AppError::internal("Falha ao gerar relatório", e))?
+            } else {
+                render_html(&report)
+            };
+            std::fs::write(&output_path, content)
+                .map_err(|e| AppError::io("Falha ao salvar relatório", e))?;
+            Ok(report)
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao gerar relatório", e))?
+    })
+    .await
+}
@@ -0,0 +1,129 @@
+// ── System Stats Module ────────────────────────────────────────
+use crate::error::AppError;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use sysinfo::{Disks, System};
+use tauri::{Emitter, Manager};
+
+const MIN_INTERVAL_MS: u64 = 1000;
+const DEFAULT_INTERVAL_MS: u64 = 2000;
+
+/// Keeps the previous CPU sample around, since usage is measured between two refreshes.
+#[derive(Default)]
+pub struct SystemMonitor {
+    system: Mutex<Option<System>>,
+    /// Stop flag of the running `system-stats` subscription, if any.
+    subscription: Mutex<Option<Arc<AtomicBool>>>,
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct DiskStats {
+    pub name: String,
+    pub mount_point: String,
+    pub file_system: String,
+    pub total_bytes: u64,
+    pub free_bytes: u64,
+    pub removable: bool,
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct SystemStats {
+    /// Overall CPU usage, 0–100.
+    pub cpu_percent: f32,
+    pub memory_total_bytes: u64,
+    pub memory_used_bytes: u64,
+    pub disks: Vec<DiskStats>,
+    pub uptime_secs: u64,
+}
+
+impl SystemMonitor {
+    fn sample(&self) -> SystemStats {
+        let mut guard = self.system.lock().unwrap();
+        let system = match guard.as_mut() {
+            Some(system) => system,
+            None => {
+                // The first reading needs a baseline, otherwise CPU usage is always 0
+                let mut system = System::new();
+                system.refresh_cpu_usage();
+                std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+                guard.insert(system)
+            }
+        };
+        system.refresh_cpu_usage();
+        system.refresh_memory();
+
+        SystemStats {
+            cpu_percent: system.global_cpu_usage(),
+            memory_total_bytes: system.total_memory(),
+            memory_used_bytes: system.used_memory(),
+            disks: disks(),
+            uptime_secs: System::uptime(),
+        }
+    }
+}
+
+pub(crate) fn disks() -> Vec<DiskStats> {
+    Disks::new_with_refreshed_list()
+        .list()
+        .iter()
+        .map(|disk| DiskStats {
+            name: disk.name().to_string_lossy().into_owned(),
+            mount_point: disk.mount_point().to_string_lossy().into_owned(),
+            file_system: disk.file_system().to_string_lossy().into_owned(),
+            total_bytes: disk.total_space(),
+            free_bytes: disk.available_space(),
+            removable: disk.is_removable(),
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub async fn get_system_stats(app: tauri::AppHandle) -> Result<SystemStats, AppError> {
+    crate::metrics::timed("get_system_stats", async move {
+        tauri::async_runtime::spawn_blocking(move || app.state::<SystemMonitor>().sample())
+            .await
+            .map_err(|e| AppError::internal("Falha ao ler dados do sistema", e))
+    })
+    .await
+}
+
+/// Emits `system-stats` every `interval_ms` until `unsubscribe_system_stats` is called.
+/// Subscribing again replaces the previous interval.
+#[tauri::command]
+pub fn subscribe_system_stats(app: tauri::AppHandle, interval_ms: Option<u64>) {
+    let interval = Duration::from_millis(
+        interval_ms
+            .unwrap_or(DEFAULT_INTERVAL_MS)
+            .max(MIN_INTERVAL_MS),
+    );
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let previous = app
+        .state::<SystemMonitor>()
+        .subscription
+        .lock()
+        .unwrap()
+        .replace(stop.clone());
+    if let Some(previous) = previous {
+        previous.store(true, Ordering::Relaxed);
+    }
+
+    std::thread::spawn(move || {
+        while !stop.load(Ordering::Relaxed) {
+            let stats = app.state::<SystemMonitor>().sample();
+            if stop.load(Ordering::Relaxed) {
+                break;
+            }
+            let _ = app.emit("system-stats", stats);
+            std::thread::sleep(interval);
+        }
+    });
+}
+
+#[tauri::command]
+pub fn unsubscribe_system_stats(monitor: tauri::State<'_, SystemMonitor>) {
+    if let Some(stop) = monitor.subscription.lock().unwrap().take() {
+        stop.store(true, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,143 @@
+// ── Text Diff Module ───────────────────────────────────────────
+use crate::error::AppError;
+use similar::{ChangeTag, TextDiff};
+use std::path::Path;
+use std::time::Duration;
+
+/// Larger files are logs or exports nobody reads line by line in a diff view.
+const MAX_DIFF_FILE_BYTES: u64 = 5 * 1024 * 1024;
+/// Past this the diff falls back to a coarser (still correct) result instead of hanging.
+const DIFF_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(serde::Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffTag {
+    Equal,
+    Insert,
+    Delete,
+}
+
+/// Piece of a line; `changed` marks the words that differ inside a modified line.
+#[derive(serde::Serialize)]
+pub struct DiffSegment {
+    pub text: String,
+    pub changed: bool,
+}
+
+#[derive(serde::Serialize)]
+pub struct DiffLine {
+    pub tag: DiffTag,
+    /// 1-based line numbers; `None` on the side the line does not exist.
+    pub old_line: Option<usize>,
+    pub new_line: Option<usize>,
+    pub segments: Vec<DiffSegment>,
+}
+
+#[derive(serde::Serialize)]
+pub struct TextDiffResult {
+    pub lines: Vec<DiffLine>,
+    pub insertions: usize,
+    pub deletions: usize,
+    pub identical: bool,
+}
+
+fn diff(old: &str, new: &str) -> TextDiffResult {
+    // A file saved on another system must not show every line as changed
+    let old = old.replace("\r\n", "\n");
+    let new = new.replace("\r\n", "\n");
+    let diff = TextDiff::configure()
+        .timeout(DIFF_TIMEOUT)
+        .diff_lines(&old, &new);
+
+    let mut lines = Vec::new();
+    let (mut insertions, mut deletions) = (0, 0);
+    for op in diff.ops() {
+        for change in diff.iter_inline_changes(op) {
+            let tag = match change.tag() {
+                ChangeTag::Equal => DiffTag::Equal,
+                ChangeTag::Insert => {
+                    insertions += 1;
+                    DiffTag::Insert
+                }
+                ChangeTag::Delete => {
+                    deletions += 1;
+                    DiffTag::Delete
+                }
+            };
+            let mut segments: Vec<DiffSegment> = change
+                .iter_strings_lossy()
+                .map(|(changed, text)| DiffSegment {
+                    text: text.into_owned(),
+                    changed,
+                })
+                .collect();
+            if let Some(last) = segments.last_mut() {
+                if last.text.ends_with('\n') {
+                    last.text.pop();
+                }
+            }
+            segments.retain(|segment| !segment.text.is_empty());
+            lines.push(DiffLine {
+                tag,
+                old_line: change.old_index().map(|index| index + 1),
+                new_line: change.new_index().map(|index| index + 1),
+                segments,
+            });
+        }
+    }
+
+    TextDiffResult {
+        identical: insertions == 0 && deletions == 0,
+        lines,
+        insertions,
+        deletions,
+    }
+}
+
+fn read_text(path: &Path) -> Result<String, AppError> {
+    let size = std::fs::metadata(path)
+        .map_err(|e| AppError::io(format!("Falha ao abrir {}", path.display()), e))?
+        .len();
+    if size > MAX_DIFF_FILE_BYTES {
+        return Err(AppError::InvalidInput(format!(
+            "Arquivo maior que {} MB: {}",
+            MAX_DIFF_FILE_BYTES / (1024 * 1024),
+            path.display()
+        )));
+    }
+    let bytes = std::fs::read(path)
+        .map_err(|e| AppError::io(format!("Falha ao ler {}", path.display()), e))?;
+    if bytes.contains(&0) {
+        return Err(AppError::Unsupported(format!(
+            "Arquivo binário não pode ser comparado: {}",
+            path.display()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Line diff of `a` (old) against `b` (new), with word-level highlights inside
+/// changed lines.
+#[tauri::command]
+pub async fn diff_text(a: String, b: String) -> Result<TextDiffResult, AppError> {
+    crate::metrics::timed("diff_text", async move {
+        tauri::async_runtime::spawn_blocking(move || diff(&a, &b))
+            .await
+            .map_err(|e| AppError::internal("Falha ao comparar textos", e))
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn diff_files(path_a: String, path_b: String) -> Result<TextDiffResult, AppError> {
+    crate::metrics::timed("diff_files", async move {
+        tauri::async_runtime::spawn_blocking(move || {
+            let a = read_text(Path::new(&path_a))?;
+            let b = read_text(Path::new(&path_b))?;
+            Ok(diff(&a, &b))
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao comparar arquivos", e))?
+    })
+    .await
+}
@@ -0,0 +1,594 @@
+// ── Todos Module ───────────────────────────────────────────────
+use crate::error::AppError;
+use crate::reminders::{parse_due_at, to_db_time};
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{Emitter, Manager};
+
+/// Tags, saved filters and subtasks for the frontend-owned `todos` table.
+pub struct Todos {
+    conn: Mutex<Connection>,
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct Tag {
+    pub id: i64,
+    pub name: String,
+    pub color: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+pub struct TodoItem {
+    pub id: i64,
+    /// Parent todo when this is a checklist step.
+    pub parent_id: Option<i64>,
+    pub title: String,
+    pub done: bool,
+    pub created_at: String,
+    pub completed_at: Option<String>,
+    pub sort_order: i64,
+    pub due_at: Option<String>,
+    pub remind_at: Option<String>,
+    pub tags: Vec<Tag>,
+}
+
+#[derive(Clone, Copy, Default, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TodoStatus {
+    #[default]
+    All,
+    Open,
+    Done,
+}
+
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct TodoFilter {
+    /// Todos carrying any of these tags.
+    pub tag_ids: Vec<i64>,
+    pub status: TodoStatus,
+    /// Due-date range, same formats as reminders; todos without a due date are left out
+    /// whenever either bound is set.
+    pub due_from: Option<String>,
+    pub due_to: Option<String>,
+    /// Open todos whose deadline has passed.
+    pub overdue: bool,
+    pub search: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+pub struct SavedTodoFilter {
+    pub id: i64,
+    pub name: String,
+    pub filter: TodoFilter,
+}
+
+fn normalize_color(color: Option<String>) -> Option<String> {
+    color
+        .map(|color| color.trim().to_string())
+        .filter(|color| !color.is_empty())
+}
+
+fn tag_name(name: &str) -> Result<&str, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::InvalidInput(
+            "A etiqueta precisa de um nome".into(),
+        ));
+    }
+    Ok(name)
+}
+
+/// Unique-constraint violations become a friendly message.
+fn tag_write_error(e: rusqlite::Error, name: &str) -> AppError {
+    match e {
+        rusqlite::Error::SqliteFailure(err, _)
+            if err.code == rusqlite::ErrorCode::ConstraintViolation =>
+        {
+            AppError::AlreadyExists(format!("Já existe uma etiqueta chamada '{}'", name))
+        }
+        e => AppError::internal("Falha ao salvar etiqueta", e),
+    }
+}
+
+impl Todos {
+    pub fn open(app: &tauri::AppHandle) -> Result<Self, String> {
+        let conn = crate::db::open(app)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS tags (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+                color TEXT,
+                created_at TEXT NOT NULL DEFAULT (datetime('now'))
+            );
+            CREATE TABLE IF NOT EXISTS todo_tags (
+                todo_id INTEGER NOT NULL REFERENCES todos(id) ON DELETE CASCADE,
+                tag_id INTEGER NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+                PRIMARY KEY (todo_id, tag_id)
+            );
+            CREATE INDEX IF NOT EXISTS idx_todo_tags_tag ON todo_tags(tag_id);
+            CREATE TABLE IF NOT EXISTS todo_filters (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+                filter TEXT NOT NULL,
+                created_at TEXT NOT NULL DEFAULT (datetime('now'))
+            );",
+        )
+        .map_err(|e| format!("Falha ao criar tabelas de etiquetas: {}", e))?;
+
+        Ok(Todos {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    fn get_tag(&self, id: i64) -> Result<Tag, AppError> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            "SELECT id, name, color FROM tags WHERE id = ?1",
+            params![id],
+            |row| {
+                Ok(Tag {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    color: row.get(2)?,
+                })
+            },
+        )
+        .optional()
+        .map_err(|e| AppError::internal("Falha ao consultar etiqueta", e))?
+        .ok_or_else(|| AppError::NotFound("Etiqueta não encontrada".into()))
+    }
+
+    /// Tags of every todo, keyed by todo id.
+    fn tags_by_todo(conn: &Connection) -> Result<HashMap<i64, Vec<Tag>>, AppError> {
+        let mut stmt = conn
+            .prepare(
+                "SELECT tt.todo_id, g.id, g.name, g.color
+                 FROM todo_tags tt JOIN tags g ON g.id = tt.tag_id
+                 ORDER BY g.name COLLATE NOCASE",
+            )
+            .map_err(|e| AppError::internal("Falha ao consultar etiquetas", e))?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    Tag {
+                        id: row.get(1)?,
+                        name: row.get(2)?,
+                        color: row.get(3)?,
+                    },
+                ))
+            })
+            .map_err(|e| AppError::internal("Falha ao consultar etiquetas", e))?;
+
+        let mut tags: HashMap<i64, Vec<Tag>> = HashMap::new();
+        for row in rows {
+            let (todo_id, tag) =
+                row.map_err(|e| AppError::internal("Falha ao ler etiquetas", e))?;
+            tags.entry(todo_id).or_default().push(tag);
+        }
+        Ok(tags)
+    }
+
+    fn list(&self, filter: &TodoFilter) -> Result<Vec<TodoItem>, AppError> {
+        let mut conditions: Vec<String> = Vec::new();
+        let mut values: Vec<Value> = Vec::new();
+
+        match filter.status {
+            TodoStatus::All => {}
+            TodoStatus::Open => conditions.push("t.done = 0".into()),
+            TodoStatus::Done => conditions.push("t.done = 1".into()),
+        }
+        if !filter.tag_ids.is_empty() {
+            let placeholders = vec!["?"; filter.tag_ids.len()].join(", ");
+            conditions.push(format!(
+                "t.id IN (SELECT todo_id FROM todo_tags WHERE tag_id IN ({}))",
+                placeholders
+            ));
+            values.extend(filter.tag_ids.iter().map(|id| Value::Integer(*id)));
+        }
+        if let Some(from) = filter.due_from.as_deref() {
+            conditions.push("t.due_at >= ?".into());
+            values.push(Value::Text(to_db_time(parse_due_at(from)?)));
+        }
+        if let Some(to) = filter.due_to.as_deref() {
+            conditions.push("t.due_at <= ?".into());
+            values.push(Value::Text(to_db_time(parse_due_at(to)?)));
+        }
+        if filter.overdue {
+            conditions.push("t.done = 0 AND t.due_at < ?".into());
+            values.push(Value::Text(to_db_time(chrono::Utc::now())));
+        }
+        if let Some(search) = filter.search.as_deref().map(str::trim) {
+            if !search.is_empty() {
+                conditions.push("t.title LIKE ?".into());
+                values.push(Value::Text(format!("%{}%", search)));
+            }
+        }
+
+        let where_clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
+
+        let conn = self.conn.lock().unwrap();
+        let mut tags = Self::tags_by_todo(&conn)?;
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT t.id, t.parent_id, t.title, t.done, t.created_at, t.completed_at,
+                        t.sort_order, t.due_at, t.remind_at
+                 FROM todos t {}
+                 ORDER BY t.done, CASE WHEN t.done = 0 THEN t.sort_order END, t.completed_at DESC",
+                where_clause
+            ))
+            .map_err(|e| AppError::internal("Falha ao consultar tarefas", e))?;
+        let rows = stmt
+            .query_map(params_from_iter(values), |row| {
+                Ok(TodoItem {
+                    id: row.get(0)?,
+                    parent_id: row.get(1)?,
+                    title: row.get(2)?,
+                    done: row.get(3)?,
+                    created_at: row.get(4)?,
+                    completed_at: row.get(5)?,
+                    sort_order: row.get(6)?,
+                    due_at: row.get(7)?,
+                    remind_at: row.get(8)?,
+                    tags: Vec::new(),
+                })
+            })
+            .map_err(|e| AppError::internal("Falha ao consultar tarefas", e))?;
+
+        rows.map(|row| {
+            row.map(|mut todo| {
+                todo.tags = tags.remove(&todo.id).unwrap_or_default();
+                todo
+            })
+            .map_err(|e| AppError::internal("Falha ao ler tarefas", e))
+        })
+        .collect()
+    }
+}
+
+/// Ids of a todo and all of its subtasks, at any depth.
+const DESCENDANTS_CTE: &str = "WITH RECURSIVE tree(id) AS (
+        SELECT ?1
+        UNION SELECT t.id FROM todos t JOIN tree ON t.parent_id = tree.id
+    )";
+
+/// Ids of a todo and every todo above it.
+const ANCESTORS_CTE: &str = "WITH RECURSIVE chain(id) AS (
+        SELECT ?1
+        UNION SELECT t.parent_id FROM todos t JOIN chain ON t.id = chain.id
+        WHERE t.parent_id IS NOT NULL
+    )";
+
+fn next_sort_order(conn: &Connection, parent_id: Option<i64>) -> Result<i64, AppError> {
+    conn.query_row(
+        "SELECT COALESCE(MAX(sort_order), 0) + 1 FROM todos
+         WHERE done = 0 AND parent_id IS ?1",
+        params![parent_id],
+        |row| row.get(0),
+    )
+    .map_err(|e| AppError::internal("Falha ao consultar tarefas", e))
+}
+
+/// Lets the tray indicator and open views catch up with backend edits.
+fn todos_changed(app: &tauri::AppHandle) {
+    let _ = app.emit("todos-changed", ());
+}
+
+#[tauri::command]
+pub fn list_tags(todos: tauri::State<'_, Todos>) -> Result<Vec<Tag>, AppError> {
+    let conn = todos.conn.lock().unwrap();
+    let mut stmt = conn
+        .prepare("SELECT id, name, color FROM tags ORDER BY name COLLATE NOCASE")
+        .map_err(|e| AppError::internal("Falha ao consultar etiquetas", e))?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(Tag {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                color: row.get(2)?,
+            })
+        })
+        .map_err(|e| AppError::internal("Falha ao consultar etiquetas", e))?;
+    rows.collect::<Result<_, _>>()
+        .map_err(|e| AppError::internal("Falha ao ler etiquetas", e))
+}
+
+#[tauri::command]
+pub fn create_tag(
+    todos: tauri::State<'_, Todos>,
+    name: String,
+    color: Option<String>,
+) -> Result<Tag, AppError> {
+    let name = tag_name(&name)?;
+    let id = {
+        let conn = todos.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO tags (name, color) VALUES (?1, ?2)",
+            params![name, normalize_color(color)],
+        )
+        .map_err(|e| tag_write_error(e, name))?;
+        conn.last_insert_rowid()
+    };
+    todos.get_tag(id)
+}
+
+#[tauri::command]
+pub fn update_tag(
+    todos: tauri::State<'_, Todos>,
+    id: i64,
+    name: String,
+    color: Option<String>,
+) -> Result<Tag, AppError> {
+    let name = tag_name(&name)?;
+    {
+        let conn = todos.conn.lock().unwrap();
+        let changed = conn
+            .execute(
+                "UPDATE tags SET name = ?1, color = ?2 WHERE id = ?3",
+                params![name, normalize_color(color), id],
+            )
+            .map_err(|e| tag_write_error(e, name))?;
+        if changed == 0 {
+            return Err(AppError::NotFound("Etiqueta não encontrada".into()));
+        }
+    }
+    todos.get_tag(id)
+}
+
+#[tauri::command]
+pub fn delete_tag(todos: tauri::State<'_, Todos>, id: i64) -> Result<(), AppError> {
+    let conn = todos.conn.lock().unwrap();
+    // This connection doesn't enforce foreign keys, so the links go explicitly
+    conn.execute("DELETE FROM todo_tags WHERE tag_id = ?1", params![id])
+        .and_then(|_| conn.execute("DELETE FROM tags WHERE id = ?1", params![id]))
+        .map_err(|e| AppError::internal("Falha ao excluir etiqueta", e))?;
+    Ok(())
+}
+
+/// Replaces the tags of a todo.
+#[tauri::command]
+pub fn set_todo_tags(
+    todos: tauri::State<'_, Todos>,
+    todo_id: i64,
+    tag_ids: Vec<i64>,
+) -> Result<(), AppError> {
+    let mut conn = todos.conn.lock().unwrap();
+    let tx = conn
+        .transaction()
+        .map_err(|e| AppError::internal("Falha ao salvar etiquetas", e))?;
+    tx.execute("DELETE FROM todo_tags WHERE todo_id = ?1", params![todo_id])
+        .map_err(|e| AppError::internal("Falha ao salvar etiquetas", e))?;
+    for tag_id in tag_ids {
+        tx.execute(
+            "INSERT OR IGNORE INTO todo_tags (todo_id, tag_id)
+             SELECT ?1, id FROM tags WHERE id = ?2",
+            params![todo_id, tag_id],
+        )
+        .map_err(|e| AppError::internal("Falha ao salvar etiquetas", e))?;
+    }
+    tx.commit()
+        .map_err(|e| AppError::internal("Falha ao salvar etiquetas", e))
+}
+
+#[tauri::command]
+pub fn list_todos(
+    todos: tauri::State<'_, Todos>,
+    filter: Option<TodoFilter>,
+) -> Result<Vec<TodoItem>, AppError> {
+    todos.list(&filter.unwrap_or_default())
+}
+
+#[tauri::command]
+pub fn list_saved_todo_filters(
+    todos: tauri::State<'_, Todos>,
+) -> Result<Vec<SavedTodoFilter>, AppError> {
+    let conn = todos.conn.lock().unwrap();
+    let mut stmt = conn
+        .prepare("SELECT id, name, filter FROM todo_filters ORDER BY name COLLATE NOCASE")
+        .map_err(|e| AppError::internal("Falha ao consultar filtros", e))?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })
+        .map_err(|e| AppError::internal("Falha ao consultar filtros", e))?;
+
+    let mut filters = Vec::new();
+    for row in rows {
+        let (id, name, filter) = row.map_err(|e| AppError::internal("Falha ao ler filtros", e))?;
+        // A filter saved by an older version just loses the fields it no longer knows
+        let filter = serde_json::from_str(&filter).unwrap_or_default();
+        filters.push(SavedTodoFilter { id, name, filter });
+    }
+    Ok(filters)
+}
+
+/// Saves under `name`, overwriting a filter with the same name.
+#[tauri::command]
+pub fn save_todo_filter(
+    todos: tauri::State<'_, Todos>,
+    name: String,
+    filter: TodoFilter,
+) -> Result<i64, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::InvalidInput("O filtro precisa de um nome".into()));
+    }
+    let json = serde_json::to_string(&filter)
+        .map_err(|e| AppError::internal("Falha ao salvar filtro", e))?;
+
+    let conn = todos.conn.lock().unwrap();
+    conn.query_row(
+        "INSERT INTO todo_filters (name, filter) VALUES (?1, ?2)
+         ON CONFLICT(name) DO UPDATE SET filter = excluded.filter
+         RETURNING id",
+        params![name, json],
+        |row| row.get(0),
+    )
+    .map_err(|e| AppError::internal("Falha ao salvar filtro", e))
+}
+
+#[tauri::command]
+pub fn delete_todo_filter(todos: tauri::State<'_, Todos>, id: i64) -> Result<(), AppError> {
+    let conn = todos.conn.lock().unwrap();
+    conn.execute("DELETE FROM todo_filters WHERE id = ?1", params![id])
+        .map_err(|e| AppError::internal("Falha ao excluir filtro", e))?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn create_subtask(
+    app: tauri::AppHandle,
+    parent_id: i64,
+    title: String,
+) -> Result<i64, AppError> {
+    let title = title.trim();
+    if title.is_empty() {
+        return Err(AppError::InvalidInput(
+            "A subtarefa precisa de um título".into(),
+        ));
+    }
+
+    let id = {
+        let todos = app.state::<Todos>();
+        let conn = todos.conn.lock().unwrap();
+        let parent_exists: bool = conn
+            .query_row(
+                "SELECT COUNT(*) > 0 FROM todos WHERE id = ?1",
+                params![parent_id],
+                |row| row.get(0),
+            )
+            .map_err(|e| AppError::internal("Falha ao consultar tarefas", e))?;
+        if !parent_exists {
+            return Err(AppError::NotFound("Tarefa principal não encontrada".into()));
+        }
+
+        let sort_order = next_sort_order(&conn, Some(parent_id))?;
+        conn.execute(
+            "INSERT INTO todos (title, parent_id, sort_order) VALUES (?1, ?2, ?3)",
+            params![title, parent_id, sort_order],
+        )
+        .map_err(|e| AppError::internal("Falha ao criar subtarefa", e))?;
+        let id = conn.last_insert_rowid();
+
+        // A new open step means the parent isn't finished anymore
+        conn.execute(
+            &format!(
+                "{} UPDATE todos SET done = 0, completed_at = NULL
+                 WHERE id IN (SELECT id FROM chain) AND done = 1",
+                ANCESTORS_CTE
+            ),
+            params![parent_id],
+        )
+        .map_err(|e| AppError::internal("Falha ao atualizar tarefa principal", e))?;
+        id
+    };
+    todos_changed(&app);
+    Ok(id)
+}
+
+/// Moves a todo under `parent_id`, or back to the top level with `None`.
+#[tauri::command]
+pub fn set_todo_parent(
+    app: tauri::AppHandle,
+    todo_id: i64,
+    parent_id: Option<i64>,
+) -> Result<(), AppError> {
+    {
+        let todos = app.state::<Todos>();
+        let conn = todos.conn.lock().unwrap();
+        if let Some(parent_id) = parent_id {
+            let creates_cycle: bool = conn
+                .query_row(
+                    &format!(
+                        "{} SELECT COUNT(*) > 0 FROM tree WHERE id = ?2",
+                        DESCENDANTS_CTE
+                    ),
+                    params![todo_id, parent_id],
+                    |row| row.get(0),
+                )
+                .map_err(|e| AppError::internal("Falha ao consultar tarefas", e))?;
+            if creates_cycle {
+                return Err(AppError::InvalidInput(
+                    "Uma tarefa não pode ficar dentro dela mesma".into(),
+                ));
+            }
+        }
+
+        let sort_order = next_sort_order(&conn, parent_id)?;
+        let changed = conn
+            .execute(
+                "UPDATE todos SET parent_id = ?1, sort_order = ?2 WHERE id = ?3",
+                params![parent_id, sort_order, todo_id],
+            )
+            .map_err(|e| AppError::internal("Falha ao mover tarefa", e))?;
+        if changed == 0 {
+            return Err(AppError::NotFound("Tarefa não encontrada".into()));
+        }
+    }
+    todos_changed(&app);
+    Ok(())
+}
+
+/// Applies the order of `ordered_ids` to the children of `parent_id` (top level when `None`).
+#[tauri::command]
+pub fn reorder_todos(
+    app: tauri::AppHandle,
+    parent_id: Option<i64>,
+    ordered_ids: Vec<i64>,
+) -> Result<(), AppError> {
+    {
+        let todos = app.state::<Todos>();
+        let mut conn = todos.conn.lock().unwrap();
+        let tx = conn
+            .transaction()
+            .map_err(|e| AppError::internal("Falha ao reordenar tarefas", e))?;
+        for (index, id) in ordered_ids.iter().enumerate() {
+            tx.execute(
+                "UPDATE todos SET sort_order = ?1 WHERE id = ?2 AND parent_id IS ?3",
+                params![index as i64 + 1, id, parent_id],
+            )
+            .map_err(|e| AppError::internal("Falha ao reordenar tarefas", e))?;
+        }
+        tx.commit()
+            .map_err(|e| AppError::internal("Falha ao reordenar tarefas", e))?;
+    }
+    todos_changed(&app);
+    Ok(())
+}
+
+/// Completing a todo completes all of its subtasks; reopening one reopens its parents.
+#[tauri::command]
+pub fn set_todo_done(app: tauri::AppHandle, todo_id: i64, done: bool) -> Result<(), AppError> {
+    {
+        let todos = app.state::<Todos>();
+        let conn = todos.conn.lock().unwrap();
+        let sql = if done {
+            format!(
+                "{} UPDATE todos SET done = 1, completed_at = datetime('now')
+                 WHERE id IN (SELECT id FROM tree) AND done = 0",
+                DESCENDANTS_CTE
+            )
+        } else {
+            format!(
+                "{} UPDATE todos SET done = 0, completed_at = NULL
+                 WHERE id IN (SELECT id FROM chain) AND done = 1",
+                ANCESTORS_CTE
+            )
+        };
+        conn.execute(&sql, params![todo_id])
+            .map_err(|e| AppError::internal("Falha ao atualizar tarefa", e))?;
+    }
+    todos_changed(&app);
+    Ok(())
+}
@@ -0,0 +1,179 @@
+// ── Updater Module ─────────────────────────────────────────────
+use crate::error::AppError;
+use crate::settings::Settings;
+use std::sync::Mutex;
+use tauri::{Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+use tauri_plugin_updater::{Update, UpdaterExt};
+
+const UPDATE_CHANNEL_KEY: &str = "update_channel";
+
+const STABLE_ENDPOINT: &str =
+    "https://github.com/RafaEdu/desktop-util/releases/latest/download/latest.json";
+const BETA_ENDPOINT: &str =
+    "https://github.com/RafaEdu/desktop-util/releases/download/beta/latest.json";
+
+#[derive(Clone, Copy, Default, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum UpdateChannel {
+    #[default]
+    Stable,
+    Beta,
+}
+
+#[derive(Clone, serde::Serialize)]
+pub struct UpdateInfo {
+    pub version: String,
+    pub current_version: String,
+    pub notes: Option<String>,
+    pub date: Option<String>,
+}
+
+/// An update that finished downloading and waits for the user to restart.
+#[derive(Default)]
+pub struct PendingUpdate(Mutex<Option<(Update, Vec<u8>)>>);
+
+fn endpoint(app: &tauri::AppHandle) -> &'static str {
+    let channel = app
+        .state::<Settings>()
+        .get::<UpdateChannel>(UPDATE_CHANNEL_KEY)
+        .unwrap_or_default();
+    match channel {
+        UpdateChannel::Stable => STABLE_ENDPOINT,
+        UpdateChannel::Beta => BETA_ENDPOINT,
+    }
+}
+
+/// Releases are unsigned until the signing key is added to tauri.conf.json.
+fn is_configured(app: &tauri::AppHandle) -> bool {
+    app.config()
+        .plugins
+        .0
+        .get("updater")
+        .and_then(|config| config.get("pubkey"))
+        .and_then(|pubkey| pubkey.as_str())
+        .is_some_and(|pubkey| !pubkey.is_empty())
+}
+
+async fn find_update(app: &tauri::AppHandle) -> Result<Option<Update>, AppError> {
+    if !is_configured(app) {
+        return Err(AppError::Unsupported(
+            "Atualização automática não configurada (chave pública ausente)".into(),
+        ));
+    }
+
+    let url = endpoint(app)
+        .parse()
+        .map_err(|e| AppError::internal("Endereço de atualização inválido", e))?;
+    app.updater_builder()
+        .endpoints(vec![url])
+        .and_then(|builder| builder.build())
+        .map_err(|e| AppError::internal("Falha ao configurar atualização", e))?
+        .check()
+        .await
+        .map_err(|e| match e {
+            tauri_plugin_updater::Error::Reqwest(_) | tauri_plugin_updater::Error::Network(_) => {
+                AppError::ServiceUnavailable("Falha ao verificar atualizações".into())
+                    .with_details(e)
+            }
+            e => AppError::internal("Falha ao verificar atualizações", e),
+        })
+}
+
+/// Downloads in the background and asks the user, via notification, to restart.
+fn download_in_background(app: tauri::AppHandle, update: Update) {
+    tauri::async_runtime::spawn(async move {
+        let bytes = match update.download(|_, _| {}, || {}).await {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                let _ = app.emit(
+                    "update-failed",
+                    format!("Falha ao baixar atualização: {}", e),
+                );
+                return;
+            }
+        };
+
+        let version = update.version.clone();
+        *app.state::<PendingUpdate>().0.lock().unwrap() = Some((update, bytes));
+
+        let _ = app.emit("update-ready", &version);
+        let _ = app
+            .notification()
+            .builder()
+            .title("Atualização disponível")
+            .body(format!(
+                "A versão {} foi baixada. Reinicie o aplicativo para instalar.",
+                version
+            ))
+            .show();
+    });
+}
+
+/// Checks the configured channel; a found update starts downloading right away.
+async fn check_and_download(app: &tauri::AppHandle) -> Result<Option<UpdateInfo>, AppError> {
+    let Some(update) = find_update(app).await? else {
+        return Ok(None);
+    };
+
+    let info = UpdateInfo {
+        version: update.version.clone(),
+        current_version: update.current_version.clone(),
+        notes: update.body.clone(),
+        date: update.date.map(|date| date.to_string()),
+    };
+
+    let already_downloaded = app
+        .state::<PendingUpdate>()
+        .0
+        .lock()
+        .unwrap()
+        .as_ref()
+        .is_some_and(|(pending, _)| pending.version == update.version);
+    if !already_downloaded {
+        download_in_background(app.clone(), update);
+    }
+
+    Ok(Some(info))
+}
+
+/// Startup check; failures are only logged since nobody asked for it.
+pub fn check_on_startup(app: &tauri::AppHandle) {
+    if !is_configured(app) {
+        return;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = check_and_download(&app).await {
+            crate::app_log::error(e);
+        }
+    });
+}
+
+// Not in the invoke handler until tauri.conf.json carries the updater pubkey
+#[allow(dead_code)]
+#[tauri::command]
+pub async fn check_for_updates(app: tauri::AppHandle) -> Result<Option<UpdateInfo>, AppError> {
+    crate::metrics::timed("check_for_updates", async move {
+        check_and_download(&app).await
+    })
+    .await
+}
+
+/// Installs the downloaded update and restarts the app.
+#[allow(dead_code)]
+#[tauri::command]
+pub fn install_update(app: tauri::AppHandle) -> Result<(), AppError> {
+    let (update, bytes) = app
+        .state::<PendingUpdate>()
+        .0
+        .lock()
+        .unwrap()
+        .take()
+        .ok_or_else(|| AppError::NotFound("Nenhuma atualização baixada".into()))?;
+
+    update
+        .install(bytes)
+        .map_err(|e| AppError::internal("Falha ao instalar atualização", e))?;
+    app.restart()
+}
@@ -0,0 +1,201 @@
+// ── WhatsApp Module ────────────────────────────────────────────
+use crate::error::AppError;
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use std::path::Path;
+
+const BRAZIL_DDI: &str = "55";
+/// Area codes in use in Brazil.
+const VALID_DDD: [u8; 67] = [
+    11, 12, 13, 14, 15, 16, 17, 18, 19, 21, 22, 24, 27, 28, 31, 32, 33, 34, 35, 37, 38, 41, 42, 43,
+    44, 45, 46, 47, 48, 49, 51, 53, 54, 55, 61, 62, 63, 64, 65, 66, 67, 68, 69, 71, 73, 74, 75, 77,
+    79, 81, 82, 83, 84, 85, 86, 87, 88, 89, 91, 92, 93, 94, 95, 96, 97, 98, 99,
+];
+
+#[derive(serde::Serialize)]
+pub struct WhatsAppChat {
+    /// Number as sent to WhatsApp: DDI + DDD + number, digits only.
+    pub phone: String,
+    pub url: String,
+    /// Shown to the user when a file was put on the clipboard to paste in the chat.
+    pub note: Option<String>,
+}
+
+fn invalid_phone(phone: &str) -> AppError {
+    AppError::InvalidInput(format!("Número de telefone inválido: {}", phone))
+}
+
+/// Accepts the ways numbers show up in client records: `(11) 98765-4321`,
+/// `011 98765-4321`, `0 15 11 98765-4321` (with carrier code), `5511987654321`
+/// or `+55 11 98765-4321`. Foreign numbers need the `+` (or `00`) prefix and are
+/// passed on as typed.
+fn normalize_phone(phone: &str) -> Result<String, AppError> {
+    let trimmed = phone.trim();
+    let mut digits: String = trimmed.chars().filter(char::is_ascii_digit).collect();
+    let mut international = trimmed.starts_with('+');
+    if !international && digits.starts_with("00") {
+        digits.drain(..2);
+        international = true;
+    }
+
+    let national = if international {
+        match digits.strip_prefix(BRAZIL_DDI) {
+            Some(national) => national.to_string(),
+            None if (8..=15).contains(&digits.len()) => return Ok(digits),
+            None => return Err(invalid_phone(phone)),
+        }
+    } else if digits.starts_with('0') {
+        // Trunk prefix, optionally followed by a 2-digit carrier code
+        match digits.len() {
+            11 | 12 => digits[1..].to_string(),
+            13 | 14 => digits[3..].to_string(),
+            _ => return Err(invalid_phone(phone)),
+        }
+    } else if digits.starts_with(BRAZIL_DDI) && matches!(digits.len(), 12 | 13) {
+        digits[2..].to_string()
+    } else {
+        digits
+    };
+
+    let ddd: u8 = national
+        .get(..2)
+        .and_then(|ddd| ddd.parse().ok())
+        .ok_or_else(|| invalid_phone(phone))?;
+    if !VALID_DDD.contains(&ddd) {
+        return Err(AppError::InvalidInput(format!("DDD inválido: {}", ddd)));
+    }
+    let number = &national[2..];
+    let number = match (number.len(), number.as_bytes().first()) {
+        (9, Some(b'9')) => number.to_string(),
+        // Mobile saved before the extra 9 was added
+        (8, Some(b'6'..=b'9')) => format!("9{}", number),
+        // Landline, for companies on WhatsApp Business
+        (8, Some(b'2'..=b'5')) => number.to_string(),
+        _ => return Err(invalid_phone(phone)),
+    };
+    Ok(format!("{}{}{}", BRAZIL_DDI, ddd, number))
+}
+
+fn chat_url(phone: &str, message: &str, web: bool) -> String {
+    let text = utf8_percent_encode(message, NON_ALPHANUMERIC);
+    match (web, message.is_empty()) {
+        (true, true) => format!("https://web.whatsapp.com/send?phone={}", phone),
+        (true, false) => format!(
+            "https://web.whatsapp.com/send?phone={}&text={}",
+            phone, text
+        ),
+        (false, true) => format!("https://wa.me/{}", phone),
+        (false, false) => format!("https://wa.me/{}?text={}", phone, text),
+    }
+}
+
+/// Puts the file on the clipboard as Explorer does on Ctrl+C, so it can be pasted
+/// into the chat.
+#[cfg(windows)]
+fn copy_file_to_clipboard(path: &Path) -> Result<(), AppError> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows_sys::Win32::Foundation::GlobalFree;
+    use windows_sys::Win32::System::DataExchange::{
+        CloseClipboard, EmptyClipboard, OpenClipboard, SetClipboardData,
+    };
+    use windows_sys::Win32::System::Memory::{
+        GlobalAlloc, GlobalLock, GlobalUnlock, GMEM_MOVEABLE,
+    };
+    use windows_sys::Win32::System::Ole::CF_HDROP;
+    use windows_sys::Win32::UI::Shell::DROPFILES;
+
+    // DROPFILES header followed by a list of one UTF-16 path, ending in two NULs
+    let wide: Vec<u16> = path.as_os_str().encode_wide().chain([0, 0]).collect();
+    let header = std::mem::size_of::<DROPFILES>();
+    let size = header + wide.len() * 2;
+
+    unsafe {
+        let memory = GlobalAlloc(GMEM_MOVEABLE, size);
+        if memory.is_null() {
+            return Err(AppError::internal(
+                "Falha ao copiar o arquivo",
+                std::io::Error::last_os_error(),
+            ));
+        }
+        let data = GlobalLock(memory) as *mut u8;
+        if data.is_null() {
+            GlobalFree(memory);
+            return Err(AppError::internal(
+                "Falha ao copiar o arquivo",
+                std::io::Error::last_os_error(),
+            ));
+        }
+        std::ptr::write_bytes(data, 0, header);
+        let dropfiles = data as *mut DROPFILES;
+        (*dropfiles).pFiles = header as u32;
+        (*dropfiles).fWide = 1;
+        std::ptr::copy_nonoverlapping(wide.as_ptr() as *const u8, data.add(header), wide.len() * 2);
+        GlobalUnlock(memory);
+
+        if OpenClipboard(std::ptr::null_mut()) == 0 {
+            GlobalFree(memory);
+            return Err(AppError::ServiceUnavailable(
+                "A área de transferência está em uso por outro programa".into(),
+            ));
+        }
+        EmptyClipboard();
+        // Once stored, the memory belongs to the clipboard
+        let stored = !SetClipboardData(CF_HDROP as u32, memory).is_null();
+        let error = std::io::Error::last_os_error();
+        CloseClipboard();
+        if !stored {
+            GlobalFree(memory);
+            return Err(AppError::internal("Falha ao copiar o arquivo", error));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(windows))]
+fn copy_file_to_clipboard(_path: &Path) -> Result<(), AppError> {
+    Err(AppError::Unsupported(
+        "Copiar arquivos para a área de transferência disponível apenas no Windows".into(),
+    ))
+}
+
+/// Opens a WhatsApp chat with `phone` and `message` filled in, through the same
+/// link handling as `open_external_link` (WhatsApp Desktop takes over `wa.me`
+/// links when installed; `web` forces WhatsApp Web). WhatsApp links cannot carry
+/// files, so `attachment` (e.g. the DANFE or boleto) is copied to the clipboard
+/// to be pasted in the chat.
+#[tauri::command]
+pub fn open_whatsapp_chat(
+    app: tauri::AppHandle,
+    phone: String,
+    message: Option<String>,
+    attachment: Option<String>,
+    web: Option<bool>,
+) -> Result<WhatsAppChat, AppError> {
+    let phone = normalize_phone(&phone)?;
+    let message = message.unwrap_or_default();
+    let url = chat_url(&phone, message.trim(), web.unwrap_or(false));
+
+    let note = match attachment.filter(|path| !path.trim().is_empty()) {
+        Some(path) => {
+            let path = Path::new(&path);
+            if !path.is_file() {
+                return Err(AppError::NotFound(format!(
+                    "Arquivo não encontrado: {}",
+                    path.display()
+                )));
+            }
+            copy_file_to_clipboard(path)?;
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            Some(format!(
+                "O arquivo {} foi copiado. Cole na conversa com Ctrl+V.",
+                name
+            ))
+        }
+        None => None,
+    };
+
+    crate::open_external_link(app, url.clone(), None, None, None)?;
+    Ok(WhatsAppChat { phone, url, note })
+}
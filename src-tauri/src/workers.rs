@@ -0,0 +1,91 @@
+// ── Workers Module ─────────────────────────────────────────────
+use crate::error::AppError;
+use crate::settings::Settings;
+use std::sync::Mutex;
+use tauri::Manager;
+
+pub(crate) const CONCURRENCY_KEY: &str = "worker_concurrency";
+/// Beyond this, PDF work is bound by the disk or the network share, not the CPU.
+const MAX_CONCURRENCY: usize = 16;
+
+/// Cores minus one, so the window stays responsive while a batch runs.
+fn default_concurrency() -> usize {
+    std::thread::available_parallelism()
+        .map(|cores| cores.get().saturating_sub(1))
+        .unwrap_or(1)
+        .clamp(1, MAX_CONCURRENCY)
+}
+
+/// How many items batch operations process at once.
+pub fn concurrency(app: &tauri::AppHandle) -> usize {
+    app.try_state::<Settings>()
+        .and_then(|settings| settings.get::<usize>(CONCURRENCY_KEY))
+        .unwrap_or_else(default_concurrency)
+        .clamp(1, MAX_CONCURRENCY)
+}
+
+/// Runs `work` on every item with at most `concurrency` threads and returns the
+/// results in the order of `items`.
+pub fn map<T: Send, R: Send>(
+    concurrency: usize,
+    items: Vec<T>,
+    work: impl Fn(T) -> R + Sync,
+) -> Vec<R> {
+    let total = items.len();
+    let queue = Mutex::new(items.into_iter().enumerate());
+    let results: Mutex<Vec<Option<R>>> = Mutex::new((0..total).map(|_| None).collect());
+
+    std::thread::scope(|scope| {
+        for _ in 0..concurrency.clamp(1, total.max(1)) {
+            scope.spawn(|| loop {
+                let Some((index, item)) = queue.lock().unwrap().next() else {
+                    break;
+                };
+                let result = work(item);
+                results.lock().unwrap()[index] = Some(result);
+            });
+        }
+    });
+
+    // Every slot is filled: a panicking worker propagates out of the scope
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+#[derive(serde::Serialize)]
+pub struct WorkerSettings {
+    pub concurrency: usize,
+    pub default_concurrency: usize,
+    pub max_concurrency: usize,
+}
+
+#[tauri::command]
+pub fn get_worker_settings(app: tauri::AppHandle) -> WorkerSettings {
+    WorkerSettings {
+        concurrency: concurrency(&app),
+        default_concurrency: default_concurrency(),
+        max_concurrency: MAX_CONCURRENCY,
+    }
+}
+
+/// `None` goes back to the default for this machine.
+#[tauri::command]
+pub fn set_worker_concurrency(
+    settings: tauri::State<'_, Settings>,
+    concurrency: Option<usize>,
+) -> Result<(), AppError> {
+    if let Some(concurrency) = concurrency {
+        if !(1..=MAX_CONCURRENCY).contains(&concurrency) {
+            return Err(AppError::InvalidInput(format!(
+                "Informe entre 1 e {} tarefas simultâneas",
+                MAX_CONCURRENCY
+            )));
+        }
+    }
+    settings.set(CONCURRENCY_KEY, concurrency)?;
+    Ok(())
+}
@@ -0,0 +1,480 @@
+// ── XML Inbox Module ───────────────────────────────────────────
+use crate::error::AppError;
+use crate::nfe::{extract_block, extract_tag_content};
+use crate::settings::Settings;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+use tauri::{Emitter, Manager};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+/// Files touched more recently than this may still be being written.
+const SETTLE_TIME: Duration = Duration::from_secs(15);
+
+pub(crate) const INBOX_FOLDER_KEY: &str = "xml_inbox_folder";
+pub(crate) const INBOX_ENABLED_KEY: &str = "xml_inbox_enabled";
+
+#[derive(serde::Serialize, Clone, Copy, PartialEq)]
+pub enum XmlKind {
+    NFe,
+    CTe,
+    NFSe,
+}
+
+impl XmlKind {
+    fn folder(self) -> &'static str {
+        match self {
+            XmlKind::NFe => "NFe",
+            XmlKind::CTe => "CTe",
+            XmlKind::NFSe => "NFSe",
+        }
+    }
+}
+
+#[derive(serde::Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum InboxStatus {
+    Moved,
+    /// What a dry run would have moved.
+    Planned,
+    /// Identical file already filed; the inbox copy was removed.
+    Duplicate,
+    Skipped,
+    Failed,
+}
+
+#[derive(serde::Serialize, Clone)]
+pub struct InboxEntry {
+    pub file_name: String,
+    pub kind: Option<XmlKind>,
+    pub client_name: Option<String>,
+    pub destination: Option<String>,
+    pub status: InboxStatus,
+    pub reason: Option<String>,
+}
+
+#[derive(serde::Serialize, Clone)]
+pub struct InboxReport {
+    pub dry_run: bool,
+    pub finished_at: String,
+    /// Moved or already filed; on a dry run, what would be moved.
+    pub moved: u32,
+    pub skipped: u32,
+    pub failed: u32,
+    pub entries: Vec<InboxEntry>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct XmlInboxSettings {
+    pub enabled: bool,
+    /// Local or network folder where downloaded XMLs land.
+    pub folder: Option<String>,
+}
+
+/// Report of the last run, manual or from the watcher.
+#[derive(Default)]
+pub struct XmlInbox {
+    last_report: Mutex<Option<InboxReport>>,
+    /// Files left in the inbox (not fiscal, no matching client, failed) and the
+    /// modification time they had then. The watcher leaves them alone until they
+    /// change; manual runs retry them.
+    left_over: Mutex<HashMap<PathBuf, SystemTime>>,
+}
+
+/// What filing needs to know about a document.
+struct FiscalXml {
+    kind: XmlKind,
+    /// `YYYY-MM-DD` (or longer) issue date.
+    issued: String,
+    /// Issuer first, then the other parties, so the issuer's folder wins when
+    /// both sides are clients of the office.
+    cnpjs: Vec<String>,
+}
+
+fn party_cnpj(xml: &str, blocks: &[&str]) -> Vec<String> {
+    blocks
+        .iter()
+        .filter_map(|block| extract_block(xml, block))
+        .filter_map(|block| {
+            extract_tag_content(&block, "CNPJ").or_else(|| extract_tag_content(&block, "Cnpj"))
+        })
+        .map(|cnpj| cnpj.trim().to_string())
+        .filter(|cnpj| cnpj.len() == 14)
+        .collect()
+}
+
+fn first_tag(xml: &str, tags: &[&str]) -> Option<String> {
+    tags.iter()
+        .find_map(|tag| extract_tag_content(xml, tag))
+        .map(|value| value.trim().to_string())
+}
+
+/// Recognizes NFe and CTe (authorized or not) and NFSe in the national layout and
+/// the ABRASF-based municipal ones.
+fn identify(xml: &str) -> Option<FiscalXml> {
+    let (kind, issued, cnpjs) = if xml.contains("<infNFe") {
+        (
+            XmlKind::NFe,
+            first_tag(xml, &["dhEmi", "dEmi"]),
+            party_cnpj(xml, &["emit", "dest"]),
+        )
+    } else if xml.contains("<infCte") {
+        (
+            XmlKind::CTe,
+            first_tag(xml, &["dhEmi"]),
+            party_cnpj(xml, &["emit", "toma", "rem", "dest", "exped", "receb"]),
+        )
+    } else if ["<infNFSe", "<Nfse", "<CompNfse", "<NFSe"]
+        .iter()
+        .any(|tag| xml.contains(tag))
+    {
+        (
+            XmlKind::NFSe,
+            first_tag(
+                xml,
+                &["dhEmi", "DataEmissao", "dhProc", "dCompet", "Competencia"],
+            ),
+            party_cnpj(
+                xml,
+                &[
+                    "emit",
+                    "PrestadorServico",
+                    "Prestador",
+                    "toma",
+                    "TomadorServico",
+                    "Tomador",
+                ],
+            ),
+        )
+    } else {
+        return None;
+    };
+
+    Some(FiscalXml {
+        kind,
+        issued: issued.filter(|date| {
+            let bytes = date.as_bytes();
+            bytes.len() >= 7
+                && bytes[..4].iter().all(u8::is_ascii_digit)
+                && bytes[5..7].iter().all(u8::is_ascii_digit)
+        })?,
+        cnpjs,
+    })
+}
+
+fn settled(path: &Path) -> bool {
+    std::fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age >= SETTLE_TIME)
+}
+
+/// `rename` cannot cross volumes, and the inbox is usually local while the
+/// client folders live on the share.
+fn move_file(from: &Path, to: &Path) -> std::io::Result<()> {
+    if std::fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    std::fs::copy(from, to)?;
+    std::fs::remove_file(from)
+}
+
+fn client_folder_name(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '\\' | '/' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => ' ',
+            c => c,
+        })
+        .collect::<String>()
+        .trim()
+        .trim_end_matches('.')
+        .to_string()
+}
+
+/// Works out where one inbox file goes and, unless `dry_run`, puts it there.
+fn file_one(
+    app: &tauri::AppHandle,
+    base: &str,
+    path: &Path,
+    dry_run: bool,
+) -> Result<InboxEntry, AppError> {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut entry = InboxEntry {
+        file_name: file_name.clone(),
+        kind: None,
+        client_name: None,
+        destination: None,
+        status: InboxStatus::Skipped,
+        reason: None,
+    };
+
+    let bytes =
+        std::fs::read(path).map_err(|e| AppError::io(format!("Falha ao ler {}", file_name), e))?;
+    // Some municipal NFSe come in ISO-8859-1; tag names are ASCII either way
+    let xml = String::from_utf8_lossy(&bytes);
+    let Some(doc) = identify(&xml) else {
+        entry.reason = Some("Não é um XML de NFe, CTe ou NFSe".into());
+        return Ok(entry);
+    };
+    entry.kind = Some(doc.kind);
+
+    let clients = app
+        .try_state::<crate::clients::Clients>()
+        .ok_or_else(|| AppError::Unsupported("Cadastro de clientes indisponível".into()))?;
+    let mut client = None;
+    for cnpj in &doc.cnpjs {
+        if let Some(found) = clients.find_by_cnpj(cnpj)? {
+            client = Some(found);
+            break;
+        }
+    }
+    let Some(client) = client else {
+        entry.reason = Some(format!(
+            "Nenhum cliente cadastrado com os CNPJs do documento ({})",
+            doc.cnpjs.join(", ")
+        ));
+        return Ok(entry);
+    };
+    entry.client_name = Some(client.name.clone());
+
+    let (client_folder, registered) = match client.folder_path.as_deref().map(str::trim) {
+        Some(folder) if !folder.is_empty() => (PathBuf::from(folder), true),
+        _ => {
+            let name = client_folder_name(&client.name);
+            if name.is_empty() {
+                entry.reason = Some("Nome do cliente não serve como nome de pasta".into());
+                return Ok(entry);
+            }
+            (Path::new(base).join(name), false)
+        }
+    };
+    // A folder named after the client is one component below the base; a registered
+    // one may hold anything (`..`, junctions), so it must exist and resolve inside it
+    let client_folder = if registered || client_folder.exists() {
+        let settings = app.state::<Settings>();
+        match crate::client_folders::validate_path(&settings, &client_folder.to_string_lossy()) {
+            Ok(folder) => folder,
+            Err(e) => {
+                entry.reason = Some(format!("Pasta do cliente recusada: {}", e));
+                return Ok(entry);
+            }
+        }
+    } else {
+        client_folder
+    };
+    let dest_dir = client_folder
+        .join(&doc.issued[..4])
+        .join(&doc.issued[5..7])
+        .join(doc.kind.folder());
+    let mut destination = dest_dir.join(&file_name);
+    if destination.exists() {
+        if std::fs::read(&destination).is_ok_and(|existing| existing == bytes) {
+            entry.destination = Some(destination.to_string_lossy().to_string());
+            entry.status = if dry_run {
+                InboxStatus::Planned
+            } else {
+                std::fs::remove_file(path)
+                    .map_err(|e| AppError::io(format!("Falha ao remover {}", file_name), e))?;
+                InboxStatus::Duplicate
+            };
+            entry.reason = Some("Arquivo idêntico já arquivado".into());
+            return Ok(entry);
+        }
+        destination =
+            crate::client_folders::unique_destination(&dest_dir, path.file_name().unwrap());
+    }
+    entry.destination = Some(destination.to_string_lossy().to_string());
+
+    if dry_run {
+        entry.status = InboxStatus::Planned;
+        return Ok(entry);
+    }
+    std::fs::create_dir_all(&dest_dir)
+        .map_err(|e| AppError::io(format!("Falha ao criar {}", dest_dir.display()), e))?;
+    move_file(path, &destination)
+        .map_err(|e| AppError::io(format!("Falha ao mover {}", file_name), e))?;
+    entry.status = InboxStatus::Moved;
+    Ok(entry)
+}
+
+/// Files every settled XML in the inbox. Unless `retry_left_over`, files already
+/// left over and unchanged since are not looked at again.
+pub(crate) fn organize(
+    app: &tauri::AppHandle,
+    dry_run: bool,
+    retry_left_over: bool,
+) -> Result<InboxReport, AppError> {
+    let settings = app.state::<Settings>();
+    if !crate::client_folders::network_module_enabled(&settings) {
+        return Err(AppError::AccessDenied(
+            "Módulo de pastas de rede desativado nas configurações".into(),
+        ));
+    }
+    let Some(folder) = settings
+        .get::<String>(INBOX_FOLDER_KEY)
+        .filter(|folder| !folder.trim().is_empty())
+    else {
+        return Err(AppError::InvalidInput(
+            "Configure a pasta de entrada de XMLs".into(),
+        ));
+    };
+    let base = crate::client_folders::network_base_path(&settings);
+
+    let mut files: Vec<PathBuf> = std::fs::read_dir(&folder)
+        .map_err(|e| AppError::io(format!("Falha ao acessar {}", folder), e))?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("xml"))
+        })
+        .collect();
+    files.sort();
+
+    let mut report = InboxReport {
+        dry_run,
+        finished_at: String::new(),
+        moved: 0,
+        skipped: 0,
+        failed: 0,
+        entries: Vec::new(),
+    };
+    let inbox = app.state::<XmlInbox>();
+    let mut left_over = inbox.left_over.lock().unwrap();
+    left_over.retain(|path, _| files.contains(path));
+    for path in files {
+        if !settled(&path) {
+            continue;
+        }
+        let modified = std::fs::metadata(&path)
+            .and_then(|meta| meta.modified())
+            .ok();
+        if !retry_left_over && modified.is_some() && left_over.get(&path) == modified.as_ref() {
+            continue;
+        }
+        let entry = file_one(app, &base, &path, dry_run).unwrap_or_else(|e| InboxEntry {
+            file_name: path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            kind: None,
+            client_name: None,
+            destination: None,
+            status: InboxStatus::Failed,
+            reason: Some(e.to_string()),
+        });
+        match entry.status {
+            InboxStatus::Moved | InboxStatus::Planned | InboxStatus::Duplicate => report.moved += 1,
+            InboxStatus::Skipped => report.skipped += 1,
+            InboxStatus::Failed => report.failed += 1,
+        }
+        match (entry.status, modified) {
+            (InboxStatus::Skipped | InboxStatus::Failed, Some(modified)) if !dry_run => {
+                left_over.insert(path, modified);
+            }
+            _ => {
+                left_over.remove(&path);
+            }
+        }
+        report.entries.push(entry);
+    }
+    drop(left_over);
+    report.finished_at = chrono::Local::now().to_rfc3339();
+
+    // A watcher pass with nothing new keeps the previous report on screen
+    if retry_left_over || !report.entries.is_empty() {
+        *inbox.last_report.lock().unwrap() = Some(report.clone());
+    }
+    Ok(report)
+}
+
+/// Polls the inbox; files that could not be filed stay there and are only looked
+/// at again once they change, so each failure is announced once.
+pub fn start_watcher(app: tauri::AppHandle) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(POLL_INTERVAL);
+
+        let settings = app.state::<Settings>();
+        if !settings.get::<bool>(INBOX_ENABLED_KEY).unwrap_or(false) {
+            continue;
+        }
+        match organize(&app, false, false) {
+            Ok(report) if report.moved > 0 || report.failed > 0 => {
+                let _ = app.emit("xml-inbox-organized", &report);
+            }
+            Ok(_) => {}
+            Err(e) => crate::app_log::error(format!("Falha ao organizar XMLs: {}", e)),
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_xml_inbox_settings(settings: tauri::State<'_, Settings>) -> XmlInboxSettings {
+    XmlInboxSettings {
+        enabled: settings.get(INBOX_ENABLED_KEY).unwrap_or(false),
+        folder: settings.get(INBOX_FOLDER_KEY),
+    }
+}
+
+#[tauri::command]
+pub fn set_xml_inbox_settings(
+    settings: tauri::State<'_, Settings>,
+    inbox: XmlInboxSettings,
+) -> Result<(), AppError> {
+    let folder = inbox
+        .folder
+        .map(|folder| folder.trim().to_string())
+        .filter(|folder| !folder.is_empty());
+    if inbox.enabled {
+        let Some(folder) = &folder else {
+            return Err(AppError::InvalidInput(
+                "Informe a pasta de entrada de XMLs".into(),
+            ));
+        };
+        if !Path::new(folder).is_dir() {
+            return Err(AppError::NotFound(format!(
+                "Pasta não encontrada: {}",
+                folder
+            )));
+        }
+    }
+
+    settings.set(INBOX_FOLDER_KEY, folder)?;
+    settings.set(INBOX_ENABLED_KEY, inbox.enabled)?;
+    Ok(())
+}
+
+/// Files the XMLs in the inbox into `{cliente}/{ano}/{mes}/{tipo}`. With `dry_run`
+/// nothing is touched and the report says where each file would go.
+#[tauri::command]
+pub async fn organize_xml_inbox(
+    app: tauri::AppHandle,
+    dry_run: Option<bool>,
+) -> Result<InboxReport, AppError> {
+    crate::metrics::timed("organize_xml_inbox", async move {
+        let dry_run = dry_run.unwrap_or(false);
+        let report = tauri::async_runtime::spawn_blocking({
+            let app = app.clone();
+            move || organize(&app, dry_run, true)
+        })
+        .await
+        .map_err(|e| AppError::internal("Falha ao organizar XMLs", e))??;
+        if !dry_run && report.moved > 0 {
+            let _ = app.emit("xml-inbox-organized", &report);
+        }
+        Ok(report)
+    })
+    .await
+}
+
+#[tauri::command]
+pub fn get_xml_inbox_report(inbox: tauri::State<'_, XmlInbox>) -> Option<InboxReport> {
+    inbox.last_report.lock().unwrap().clone()
+}
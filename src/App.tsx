@@ -64,6 +64,9 @@ function App() {
       // Sync state to Rust backend
       await invoke("set_movable_mode", { enabled: initialMovable });
 
+      // Launched by autostart: leave the window in the tray
+      const startHidden = await invoke<boolean>("should_start_hidden");
+
       if (initialMovable) {
         await win.setDecorations(true);
         const savedPos = localStorage.getItem("windowPosition");
@@ -73,10 +76,12 @@ function App() {
         }
       } else {
         await win.setDecorations(false);
+        if (startHidden) return;
         await showWindowAboveTray();
         return; // showWindowAboveTray already calls show + setFocus
       }
 
+      if (startHidden) return;
       await win.show();
       await win.setFocus();
     }
@@ -1,5 +1,6 @@
 import { useMemo, useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import {
   RefreshCw,
   ShieldCheck,
@@ -11,6 +12,7 @@ import {
 } from "lucide-react";
 import dayjs from "dayjs";
 import { cn } from "../lib/cn";
+import { errorMessage } from "../lib/errors";
 
 interface CertInfo {
   subject: string;
@@ -35,13 +37,19 @@ export function Certificates() {
   const loadCerts = async () => {
     setLoading(true);
     setError(null);
+    setCerts([]);
+    // A3 tokens make the enumeration slow; show each certificate as it is read
+    const unlisten = await listen<CertInfo>("certificate-found", (event) => {
+      setCerts((prev) => [...prev, event.payload]);
+    });
     try {
       const data = await invoke<CertInfo[]>("get_certificates");
       setCerts(data);
       setFetched(true);
     } catch (err) {
-      setError(String(err));
+      setError(errorMessage(err));
     } finally {
+      unlisten();
       setLoading(false);
     }
   };
@@ -80,7 +88,7 @@ export function Certificates() {
       setCertToDelete(null);
       await loadCerts();
     } catch (err) {
-      setError(`Erro ao excluir: ${String(err)}`);
+      setError(`Erro ao excluir: ${errorMessage(err)}`);
     } finally {
       setLoading(false);
     }
@@ -176,7 +184,7 @@ export function Certificates() {
               Clique em "Carregar" para listar os certificados.
             </p>
           </div>
-        ) : loading && !fetched ? (
+        ) : loading && certs.length === 0 ? (
           <div className="flex items-center justify-center h-32">
             <div className="w-5 h-5 border-2 border-indigo-400 border-t-transparent rounded-full animate-spin" />
           </div>
@@ -26,6 +26,7 @@ import { invoke } from "@tauri-apps/api/core";
 import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
 import { getDb, ClientFolder } from "../lib/db";
 import { cn } from "../lib/cn";
+import { errorMessage } from "../lib/errors";
 
 interface DirEntry {
   name: string;
@@ -205,7 +206,7 @@ export function ClientManager() {
       });
       await refreshDirectory();
     } catch (err) {
-      setError(String(err));
+      setError(errorMessage(err));
     } finally {
       setIsCopying(false);
     }
@@ -223,7 +224,7 @@ export function ClientManager() {
       setNewFolderName("");
       await refreshDirectory();
     } catch (err) {
-      setError(String(err));
+      setError(errorMessage(err));
     }
   };
 
@@ -238,7 +239,7 @@ export function ClientManager() {
       setPathHistory([]);
       setViewMode("explorer");
     } catch (err) {
-      setError(String(err));
+      setError(errorMessage(err));
     } finally {
       setLoading(false);
     }
@@ -257,7 +258,7 @@ export function ClientManager() {
       setPathHistory((prev) => [...prev, currentPath]);
       setCurrentPath(newPath);
     } catch (err) {
-      setError(String(err));
+      setError(errorMessage(err));
     } finally {
       setLoading(false);
     }
@@ -280,7 +281,7 @@ export function ClientManager() {
       setCurrentPath(prevPath);
       setPathHistory((prev) => prev.slice(0, -1));
     } catch (err) {
-      setError(String(err));
+      setError(errorMessage(err));
     } finally {
       setLoading(false);
     }
@@ -296,7 +297,7 @@ export function ClientManager() {
       });
       setDirEntries(entries);
     } catch (err) {
-      setError(String(err));
+      setError(errorMessage(err));
     } finally {
       setLoading(false);
     }
@@ -309,7 +310,7 @@ export function ClientManager() {
     } else {
       const fullPath = `${currentPath}\\${entry.name}`;
       invoke("open_file", { path: fullPath }).catch((err) =>
-        setError(String(err)),
+        setError(errorMessage(err)),
       );
     }
   };
@@ -329,7 +330,7 @@ export function ClientManager() {
       );
       setNetworkFolders(available);
     } catch (err) {
-      setError(String(err));
+      setError(errorMessage(err));
       setShowAddModal(false);
     } finally {
       setAddLoading(false);
@@ -351,7 +352,7 @@ export function ClientManager() {
       await loadFolders();
       setShowAddModal(false);
     } catch (err) {
-      setError(String(err));
+      setError(errorMessage(err));
     }
   };
 
@@ -410,7 +411,7 @@ export function ClientManager() {
       if (viewMode === "explorer") await refreshDirectory();
       setRenameDialog(null);
     } catch (err) {
-      setError(String(err));
+      setError(errorMessage(err));
     }
   };
 
@@ -423,7 +424,7 @@ export function ClientManager() {
       const result = await invoke<string[]>("list_network_folders");
       setMoveFolders(result);
     } catch (err) {
-      setError(String(err));
+      setError(errorMessage(err));
       setMoveDialog(null);
     }
   };
@@ -439,7 +440,7 @@ export function ClientManager() {
       if (viewMode === "explorer") await refreshDirectory();
       setMoveDialog(null);
     } catch (err) {
-      setError(String(err));
+      setError(errorMessage(err));
     }
   };
 
@@ -463,7 +464,7 @@ export function ClientManager() {
       if (viewMode === "explorer") await refreshDirectory();
       setDeleteDialog(null);
     } catch (err) {
-      setError(String(err));
+      setError(errorMessage(err));
     }
   };
 
@@ -485,7 +486,7 @@ export function ClientManager() {
       await loadFolders();
       setRemoveDialog(null);
     } catch (err) {
-      setError(String(err));
+      setError(errorMessage(err));
     }
   };
 
@@ -2,6 +2,7 @@ import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { FileSearch, ShieldCheck, RefreshCw, ChevronDown } from "lucide-react";
 import { cn } from "../lib/cn";
+import { errorMessage } from "../lib/errors";
 
 // ... (UF_NAMES, formatCnpj, parseAccessKey mantidos iguais) ...
 // UF map for display from access key
@@ -57,8 +58,14 @@ interface CertInfo {
   issuer: string;
   not_after: string;
   thumbprint: string;
+  is_exportable: boolean;
+  cert_type: string | null;
 }
 
+// The query is made as the company, so e-CPF certificates are refused by SEFAZ
+const isUsable = (c: CertInfo) =>
+  c.is_exportable && !c.cert_type?.startsWith("e-CPF");
+
 export function NfeQuery() {
   const [accessKeyRaw, setAccessKeyRaw] = useState("");
   const [error, setError] = useState<string | null>(null);
@@ -69,15 +76,20 @@ export function NfeQuery() {
 
   const accessKey = accessKeyRaw.replace(/\D/g, "").slice(0, 44);
   const parsed = parseAccessKey(accessKey);
-  const isValid = accessKey.length === 44;
+  const selected = certs.find((c) => c.thumbprint === selectedCert);
+  // A3 tokens, non-exportable keys and e-CPF certificates cannot be used for the query
+  const canQuery =
+    accessKey.length === 44 &&
+    (method === "portal" || (selected !== undefined && isUsable(selected)));
 
   const loadCerts = async () => {
     setLoadingCerts(true);
     try {
       const data = await invoke<CertInfo[]>("get_certificates");
       setCerts(data);
-      if (data.length > 0 && !selectedCert) {
-        setSelectedCert(data[0].thumbprint);
+      const firstUsable = data.find(isUsable);
+      if (firstUsable && !selectedCert) {
+        setSelectedCert(firstUsable.thumbprint);
       }
     } catch (err) {
       console.error("Failed to load certs", err);
@@ -109,7 +121,7 @@ export function NfeQuery() {
         await invoke("open_danfe", { filePath });
       }
     } catch (err) {
-      setError(String(err));
+      setError(errorMessage(err));
     }
   };
 
@@ -189,12 +201,17 @@ export function NfeQuery() {
                     className="w-full bg-field border border-edge-2 rounded-lg pl-3 pr-8 py-2 text-sm text-fg appearance-none focus:outline-none focus:ring-2 focus:ring-indigo-500"
                   >
                     {certs.map((c) => (
-                      <option key={c.thumbprint} value={c.thumbprint}>
+                      <option
+                        key={c.thumbprint}
+                        value={c.thumbprint}
+                        disabled={!isUsable(c)}
+                      >
                         {c.subject.split(",")[0]} (Val:{" "}
                         {c.not_after !== "N/A"
                           ? c.not_after.split("T")[0]
                           : "N/A"}
-                        )
+                        ){c.cert_type && ` - ${c.cert_type}`}
+                        {!c.is_exportable && " - A3/não exportável"}
                       </option>
                     ))}
                   </select>
@@ -249,10 +266,10 @@ export function NfeQuery() {
 
         <button
           onClick={handleQuery}
-          disabled={!isValid}
+          disabled={!canQuery}
           className={cn(
             "w-full flex items-center justify-center gap-2 py-2.5 rounded-lg text-sm font-medium transition-all duration-200",
-            isValid
+            canQuery
               ? "bg-indigo-600 hover:bg-indigo-500 text-white shadow-lg shadow-indigo-500/25"
               : "bg-field text-fg-6 cursor-not-allowed",
           )}
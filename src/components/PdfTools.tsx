@@ -14,6 +14,7 @@ import {
 import { invoke } from "@tauri-apps/api/core";
 import { open, save } from "@tauri-apps/plugin-dialog";
 import { cn } from "../lib/cn";
+import { errorMessage } from "../lib/errors";
 
 type PdfTab = "merge" | "split" | "compress";
 
@@ -138,7 +139,7 @@ function MergePdf() {
       setSuccess(`PDF salvo em: ${result}`);
       setFiles([]);
     } catch (err) {
-      setError(String(err));
+      setError(errorMessage(err));
     } finally {
       setLoading(false);
     }
@@ -307,7 +308,7 @@ function SplitPdf() {
     try {
       strategy = buildStrategy();
     } catch (err) {
-      setError(err instanceof Error ? err.message : String(err));
+      setError(errorMessage(err));
       return;
     }
 
@@ -324,7 +325,7 @@ function SplitPdf() {
       });
       setSuccess(`${result.length} arquivo(s) gerado(s) com sucesso.`);
     } catch (err) {
-      setError(String(err));
+      setError(errorMessage(err));
     } finally {
       setLoading(false);
     }
@@ -501,7 +502,7 @@ function CompressPdf() {
         }>("get_pdf_info", { path });
         setPdfInfo(info);
       } catch (err) {
-        setError(String(err));
+        setError(errorMessage(err));
       }
     }
   };
@@ -529,7 +530,7 @@ function CompressPdf() {
       const reductionPercent = ((pdfInfo.size - newSize) / pdfInfo.size) * 100;
       setSuccess({ newSize, reductionPercent });
     } catch (err) {
-      setError(String(err));
+      setError(errorMessage(err));
     } finally {
       setLoading(false);
     }
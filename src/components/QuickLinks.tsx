@@ -13,6 +13,33 @@ import { message } from "@tauri-apps/plugin-dialog";
 import { cn } from "../lib/cn";
 import { getDb, type QuickLink } from "../lib/db";
 
+// Links like "mailto:" or "whatsapp:" keep their scheme; bare addresses become https
+function withDefaultScheme(url: string) {
+  if (!url || /^[a-z][a-z0-9+-]*:/i.test(url)) return url;
+  return "https://" + url;
+}
+
+// Icons are downloaded and cached by the backend, avoiding cross-origin requests here
+function LinkFavicon({ url }: { url: string }) {
+  const [src, setSrc] = useState<string | null>(null);
+
+  useEffect(() => {
+    let cancelled = false;
+    setSrc(null);
+    invoke<{ data_url: string }>("fetch_favicon", { url })
+      .then((icon) => {
+        if (!cancelled) setSrc(icon.data_url);
+      })
+      .catch(() => {});
+    return () => {
+      cancelled = true;
+    };
+  }, [url]);
+
+  if (!src) return <Link className="w-4 h-4 flex-shrink-0 text-fg-6" />;
+  return <img src={src} alt="" className="w-4 h-4 flex-shrink-0 rounded-sm" />;
+}
+
 export function QuickLinks() {
   const [links, setLinks] = useState<QuickLink[]>([]);
   const [title, setTitle] = useState("");
@@ -42,17 +69,14 @@ export function QuickLinks() {
 
   const addLink = async () => {
     const t = title.trim();
-    let u = url.trim();
+    const u = withDefaultScheme(url.trim());
     if (!t || !u) return;
-    if (!u.startsWith("http://") && !u.startsWith("https://")) {
-      u = "https://" + u;
-    }
     try {
       const db = await getDb();
-      await db.execute("INSERT INTO quick_links (title, url) VALUES (?, ?)", [
-        t,
-        u,
-      ]);
+      await db.execute(
+        "INSERT INTO quick_links (title, url, sort_order) VALUES (?, ?, (SELECT COALESCE(MAX(sort_order), 0) + 1 FROM quick_links))",
+        [t, u],
+      );
       setTitle("");
       setUrl("");
       await loadLinks();
@@ -76,11 +100,8 @@ export function QuickLinks() {
   const saveEdit = async () => {
     if (editingId === null) return;
     const t = editTitle.trim();
-    let u = editUrl.trim();
+    const u = withDefaultScheme(editUrl.trim());
     if (!t || !u) return;
-    if (!u.startsWith("http://") && !u.startsWith("https://")) {
-      u = "https://" + u;
-    }
     try {
       const db = await getDb();
       await db.execute(
@@ -104,18 +125,27 @@ export function QuickLinks() {
     }
   };
 
-  const handleOpenLink = async (linkUrl: string) => {
+  // Click counts order the tray submenu by most used
+  const recordClick = (id: number) => {
+    invoke("record_quick_link_click", { id }).catch((err) =>
+      console.error("Failed to record link click:", err),
+    );
+  };
+
+  const handleOpenLink = async (link: QuickLink) => {
     try {
       await invoke("open_external_link", {
-        url: linkUrl,
+        url: link.url,
         mode: "normal",
       });
+      recordClick(link.id);
     } catch (err) {
       console.error("Failed to open link:", err);
     }
   };
 
-  const handleOpenAnonymousLink = async (linkUrl: string) => {
+  const handleOpenAnonymousLink = async (link: QuickLink) => {
+    const linkUrl = link.url;
     const chromeApi = (
       globalThis as {
         chrome?: {
@@ -132,6 +162,7 @@ export function QuickLinks() {
           url: linkUrl,
           incognito: true,
         });
+        recordClick(link.id);
         return;
       } catch (err) {
         console.error("Failed to open anonymous link via chrome API:", err);
@@ -143,6 +174,7 @@ export function QuickLinks() {
         url: linkUrl,
         mode: "incognito",
       });
+      recordClick(link.id);
     } catch (err) {
       console.error("Failed to open anonymous link:", err);
       await message(
@@ -283,22 +315,23 @@ export function QuickLinks() {
                   className="group flex items-center gap-3 px-3 py-2.5 rounded-lg border
                              bg-surface border-edge hover:border-edge-2 transition-all duration-200"
                 >
+                  <LinkFavicon url={link.url} />
                   <button
-                    onClick={() => handleOpenLink(link.url)}
+                    onClick={() => handleOpenLink(link)}
                     className="flex-1 min-w-0 text-left"
                   >
                     <p className="text-sm text-fg-2 truncate">{link.title}</p>
                     <p className="text-xs text-fg-5 truncate">{link.url}</p>
                   </button>
                   <button
-                    onClick={() => handleOpenLink(link.url)}
+                    onClick={() => handleOpenLink(link)}
                     className="flex-shrink-0 text-fg-5 hover:text-indigo-400 transition-colors"
                     title="Abrir link (Normal)"
                   >
                     <ExternalLink className="w-4 h-4" />
                   </button>
                   <button
-                    onClick={() => handleOpenAnonymousLink(link.url)}
+                    onClick={() => handleOpenAnonymousLink(link)}
                     className="flex-shrink-0 px-2 py-1 rounded-md text-xs font-medium
                                text-fg-3 bg-field hover:bg-subtle hover:text-white transition-colors"
                     title="Abrir link (Anônimo)"
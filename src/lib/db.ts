@@ -7,6 +7,20 @@ export interface Todo {
   created_at: string;
   completed_at: string | null;
   sort_order: number;
+  due_at: string | null; // UTC RFC 3339, set via set_todo_schedule
+  remind_at: string | null;
+  parent_id: number | null;
+  uid: string; // stable id across synced workstations
+}
+
+export interface Note {
+  id: number;
+  title: string;
+  body: string; // markdown
+  tags: string[];
+  created_at: string;
+  updated_at: string;
+  uid: string;
 }
 
 export interface QuickLink {
@@ -14,6 +28,10 @@ export interface QuickLink {
   title: string;
   url: string;
   created_at: string;
+  group_name: string | null;
+  sort_order: number;
+  click_count: number;
+  uid: string;
 }
 
 export interface ClientFolder {
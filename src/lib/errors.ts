@@ -0,0 +1,35 @@
+/** Error shape returned by backend commands (see `AppError` in src-tauri). */
+export interface AppError {
+  code:
+    | "invalid_input"
+    | "not_found"
+    | "already_exists"
+    | "access_denied"
+    | "service_unavailable"
+    | "service_rejected"
+    | "unsupported"
+    | "cancelled"
+    | "internal";
+  message: string;
+  details: string | null;
+}
+
+export function isAppError(err: unknown): err is AppError {
+  return (
+    typeof err === "object" &&
+    err !== null &&
+    "code" in err &&
+    "message" in err
+  );
+}
+
+/**
+ * Human-readable text for anything thrown by `invoke`, plugins or JS itself.
+ */
+export function errorMessage(err: unknown): string {
+  if (isAppError(err)) {
+    return err.details ? `${err.message}: ${err.details}` : err.message;
+  }
+  if (err instanceof Error) return err.message;
+  return String(err);
+}